@@ -0,0 +1,79 @@
+//! Benchmarks of the APIs that pass names and paths to flecs.
+//!
+//! Run with `cargo bench -p flecs_ecs --bench name_lookup`.
+
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use flecs_ecs::prelude::*;
+
+const SHORT_PATH: &str = "world::level::player";
+const LONG_PATH: &str = "world_with_a_long_name::level_with_a_long_name::room_with_a_long_name::entity_with_a_name_that_is_too_long_to_be_stored_inline_0123456789";
+
+fn create_path(world: &World, path: &str) -> Entity {
+    let mut parent: Option<EntityView> = None;
+    for name in path.split("::") {
+        let e = world.entity_named(name);
+        if let Some(parent) = parent {
+            e.child_of_id(parent);
+        }
+        parent = Some(e);
+    }
+    parent.unwrap().id()
+}
+
+fn lookup(c: &mut Criterion) {
+    let world = World::new();
+    create_path(&world, SHORT_PATH);
+    create_path(&world, LONG_PATH);
+
+    let mut group = c.benchmark_group("lookup");
+    group.bench_function("short_path", |b| {
+        b.iter(|| world.try_lookup(black_box(SHORT_PATH)).is_some());
+    });
+    group.bench_function("long_path", |b| {
+        b.iter(|| world.try_lookup(black_box(LONG_PATH)).is_some());
+    });
+    group.bench_function("recursive", |b| {
+        b.iter(|| world.try_lookup_recursive(black_box("player")).is_some());
+    });
+    group.finish();
+}
+
+fn lookup_child(c: &mut Criterion) {
+    let world = World::new();
+    let player = create_path(&world, SHORT_PATH);
+    world.entity_named("sword").child_of_id(player);
+    let player = world.entity_from_id(player);
+
+    c.bench_function("lookup_child", |b| {
+        b.iter(|| player.try_lookup(black_box("sword")).is_some());
+    });
+}
+
+fn set_name(c: &mut Criterion) {
+    let world = World::new();
+    let e = world.entity();
+    let names = ["first", "second"];
+    let mut i = 0;
+
+    c.bench_function("set_name", |b| {
+        b.iter(|| {
+            i ^= 1;
+            e.set_name(black_box(names[i]));
+        });
+    });
+}
+
+fn find_var(c: &mut Criterion) {
+    let world = World::new();
+    let query = world
+        .query::<()>()
+        .with_first_name::<flecs::ChildOf>("$parent")
+        .build();
+
+    c.bench_function("find_var", |b| {
+        b.iter(|| query.find_var(black_box("parent")));
+    });
+}
+
+criterion_group!(benches, lookup, lookup_child, set_name, find_var);
+criterion_main!(benches);
@@ -0,0 +1,205 @@
+//! Benchmarks of the Rust API against the same operations done with the C API through
+//! `flecs_ecs::sys`, to catch regressions in the overhead of the wrapper.
+//!
+//! Every group has a `rust` and a `c` variant of the same operation.
+//!
+//! Run with `cargo bench -p flecs_ecs --bench wrapper_overhead`.
+
+use core::ffi::c_void;
+
+use criterion::{BatchSize, Criterion, black_box, criterion_group, criterion_main};
+use flecs_ecs::prelude::*;
+use flecs_ecs::sys;
+
+const ENTITY_COUNT: usize = 1000;
+
+#[derive(Component, Clone, Copy)]
+struct Position {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Component, Clone, Copy)]
+struct Velocity {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Component)]
+struct Tag;
+
+fn populated_world() -> World {
+    let world = World::new();
+    for i in 0..ENTITY_COUNT {
+        world
+            .entity()
+            .set(Position {
+                x: i as f32,
+                y: 0.0,
+            })
+            .set(Velocity { x: 1.0, y: 1.0 });
+    }
+    world
+}
+
+fn entity_creation(c: &mut Criterion) {
+    let mut group = c.benchmark_group("entity_new");
+    group.bench_function("rust", |b| {
+        b.iter_batched(
+            World::new,
+            |world| {
+                for _ in 0..ENTITY_COUNT {
+                    black_box(world.entity());
+                }
+                world
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.bench_function("c", |b| {
+        b.iter_batched(
+            World::new,
+            |world| {
+                for _ in 0..ENTITY_COUNT {
+                    black_box(unsafe { sys::ecs_new(world.ptr_mut()) });
+                }
+                world
+            },
+            BatchSize::LargeInput,
+        );
+    });
+    group.finish();
+}
+
+fn add_remove(c: &mut Criterion) {
+    let world = World::new();
+    let e = world.entity().set(Position { x: 0.0, y: 0.0 });
+    let tag = world.component::<Tag>().id();
+
+    let mut group = c.benchmark_group("add_remove");
+    group.bench_function("rust", |b| {
+        b.iter(|| {
+            e.add::<Tag>();
+            e.remove::<Tag>();
+        });
+    });
+    group.bench_function("c", |b| {
+        b.iter(|| unsafe {
+            sys::ecs_add_id(world.ptr_mut(), *e.id(), *tag);
+            sys::ecs_remove_id(world.ptr_mut(), *e.id(), *tag);
+        });
+    });
+    group.finish();
+}
+
+fn set(c: &mut Criterion) {
+    let world = World::new();
+    let e = world.entity().set(Position { x: 0.0, y: 0.0 });
+    let position = world.component::<Position>().id();
+
+    let mut group = c.benchmark_group("set");
+    group.bench_function("rust", |b| {
+        b.iter(|| {
+            e.set(black_box(Position { x: 1.0, y: 2.0 }));
+        });
+    });
+    group.bench_function("c", |b| {
+        b.iter(|| {
+            let value = black_box(Position { x: 1.0, y: 2.0 });
+            unsafe {
+                sys::ecs_set_id(
+                    world.ptr_mut(),
+                    *e.id(),
+                    *position,
+                    size_of::<Position>(),
+                    &value as *const Position as *const c_void,
+                );
+            }
+        });
+    });
+    group.finish();
+}
+
+fn get(c: &mut Criterion) {
+    let world = World::new();
+    let e = world.entity().set(Position { x: 1.0, y: 2.0 });
+    let position = world.component::<Position>().id();
+
+    let mut group = c.benchmark_group("get");
+    group.bench_function("rust", |b| {
+        b.iter(|| e.get::<&Position>(|p| black_box(p.x)));
+    });
+    group.bench_function("c", |b| {
+        b.iter(|| {
+            let p = unsafe { sys::ecs_get_id(world.ptr_mut(), *e.id(), *position) };
+            black_box(unsafe { (*(p as *const Position)).x })
+        });
+    });
+    group.finish();
+}
+
+fn iteration(c: &mut Criterion) {
+    let world = populated_world();
+    let query = world.new_query::<(&mut Position, &Velocity)>();
+
+    let mut group = c.benchmark_group("iterate");
+    group.bench_function("rust_each", |b| {
+        b.iter(|| {
+            query.each(|(p, v)| {
+                p.x += v.x;
+                p.y += v.y;
+            });
+        });
+    });
+    group.bench_function("rust_run", |b| {
+        b.iter(|| {
+            query.run(|mut it| {
+                while it.next() {
+                    let mut p = it.field_mut::<Position>(0).unwrap();
+                    let v = it.field::<Velocity>(1).unwrap();
+                    for i in it.iter() {
+                        p[i].x += v[i].x;
+                        p[i].y += v[i].y;
+                    }
+                }
+            });
+        });
+    });
+    group.bench_function("c", |b| {
+        b.iter(|| unsafe {
+            let mut it = sys::ecs_query_iter(world.ptr_mut(), query.query_ptr());
+            while sys::ecs_query_next(&mut it) {
+                let p = sys::ecs_field_w_size(&it, size_of::<Position>(), 0) as *mut Position;
+                let v = sys::ecs_field_w_size(&it, size_of::<Velocity>(), 1) as *const Velocity;
+                for i in 0..it.count as usize {
+                    (*p.add(i)).x += (*v.add(i)).x;
+                    (*p.add(i)).y += (*v.add(i)).y;
+                }
+            }
+        });
+    });
+    group.finish();
+}
+
+fn system(c: &mut Criterion) {
+    let world = populated_world();
+    let system = world.system::<(&mut Position, &Velocity)>().each(|(p, v)| {
+        p.x += v.x;
+        p.y += v.y;
+    });
+
+    c.bench_function("system_run", |b| {
+        b.iter(|| system.run());
+    });
+}
+
+criterion_group!(
+    benches,
+    entity_creation,
+    add_remove,
+    set,
+    get,
+    iteration,
+    system
+);
+criterion_main!(benches);
@@ -206,7 +206,7 @@ fn transfer_items(dst: EntityView<'_>, src: EntityView<'_>) {
 
     // Defer is recommended in Flecs so you can safely modify ECS while iterating.
     // In flecs-rust you can do `world.defer(|w| { ... })`.
-    world.defer(|| {
+    world.defer(|world| {
         let dst_container = world.entity_from_id(get_container(dst));
         let src_container = world.entity_from_id(get_container(src));
 
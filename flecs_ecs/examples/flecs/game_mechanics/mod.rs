@@ -1 +1,2 @@
 mod inventory_system;
+mod state_machine;
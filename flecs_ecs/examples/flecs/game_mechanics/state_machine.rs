@@ -0,0 +1,83 @@
+//! This example shows how to implement a state machine with the state machine
+//! addon, which is built on enum relationships.
+//!
+//! Enums are registered as exclusive relationships, so an entity can only be
+//! in one state of a state machine at a time: entering a new state replaces
+//! the previous one. Transitions that are not defined are rejected, and
+//! transitions with a guard are only taken when the guard returns true.
+//! Observers for entering and exiting a state are created with `on_enter` and
+//! `on_exit`.
+
+use flecs_ecs::prelude::*;
+
+//MARK: Components
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+enum PlayerState {
+    Idle,
+    Walking,
+    Jumping,
+}
+
+#[derive(Component, Debug)]
+struct Grounded;
+
+/// Move the player to a new state, and print why when that is not allowed.
+fn transition(e: EntityView, to: PlayerState) {
+    if let Err(err) = e.transition(to) {
+        println!("{}: {}", e.name(), err);
+    }
+}
+
+fn main() {
+    let world = World::new();
+
+    world
+        .state_machine::<PlayerState>()
+        .transition(PlayerState::Idle, PlayerState::Walking)
+        .transition(PlayerState::Walking, PlayerState::Idle)
+        .transition_if(PlayerState::Idle, PlayerState::Jumping, |e| {
+            e.has::<Grounded>()
+        })
+        .transition_if(PlayerState::Walking, PlayerState::Jumping, |e| {
+            e.has::<Grounded>()
+        })
+        .transition_if(PlayerState::Jumping, PlayerState::Idle, |e| {
+            e.has::<Grounded>()
+        })
+        .on_enter(PlayerState::Jumping, |e| {
+            println!("{}: enter Jumping", e.name());
+            e.remove::<Grounded>();
+        })
+        .on_exit(PlayerState::Jumping, |e| {
+            println!("{}: exit Jumping", e.name());
+        })
+        .on_enter(PlayerState::Walking, |e| {
+            println!("{}: enter Walking", e.name());
+        })
+        .on_exit(PlayerState::Walking, |e| {
+            println!("{}: exit Walking", e.name());
+        });
+
+    let player = world
+        .entity_named("Player")
+        .add::<Grounded>()
+        .add_enum(PlayerState::Idle);
+
+    transition(player, PlayerState::Walking);
+    transition(player, PlayerState::Jumping);
+
+    // Can't land while in the air
+    transition(player, PlayerState::Idle);
+
+    player.add::<Grounded>();
+    transition(player, PlayerState::Idle);
+
+    // Output:
+    //  Player: enter Walking
+    //  Player: exit Walking
+    //  Player: enter Jumping
+    //  Player: transition from Jumping to Idle is not allowed
+    //  Player: exit Jumping
+}
@@ -35,7 +35,7 @@ extern "C-unwind" fn callback_group_by_relationship(
     // Use sys::ecs_search to find the target for the relationship in the table
     let mut match_id: sys::ecs_id_t = Default::default();
     let world = unsafe { WorldRef::from_ptr(world) };
-    let id = IdView::new_from_id(world, (id, flecs::Wildcard::ID)).id();
+    let id = IdView::new_from_id(world, (id, flecs::Wildcard)).id();
     if unsafe { sys::ecs_search(world.world_ptr_mut(), table, *id, &mut match_id) } != -1 {
         *IdView::new_from_id(world, match_id).second_id().id() // First, Second or Third
     } else {
@@ -31,7 +31,7 @@ fn main() {
     // Wildcards can be used to match relationships
     println!(
         "Bob grows food? {}, {}",
-        bob.has_id((grows, flecs::Wildcard::ID)),
+        bob.has_id((grows, flecs::Wildcard)),
         //or you can do
         bob.has_second::<flecs::Wildcard>(grows)
     );
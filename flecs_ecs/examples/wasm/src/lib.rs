@@ -0,0 +1,63 @@
+//! Moves a few entities around in the browser, see the README for how to run it.
+
+use flecs_ecs::prelude::*;
+use wasm_bindgen::prelude::*;
+
+#[derive(Component, Debug)]
+#[meta]
+struct Position {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Component, Debug)]
+#[meta]
+struct Velocity {
+    x: f32,
+    y: f32,
+}
+
+/// The world of the page, driven by `requestAnimationFrame` in `index.html`.
+#[wasm_bindgen]
+pub struct App {
+    world: World,
+}
+
+#[wasm_bindgen]
+impl App {
+    #[wasm_bindgen(constructor)]
+    #[allow(clippy::new_without_default)]
+    pub fn new() -> App {
+        let world = World::new();
+
+        world
+            .system_named::<(&mut Position, &Velocity)>("Move")
+            .each_iter(|it, _, (pos, vel)| {
+                pos.x += vel.x * it.delta_time();
+                pos.y += vel.y * it.delta_time();
+            });
+
+        for i in 0..5 {
+            world
+                .entity_named(&format!("e{i}"))
+                .set(Position { x: 0.0, y: 0.0 })
+                .set(Velocity {
+                    x: 1.0 + i as f32,
+                    y: 0.5 * i as f32,
+                });
+        }
+
+        App { world }
+    }
+
+    /// Run a frame and return the positions of the entities, one per line.
+    pub fn frame(&self, delta_time: f32) -> String {
+        self.world.progress_time(delta_time);
+
+        let mut out = String::new();
+        self.world.each_entity::<&Position>(|e, pos| {
+            out.push_str(&format!("{}: ({:.1}, {:.1})\n", e.name(), pos.x, pos.y));
+        });
+        out
+    }
+}
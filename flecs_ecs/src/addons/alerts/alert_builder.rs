@@ -92,7 +92,7 @@ where
 
     /// Create a new `AlertBuilder` with a name
     pub(crate) fn new_named(world: &'a World, name: &str) -> Self {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
 
         let mut obj = Self {
             desc: Default::default(),
@@ -380,14 +380,14 @@ where
     where
         With: ComponentId,
     {
-        let member_name = compact_str::format_compact!("{}\0", member_name);
+        let c_member_name = SmallCString::new(member_name);
         let world = self.world();
         let id = With::id(world);
         let member_id = unsafe {
             sys::ecs_lookup_path_w_sep(
                 world.world_ptr_mut(),
                 id,
-                member_name.as_ptr() as *const _,
+                c_member_name.as_ptr() as *const _,
                 SEPARATOR.as_ptr(),
                 SEPARATOR.as_ptr(),
                 false,
@@ -0,0 +1,209 @@
+//! Run futures for entities and apply their results to the world, see
+//! [`EntityView::spawn_task()`].
+//!
+//! Tasks run outside of the world, on an executor set with [`World::set_task_spawner()`], such as
+//! a tokio or async-std runtime. When a task finishes, its result is sent through the
+//! [`CommandSender`] of the world and applied at the start of the next frame, by the system that
+//! applies sent commands.
+
+extern crate std;
+
+use core::future::Future;
+use core::marker::PhantomData;
+use core::pin::Pin;
+use core::sync::atomic::{AtomicBool, Ordering};
+use core::task::{Context, Poll, Waker};
+
+use crate::core::*;
+use flecs_ecs_derive::Component;
+
+extern crate alloc;
+use alloc::boxed::Box;
+use alloc::sync::{Arc, Weak};
+
+/// A task passed to the spawner set with [`World::set_task_spawner()`].
+pub type TaskFuture = Pin<Box<dyn Future<Output = ()> + Send>>;
+
+pub(crate) type TaskSpawner = Box<dyn Fn(TaskFuture)>;
+
+#[derive(Default)]
+struct TaskState {
+    finished: AtomicBool,
+}
+
+/// Component of an entity that waits for a task with a result of type `T`, added by
+/// [`EntityView::spawn_task()`].
+///
+/// The handle is removed when the result of the task is applied. Removing the handle, deleting
+/// the entity, or spawning another task with the same result type for the entity cancels the
+/// task: the future is dropped the next time it is polled and its result is discarded.
+#[derive(Component)]
+pub struct TaskHandle<T: Send + 'static> {
+    state: Arc<TaskState>,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Send + 'static> TaskHandle<T> {
+    /// Test if the task finished, and its result waits to be applied at the start of the next
+    /// frame.
+    pub fn is_finished(&self) -> bool {
+        self.state.finished.load(Ordering::Acquire)
+    }
+}
+
+/// A future that completes without output once its [`TaskHandle`] is dropped.
+struct Cancellable<F: Future> {
+    future: Pin<Box<F>>,
+    state: Weak<TaskState>,
+}
+
+impl<F: Future> Future for Cancellable<F> {
+    type Output = Option<F::Output>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.state.strong_count() == 0 {
+            return Poll::Ready(None);
+        }
+        this.future.as_mut().poll(cx).map(Some)
+    }
+}
+
+struct ThreadWaker(std::thread::Thread);
+
+impl alloc::task::Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Run a task to completion on the current thread.
+fn block_on(mut task: TaskFuture) {
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    while task.as_mut().poll(&mut cx).is_pending() {
+        std::thread::park();
+    }
+}
+
+impl World {
+    /// Set the function that runs the tasks spawned with [`EntityView::spawn_task()`].
+    ///
+    /// Without a spawner, every task runs on a thread of its own.
+    ///
+    /// # Example
+    ///
+    /// ```ignore
+    /// let runtime = tokio::runtime::Runtime::new().unwrap();
+    /// let handle = runtime.handle().clone();
+    /// world.set_task_spawner(move |task| {
+    ///     handle.spawn(task);
+    /// });
+    /// ```
+    pub fn set_task_spawner(&self, spawn: impl Fn(TaskFuture) + 'static) {
+        self.world_ctx_mut().task_spawner = Some(Box::new(spawn));
+    }
+
+    fn spawn_task_future(&self, task: TaskFuture) {
+        match &self.world_ctx().task_spawner {
+            Some(spawn) => spawn(task),
+            None => {
+                std::thread::spawn(move || block_on(task));
+            }
+        }
+    }
+}
+
+impl<'a> EntityView<'a> {
+    /// Run a future for the entity, and set its output as a component of the entity once it
+    /// finishes.
+    ///
+    /// The entity gets a [`TaskHandle<T>`] until the output is set at the start of the frame
+    /// after the future finished.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// # use flecs_ecs::addons::async_task::TaskHandle;
+    /// #[derive(Component, Debug, PartialEq)]
+    /// struct Loaded(u32);
+    ///
+    /// let world = World::new();
+    /// let e = world.entity().spawn_task(async { Loaded(42) });
+    /// assert!(e.has::<TaskHandle<Loaded>>());
+    ///
+    /// while e.has::<TaskHandle<Loaded>>() {
+    ///     world.progress();
+    /// }
+    /// e.get::<&Loaded>(|loaded| assert_eq!(loaded.0, 42));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::spawn_task_with()`]
+    /// * [`World::set_task_spawner()`]
+    pub fn spawn_task<T>(self, future: impl Future<Output = T> + Send + 'static) -> Self
+    where
+        T: ComponentId + DataComponent + Send,
+    {
+        self.spawn_task_with(future, |entity, output| {
+            entity.set(output);
+        })
+    }
+
+    /// Run a future for the entity, and call `apply` with its output once it finishes.
+    ///
+    /// The entity gets a [`TaskHandle<T>`] until `apply` is called at the start of the frame after
+    /// the future finished. `apply` is not called when the task is cancelled.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::spawn_task()`]
+    pub fn spawn_task_with<T>(
+        self,
+        future: impl Future<Output = T> + Send + 'static,
+        apply: impl FnOnce(EntityView, T) + Send + 'static,
+    ) -> Self
+    where
+        T: Send + 'static,
+    {
+        let world = self.world();
+        let state = Arc::new(TaskState::default());
+        let task = Cancellable {
+            future: Box::pin(future),
+            state: Arc::downgrade(&state),
+        };
+        let weak_state = Arc::downgrade(&state);
+        self.set(TaskHandle::<T> {
+            state,
+            _marker: PhantomData,
+        });
+
+        let sender = world.command_sender();
+        let entity = self.id();
+        world.spawn_task_future(Box::pin(async move {
+            let Some(output) = task.await else {
+                return;
+            };
+            let Some(state) = weak_state.upgrade() else {
+                return;
+            };
+            state.finished.store(true, Ordering::Release);
+            drop(state);
+
+            sender.run(entity, move |entity| {
+                let is_current = entity
+                    .try_get::<&TaskHandle<T>>(|handle| {
+                        core::ptr::eq(Arc::as_ptr(&handle.state), weak_state.as_ptr())
+                    })
+                    .unwrap_or(false);
+                if is_current {
+                    entity.remove::<TaskHandle<T>>();
+                    apply(entity, output);
+                }
+            });
+        }));
+        self
+    }
+}
@@ -553,7 +553,7 @@ impl World {
     #[doc(alias = "world::set_doc_name")]
     #[inline(always)]
     pub fn set_doc_name_id(&self, entity: impl Into<Entity>, name: &str) {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
         unsafe { sys::ecs_doc_set_name(self.ptr_mut(), *entity.into(), name.as_ptr() as *const _) };
     }
 
@@ -0,0 +1,212 @@
+//! Buffered events that systems send and read, enabled with the `flecs_events` feature.
+//!
+//! Unlike events emitted with [`World::event()`], which are handled right away by observers of an
+//! entity, [`Events<T>`] is a singleton that buffers events of type `T`. Any system can send
+//! events, and every system that reads them keeps its own [`EventReader`], so each system sees an
+//! event once no matter in which order the systems run. Events are dropped after they were
+//! buffered for two updates, which happen at the start of every frame.
+//!
+//! # Example
+//!
+//! ```
+//! use flecs_ecs::addons::events::*;
+//! use flecs_ecs::prelude::*;
+//!
+//! #[derive(Component)]
+//! struct Health(i32);
+//!
+//! struct Damage {
+//!     target: Entity,
+//!     amount: i32,
+//! }
+//!
+//! let world = World::new();
+//! world.add_events::<Damage>();
+//!
+//! let goblin = world.entity().set(Health(10));
+//! let target = goblin.id();
+//!
+//! world
+//!     .system::<&mut Events<Damage>>()
+//!     .term_at(0)
+//!     .singleton()
+//!     .each(move |events| events.send(Damage { target, amount: 3 }));
+//!
+//! let mut reader = EventReader::<Damage>::default();
+//! world
+//!     .system::<&Events<Damage>>()
+//!     .term_at(0)
+//!     .singleton()
+//!     .each_iter(move |it, _, events| {
+//!         let world = it.world();
+//!         for damage in reader.read(events) {
+//!             let target = world.entity_from_id(damage.target);
+//!             target.get::<&mut Health>(|health| health.0 -= damage.amount);
+//!         }
+//!     });
+//!
+//! world.progress();
+//! world.progress();
+//! goblin.get::<&Health>(|health| assert_eq!(health.0, 4));
+//! ```
+
+use core::marker::PhantomData;
+
+use crate::core::*;
+use crate::prelude::*;
+
+extern crate alloc;
+use alloc::collections::VecDeque;
+
+/// Singleton that buffers events of type `T`, added with [`World::add_events()`].
+///
+/// # See also
+///
+/// * [`EventReader`]
+/// * [`addons::events`](crate::addons::events)
+#[derive(Component)]
+pub struct Events<T: Send + Sync + 'static> {
+    events: VecDeque<T>,
+    /// The id of the first buffered event.
+    start: u64,
+    /// The number of buffered events that were sent before the last update.
+    previous: usize,
+}
+
+impl<T: Send + Sync + 'static> Default for Events<T> {
+    fn default() -> Self {
+        Self {
+            events: VecDeque::new(),
+            start: 0,
+            previous: 0,
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> Events<T> {
+    /// Send an event.
+    pub fn send(&mut self, event: T) {
+        self.events.push_back(event);
+    }
+
+    /// Take all buffered events, also the ones readers haven't read yet.
+    pub fn drain(&mut self) -> impl Iterator<Item = T> + '_ {
+        self.start += self.events.len() as u64;
+        self.previous = 0;
+        self.events.drain(..)
+    }
+
+    /// Drop the events that were buffered for two updates.
+    ///
+    /// This is done at the start of every frame by a system added with [`World::add_events()`],
+    /// so it only has to be called directly when the world is not progressed.
+    pub fn update(&mut self) {
+        self.events.drain(..self.previous);
+        self.start += self.previous as u64;
+        self.previous = self.events.len();
+    }
+
+    /// Iterate the buffered events.
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.events.iter()
+    }
+
+    /// The number of buffered events.
+    pub fn len(&self) -> usize {
+        self.events.len()
+    }
+
+    /// Test if no events are buffered.
+    pub fn is_empty(&self) -> bool {
+        self.events.is_empty()
+    }
+
+    fn end(&self) -> u64 {
+        self.start + self.events.len() as u64
+    }
+}
+
+/// A cursor in the [`Events<T>`] of a world, which reads every event once.
+///
+/// A system that reads events owns a reader, usually by moving it into its callback. A new reader
+/// reads all buffered events. Events that are dropped before a reader reads them are missed.
+pub struct EventReader<T: Send + Sync + 'static> {
+    /// The id of the next event to read.
+    cursor: u64,
+    _marker: PhantomData<fn() -> T>,
+}
+
+impl<T: Send + Sync + 'static> Default for EventReader<T> {
+    fn default() -> Self {
+        Self {
+            cursor: 0,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: Send + Sync + 'static> EventReader<T> {
+    /// Iterate the events that this reader hasn't read yet.
+    pub fn read<'e>(&mut self, events: &'e Events<T>) -> impl Iterator<Item = &'e T> + 'e {
+        let skip = self.cursor.saturating_sub(events.start) as usize;
+        self.cursor = events.end();
+        events.events.iter().skip(skip)
+    }
+
+    /// The number of events that this reader hasn't read yet.
+    pub fn len(&self, events: &Events<T>) -> usize {
+        events.end().saturating_sub(self.cursor.max(events.start)) as usize
+    }
+
+    /// Test if this reader has read all events.
+    pub fn is_empty(&self, events: &Events<T>) -> bool {
+        self.len(events) == 0
+    }
+}
+
+impl World {
+    /// Add the [`Events<T>`] singleton, with a system that updates it at the start of every
+    /// frame. Does nothing if the events are already added.
+    ///
+    /// # See also
+    ///
+    /// * [`addons::events`](crate::addons::events)
+    pub fn add_events<T: Send + Sync + 'static>(&self) {
+        if self.has::<Events<T>>() {
+            return;
+        }
+        self.set(Events::<T>::default());
+        self.system::<&mut Events<T>>()
+            .term_at(0)
+            .singleton()
+            .kind::<flecs::pipeline::OnLoad>()
+            .each(Events::update);
+    }
+
+    /// Send an event to the [`Events<T>`] singleton, which is added if it doesn't exist yet.
+    ///
+    /// When the world is deferred, the singleton is still added right away, so that events sent
+    /// before the deferred operations are flushed aren't lost.
+    ///
+    /// # Panics
+    ///
+    /// If the world is read-only, such as inside a system, and [`Events<T>`] wasn't added yet. Use
+    /// [`World::add_events()`] before running the systems that send events of a new type.
+    ///
+    /// # See also
+    ///
+    /// * [`Events::send()`]
+    pub fn send_event<T: Send + Sync + 'static>(&self, event: T) {
+        if !self.has::<Events<T>>() {
+            let suspend = self.is_deferred() && !self.is_readonly();
+            if suspend {
+                self.defer_suspend();
+            }
+            self.add_events::<T>();
+            if suspend {
+                self.defer_resume();
+            }
+        }
+        self.get::<&mut Events<T>>(|events| events.send(event));
+    }
+}
@@ -0,0 +1,172 @@
+//! Component level difference between two entities.
+
+use flecs_ecs::sys;
+
+use crate::core::*;
+
+extern crate alloc;
+use alloc::{string::String, vec::Vec};
+
+/// Difference in components between two entities, created with [`EntityView::diff()`].
+///
+/// Component values are stored as JSON, which requires the component to have reflection data.
+/// Components without reflection data are only compared by presence. The identity and place in
+/// the hierarchy of the entities, such as their name, parent, prefab and whether they're disabled,
+/// is not part of the difference.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct EntityDiff {
+    /// Ids the other entity has, but this entity doesn't, with their value if the id has data.
+    pub added: Vec<(Id, Option<String>)>,
+    /// Ids this entity has, but the other entity doesn't.
+    pub removed: Vec<Id>,
+    /// Components both entities have, with the value of the other entity.
+    pub changed: Vec<(Id, String)>,
+}
+
+impl EntityDiff {
+    /// Returns true if there is no difference between the entities.
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.removed.is_empty() && self.changed.is_empty()
+    }
+}
+
+/// Returns true for ids that are part of the identity of an entity instead of its components.
+pub(crate) fn is_structural(id: Id) -> bool {
+    if id.is_pair() {
+        let first = id.first();
+        first == flecs::Identifier::ID
+            || first == flecs::ChildOf::ID
+            || first == flecs::IsA::ID
+            || first == flecs::Poly::ID
+    } else {
+        *id == flecs::Prefab::ID || *id == flecs::Disabled::ID
+    }
+}
+
+/// Serialize the value of `id` on `entity`, or `None` if it has no data or no reflection data.
+pub(crate) fn value_to_json(entity: EntityView, id: Id) -> Option<String> {
+    let world = entity.world();
+    let type_id = unsafe { sys::ecs_get_typeid(world.world_ptr(), *id) };
+    if type_id == 0
+        || !unsafe { sys::ecs_has_id(world.world_ptr(), type_id, flecs::meta::Type::ID) }
+    {
+        return None;
+    }
+
+    let ptr = entity.get_untyped(id);
+    if ptr.is_null() {
+        return None;
+    }
+
+    Some(world.to_json_id(type_id, ptr))
+}
+
+/// Add `id` without a value, unless flecs can't construct a value for it, which is the case for
+/// components without [`Default`]. Returns whether the id was added.
+pub(crate) fn add_without_value(entity: EntityView, id: Id) -> bool {
+    let world = entity.world();
+    let is_tag = unsafe { sys::ecs_get_typeid(world.world_ptr(), *id) } == 0;
+    if !is_tag && !has_default_hook(world.world_ptr(), *id) {
+        return false;
+    }
+    unsafe { sys::ecs_add_id(world.world_ptr_mut(), *entity.id(), *id) };
+    true
+}
+
+impl EntityView<'_> {
+    /// Compute the difference in components between this entity and `other`.
+    ///
+    /// Applying the result to this entity with [`EntityView::apply_diff()`] makes its components
+    /// equal to the components of `other`, which can be used for undo/redo stacks.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// #[meta]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.component::<Position>().meta();
+    ///
+    /// let e = world.entity().set(Position { x: 1.0, y: 2.0 });
+    /// let snapshot = world.entity().set(Position { x: 1.0, y: 2.0 });
+    ///
+    /// e.set(Position { x: 10.0, y: 20.0 });
+    ///
+    /// // undo
+    /// let diff = e.diff(snapshot);
+    /// assert_eq!(diff.changed.len(), 1);
+    /// e.apply_diff(&diff);
+    ///
+    /// e.get::<&Position>(|p| assert_eq!(p.x, 1.0));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::apply_diff()`]
+    pub fn diff(self, other: impl Into<Entity>) -> EntityDiff {
+        let other = EntityView::new_from(self.world, other.into());
+        let self_archetype = self.archetype();
+        let other_archetype = other.archetype();
+        let self_ids = self_archetype.as_slice();
+        let other_ids = other_archetype.as_slice();
+
+        let mut diff = EntityDiff::default();
+
+        for &id in other_ids.iter().filter(|&&id| !is_structural(id)) {
+            if self_ids.contains(&id) {
+                if let Some(other_value) = value_to_json(other, id) {
+                    if value_to_json(self, id).as_ref() != Some(&other_value) {
+                        diff.changed.push((id, other_value));
+                    }
+                }
+            } else {
+                diff.added.push((id, value_to_json(other, id)));
+            }
+        }
+
+        for &id in self_ids.iter().filter(|&&id| !is_structural(id)) {
+            if !other_ids.contains(&id) {
+                diff.removed.push(id);
+            }
+        }
+
+        diff
+    }
+
+    /// Apply a difference created with [`EntityView::diff()`] to this entity.
+    ///
+    /// Added components without reflection data have no value in the difference, so they're added
+    /// with their [`Default`] value. Added components that don't implement [`Default`] are skipped.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::diff()`]
+    pub fn apply_diff(self, diff: &EntityDiff) -> Self {
+        for &id in diff.removed.iter().filter(|&&id| !is_structural(id)) {
+            self.remove_id(id);
+        }
+
+        for (id, value) in diff.added.iter().filter(|(id, _)| !is_structural(*id)) {
+            match value {
+                Some(json) => {
+                    self.set_json_id(*id, json, None);
+                }
+                None => {
+                    add_without_value(self, *id);
+                }
+            }
+        }
+
+        for (id, json) in diff.changed.iter().filter(|(id, _)| !is_structural(*id)) {
+            self.set_json_id(*id, json, None);
+        }
+
+        self
+    }
+}
@@ -0,0 +1,343 @@
+//! Stable entity identifiers for save/load, see [`EntityView::guid()`].
+
+use core::ffi::{CStr, c_char, c_void};
+use core::hash::BuildHasher;
+
+use flecs_ecs::sys;
+
+use crate::addons::meta::{EcsSerializer, Serializer};
+use crate::core::*;
+use flecs_ecs_derive::Component;
+
+extern crate alloc;
+use alloc::{format, string::String, vec::Vec};
+
+/// An identifier of an entity that stays the same when the world is saved and loaded again,
+/// unlike the entity id. Assigned with [`EntityView::guid()`] and resolved with
+/// [`World::resolve_guid()`].
+///
+/// Guids are serialized to JSON as numbers, which flecs reads back as signed integers, so guids
+/// that are set directly should not be larger than [`i64::MAX`].
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[repr(C)]
+pub struct Guid(pub u64);
+
+/// Entities by their [`Guid`], stored per world, see [`World::enable_guids()`].
+#[derive(Default)]
+pub(crate) struct GuidIndex {
+    entities: hashbrown::HashMap<Guid, Entity>,
+    guids: hashbrown::HashMap<Entity, Guid>,
+}
+
+impl GuidIndex {
+    fn insert(&mut self, entity: Entity, guid: Guid) {
+        self.remove(entity);
+        self.entities.insert(guid, entity);
+        self.guids.insert(entity, guid);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some(guid) = self.guids.remove(&entity) {
+            self.entities.remove(&guid);
+        }
+    }
+
+    /// Create a guid that isn't used by an entity of the world.
+    fn generate(&self) -> Guid {
+        loop {
+            let hash = foldhash::fast::RandomState::default().hash_one(self.guids.len());
+            let guid = Guid(hash & i64::MAX as u64);
+            if guid.0 != 0 && !self.entities.contains_key(&guid) {
+                return guid;
+            }
+        }
+    }
+}
+
+fn index_mut<'a>(world: *mut sys::ecs_world_t) -> Option<&'a mut GuidIndex> {
+    unsafe { (*World::get_context(world)).guids.as_mut() }
+}
+
+/// Maps the entities of a world saved with [`World::to_json_world_with_guids()`] to the entities
+/// of the world it's loaded into.
+struct GuidLookup {
+    world: *mut sys::ecs_world_t,
+    /// The guids of the saved anonymous entities, by the id they were serialized with.
+    saved: hashbrown::HashMap<u32, Guid>,
+    entities: hashbrown::HashMap<u32, Entity>,
+}
+
+unsafe extern "C-unwind" fn lookup_guid(
+    world: *const sys::ecs_world_t,
+    name: *const c_char,
+    ctx: *mut c_void,
+) -> sys::ecs_entity_t {
+    let lookup = unsafe { &mut *(ctx as *mut GuidLookup) };
+    let world = world as *mut sys::ecs_world_t;
+    let index = index_mut(lookup.world);
+
+    let anonymous_id = unsafe { CStr::from_ptr(name) }
+        .to_str()
+        .ok()
+        .and_then(|name| name.strip_prefix('#'))
+        .and_then(|id| id.parse::<u32>().ok());
+
+    match anonymous_id {
+        Some(id) => {
+            let entity = *lookup.entities.entry(id).or_insert_with(|| {
+                lookup
+                    .saved
+                    .get(&id)
+                    .and_then(|guid| index?.entities.get(guid).copied())
+                    .unwrap_or_else(|| Entity::new(unsafe { sys::ecs_new(world) }))
+            });
+            *entity
+        }
+        None => {
+            let entity = unsafe {
+                sys::ecs_lookup_path_w_sep(world, 0, name, c".".as_ptr(), core::ptr::null(), false)
+            };
+            if entity != 0 {
+                return entity;
+            }
+            let desc = sys::ecs_entity_desc_t {
+                name,
+                ..Default::default()
+            };
+            unsafe { sys::ecs_entity_init(world, &desc) }
+        }
+    }
+}
+
+/// The entity ids and guids of the anonymous entities of a saved world, as a flat list which flecs
+/// serializes as a JSON array.
+#[derive(Component, Default)]
+struct SavedGuids(Vec<u64>);
+
+fn ensure_saved_guid(data: &mut SavedGuids, elem: usize) -> &mut u64 {
+    if data.0.len() <= elem {
+        data.0.resize(elem + 1, 0);
+    }
+    &mut data.0[elem]
+}
+
+/// Split JSON created by [`World::to_json_world_with_guids()`] into the guids of the anonymous
+/// entities and the JSON of the world.
+fn parse_saved_guids<'a>(
+    world: &World,
+    json: &'a str,
+) -> Option<(hashbrown::HashMap<u32, Guid>, &'a str)> {
+    let rest = json.trim().strip_prefix("{\"guids\":")?;
+    let json_c = compact_str::format_compact!("{}\0", rest);
+
+    let mut saved = SavedGuids::default();
+    let end = unsafe {
+        sys::ecs_ptr_from_json(
+            world.world_ptr(),
+            *world.component_id::<SavedGuids>(),
+            &mut saved as *mut SavedGuids as *mut c_void,
+            json_c.as_ptr() as *const c_char,
+            core::ptr::null(),
+        )
+    };
+    if end.is_null() || saved.0.len() % 2 != 0 {
+        return None;
+    }
+
+    let parsed = unsafe { end.offset_from(json_c.as_ptr() as *const c_char) } as usize;
+    let world = rest[parsed..]
+        .trim_start()
+        .strip_prefix(",\"world\":")?
+        .strip_suffix('}')?;
+    let saved = saved
+        .0
+        .chunks_exact(2)
+        .map(|entry| (entry[0] as u32, Guid(entry[1])))
+        .collect();
+    Some((saved, world))
+}
+
+impl EntityView<'_> {
+    /// Get the [`Guid`] of the entity, assigning a new one if it doesn't have one yet.
+    ///
+    /// While the world is deferred, the [`Guid`] component is only set when the world is merged,
+    /// but the guid is returned and resolved right away.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    /// let e = world.entity();
+    ///
+    /// let guid = e.guid();
+    /// assert_eq!(e.guid(), guid);
+    /// assert_eq!(world.resolve_guid(guid).unwrap(), e);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::resolve_guid()`]
+    pub fn guid(self) -> Guid {
+        let world = self.world().real_world();
+        world.enable_guids();
+
+        let guid = {
+            let index = world.world_ctx_mut().guids.as_mut().unwrap();
+            if let Some(&guid) = index.guids.get(&self.id()) {
+                return guid;
+            }
+            // index the guid right away, as the set is only merged later when the world is deferred
+            let guid = index.generate();
+            index.insert(self.id(), guid);
+            guid
+        };
+        self.set(guid);
+        guid
+    }
+}
+
+impl World {
+    /// Keep an index of the entities with a [`Guid`], which is required to resolve them.
+    ///
+    /// The index is enabled by the other guid operations, so this only needs to be called
+    /// before setting [`Guid`] directly, such as to give entities a guid that is known up front.
+    /// It also registers reflection data for [`Guid`], so it is serialized to JSON.
+    pub fn enable_guids(&self) {
+        if self.world_ctx().guids.is_some() {
+            return;
+        }
+
+        self.component::<Guid>().member::<u64>("value");
+        self.component::<SavedGuids>()
+            .opaque_collection_vector::<u64>()
+            .serialize(|s: &Serializer, data: &SavedGuids| {
+                for guid in &data.0 {
+                    s.value(guid);
+                }
+                0
+            })
+            .count(|data: &mut SavedGuids| data.0.len())
+            .ensure_element(ensure_saved_guid)
+            .resize(|data: &mut SavedGuids, size: usize| data.0.resize(size, 0));
+
+        let mut index = GuidIndex::default();
+        self.query::<&Guid>()
+            .query_flags(QueryFlags::MatchPrefab | QueryFlags::MatchDisabled)
+            .build()
+            .each_entity(|e, guid| index.insert(e.id(), *guid));
+        self.world_ctx_mut().guids = Some(index);
+
+        self.observer::<flecs::OnSet, &Guid>()
+            .add_event::<flecs::OnRemove>()
+            .each_iter(|it, row, guid| {
+                let Some(index) = index_mut(it.world().world_ptr_mut()) else {
+                    return;
+                };
+                let entity = it.entity(row).id();
+                if it.event().id() == flecs::OnSet::ID {
+                    index.insert(entity, *guid);
+                } else {
+                    index.remove(entity);
+                }
+            });
+    }
+
+    /// Find the entity with a [`Guid`].
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::guid()`]
+    pub fn resolve_guid(&self, guid: Guid) -> Option<EntityView<'_>> {
+        self.enable_guids();
+        self.world_ctx()
+            .guids
+            .as_ref()
+            .and_then(|index| index.entities.get(&guid))
+            .map(|&e| EntityView::new_from(self, e))
+            // entities that were deleted before a deferred guid was set stay in the index
+            .filter(|e| e.is_alive())
+    }
+
+    /// Serialize the world to JSON that can be loaded with
+    /// [`World::from_json_world_with_guids()`].
+    ///
+    /// The JSON contains the world as serialized by [`World::to_json_world()`], together with the
+    /// guids of the anonymous entities.
+    pub fn to_json_world_with_guids(&self, desc: Option<&super::WorldToJsonDesc>) -> String {
+        self.enable_guids();
+
+        let mut guids = SavedGuids::default();
+        self.query::<&Guid>()
+            .query_flags(QueryFlags::MatchPrefab | QueryFlags::MatchDisabled)
+            .build()
+            .each_entity(|e, guid| guids.0.extend([*e.id() as u32 as u64, guid.0]));
+
+        format!(
+            "{{\"guids\":{},\"world\":{}}}",
+            self.to_json::<SavedGuids>(&guids),
+            self.to_json_world(desc)
+        )
+    }
+
+    /// Deserialize JSON created with [`World::to_json_world_with_guids()`] into the world.
+    ///
+    /// Entities with a [`Guid`] that already exists in this world are loaded into the entity with
+    /// that guid, other anonymous entities are created. Named entities are matched by their path.
+    /// Entity references inside component values are mapped to the same entities, so they stay
+    /// valid when the ids of the entities change.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// #[meta]
+    /// struct Health {
+    ///     value: i32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.component::<Health>().meta();
+    ///
+    /// let e = world.entity().set(Health { value: 10 });
+    /// let guid = e.guid();
+    /// let json = world.to_json_world_with_guids(None);
+    ///
+    /// let world = World::new();
+    /// world.component::<Health>().meta();
+    /// world.from_json_world_with_guids(&json).unwrap();
+    ///
+    /// world
+    ///     .resolve_guid(guid)
+    ///     .unwrap()
+    ///     .get::<&Health>(|health| assert_eq!(health.value, 10));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::from_json_world()`]
+    pub fn from_json_world_with_guids(&self, json: &str) -> Result<&Self, FlecsError> {
+        self.enable_guids();
+
+        let (saved, world_json) = parse_saved_guids(self, json)
+            .ok_or_else(|| FlecsError::ParseError("expected JSON with guids".into()))?;
+
+        let mut lookup = GuidLookup {
+            world: self.world_ptr_mut(),
+            saved,
+            entities: hashbrown::HashMap::new(),
+        };
+        let desc = super::FromJsonDesc {
+            name: core::ptr::null(),
+            expr: core::ptr::null(),
+            lookup_action: Some(lookup_guid),
+            lookup_ctx: &mut lookup as *mut GuidLookup as *mut c_void,
+            strict: false,
+        };
+
+        self.try_from_json_world(world_json, Some(&desc))
+    }
+}
@@ -0,0 +1,127 @@
+//! Upgrade components of deserialized worlds that were saved with an older component layout.
+
+use flecs_ecs::sys;
+
+use crate::core::*;
+
+extern crate alloc;
+use alloc::{boxed::Box, vec::Vec};
+
+/// Converts the value of an old component on an entity to its new component.
+pub(crate) struct ComponentMigration {
+    from: Entity,
+    migrate: Box<dyn Fn(EntityView)>,
+}
+
+impl World {
+    /// Register a function that upgrades component `From` to component `To`.
+    ///
+    /// After a world is deserialized with [`World::from_json_world()`], every entity that has
+    /// `From` gets `To` set to the result of `migrate` and `From` removed. The old type must stay
+    /// registered with reflection data so the saved values can be deserialized.
+    ///
+    /// Migrations run in the order they are registered, which allows chaining upgrades, e.g.
+    /// `V1` to `V2` followed by `V2` to `V3`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// #[meta]
+    /// struct PositionV1 {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// #[meta]
+    /// struct PositionV2 {
+    ///     x: f32,
+    ///     y: f32,
+    ///     z: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.component::<PositionV1>().meta();
+    /// world.component::<PositionV2>().meta();
+    ///
+    /// world.register_migration(|p: &PositionV1| PositionV2 {
+    ///     x: p.x,
+    ///     y: p.y,
+    ///     z: 0.0,
+    /// });
+    ///
+    /// // a save made before PositionV2 existed
+    /// let e = world.entity_named("e").set(PositionV1 { x: 1.0, y: 2.0 });
+    /// let json = world.to_json_world(None);
+    /// e.destruct();
+    ///
+    /// world.from_json_world(&json, None);
+    ///
+    /// let e = world.lookup("e");
+    /// assert!(!e.has::<PositionV1>());
+    /// e.get::<&PositionV2>(|p| assert_eq!((p.x, p.y, p.z), (1.0, 2.0, 0.0)));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::apply_migrations()`]
+    pub fn register_migration<From, To>(&self, migrate: impl Fn(&From) -> To + 'static) -> &Self
+    where
+        From: ComponentId + DataComponent,
+        To: ComponentId + DataComponent,
+    {
+        let from = Entity::new(From::id(self));
+        let migration = ComponentMigration {
+            from,
+            migrate: Box::new(move |entity: EntityView| {
+                let value = entity.get_untyped(from) as *const From;
+                if value.is_null() {
+                    return;
+                }
+                let value = migrate(unsafe { &*value });
+                entity.set(value).remove_id(from);
+            }),
+        };
+        self.world_ctx_mut().migrations.push(migration);
+        self
+    }
+
+    /// Run the registered migrations on all entities in the world.
+    ///
+    /// This is done automatically when deserializing a world from JSON, but can be used to
+    /// upgrade worlds that were loaded in another way.
+    ///
+    /// # See also
+    ///
+    /// * [`World::register_migration()`]
+    pub fn apply_migrations(&self) -> &Self {
+        // the migrations are taken out of the world while they run, as they can register
+        // migrations themselves, e.g. from an observer of the component they set
+        let migrations = core::mem::take(&mut self.world_ctx_mut().migrations);
+        for migration in &migrations {
+            let mut entities = Vec::new();
+            let mut it = unsafe { sys::ecs_each_id(self.world_ptr(), *migration.from) };
+            while unsafe { sys::ecs_each_next(&mut it) } {
+                for i in 0..it.count as usize {
+                    entities.push(unsafe { *it.entities.add(i) });
+                }
+            }
+
+            for entity in entities {
+                // an earlier migration of the same run can have deleted the entity or removed
+                // the component
+                let entity = self.entity_from_id(entity);
+                if entity.is_alive() && entity.owns_id(migration.from) {
+                    (migration.migrate)(entity);
+                }
+            }
+        }
+
+        let ctx = self.world_ctx_mut();
+        let registered = core::mem::replace(&mut ctx.migrations, migrations);
+        ctx.migrations.extend(registered);
+        self
+    }
+}
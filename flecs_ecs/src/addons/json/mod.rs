@@ -10,6 +10,21 @@ use crate::core::*;
 
 use super::meta::FetchedId;
 
+mod entity_diff;
+pub use entity_diff::*;
+mod guid;
+pub(crate) use guid::GuidIndex;
+pub use guid::*;
+mod migration;
+pub(crate) use migration::ComponentMigration;
+mod replay;
+pub use replay::*;
+mod replication;
+pub(crate) use replication::Replication;
+pub use replication::*;
+mod subtree;
+pub use subtree::*;
+
 #[cfg(feature = "std")]
 extern crate std;
 
@@ -22,6 +37,17 @@ pub type WorldToJsonDesc = sys::ecs_world_to_json_desc_t;
 pub type EntityToJsonDesc = sys::ecs_entity_to_json_desc_t;
 pub type IterToJsonDesc = sys::ecs_iter_to_json_desc_t;
 
+/// Run a JSON deserialization function that returns NULL on failure, converting the failure
+/// into a [`FlecsError::ParseError`] with the error message logged by flecs.
+fn try_parse_json(parse: impl FnOnce() -> *const core::ffi::c_char) -> Result<(), FlecsError> {
+    let (result, message) = ecs_os_api::capture_errors(parse);
+    if result.is_null() {
+        Err(FlecsError::ParseError(message.unwrap_or_default()))
+    } else {
+        Ok(())
+    }
+}
+
 impl EntityView<'_> {
     /// Set component or pair id from JSON.
     ///
@@ -64,6 +90,141 @@ impl EntityView<'_> {
         self
     }
 
+    /// Set component or pair id from JSON, returning an error if it can't be deserialized.
+    ///
+    /// If the JSON is invalid, no `OnSet` event is emitted and a component the entity didn't
+    /// have is removed again. A component the entity already had keeps the members that were
+    /// parsed before the error.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// #[meta]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// let pos = world.component::<Position>().meta();
+    ///
+    /// let e = world.entity();
+    /// assert!(matches!(
+    ///     e.try_set_json_id(pos, r#"{"x": 10, "z": 20}"#, None),
+    ///     Err(FlecsError::ParseError(_))
+    /// ));
+    /// assert!(!e.has_id(pos));
+    ///
+    /// assert!(e.try_set_json_id(pos, r#"{"x": 10, "y": 20}"#, None).is_ok());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::set_json_id()`]
+    pub fn try_set_json_id(
+        self,
+        comp: impl IntoId,
+        json: &str,
+        desc: Option<&FromJsonDesc>,
+    ) -> Result<Self, FlecsError> {
+        let comp: u64 = *comp.into();
+        let world = self.world_ptr_mut();
+        let id = *self.id;
+
+        if !self.is_alive() {
+            return Err(FlecsError::NotAlive);
+        }
+
+        let type_ = unsafe { sys::ecs_get_typeid(world, comp) };
+        if type_ == 0 {
+            return Err(FlecsError::NotAComponent);
+        }
+
+        let desc_ptr = desc
+            .map(|d| d as *const FromJsonDesc)
+            .unwrap_or(core::ptr::null());
+        let json = compact_str::format_compact!("{}\0", json);
+
+        unsafe {
+            let had_comp = sys::ecs_owns_id(world, id, comp);
+            let ptr = sys::ecs_ensure_id(world, id, comp);
+            let parsed = try_parse_json(|| {
+                sys::ecs_ptr_from_json(world, type_, ptr, json.as_ptr() as *const _, desc_ptr)
+            });
+            if parsed.is_err() && !had_comp {
+                sys::ecs_remove_id(world, id, comp);
+            }
+            parsed?;
+            sys::ecs_modified_id(world, id, comp);
+        }
+
+        Ok(self)
+    }
+
+    /// Set a component from JSON by the name of the component, for tooling and scripting code
+    /// that only knows components at run time.
+    ///
+    /// The component is found with [`World::try_lookup_component()`], so the name is its path.
+    ///
+    /// # Errors
+    ///
+    /// * [`FlecsError::Unresolved`] with the name if there is no component with that name.
+    /// * The errors of [`EntityView::try_set_json_id()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// #[meta]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.component_named::<Position>("Position").meta();
+    ///
+    /// let e = world
+    ///     .entity()
+    ///     .set_json_by_name("Position", r#"{"x": 10, "y": 20}"#, None)
+    ///     .unwrap();
+    /// e.get::<&Position>(|p| assert_eq!(p.y, 20.0));
+    ///
+    /// assert_eq!(
+    ///     e.set_json_by_name("Velocity", r#"{"x": 1}"#, None),
+    ///     Err(FlecsError::Unresolved("Velocity".into()))
+    /// );
+    /// ```
+    pub fn set_json_by_name(
+        self,
+        name: &str,
+        json: &str,
+        desc: Option<&FromJsonDesc>,
+    ) -> Result<Self, FlecsError> {
+        let world = self.world();
+        let component = world
+            .try_lookup_component(name)
+            .ok_or_else(|| FlecsError::Unresolved(name.into()))?;
+        self.try_set_json_id(component.id(), json, desc)
+    }
+
+    /// Set component or pair from JSON, returning an error if it can't be deserialized.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::set_json()`]
+    /// * [`EntityView::try_set_json_id()`]
+    pub fn try_set_json<T: ComponentOrPairId>(
+        self,
+        json: &str,
+        desc: Option<&FromJsonDesc>,
+    ) -> Result<Self, FlecsError> {
+        self.try_set_json_id(T::get_id(self.world), json, desc)
+    }
+
     /// Set component or pair from JSON.
     ///
     /// # See also
@@ -139,6 +300,21 @@ impl EntityView<'_> {
         }
         self
     }
+
+    /// Deserialize entity from JSON, returning an error if the JSON is invalid.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::from_json()`]
+    pub fn try_from_json(self, json: &str) -> Result<Self, FlecsError> {
+        let world = self.world_ptr_mut();
+        let id = *self.id;
+        let json = compact_str::format_compact!("{}\0", json);
+        try_parse_json(|| unsafe {
+            sys::ecs_entity_from_json(world, id, json.as_ptr() as *const _, core::ptr::null())
+        })?;
+        Ok(self)
+    }
 }
 
 impl World {
@@ -257,6 +433,14 @@ impl World {
 
     /// Deserialize JSON into world.
     ///
+    /// Components with a migration registered with [`World::register_migration()`] are upgraded
+    /// after the world is deserialized.
+    ///
+    /// Named entities are matched by their path, which makes names the stable identity to
+    /// use for save/load. Anonymous entities are serialized by id and bind to the same id
+    /// when it is free or anonymous in this world, otherwise a new id is created. Entity
+    /// references inside component values are resolved through the same mapping.
+    ///
     /// # See also
     ///
     /// * C++ API: `world::from_json`
@@ -273,7 +457,32 @@ impl World {
             sys::ecs_world_from_json(world, json.as_ptr() as *const _, desc_ptr);
         }
 
-        self
+        self.apply_migrations()
+    }
+
+    /// Deserialize JSON into world, returning an error if the JSON is invalid.
+    ///
+    /// Entities that were deserialized before the error was encountered stay in the world.
+    ///
+    /// # See also
+    ///
+    /// * [`World::from_json_world()`]
+    pub fn try_from_json_world(
+        &self,
+        json: &str,
+        desc: Option<&FromJsonDesc>,
+    ) -> Result<&Self, FlecsError> {
+        let world = self.ptr_mut();
+        let json = compact_str::format_compact!("{}\0", json);
+        let desc_ptr = desc
+            .map(|d| d as *const FromJsonDesc)
+            .unwrap_or(core::ptr::null());
+
+        try_parse_json(|| unsafe {
+            sys::ecs_world_from_json(world, json.as_ptr() as *const _, desc_ptr)
+        })?;
+
+        Ok(self.apply_migrations())
     }
 
     /// Deserialize JSON file into world.
@@ -298,6 +507,7 @@ impl World {
             sys::ecs_world_from_json_file(world, json_file.as_ptr() as *const _, desc_ptr);
         }
 
+        self.apply_migrations();
         self
     }
 }
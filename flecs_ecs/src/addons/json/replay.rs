@@ -0,0 +1,331 @@
+//! Record structural operations on a world and replay them into another world.
+
+use crate::core::*;
+
+use super::{add_without_value, value_to_json};
+
+extern crate alloc;
+use alloc::{format, rc::Rc, string::String, vec::Vec};
+use core::cell::RefCell;
+
+/// An operation recorded by a [`Recorder`].
+///
+/// Entities and ids are the ones of the recording world, [`ReplayLog::replay()`] maps them to
+/// the entities of the world the log is replayed into.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum RecordedOp {
+    /// The entity was used for the first time, with its path if it had a name at that point.
+    ///
+    /// Named entities are looked up by path when the log is replayed, and created if they don't
+    /// exist, so entities that existed before recording keep their name.
+    Create(Entity, Option<String>),
+    /// The entity was deleted.
+    Delete(Entity),
+    /// The id was added to the entity.
+    Add(Entity, Id),
+    /// The id was removed from the entity.
+    Remove(Entity, Id),
+    /// The component was set to a value, serialized as JSON.
+    Set(Entity, Id, String),
+    /// The name of the entity was set.
+    SetName(Entity, String),
+}
+
+/// Operations recorded by a [`Recorder`], created with [`Recorder::stop()`].
+#[derive(Debug, Default, Clone)]
+pub struct ReplayLog {
+    /// The recorded operations, in the order they happened.
+    pub ops: Vec<RecordedOp>,
+    /// Paths of the components and other internal entities the recorded ids refer to.
+    paths: hashbrown::HashMap<Entity, String>,
+    /// Entities a [`RecordedOp::Create`] was recorded for.
+    created: hashbrown::HashSet<Entity>,
+}
+
+/// Records the operations done on a world until it is stopped, created with [`World::record()`].
+///
+/// Dropping the recorder stops recording and discards the log.
+pub struct Recorder<'a> {
+    world: WorldRef<'a>,
+    observers: [Entity; 2],
+    log: Rc<RefCell<ReplayLog>>,
+}
+
+/// Test if the entity belongs to flecs or the application, rather than to the recorded state.
+fn is_internal(entity: EntityView) -> bool {
+    entity.has_id(flecs::Component::ID)
+        || entity.has_id(flecs::Module::ID)
+        || entity.has_id((flecs::Poly::ID, flecs::Wildcard::ID))
+        || entity.world().real_world().is_builtin(*entity.id())
+}
+
+impl ReplayLog {
+    fn create(&mut self, entity: EntityView) {
+        if self.created.insert(entity.id()) {
+            let path = entity.get_name().and_then(|_| entity.path());
+            self.ops.push(RecordedOp::Create(entity.id(), path));
+        }
+    }
+
+    fn record(&mut self, world: WorldRef, event: Entity, id: Id, entity: EntityView) {
+        self.create(entity);
+
+        if id.is_pair() && id.first() == flecs::Identifier::ID {
+            if id.second() == flecs::Name::ID && event == flecs::OnSet::ID {
+                if let Some(name) = entity.get_name() {
+                    self.ops.push(RecordedOp::SetName(entity.id(), name));
+                }
+            }
+            return;
+        }
+
+        if id.is_pair() && id.first() == flecs::Poly::ID {
+            return;
+        }
+
+        let parts = if id.is_pair() {
+            [id.first(), id.second()]
+        } else {
+            [Entity::new(*id.remove_flags()), Entity::new(0)]
+        };
+        for part in parts {
+            if part == 0 || self.paths.contains_key(&part) {
+                continue;
+            }
+            let part_entity = world.entity_from_id(part);
+            if is_internal(part_entity) {
+                if let Some(path) = part_entity.path() {
+                    self.paths.insert(part, path);
+                }
+            } else {
+                self.create(part_entity);
+            }
+        }
+
+        let op = if event == flecs::OnAdd::ID {
+            RecordedOp::Add(entity.id(), id)
+        } else if event == flecs::OnSet::ID {
+            match value_to_json(entity, id) {
+                Some(json) => RecordedOp::Set(entity.id(), id, json),
+                None => return,
+            }
+        } else {
+            RecordedOp::Remove(entity.id(), id)
+        };
+
+        self.ops.push(op);
+    }
+
+    fn is_deleting(&self, entity: Entity) -> bool {
+        self.ops.last() == Some(&RecordedOp::Delete(entity))
+    }
+
+    /// Replay the operations into `world`.
+    ///
+    /// Entities of the recording world are created in `world` the first time they are used.
+    /// Components are looked up by path, so they need to be registered before the log is
+    /// replayed. Builtin ids are the same in every world.
+    ///
+    /// Adding a component that doesn't implement [`Default`] is skipped, as it can't be constructed
+    /// without a value. If the component has reflection data, it's added by the
+    /// [`RecordedOp::Set`] that follows.
+    ///
+    /// # Returns
+    ///
+    /// The entities of `world` the recorded entities were mapped to, or
+    /// [`FlecsError::Unresolved`] if a component or other id can't be found in `world`. Operations
+    /// before the failing one are not undone.
+    ///
+    /// # See also
+    ///
+    /// * [`World::record()`]
+    pub fn replay(&self, world: &World) -> Result<hashbrown::HashMap<Entity, Entity>, FlecsError> {
+        let mut entities = hashbrown::HashMap::new();
+
+        let map_entity = |entities: &hashbrown::HashMap<Entity, Entity>, entity: Entity| {
+            if let Some(mapped) = entities.get(&entity) {
+                return Ok(*mapped);
+            }
+            match self.paths.get(&entity) {
+                Some(path) => world
+                    .try_lookup(path)
+                    .map(|e| e.id())
+                    .ok_or_else(|| FlecsError::Unresolved(path.clone())),
+                None => Err(FlecsError::Unresolved(format!("#{entity}"))),
+            }
+        };
+
+        let map_id =
+            |entities: &hashbrown::HashMap<Entity, Entity>, id: Id| -> Result<Id, FlecsError> {
+                if id.is_pair() {
+                    let first = map_entity(entities, id.first())?;
+                    let second = map_entity(entities, id.second())?;
+                    Ok(Id::pair(first, second))
+                } else {
+                    Ok(id.flags() | map_entity(entities, Entity::new(*id.remove_flags()))?)
+                }
+            };
+
+        for op in &self.ops {
+            match op {
+                RecordedOp::Create(entity, path) => {
+                    let mapped = match path {
+                        Some(path) => world.entity_named(path),
+                        None => world.entity(),
+                    };
+                    entities.insert(*entity, mapped.id());
+                }
+                RecordedOp::Delete(entity) => {
+                    if let Some(mapped) = entities.remove(entity) {
+                        world.entity_from_id(mapped).destruct();
+                    }
+                }
+                RecordedOp::Add(entity, id) => {
+                    let entity = map_entity(&entities, *entity)?;
+                    let id = map_id(&entities, *id)?;
+                    add_without_value(world.entity_from_id(entity), id);
+                }
+                RecordedOp::Remove(entity, id) => {
+                    let entity = map_entity(&entities, *entity)?;
+                    let id = map_id(&entities, *id)?;
+                    world.entity_from_id(entity).remove_id(id);
+                }
+                RecordedOp::Set(entity, id, json) => {
+                    let entity = map_entity(&entities, *entity)?;
+                    let id = map_id(&entities, *id)?;
+                    world
+                        .entity_from_id(entity)
+                        .try_set_json_id(id, json, None)?;
+                }
+                RecordedOp::SetName(entity, name) => {
+                    let entity = map_entity(&entities, *entity)?;
+                    world.entity_from_id(entity).set_name(name);
+                }
+            }
+        }
+
+        Ok(entities)
+    }
+}
+
+impl Recorder<'_> {
+    /// Stop recording and return the recorded operations.
+    ///
+    /// Operations on components, systems, observers and modules are left out, as these are
+    /// created by the application when it sets up the world to replay into.
+    pub fn stop(self) -> ReplayLog {
+        let mut log = self.log.take();
+        let world = self.world;
+        log.ops.retain(|op| {
+            let entity = match op {
+                RecordedOp::Create(entity, _)
+                | RecordedOp::Delete(entity)
+                | RecordedOp::Add(entity, _)
+                | RecordedOp::Remove(entity, _)
+                | RecordedOp::Set(entity, _, _)
+                | RecordedOp::SetName(entity, _) => *entity,
+            };
+            !world.is_alive(entity) || !is_internal(world.entity_from_id(entity))
+        });
+        log
+    }
+}
+
+impl Drop for Recorder<'_> {
+    fn drop(&mut self) {
+        for observer in self.observers {
+            if self.world.is_alive(observer) {
+                self.world.entity_from_id(observer).destruct();
+            }
+        }
+    }
+}
+
+impl World {
+    /// Start recording the operations done on the world.
+    ///
+    /// Entity creation, deletion, and adding, removing and setting ids are recorded until
+    /// [`Recorder::stop()`] is called. Component values are recorded as JSON, which requires the
+    /// component to have reflection data. Components without reflection data are recorded as
+    /// added without a value. Entities that never get an id are not recorded.
+    ///
+    /// Replaying the log into a fresh world with [`ReplayLog::replay()`] reproduces the state of
+    /// the recorded entities, which can be used to reproduce bugs or drive deterministic tests.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// #[meta]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// let world = World::new();
+    /// world.component::<Position>().meta();
+    /// world.component::<Enemy>();
+    ///
+    /// let recorder = world.record();
+    /// let e = world
+    ///     .entity_named("goblin")
+    ///     .set(Position { x: 1.0, y: 2.0 })
+    ///     .add::<Enemy>();
+    /// e.set(Position { x: 3.0, y: 4.0 });
+    /// world.entity().add::<Enemy>().destruct();
+    /// let log = recorder.stop();
+    ///
+    /// let replay_world = World::new();
+    /// replay_world.component::<Position>().meta();
+    /// replay_world.component::<Enemy>();
+    /// log.replay(&replay_world).unwrap();
+    ///
+    /// let goblin = replay_world.lookup("goblin");
+    /// assert!(goblin.has::<Enemy>());
+    /// goblin.get::<&Position>(|p| assert_eq!((p.x, p.y), (3.0, 4.0)));
+    /// assert_eq!(replay_world.count::<Enemy>(), 1);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`ReplayLog::replay()`]
+    pub fn record(&self) -> Recorder<'_> {
+        let log = Rc::new(RefCell::new(ReplayLog::default()));
+
+        let observe = |id: Id| {
+            let log = log.clone();
+            self.observer::<flecs::OnAdd, ()>()
+                .add_event::<flecs::OnRemove>()
+                .add_event::<flecs::OnSet>()
+                .with_id(id)
+                .each_iter(move |it, row, _| {
+                    let entity = it.entity(row);
+                    let event = it.event().id();
+                    let mut log = log.borrow_mut();
+                    if event == flecs::OnRemove::ID && it.other_table().is_none() {
+                        if !log.is_deleting(entity.id()) {
+                            log.ops.push(RecordedOp::Delete(entity.id()));
+                        }
+                        return;
+                    }
+                    log.record(it.world(), event, it.event_id().id(), entity);
+                })
+                .id()
+        };
+
+        let observers = [
+            observe(Id::from(flecs::Wildcard::ID)),
+            observe(Id::pair(flecs::Wildcard::ID, flecs::Wildcard::ID)),
+        ];
+
+        Recorder {
+            world: self.world(),
+            observers,
+            log,
+        }
+    }
+}
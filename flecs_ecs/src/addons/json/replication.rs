@@ -0,0 +1,288 @@
+//! Capture changes to replicated components and apply them to another world, see
+//! [`World::replicate()`].
+
+use flecs_ecs::sys;
+
+use crate::core::*;
+
+use super::{Guid, value_to_json};
+
+extern crate alloc;
+use alloc::{string::String, vec::Vec};
+
+/// A change to a replicated component of an entity, see [`World::drain_changes()`].
+///
+/// Entities are referred to by their [`Guid`] and components by their path, so the change can
+/// be applied to another world with [`World::apply_changes()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Change {
+    /// The component was added to the entity.
+    Add { entity: Guid, component: String },
+    /// The component was removed from the entity.
+    Remove { entity: Guid, component: String },
+    /// The component was set to a value, serialized as JSON.
+    Set {
+        entity: Guid,
+        component: String,
+        value: String,
+    },
+    /// The entity was deleted.
+    Delete(Guid),
+}
+
+/// The changes to replicated components since they were last drained, created with
+/// [`World::drain_changes()`].
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct ChangeSet {
+    /// The changes, in the order they happened.
+    pub changes: Vec<Change>,
+}
+
+impl ChangeSet {
+    /// Returns true if nothing changed.
+    pub fn is_empty(&self) -> bool {
+        self.changes.is_empty()
+    }
+
+    fn push(&mut self, change: Change) {
+        match &change {
+            // only the last value of a component is sent
+            Change::Set {
+                entity, component, ..
+            } => self.changes.retain(|pending| {
+                !matches!(pending, Change::Set { entity: e, component: c, .. }
+                    if e == entity && c == component)
+            }),
+            // an entity with more than one replicated component is deleted once
+            Change::Delete(_) if self.changes.last() == Some(&change) => return,
+            _ => {}
+        }
+        self.changes.push(change);
+    }
+}
+
+/// The changes captured by the observers of the replicated components, stored per world.
+#[derive(Default)]
+pub(crate) struct Replication {
+    pending: ChangeSet,
+    /// Set while remote changes are applied, so they aren't captured again.
+    applying: bool,
+}
+
+fn replication_mut<'a>(world: *mut sys::ecs_world_t) -> Option<&'a mut Replication> {
+    unsafe { (*World::get_context(world)).replication.as_mut() }
+}
+
+impl World {
+    /// Replicate a component, see [`World::replicate_id()`].
+    pub fn replicate<T: ComponentId>(&self) -> &Self {
+        self.replicate_id(self.component_id::<T>())
+    }
+
+    /// Capture the changes to a component, so they can be sent to another world with
+    /// [`World::drain_changes()`] and [`World::apply_changes()`].
+    ///
+    /// Only changes to entities with a [`Guid`] are captured, which identifies the entity in the
+    /// other world, see [`EntityView::guid()`]. Values are serialized as JSON, which requires the
+    /// component to have reflection data. Changes to components without reflection data are
+    /// captured without their value.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// #[meta]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let server = World::new();
+    /// server.component::<Position>().meta().replicate();
+    ///
+    /// let client = World::new();
+    /// client.component::<Position>().meta();
+    ///
+    /// let e = server.entity();
+    /// let guid = e.guid();
+    /// e.set(Position { x: 1.0, y: 2.0 });
+    /// e.set(Position { x: 3.0, y: 4.0 });
+    ///
+    /// // once per frame
+    /// let changes = server.drain_changes();
+    /// assert_eq!(changes.changes.len(), 2);
+    /// client.apply_changes(&changes).unwrap();
+    ///
+    /// client
+    ///     .resolve_guid(guid)
+    ///     .unwrap()
+    ///     .get::<&Position>(|p| assert_eq!((p.x, p.y), (3.0, 4.0)));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::drain_changes()`]
+    /// * [`World::apply_changes()`]
+    pub fn replicate_id(&self, component: impl Into<Entity>) -> &Self {
+        let component = component.into();
+        let path = self
+            .entity_from_id(component)
+            .path()
+            .expect("replicated component has no path");
+
+        self.enable_guids();
+        let ctx = self.world_ctx_mut();
+        if ctx.replication.is_none() {
+            ctx.replication = Some(Replication::default());
+        }
+
+        self.observer::<flecs::OnAdd, ()>()
+            .add_event::<flecs::OnRemove>()
+            .add_event::<flecs::OnSet>()
+            .with_id(component)
+            .each_iter(move |it, row, _| {
+                let Some(replication) = replication_mut(it.world().world_ptr_mut()) else {
+                    return;
+                };
+                if replication.applying {
+                    return;
+                }
+                let entity = it.entity(row);
+                let Some(guid) = entity.try_cloned::<&Guid>() else {
+                    return;
+                };
+
+                let event = it.event().id();
+                let change = if event == flecs::OnAdd::ID {
+                    Change::Add {
+                        entity: guid,
+                        component: path.clone(),
+                    }
+                } else if event == flecs::OnSet::ID {
+                    let Some(value) = value_to_json(entity, component.into()) else {
+                        return;
+                    };
+                    Change::Set {
+                        entity: guid,
+                        component: path.clone(),
+                        value,
+                    }
+                } else if it.other_table().is_none() {
+                    Change::Delete(guid)
+                } else {
+                    Change::Remove {
+                        entity: guid,
+                        component: path.clone(),
+                    }
+                };
+                replication.pending.push(change);
+            });
+        self
+    }
+
+    /// Take the changes to replicated components since the last call, which is typically done
+    /// once per frame.
+    ///
+    /// # See also
+    ///
+    /// * [`World::replicate()`]
+    /// * [`World::apply_changes()`]
+    pub fn drain_changes(&self) -> ChangeSet {
+        self.world_ctx_mut()
+            .replication
+            .as_mut()
+            .map(|replication| core::mem::take(&mut replication.pending))
+            .unwrap_or_default()
+    }
+
+    /// Apply changes drained from another world with [`World::drain_changes()`].
+    ///
+    /// Entities are found by their [`Guid`] and created when they don't exist yet. Components
+    /// are looked up by path, so they need to be registered, with reflection data for the
+    /// changes with values. Changes applied to replicated components of this world are not
+    /// captured again.
+    ///
+    /// # Returns
+    ///
+    /// [`FlecsError::Unresolved`] if a component can't be found, or an error if a value can't be
+    /// deserialized. Changes before the failing one stay applied.
+    ///
+    /// # See also
+    ///
+    /// * [`World::replicate()`]
+    pub fn apply_changes(&self, changes: &ChangeSet) -> Result<(), FlecsError> {
+        self.enable_guids();
+
+        let ctx = self.world_ctx_mut();
+        let was_applying = ctx.replication.as_ref().is_some_and(|r| r.applying);
+        if let Some(replication) = ctx.replication.as_mut() {
+            replication.applying = true;
+        }
+
+        let result = changes
+            .changes
+            .iter()
+            .try_for_each(|change| self.apply_change(change));
+
+        if let Some(replication) = self.world_ctx_mut().replication.as_mut() {
+            replication.applying = was_applying;
+        }
+        result
+    }
+
+    fn apply_change(&self, change: &Change) -> Result<(), FlecsError> {
+        let component = |path: &String| {
+            self.try_lookup(path)
+                .map(|c| c.id())
+                .ok_or_else(|| FlecsError::Unresolved(path.clone()))
+        };
+        let entity = |guid: Guid| {
+            self.resolve_guid(guid)
+                .unwrap_or_else(|| self.entity().set(guid))
+        };
+
+        match change {
+            Change::Add {
+                entity: guid,
+                component: path,
+            } => {
+                let component = component(path)?;
+                let entity = entity(*guid);
+                unsafe { sys::ecs_add_id(self.world_ptr_mut(), *entity.id(), *component) };
+            }
+            Change::Remove {
+                entity: guid,
+                component: path,
+            } => {
+                let component = component(path)?;
+                if let Some(e) = self.resolve_guid(*guid) {
+                    e.remove_id(component);
+                }
+            }
+            Change::Set {
+                entity: guid,
+                component: path,
+                value,
+            } => {
+                let component = component(path)?;
+                entity(*guid).try_set_json_id(component, value, None)?;
+            }
+            Change::Delete(guid) => {
+                if let Some(e) = self.resolve_guid(*guid) {
+                    e.destruct();
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: ComponentId> crate::core::Component<'_, T> {
+    /// Replicate the component, see [`World::replicate_id()`].
+    pub fn replicate(self) -> Self {
+        self.world().real_world().replicate_id(self.id());
+        self
+    }
+}
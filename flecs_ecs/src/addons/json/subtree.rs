@@ -0,0 +1,252 @@
+//! Save an entity with its children and restore it later, see [`EntityView::save_subtree()`].
+
+use crate::core::*;
+
+use super::{add_without_value, value_to_json};
+
+extern crate alloc;
+use alloc::{format, string::String, vec, vec::Vec};
+
+/// An entity and its children, saved with [`EntityView::save_subtree()`] and restored with
+/// [`World::restore_subtree()`].
+///
+/// Component values are stored as JSON, which requires the component to have reflection data.
+/// Components without reflection data are restored without their value, like they were added
+/// with [`EntityView::add_id()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SerializedTree {
+    /// The saved entities, with the root first and parents before their children.
+    entities: Vec<SerializedEntity>,
+    /// Paths of the entities outside of the tree the saved ids refer to.
+    paths: hashbrown::HashMap<Entity, String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct SerializedEntity {
+    id: Entity,
+    /// The parent in the tree, or 0 for the root.
+    parent: Entity,
+    name: Option<String>,
+    components: Vec<(Id, Option<String>)>,
+}
+
+fn is_saved(id: Id) -> bool {
+    !id.is_pair()
+        || (id.first() != flecs::ChildOf::ID
+            && id.first() != flecs::Identifier::ID
+            && id.first() != flecs::Poly::ID)
+}
+
+impl EntityView<'_> {
+    /// Save this entity with its children, recursively.
+    ///
+    /// This makes it possible to store a checkpoint of part of the world, such as a single room
+    /// or boss fight, without a snapshot of the whole world. The tree is restored with
+    /// [`World::restore_subtree()`].
+    ///
+    /// Relationships between entities of the tree are restored between the new entities. Other
+    /// entities, such as components and relationship targets outside of the tree, are referred
+    /// to by their path, so they can be renamed or recreated between saving and restoring.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// #[meta]
+    /// struct Health {
+    ///     value: i32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.component::<Health>().meta();
+    ///
+    /// let room = world.entity_named("boss_room");
+    /// let boss = world
+    ///     .entity_named("boss")
+    ///     .child_of_id(room)
+    ///     .set(Health { value: 100 });
+    ///
+    /// let checkpoint = room.save_subtree();
+    ///
+    /// // the player loses the fight
+    /// boss.set(Health { value: 20 });
+    /// room.destruct();
+    ///
+    /// let room = world.restore_subtree(&checkpoint, Entity::null()).unwrap();
+    /// assert_eq!(room.name(), "boss_room");
+    /// world
+    ///     .lookup("boss_room::boss")
+    ///     .get::<&Health>(|health| assert_eq!(health.value, 100));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::restore_subtree()`]
+    pub fn save_subtree(self) -> SerializedTree {
+        let world = self.world();
+
+        let mut entities = Vec::new();
+        let mut stack = vec![(self.id(), Entity::null())];
+        while let Some((id, parent)) = stack.pop() {
+            let e = world.entity_from_id(id);
+            entities.push(SerializedEntity {
+                id,
+                parent,
+                name: e.get_name(),
+                components: Vec::new(),
+            });
+            e.each_child(|child| stack.push((child.id(), id)));
+        }
+
+        let in_tree: hashbrown::HashSet<Entity> = entities.iter().map(|saved| saved.id).collect();
+        let mut paths = hashbrown::HashMap::new();
+
+        for saved in &mut entities {
+            let e = world.entity_from_id(saved.id);
+            for &id in e.archetype().as_slice() {
+                if !is_saved(id) {
+                    continue;
+                }
+
+                let parts = if id.is_pair() {
+                    [id.first(), id.second()]
+                } else {
+                    [Entity::new(*id.remove_flags()), Entity::new(0)]
+                };
+                for part in parts {
+                    if part == 0
+                        || in_tree.contains(&part)
+                        || paths.contains_key(&part)
+                        || world.real_world().is_builtin(*part)
+                    {
+                        continue;
+                    }
+                    if let Some(path) = world.entity_from_id(part).path() {
+                        paths.insert(part, path);
+                    }
+                }
+
+                saved.components.push((id, value_to_json(e, id)));
+            }
+        }
+
+        SerializedTree { entities, paths }
+    }
+}
+
+impl World {
+    /// Restore a tree saved with [`EntityView::save_subtree()`] as new entities.
+    ///
+    /// The root of the tree is created as child of `parent`, or without a parent if `parent` is
+    /// [`Entity::null()`]. The entities keep their names, so the saved entities should be deleted
+    /// first when the tree is restored under the same parent.
+    ///
+    /// Components and other entities outside of the tree are looked up by path, so they need to
+    /// exist when the tree is restored. Builtin ids are the same in every world.
+    ///
+    /// Components without reflection data are restored with their [`Default`] value. Components
+    /// without reflection data that don't implement [`Default`] are skipped.
+    ///
+    /// # Returns
+    ///
+    /// The restored root, or [`FlecsError::Unresolved`] if an entity outside of the tree can't be
+    /// found, in which case nothing is created. If a saved value can't be deserialized the restored
+    /// entities are deleted again and the error is returned.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::save_subtree()`]
+    pub fn restore_subtree(
+        &self,
+        tree: &SerializedTree,
+        parent: impl Into<Entity>,
+    ) -> Result<EntityView<'_>, FlecsError> {
+        let parent = parent.into();
+
+        let in_tree: hashbrown::HashSet<Entity> =
+            tree.entities.iter().map(|saved| saved.id).collect();
+        let mut external = hashbrown::HashMap::new();
+        for saved in &tree.entities {
+            for (id, _) in &saved.components {
+                let parts = if id.is_pair() {
+                    [id.first(), id.second()]
+                } else {
+                    [Entity::new(*id.remove_flags()), Entity::new(0)]
+                };
+                for part in parts {
+                    if part == 0 || in_tree.contains(&part) || external.contains_key(&part) {
+                        continue;
+                    }
+                    let mapped = match tree.paths.get(&part) {
+                        Some(path) => self
+                            .try_lookup(path)
+                            .map(|e| e.id())
+                            .ok_or_else(|| FlecsError::Unresolved(path.clone()))?,
+                        None if self.is_builtin(*part) => part,
+                        None => return Err(FlecsError::Unresolved(format!("#{part}"))),
+                    };
+                    external.insert(part, mapped);
+                }
+            }
+        }
+
+        let mut entities = hashbrown::HashMap::with_capacity(tree.entities.len());
+        for saved in &tree.entities {
+            let e = self.entity();
+            let e_parent = if saved.parent == 0 {
+                parent
+            } else {
+                entities[&saved.parent]
+            };
+            if e_parent != 0 {
+                e.child_of_id(e_parent);
+            }
+            if let Some(name) = &saved.name {
+                e.set_name(name);
+            }
+            entities.insert(saved.id, e.id());
+        }
+
+        let map_entity = |entity: Entity| {
+            if entity == 0 {
+                entity
+            } else {
+                entities
+                    .get(&entity)
+                    .copied()
+                    .unwrap_or_else(|| external[&entity])
+            }
+        };
+
+        let map_id = |id: Id| {
+            if id.is_pair() {
+                Id::pair(map_entity(id.first()), map_entity(id.second()))
+            } else {
+                id.flags() | map_entity(Entity::new(*id.remove_flags()))
+            }
+        };
+
+        let root = self.entity_from_id(entities[&tree.entities[0].id]);
+        for saved in &tree.entities {
+            let e = self.entity_from_id(entities[&saved.id]);
+            for (id, value) in &saved.components {
+                let id = map_id(*id);
+                match value {
+                    Some(json) => {
+                        if let Err(err) = e.try_set_json_id(id, json, None) {
+                            root.destruct();
+                            return Err(err);
+                        }
+                    }
+                    None => {
+                        add_without_value(e, id);
+                    }
+                }
+            }
+        }
+
+        Ok(root)
+    }
+}
@@ -42,10 +42,16 @@ impl<'a> Cursor<'a> {
 
     /// Move to member by name
     pub fn member(&mut self, name: &str) -> i32 {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
         unsafe { sys::ecs_meta_member(&mut self.cursor, name.as_ptr() as *const _) }
     }
 
+    /// Move to member by dot-separated path, such as `position.x`
+    pub fn dotmember(&mut self, name: &str) -> i32 {
+        let name = SmallCString::new(name);
+        unsafe { sys::ecs_meta_dotmember(&mut self.cursor, name.as_ptr() as *const _) }
+    }
+
     /// Move to element by index
     pub fn elem(&mut self, elem: i32) -> i32 {
         unsafe { sys::ecs_meta_elem(&mut self.cursor, elem) }
@@ -142,6 +148,19 @@ impl<'a> Cursor<'a> {
         unsafe { sys::ecs_meta_set_null(&mut self.cursor) }
     }
 
+    /// Set value of type `type_id` from `ptr`, converting between primitive types
+    pub(crate) fn set_value(
+        &mut self,
+        type_id: impl Into<Entity>,
+        ptr: *const core::ffi::c_void,
+    ) -> i32 {
+        let value = sys::ecs_value_t {
+            type_: *type_id.into(),
+            ptr: ptr as *mut _,
+        };
+        unsafe { sys::ecs_meta_set_value(&mut self.cursor, &value) }
+    }
+
     /// Get boolean value
     pub fn get_bool(&self) -> bool {
         unsafe { sys::ecs_meta_get_bool(&self.cursor) }
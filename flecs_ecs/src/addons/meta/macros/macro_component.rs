@@ -127,6 +127,7 @@ macro_rules! member_ext {
 }
 
 #[allow(dead_code, clippy::print_stdout)]
+#[cfg(feature = "std")]
 /// Run this to regenerate the tuple rules for [`component_ext!`]
 fn codegen_tuple_struct_macro() {
     for i in 1..=12 {
@@ -219,7 +219,7 @@ impl EcsSerializer for sys::ecs_serializer_t {
     }
 
     fn member(&self, name: &str) -> i32 {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
         if let Some(member_func) = self.member {
             unsafe { member_func(self, name.as_ptr() as *const _) }
         } else {
@@ -360,7 +360,7 @@ impl<T: EnumComponentInfo + 'static> Component<'_, T> {
     pub fn constant(&self, name: &str, id: impl Into<Entity>, value: T) -> &Self {
         unsafe { sys::ecs_add_id(self.world_ptr_mut(), *self.id, flecs::meta::EcsEnum::ID) };
 
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
 
         let desc = sys::ecs_entity_desc_t {
             name: name.as_ptr() as *const _,
@@ -394,7 +394,7 @@ impl UntypedComponent<'_> {
     ///
     /// * C++ API: `untyped_component::constant`
     pub fn constant(&self, name: &str, value: impl Into<i32>) -> &Self {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
         let value: i32 = value.into();
         let world = self.world_ptr_mut();
         let id = *self.id;
@@ -441,7 +441,7 @@ impl UntypedComponent<'_> {
         unit: impl Into<Entity>,
         data: Meta,
     ) -> Self {
-        let name = compact_str::format_compact!("{}\0", data.name());
+        let name = SmallCString::new(data.name());
         let world = self.world_ptr_mut();
         let id = *self.id;
         let type_id = *type_id.into();
@@ -567,7 +567,7 @@ impl UntypedComponent<'_> {
     ///
     /// * C++ API: `untyped_component::bit`
     pub fn bit(self, name: &str, value: u32) -> Self {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
         let world = self.world_ptr_mut();
         let id = *self.id;
 
@@ -708,6 +708,74 @@ pub fn flecs_entity_support<'a>(world: impl WorldProvider<'a>) -> Opaque<'a, Ent
     opaque
 }
 
+impl<'a, const IS_RUN: bool, P: ComponentId> TableIter<'a, IS_RUN, P> {
+    /// Return meta cursor to the value of a field, for components that are only known at run time.
+    ///
+    /// The type of the value is the type of the matched id, so this also works for pairs that
+    /// carry the data of one of their elements. Like [`TableIter::field_at_untyped()`], shared
+    /// fields are accessed with `row` set to 0.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The field index.
+    /// * `row` - The row of the entity in the iterated table.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field is not set, is read-only, such as a `&T` term, or if the matched id
+    /// has no data.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component, Default)]
+    /// struct Health {
+    ///     value: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world
+    ///     .component_named::<Health>("Health")
+    ///     .member::<f32>("value");
+    /// let e = world.entity().set(Health { value: 1.0 });
+    ///
+    /// // code that only knows the component at run time
+    /// let health = world.lookup_component("Health");
+    /// world
+    ///     .query::<()>()
+    ///     .with_id(health)
+    ///     .build()
+    ///     .each_iter(|it, row, _| {
+    ///         let mut cursor = it.field_cursor(0, row as i32);
+    ///         cursor.push();
+    ///         cursor.member("value");
+    ///         cursor.set_float(10.0);
+    ///     });
+    ///
+    /// e.get::<&Health>(|h| assert_eq!(h.value, 10.0));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::cursor_id()`]
+    /// * [`TableIter::field_at_untyped()`]
+    pub fn field_cursor(&self, index: i8, row: i32) -> Cursor<'a> {
+        assert!(self.is_set(index), "field {index} is not set");
+        assert!(
+            !self.is_readonly(index),
+            "field {index} is read-only, so its value can't be written through a cursor"
+        );
+        let type_id = self.id(index).type_id();
+        assert!(
+            type_id.is_valid(),
+            "the id of field {index} has no data, so it has no value to access"
+        );
+        Cursor::new(self.world(), type_id, self.field_at_untyped(index, row))
+    }
+}
+
 impl EntityView<'_> {
     /// Make entity a unit
     ///
@@ -725,7 +793,7 @@ impl EntityView<'_> {
         power: i32,
     ) -> &Self {
         if let Some(symbol) = symbol {
-            let symbol = compact_str::format_compact!("{}\0", symbol);
+            let symbol = SmallCString::new(symbol);
             let desc = sys::ecs_unit_desc_t {
                 entity: *self.id,
                 symbol: symbol.as_ptr() as *const _,
@@ -761,7 +829,7 @@ impl EntityView<'_> {
     /// * C++ API: `entity_builder::unit_prefix`
     #[doc(alias = "entity_builder::unit_prefix")]
     pub fn unit_prefix(&self, symbol: &str, factor: i32, power: i32) -> &Self {
-        let symbol = compact_str::format_compact!("{}\0", symbol);
+        let symbol = SmallCString::new(symbol);
         let desc = sys::ecs_unit_prefix_desc_t {
             entity: *self.id,
             symbol: symbol.as_ptr() as *const _,
@@ -809,6 +877,188 @@ impl EntityView<'_> {
         unsafe { sys::ecs_add_id(self.world_ptr_mut(), *self.id, flecs::meta::Quantity::ID) };
         self
     }
+
+    /// Get the value of a component member by path, such as `Transform.translation.y`.
+    ///
+    /// The path starts with the name of the component, followed by the (nested) member. The
+    /// value is converted to `T` when the member is a primitive of a different type.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the path can't be resolved, the entity doesn't have the component, or the
+    /// member can't be converted to `T`. See [`EntityView::try_get_member()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// #[meta]
+    /// struct Vec3 {
+    ///     x: f32,
+    ///     y: f32,
+    ///     z: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// #[meta]
+    /// struct Transform {
+    ///     translation: Vec3,
+    ///     scale: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.component::<Vec3>().meta();
+    /// world.component_named::<Transform>("Transform").meta();
+    ///
+    /// let e = world.entity().set(Transform {
+    ///     translation: Vec3 { x: 1.0, y: 2.0, z: 3.0 },
+    ///     scale: 1.0,
+    /// });
+    ///
+    /// e.set_member("Transform.translation.y", 5.0f32);
+    /// assert_eq!(e.get_member::<f32>("Transform.translation.y"), 5.0);
+    ///
+    /// // primitives are converted
+    /// assert_eq!(e.get_member::<f64>("Transform.scale"), 1.0);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::set_member()`]
+    /// * [`Cursor::dotmember()`]
+    /// * C API: `ecs_meta_dotmember`
+    #[doc(alias = "ecs_meta_dotmember")]
+    pub fn get_member<T: ComponentId + Default + Clone>(&self, path: &str) -> T {
+        self.try_get_member(path)
+            .unwrap_or_else(|err| panic!("failed to get member '{path}': {err}"))
+    }
+
+    /// Get the value of a component member by path, such as `Transform.translation.y`.
+    ///
+    /// # Errors
+    ///
+    /// * [`FlecsError::NotAComponent`] when the path doesn't start with a component name.
+    /// * [`FlecsError::InvalidParameter`] when the entity doesn't have the component.
+    /// * [`FlecsError::ParseError`] when the member doesn't exist or can't be converted to `T`.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::get_member()`]
+    #[doc(alias = "ecs_meta_dotmember")]
+    pub fn try_get_member<T: ComponentId + Default + Clone>(
+        &self,
+        path: &str,
+    ) -> Result<T, FlecsError> {
+        let world = self.world();
+        let (component, member) = split_member_path(world, path)?;
+        let ptr = unsafe { sys::ecs_get_id(world.world_ptr(), *self.id, component) };
+        if ptr.is_null() {
+            return Err(FlecsError::InvalidParameter);
+        }
+
+        let mut cursor = member_cursor(world, component, ptr as *mut c_void, member)?;
+        let member_type = cursor.get_type().id();
+        let type_id = T::id(world);
+        if *member_type == type_id {
+            return Ok(unsafe { (*(cursor.get_ptr() as *const T)).clone() });
+        }
+
+        let mut value = T::default();
+        let mut dst = Cursor::new(world, type_id, &mut value as *mut T as *mut c_void);
+        let (result, errors) =
+            ecs_os_api::capture_errors(|| dst.set_value(member_type, cursor.get_ptr()));
+        if result != 0 {
+            return Err(FlecsError::ParseError(errors.unwrap_or_default()));
+        }
+        Ok(value)
+    }
+
+    /// Set the value of a component member by path, such as `Transform.translation.y`.
+    ///
+    /// The value is converted to the type of the member when both are primitives. The entity
+    /// must own the component, and `OnSet` observers are invoked for it.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the path can't be resolved, the entity doesn't own the component, or the
+    /// value can't be converted to the type of the member. See [`EntityView::try_set_member()`].
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::get_member()`]
+    /// * C API: `ecs_meta_dotmember`
+    #[doc(alias = "ecs_meta_dotmember")]
+    pub fn set_member<T: ComponentId>(self, path: &str, value: T) -> Self {
+        self.try_set_member(path, value)
+            .unwrap_or_else(|err| panic!("failed to set member '{path}': {err}"))
+    }
+
+    /// Set the value of a component member by path, such as `Transform.translation.y`.
+    ///
+    /// # Errors
+    ///
+    /// * [`FlecsError::NotAComponent`] when the path doesn't start with a component name.
+    /// * [`FlecsError::InvalidParameter`] when the entity doesn't own the component.
+    /// * [`FlecsError::ParseError`] when the member doesn't exist or the value can't be
+    ///   converted to its type.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::set_member()`]
+    #[doc(alias = "ecs_meta_dotmember")]
+    pub fn try_set_member<T: ComponentId>(self, path: &str, value: T) -> Result<Self, FlecsError> {
+        let world = self.world();
+        let (component, member) = split_member_path(world, path)?;
+        if !self.owns_id(component) {
+            return Err(FlecsError::InvalidParameter);
+        }
+        let ptr = unsafe { sys::ecs_get_mut_id(world.world_ptr_mut(), *self.id, component) };
+
+        let mut cursor = member_cursor(world, component, ptr, member)?;
+        let type_id = T::id(world);
+        if *cursor.get_type().id() == type_id {
+            unsafe { *(cursor.get_ptr() as *mut T) = value };
+        } else {
+            let (result, errors) = ecs_os_api::capture_errors(|| {
+                cursor.set_value(type_id, &value as *const T as *const c_void)
+            });
+            if result != 0 {
+                return Err(FlecsError::ParseError(errors.unwrap_or_default()));
+            }
+        }
+
+        unsafe { sys::ecs_modified_id(world.world_ptr_mut(), *self.id, component) };
+        Ok(self)
+    }
+}
+
+/// Split a member path such as `Transform.translation.y` into the component and member path.
+fn split_member_path<'a>(world: WorldRef, path: &'a str) -> Result<(u64, &'a str), FlecsError> {
+    let (name, member) = path.split_once('.').ok_or(FlecsError::InvalidParameter)?;
+    let component = world
+        .try_lookup_component(name)
+        .ok_or(FlecsError::NotAComponent)?;
+    Ok((*component.id(), member))
+}
+
+/// Create a cursor for the component at `ptr`, moved to `member`.
+fn member_cursor<'a>(
+    world: WorldRef<'a>,
+    component: u64,
+    ptr: *mut c_void,
+    member: &str,
+) -> Result<Cursor<'a>, FlecsError> {
+    let mut cursor = Cursor::new(world, component, ptr);
+    let (result, errors) = ecs_os_api::capture_errors(|| match cursor.push() {
+        0 => cursor.dotmember(member),
+        err => err,
+    });
+    if result != 0 {
+        return Err(FlecsError::ParseError(errors.unwrap_or_default()));
+    }
+    Ok(cursor)
 }
 
 #[cfg(test)]
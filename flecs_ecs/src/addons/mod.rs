@@ -19,6 +19,9 @@ pub mod stats;
 #[cfg(feature = "flecs_timer")]
 pub mod timer;
 
+#[cfg(feature = "flecs_pipeline")]
+pub mod time;
+
 #[cfg(feature = "flecs_meta")]
 pub mod meta;
 
@@ -37,6 +40,32 @@ pub mod metrics;
 #[cfg(feature = "flecs_alerts")]
 pub mod alerts;
 
+#[cfg(feature = "flecs_scenes")]
+pub mod scenes;
+
+#[cfg(feature = "flecs_spatial")]
+pub mod spatial;
+
+#[cfg(all(feature = "std", feature = "flecs_script", feature = "flecs_json"))]
+pub mod prefab_registry;
+
+#[cfg(feature = "flecs_tween")]
+pub mod tween;
+
+pub mod state_machine;
+
+#[cfg(feature = "flecs_async")]
+pub mod async_task;
+
+#[cfg(feature = "flecs_events")]
+pub mod events;
+
+#[cfg(feature = "flecs_rust_rest")]
+pub mod rest_server;
+
+#[cfg(feature = "flecs_profiler")]
+pub mod profiler;
+
 // this is not feature gated to flecs_meta so calling `.meta()` on a component will always work despite meta being disabled.
 pub trait Meta<Component> {
     fn meta(component: flecs_ecs::core::Component<Component>);
@@ -58,7 +87,7 @@ macro_rules! create_pre_registered_extern_component {
         create_pre_registered_extern_component!($struct_name, $static_id, "");
     };
     ($struct_name:ident, $static_id:ident, $doc:tt) => {
-        #[derive(Debug, Default)]
+        #[derive(Debug, Default, Clone, Copy)]
         #[allow(clippy::empty_docs)]
         #[doc = $doc]
         pub struct $struct_name;
@@ -70,6 +99,17 @@ macro_rules! create_pre_registered_extern_component {
             }
         }
 
+        impl From<$struct_name> for flecs_ecs::core::Id {
+            #[inline]
+            fn from(_view: $struct_name) -> Self {
+                flecs_ecs::core::Id(unsafe { $static_id })
+            }
+        }
+
+        impl flecs_ecs::core::IntoId for $struct_name {
+            const IS_PAIR: bool = false;
+        }
+
         impl Deref for $struct_name {
             type Target = u64;
             #[inline(always)]
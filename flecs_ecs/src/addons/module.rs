@@ -4,8 +4,8 @@
 //! * To import a module, see [`World::import()`].
 //! * To override the name of a module, see [`World::module()`].
 use crate::core::{
-    ComponentId, EntityView, FlecsConstantId, IdOperations, SEPARATOR, World, WorldProvider,
-    ecs_pair, flecs, register_componment_data_explicit,
+    ComponentId, EntityView, FlecsConstantId, IdOperations, SEPARATOR, SmallCString, World,
+    WorldProvider, ecs_pair, flecs, register_componment_data_explicit,
 };
 use crate::sys;
 
@@ -91,13 +91,19 @@ impl World {
             #[cfg(feature = "flecs_meta")]
             {
                 self.components_map()
-                    .insert(core::any::TypeId::of::<Self>(), id_u64);
+                    .insert(core::any::TypeId::of::<T>(), id_u64);
             }
             id
         };
 
-        // If we have already registered this type don't re-create the module
+        // If we have already registered this type don't re-create the module, unless its
+        // callbacks were detached and have to be bound again
         if module.has::<flecs::Module>() {
+            if self.world_ctx().detached_callbacks.is_some() {
+                self.set_scope_id(module);
+                T::module(self);
+                self.set_scope_id(prev_scope);
+            }
             return module;
         }
 
@@ -154,7 +160,7 @@ impl World {
         }
 
         if !name.is_empty() {
-            let name = compact_str::format_compact!("{}\0", name);
+            let name = SmallCString::new(name);
             let prev_parent = comp.parent().unwrap_or(EntityView::new_null(self));
             unsafe {
                 sys::ecs_add_path_w_sep(
@@ -3,6 +3,11 @@
 mod pipeline_builder;
 pub use pipeline_builder::*;
 
+#[cfg(feature = "flecs_stats")]
+mod schedule;
+#[cfg(feature = "flecs_stats")]
+pub use schedule::*;
+
 use core::ops::{Deref, DerefMut};
 
 use crate::core::*;
@@ -93,3 +98,111 @@ where
         self.entity
     }
 }
+
+/// Runs a pipeline at a fixed rate, independent of the frame rate.
+///
+/// Time passed to [`FixedTimestep::run()`] is accumulated, and the pipeline is run once for
+/// every full step in the accumulator. Systems in the pipeline receive the step as delta time,
+/// which makes it suitable for physics and other systems that need deterministic updates.
+///
+/// # Example
+///
+/// ```
+/// # use flecs_ecs::prelude::*;
+/// use flecs_ecs::addons::pipeline::FixedTimestep;
+///
+/// #[derive(Component)]
+/// struct FixedUpdate;
+///
+/// let world = World::new();
+///
+/// let fixed_pipeline = world
+///     .pipeline()
+///     .with_id(flecs::system::System::ID)
+///     .with::<&FixedUpdate>()
+///     .build();
+///
+/// world.system::<()>().kind::<FixedUpdate>().run(|mut it| {
+///     while it.next() {
+///         assert_eq!(it.delta_time(), 0.25);
+///     }
+/// });
+///
+/// let mut fixed = FixedTimestep::new(fixed_pipeline, 0.25);
+///
+/// world.progress_time(0.5);
+/// assert_eq!(fixed.run(&world, world.info().delta_time), 2);
+/// ```
+///
+/// # See also
+///
+/// * [`World::pipeline()`]
+/// * [`World::run_pipeline_id_time()`]
+pub struct FixedTimestep {
+    pipeline: Entity,
+    step: FTime,
+    accumulator: FTime,
+    max_steps: u32,
+}
+
+impl FixedTimestep {
+    /// Create a fixed timestep for `pipeline` that runs it every `step` seconds.
+    pub fn new(pipeline: impl Into<Entity>, step: FTime) -> Self {
+        ecs_assert!(
+            step > 0.0,
+            FlecsErrorCode::InvalidParameter,
+            "fixed timestep must be larger than 0"
+        );
+
+        Self {
+            pipeline: pipeline.into(),
+            step,
+            accumulator: 0.0,
+            max_steps: u32::MAX,
+        }
+    }
+
+    /// Limit the number of times the pipeline is run by a single call to [`FixedTimestep::run()`].
+    ///
+    /// Time that doesn't fit in the maximum number of steps is dropped, which prevents a slow
+    /// frame from causing an ever growing number of steps.
+    pub fn set_max_steps(&mut self, max_steps: u32) -> &mut Self {
+        self.max_steps = max_steps;
+        self
+    }
+
+    /// Advance the accumulator by `delta_time` and run the pipeline zero or more times.
+    ///
+    /// # Returns
+    ///
+    /// The number of times the pipeline was run.
+    pub fn run(&mut self, world: &World, delta_time: FTime) -> u32 {
+        self.accumulator += delta_time;
+
+        let mut steps = 0;
+        while self.accumulator >= self.step {
+            if steps == self.max_steps {
+                self.accumulator %= self.step;
+                break;
+            }
+
+            world.run_pipeline_id_time(self.pipeline, self.step);
+            self.accumulator -= self.step;
+            steps += 1;
+        }
+
+        steps
+    }
+
+    /// The time step the pipeline is run with.
+    pub fn step(&self) -> FTime {
+        self.step
+    }
+
+    /// Fraction of a step that is left in the accumulator, in the range `0.0..1.0`.
+    ///
+    /// This can be used to interpolate between the last two fixed updates when rendering.
+    pub fn alpha(&self) -> FTime {
+        self.accumulator / self.step
+    }
+}
@@ -0,0 +1,637 @@
+//! Inspect the order in which a pipeline runs its systems.
+
+use core::fmt::Write;
+
+use crate::core::*;
+use crate::sys;
+
+extern crate alloc;
+use alloc::{format, string::String, vec::Vec};
+
+/// How a system accesses the components of its query, derived from the inout kinds of its terms.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SystemAccess {
+    /// The system.
+    pub system: Entity,
+    /// The phase of the system, if it has one.
+    pub phase: Option<Entity>,
+    /// Ids the system reads from storage.
+    pub reads: Vec<Id>,
+    /// Ids the system writes in storage.
+    pub writes: Vec<Id>,
+    /// Ids the system writes with commands, for example by adding or setting components on
+    /// entities it doesn't match (`.write::<T>()`).
+    pub deferred_writes: Vec<Id>,
+    /// Whether the system can run on multiple threads.
+    pub multi_threaded: bool,
+    /// Whether the system runs without deferring its operations.
+    pub immediate: bool,
+}
+
+impl SystemAccess {
+    /// Get the access of a system to the components of its query.
+    pub fn new(world: &World, system: impl Into<Entity>) -> Self {
+        let system = system.into();
+        let world_ptr = world.world_ptr();
+        let poly = unsafe { sys::ecs_system_get(world_ptr, *system) };
+        ecs_assert!(
+            !poly.is_null(),
+            FlecsErrorCode::InvalidParameter,
+            "entity is not a system"
+        );
+        let poly = unsafe { &*poly };
+
+        let mut access = SystemAccess {
+            system,
+            phase: None,
+            reads: Vec::new(),
+            writes: Vec::new(),
+            deferred_writes: Vec::new(),
+            multi_threaded: poly.multi_threaded,
+            immediate: poly.immediate,
+        };
+
+        access.phase = world
+            .entity_from_id(system)
+            .archetype()
+            .as_slice()
+            .iter()
+            .map(|id| **id)
+            .find(|&id| {
+                !ecs_is_pair(id)
+                    && unsafe { sys::ecs_has_id(world_ptr, id, flecs::pipeline::Phase::ID) }
+            })
+            .map(Entity::new);
+
+        let query = unsafe { &*poly.query };
+        for term in &query.terms[..query.term_count as usize] {
+            access.add_term(term);
+        }
+
+        access
+    }
+
+    /// Classify a term the same way the pipeline does when it inserts sync points.
+    fn add_term(&mut self, term: &sys::ecs_term_t) {
+        let mut inout = term.inout as sys::ecs_inout_kind_t;
+        if inout == sys::ecs_inout_kind_t_EcsInOutNone
+            || inout == sys::ecs_inout_kind_t_EcsInOutFilter
+        {
+            return;
+        }
+
+        let src = term.src.id;
+        let src_id = src & !flecs::TermRefFlags::ID;
+        let mut from_any = src_id == 0 && src & flecs::IsEntity::ID != 0;
+        let from_this = src & flecs::IsVariable::ID != 0 && src_id == flecs::This_::ID;
+        let is_shared = !from_any && (!from_this || src & flecs::Self_::ID == 0);
+
+        if inout == sys::ecs_inout_kind_t_EcsInOutDefault {
+            if from_any {
+                return;
+            }
+            inout = if is_shared {
+                sys::ecs_inout_kind_t_EcsIn
+            } else {
+                sys::ecs_inout_kind_t_EcsInOut
+            };
+        }
+
+        if term.oper as sys::ecs_oper_kind_t == sys::ecs_oper_kind_t_EcsNot
+            && inout == sys::ecs_inout_kind_t_EcsOut
+        {
+            from_any = true;
+        }
+
+        let id = Id(term.id);
+        if from_any {
+            if inout == sys::ecs_inout_kind_t_EcsOut || inout == sys::ecs_inout_kind_t_EcsInOut {
+                self.deferred_writes.push(id);
+            }
+            return;
+        }
+
+        if inout == sys::ecs_inout_kind_t_EcsIn || inout == sys::ecs_inout_kind_t_EcsInOut {
+            self.reads.push(id);
+        }
+        if inout == sys::ecs_inout_kind_t_EcsOut || inout == sys::ecs_inout_kind_t_EcsInOut {
+            self.writes.push(id);
+        }
+    }
+
+    /// Test if the system reads or writes `id` in storage.
+    pub fn accesses(&self, id: Id) -> bool {
+        self.reads.iter().any(|other| ids_match(*other, id))
+            || self.writes.iter().any(|other| ids_match(*other, id))
+    }
+
+    /// Get the reason why this system and `other` can't run at the same time, if any.
+    ///
+    /// # See also
+    ///
+    /// * [`PipelineSchedule::conflicts()`]
+    pub fn conflict(&self, other: &SystemAccess) -> Option<ConflictReason> {
+        if self.immediate {
+            return Some(ConflictReason::Immediate(self.system));
+        }
+        if other.immediate {
+            return Some(ConflictReason::Immediate(other.system));
+        }
+
+        let find = |ids: &[Id], others: &[Id]| {
+            ids.iter()
+                .find(|id| others.iter().any(|other| ids_match(**id, *other)))
+                .copied()
+        };
+
+        if let Some(id) = find(&self.writes, &other.writes) {
+            return Some(ConflictReason::WriteWrite(id));
+        }
+        if let Some(id) =
+            find(&self.writes, &other.reads).or_else(|| find(&self.reads, &other.writes))
+        {
+            return Some(ConflictReason::ReadWrite(id));
+        }
+
+        let deferred = |writer: &SystemAccess, reader: &SystemAccess| {
+            writer
+                .deferred_writes
+                .iter()
+                .find(|id| reader.accesses(**id))
+                .copied()
+        };
+        deferred(self, other)
+            .or_else(|| deferred(other, self))
+            .map(ConflictReason::DeferredWrite)
+    }
+}
+
+fn ids_match(a: Id, b: Id) -> bool {
+    unsafe { sys::ecs_id_match(*a, *b) || sys::ecs_id_match(*b, *a) }
+}
+
+/// The reason two systems can't run at the same time, see [`SystemAccess::conflict()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ConflictReason {
+    /// Both systems write the id.
+    WriteWrite(Id),
+    /// One system writes the id the other system reads.
+    ReadWrite(Id),
+    /// One system writes the id with commands, which the other system accesses.
+    DeferredWrite(Id),
+    /// The system is immediate and needs exclusive access to the world.
+    Immediate(Entity),
+}
+
+/// Two systems that can't run at the same time, created with [`PipelineSchedule::conflicts()`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SystemConflict {
+    /// The system that runs first.
+    pub first: Entity,
+    /// The system that runs second.
+    pub second: Entity,
+    /// Why the systems conflict.
+    pub reason: ConflictReason,
+}
+
+/// A step in a [`PipelineSchedule`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ScheduleStep {
+    /// A system that runs in this step.
+    System(SystemAccess),
+    /// A sync point, where the commands enqueued by the previous systems are merged.
+    SyncPoint,
+}
+
+/// The order in which a pipeline runs its systems, created with [`World::pipeline_schedule()`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PipelineSchedule {
+    /// The pipeline.
+    pub pipeline: Entity,
+    /// The systems and sync points, in the order in which they run.
+    /// The merge at the end of the frame is not included.
+    pub steps: Vec<ScheduleStep>,
+}
+
+impl PipelineSchedule {
+    /// Iterate the systems of the schedule, in the order in which they run.
+    pub fn systems(&self) -> impl Iterator<Item = &SystemAccess> {
+        self.steps.iter().filter_map(|step| match step {
+            ScheduleStep::System(system) => Some(system),
+            ScheduleStep::SyncPoint => None,
+        })
+    }
+
+    /// Get the number of sync points in a frame, excluding the merge at the end of the frame.
+    pub fn sync_point_count(&self) -> usize {
+        self.steps
+            .iter()
+            .filter(|step| matches!(step, ScheduleStep::SyncPoint))
+            .count()
+    }
+
+    /// Get all pairs of systems in the schedule that can't run at the same time.
+    ///
+    /// Systems that don't appear in the result have no conflicting access and could run
+    /// concurrently. Single threaded systems are reported by [`SystemAccess::multi_threaded`].
+    ///
+    /// # See also
+    ///
+    /// * [`World::pipeline_conflicts_to_string()`]
+    pub fn conflicts(&self) -> Vec<SystemConflict> {
+        let systems: Vec<&SystemAccess> = self.systems().collect();
+        let mut conflicts = Vec::new();
+        for (index, first) in systems.iter().enumerate() {
+            for second in &systems[index + 1..] {
+                if let Some(reason) = first.conflict(second) {
+                    conflicts.push(SystemConflict {
+                        first: first.system,
+                        second: second.system,
+                        reason,
+                    });
+                }
+            }
+        }
+        conflicts
+    }
+
+    /// Explain why the sync point at `step` was inserted.
+    fn sync_point_reason(&self, world: &World, step: usize) -> String {
+        let next = self.steps[step + 1..].iter().find_map(|step| match step {
+            ScheduleStep::System(system) => Some(system),
+            ScheduleStep::SyncPoint => None,
+        });
+        let Some(next) = next else {
+            return String::new();
+        };
+
+        // find the deferred write since the previous sync point that the next system accesses
+        for previous in self.steps[..step].iter().rev() {
+            let ScheduleStep::System(previous) = previous else {
+                break;
+            };
+            if let Some(id) = previous
+                .deferred_writes
+                .iter()
+                .find(|id| next.accesses(**id))
+            {
+                return format!(
+                    "{} accesses {} written by {}",
+                    system_name(world, next.system),
+                    id_str(world, *id),
+                    system_name(world, previous.system)
+                );
+            }
+        }
+
+        if next.immediate {
+            format!("{} is immediate", system_name(world, next.system))
+        } else {
+            String::from("systems switch between single and multi threaded")
+        }
+    }
+}
+
+fn system_name(world: &World, system: Entity) -> String {
+    let system = world.entity_from_id(system);
+    system
+        .get_name()
+        .unwrap_or_else(|| format!("#{}", system.id()))
+}
+
+fn id_str(world: &World, id: Id) -> String {
+    world.id_from_id(id).to_str().into()
+}
+
+fn ids_str(world: &World, ids: &[Id]) -> String {
+    let ids: Vec<String> = ids.iter().map(|id| id_str(world, *id)).collect();
+    ids.join(", ")
+}
+
+impl World {
+    /// Get the order in which the current pipeline runs its systems, including sync points.
+    ///
+    /// The schedule is built when the pipeline runs, so this returns `None` until the world
+    /// has progressed at least once, or if the pipeline has no systems. Inactive systems,
+    /// which don't match any entities, are not part of the schedule.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// # use flecs_ecs::addons::pipeline::ScheduleStep;
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .system_named::<()>("Spawn")
+    ///     .write::<Position>()
+    ///     .run(|mut it| while it.next() {});
+    ///
+    /// world
+    ///     .system_named::<&Position>("Move")
+    ///     .each(|_p| {});
+    ///
+    /// // systems without matching entities are inactive and not scheduled
+    /// world.entity().set(Position { x: 0.0, y: 0.0 });
+    /// world.progress();
+    ///
+    /// let schedule = world.pipeline_schedule().unwrap();
+    /// assert_eq!(schedule.sync_point_count(), 1);
+    /// assert!(matches!(schedule.steps[1], ScheduleStep::SyncPoint));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::pipeline_to_string()`]
+    /// * [`World::pipeline_to_dot()`]
+    /// * C API: `ecs_pipeline_stats_get`
+    #[doc(alias = "ecs_pipeline_stats_get")]
+    pub fn pipeline_schedule(&self) -> Option<PipelineSchedule> {
+        let pipeline = self.get_pipeline().id();
+        let mut stats: sys::ecs_pipeline_stats_t = unsafe { core::mem::zeroed() };
+
+        let ids = unsafe {
+            if !sys::ecs_pipeline_stats_get(self.world_ptr_mut(), *pipeline, &mut stats) {
+                sys::ecs_pipeline_stats_fini(&mut stats);
+                return None;
+            }
+
+            let count = sys::ecs_vec_count(&stats.systems) as usize;
+            let ids = if count > 0 {
+                core::slice::from_raw_parts(
+                    sys::ecs_vec_first(&stats.systems) as *const sys::ecs_entity_t,
+                    count,
+                )
+                .to_vec()
+            } else {
+                Vec::new()
+            };
+            sys::ecs_pipeline_stats_fini(&mut stats);
+            ids
+        };
+
+        if ids.is_empty() {
+            return None;
+        }
+
+        let mut steps: Vec<ScheduleStep> = ids
+            .into_iter()
+            .map(|id| {
+                if id == 0 {
+                    ScheduleStep::SyncPoint
+                } else {
+                    ScheduleStep::System(SystemAccess::new(self, id))
+                }
+            })
+            .collect();
+
+        // the merge at the end of the frame
+        if matches!(steps.last(), Some(ScheduleStep::SyncPoint)) {
+            steps.pop();
+        }
+
+        Some(PipelineSchedule { pipeline, steps })
+    }
+
+    /// Describe the order of the systems in the current pipeline, grouped by phase, with the
+    /// components they read and write and the reason for each sync point.
+    ///
+    /// Returns an empty string until the world has progressed at least once.
+    ///
+    /// # See also
+    ///
+    /// * [`World::pipeline_schedule()`]
+    /// * [`World::pipeline_to_dot()`]
+    pub fn pipeline_to_string(&self) -> String {
+        let Some(schedule) = self.pipeline_schedule() else {
+            return String::new();
+        };
+
+        let mut out = String::new();
+        let mut phase = None;
+        for (index, step) in schedule.steps.iter().enumerate() {
+            match step {
+                ScheduleStep::System(system) => {
+                    if phase != Some(system.phase) {
+                        phase = Some(system.phase);
+                        let name = system
+                            .phase
+                            .map(|phase| system_name(self, phase))
+                            .unwrap_or_else(|| String::from("(no phase)"));
+                        let _ = writeln!(out, "{name}");
+                    }
+
+                    let _ = write!(out, "  {}", system_name(self, system.system));
+                    if system.multi_threaded {
+                        out.push_str(" [multi_threaded]");
+                    }
+                    if system.immediate {
+                        out.push_str(" [immediate]");
+                    }
+                    if !system.reads.is_empty() {
+                        let _ = write!(out, " reads: {}", ids_str(self, &system.reads));
+                    }
+                    if !system.writes.is_empty() {
+                        let _ = write!(out, " writes: {}", ids_str(self, &system.writes));
+                    }
+                    if !system.deferred_writes.is_empty() {
+                        let _ = write!(
+                            out,
+                            " deferred writes: {}",
+                            ids_str(self, &system.deferred_writes)
+                        );
+                    }
+                    out.push('\n');
+                }
+                ScheduleStep::SyncPoint => {
+                    let _ = writeln!(
+                        out,
+                        "  -- sync point: {} --",
+                        schedule.sync_point_reason(self, index)
+                    );
+                }
+            }
+        }
+
+        out
+    }
+
+    /// Describe which systems in the current pipeline can't run at the same time and why, as a
+    /// table with a row per conflicting pair of systems.
+    ///
+    /// Systems that aren't multi threaded get a row of their own. Returns an empty string until
+    /// the world has progressed at least once.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .system_named::<&mut Position>("Move")
+    ///     .multi_threaded()
+    ///     .each(|_p| {});
+    ///
+    /// world
+    ///     .system_named::<&Position>("Render")
+    ///     .multi_threaded()
+    ///     .each(|_p| {});
+    ///
+    /// world.entity().set(Position { x: 0.0, y: 0.0 });
+    /// world.progress();
+    ///
+    /// let table = world.pipeline_conflicts_to_string();
+    /// assert!(table.contains("Move"));
+    /// assert!(table.contains("Render"));
+    /// assert!(table.contains("read/write"));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`PipelineSchedule::conflicts()`]
+    /// * [`World::pipeline_to_string()`]
+    pub fn pipeline_conflicts_to_string(&self) -> String {
+        let Some(schedule) = self.pipeline_schedule() else {
+            return String::new();
+        };
+
+        let mut rows = Vec::new();
+        rows.push([
+            String::from("first"),
+            String::from("second"),
+            String::from("reason"),
+        ]);
+        for conflict in schedule.conflicts() {
+            let reason = match conflict.reason {
+                ConflictReason::WriteWrite(id) => format!("write/write {}", id_str(self, id)),
+                ConflictReason::ReadWrite(id) => format!("read/write {}", id_str(self, id)),
+                ConflictReason::DeferredWrite(id) => {
+                    format!("deferred write {}", id_str(self, id))
+                }
+                ConflictReason::Immediate(system) => {
+                    format!("{} is immediate", system_name(self, system))
+                }
+            };
+            rows.push([
+                system_name(self, conflict.first),
+                system_name(self, conflict.second),
+                reason,
+            ]);
+        }
+
+        for system in schedule.systems().filter(|system| !system.multi_threaded) {
+            rows.push([
+                system_name(self, system.system),
+                String::new(),
+                String::from("not multi threaded"),
+            ]);
+        }
+
+        let width = |column: usize| rows.iter().map(|row| row[column].len()).max().unwrap_or(0);
+        let (first_width, second_width) = (width(0), width(1));
+
+        let mut out = String::new();
+        for row in &rows {
+            let _ = writeln!(
+                out,
+                "{:first_width$} | {:second_width$} | {}",
+                row[0], row[1], row[2]
+            );
+        }
+        out
+    }
+
+    /// Describe the order of the systems in the current pipeline as a graphviz dot graph.
+    ///
+    /// Systems are grouped in a cluster per phase and sync points are drawn as separate nodes.
+    /// Returns an empty graph until the world has progressed at least once.
+    ///
+    /// # See also
+    ///
+    /// * [`World::pipeline_schedule()`]
+    /// * [`World::pipeline_to_string()`]
+    pub fn pipeline_to_dot(&self) -> String {
+        let mut out = String::from("digraph pipeline {\n  rankdir=LR;\n  node [shape=box];\n");
+        let Some(schedule) = self.pipeline_schedule() else {
+            out.push_str("}\n");
+            return out;
+        };
+
+        let mut edges = Vec::new();
+        let mut previous: Option<String> = None;
+        let mut phase = None;
+        let mut in_cluster = false;
+        for (index, step) in schedule.steps.iter().enumerate() {
+            let node = format!("n{index}");
+            match step {
+                ScheduleStep::System(system) => {
+                    if phase != Some(system.phase) {
+                        if in_cluster {
+                            out.push_str("  }\n");
+                        }
+                        phase = Some(system.phase);
+                        in_cluster = system.phase.is_some();
+                        if let Some(phase) = system.phase {
+                            let _ = writeln!(
+                                out,
+                                "  subgraph cluster_{index} {{\n    label=\"{}\";",
+                                system_name(self, phase)
+                            );
+                        }
+                    }
+
+                    let mut label = system_name(self, system.system);
+                    if !system.reads.is_empty() {
+                        let _ = write!(label, "\\nreads: {}", ids_str(self, &system.reads));
+                    }
+                    if !system.writes.is_empty() {
+                        let _ = write!(label, "\\nwrites: {}", ids_str(self, &system.writes));
+                    }
+                    if !system.deferred_writes.is_empty() {
+                        let _ = write!(
+                            label,
+                            "\\ndeferred writes: {}",
+                            ids_str(self, &system.deferred_writes)
+                        );
+                    }
+                    let _ = writeln!(out, "    {node} [label=\"{label}\"];");
+                }
+                ScheduleStep::SyncPoint => {
+                    let _ = writeln!(
+                        out,
+                        "    {node} [label=\"sync\\n{}\", shape=diamond];",
+                        schedule.sync_point_reason(self, index)
+                    );
+                }
+            }
+
+            if let Some(previous) = previous {
+                edges.push(format!("  {previous} -> {node};\n"));
+            }
+            previous = Some(node);
+        }
+
+        if in_cluster {
+            out.push_str("  }\n");
+        }
+        // edges are added outside of the clusters, so nodes stay in the cluster of their phase
+        for edge in edges {
+            out.push_str(&edge);
+        }
+        out.push_str("}\n");
+        out
+    }
+}
@@ -0,0 +1,400 @@
+//! Load prefabs from files and re-apply them to their instances when the files change, see
+//! [`World::prefab_registry()`].
+//!
+//! Files ending in `.json` are loaded as JSON created by [`World::to_json_world()`], other files
+//! are run as flecs scripts. With the `flecs_prefab_watch` feature the files can be watched with
+//! [`PrefabRegistry::watch()`], which reloads them when they're saved.
+
+extern crate std;
+
+use std::path::{Path, PathBuf};
+use std::time::SystemTime;
+
+use flecs_ecs::sys;
+
+use crate::addons::json::{is_structural, value_to_json};
+use crate::core::*;
+
+extern crate alloc;
+use alloc::{format, string::String, vec::Vec};
+
+/// The owned components of an entity with their values, if they have reflection data.
+type Snapshot = Vec<(Id, Option<String>)>;
+
+struct PrefabFile {
+    path: PathBuf,
+    /// The modification time of the file when it was last loaded.
+    modified: Option<SystemTime>,
+    /// The prefabs defined by the file.
+    prefabs: Vec<Entity>,
+}
+
+/// The files loaded by the prefab registry, stored per world.
+#[derive(Default)]
+pub(crate) struct PrefabFiles {
+    files: Vec<PrefabFile>,
+}
+
+impl PrefabFiles {
+    fn get(&self, path: &Path) -> Option<&PrefabFile> {
+        self.files.iter().find(|file| file.path == path)
+    }
+
+    fn get_or_insert(&mut self, path: &Path) -> &mut PrefabFile {
+        let index = match self.files.iter().position(|file| file.path == path) {
+            Some(index) => index,
+            None => {
+                self.files.push(PrefabFile {
+                    path: path.to_path_buf(),
+                    modified: None,
+                    prefabs: Vec::new(),
+                });
+                self.files.len() - 1
+            }
+        };
+        &mut self.files[index]
+    }
+}
+
+fn modified(path: &Path) -> Option<SystemTime> {
+    std::fs::metadata(path)
+        .and_then(|metadata| metadata.modified())
+        .ok()
+}
+
+fn snapshot(entity: EntityView) -> Snapshot {
+    entity
+        .archetype()
+        .as_slice()
+        .iter()
+        .filter(|&&id| !is_structural(id))
+        .map(|&id| (id, value_to_json(entity, id)))
+        .collect()
+}
+
+fn find(snapshot: &Snapshot, id: Id) -> Option<&Option<String>> {
+    snapshot
+        .iter()
+        .find(|(other, _)| *other == id)
+        .map(|(_, value)| value)
+}
+
+/// Returns true if the id is copied to instances, which is the default.
+fn is_overridden(world: &World, id: Id) -> bool {
+    let component = if id.is_pair() { *id.first() } else { *id };
+    let policy =
+        unsafe { sys::ecs_get_target(world.world_ptr(), component, flecs::OnInstantiate::ID, 0) };
+    policy == 0 || policy == flecs::Override::ID
+}
+
+fn set_value(entity: EntityView, id: Id, value: &Option<String>) -> Result<(), FlecsError> {
+    match value {
+        Some(json) => {
+            entity.try_set_json_id(id, json, None)?;
+        }
+        None => unsafe { sys::ecs_add_id(entity.world_ptr_mut(), *entity.id(), *id) },
+    }
+    Ok(())
+}
+
+/// Copy a component from a prefab to an instance.
+fn copy_from(prefab: Entity, instance: EntityView, id: Id) {
+    let world = instance.world_ptr_mut();
+    unsafe {
+        let ptr = sys::ecs_get_id(world, *prefab, *id);
+        let type_info = sys::ecs_get_type_info(world, *id);
+        if ptr.is_null() || type_info.is_null() {
+            sys::ecs_add_id(world, *instance.id(), *id);
+        } else {
+            sys::ecs_set_id(world, *instance.id(), *id, (*type_info).size as usize, ptr);
+        }
+    }
+}
+
+fn all_prefabs(world: &World) -> Vec<Entity> {
+    let mut prefabs = Vec::new();
+    world
+        .query::<()>()
+        .with::<flecs::Prefab>()
+        .query_flags(QueryFlags::MatchDisabled)
+        .build()
+        .each_entity(|e, _| prefabs.push(e.id()));
+    prefabs
+}
+
+/// Make the components of `entity` equal to a snapshot.
+fn restore(entity: EntityView, snapshot: &Snapshot) -> Result<(), FlecsError> {
+    for (id, _) in self::snapshot(entity) {
+        entity.remove_id(id);
+    }
+    for (id, value) in snapshot {
+        set_value(entity, *id, value)?;
+    }
+    Ok(())
+}
+
+/// Apply the changes of a prefab from `old` to `new` to its instances.
+///
+/// Components an instance got from the prefab are updated, components that were changed on the
+/// instance itself are left alone. Instances that are prefabs pass their changes on to their own
+/// instances.
+fn update_instances(world: &World, prefab: Entity, old: &Snapshot, new: &Snapshot) {
+    let is_a = Id::from((flecs::IsA::ID, prefab));
+    let mut instances = Vec::new();
+    world
+        .query::<()>()
+        .with_id(is_a)
+        .query_flags(QueryFlags::MatchPrefab | QueryFlags::MatchDisabled)
+        .build()
+        .each_entity(|e, _| {
+            // IsA is transitive, instances of instances are updated by their own prefab
+            if e.owns_id(is_a) {
+                instances.push(e.id());
+            }
+        });
+
+    for instance in instances {
+        let instance = EntityView::new_from(world, instance);
+        let before = snapshot(instance);
+        let is_unchanged = |id: Id, value: &Option<String>| {
+            instance.owns_id(id) && value_to_json(instance, id) == *value
+        };
+
+        for (id, old_value) in old {
+            if find(new, *id).is_none() && is_unchanged(*id, old_value) {
+                instance.remove_id(*id);
+            }
+        }
+        for (id, new_value) in new {
+            match find(old, *id) {
+                None if !instance.owns_id(*id) && is_overridden(world, *id) => {
+                    copy_from(prefab, instance, *id);
+                }
+                Some(old_value) if old_value != new_value && is_unchanged(*id, old_value) => {
+                    copy_from(prefab, instance, *id);
+                }
+                _ => {}
+            }
+        }
+
+        if instance.has::<flecs::Prefab>() {
+            let after = snapshot(instance);
+            if before != after {
+                update_instances(world, instance.id(), &before, &after);
+            }
+        }
+    }
+}
+
+/// Loads prefabs from files, created by [`World::prefab_registry()`].
+///
+/// Loading a file again replaces the components of the prefabs it defined with the components in
+/// the file, and applies the changes to the instances of the prefabs. Components that an instance
+/// got from a prefab are updated, components that were changed on the instance are left alone.
+#[derive(Clone, Copy)]
+pub struct PrefabRegistry<'a> {
+    world: WorldRef<'a>,
+}
+
+impl<'a> PrefabRegistry<'a> {
+    /// Load the prefabs of a file, or reload them if the file was loaded before.
+    ///
+    /// Components of the prefabs that aren't in the file anymore are removed.
+    ///
+    /// # Returns
+    ///
+    /// The prefabs defined by the file, or an error if the file can't be read or loaded. When
+    /// loading fails, prefabs that were loaded from the file before keep their components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// #[meta]
+    /// struct Attack {
+    ///     value: i32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.component_named::<Attack>("Attack").meta();
+    ///
+    /// let path = std::env::temp_dir().join("prefab_registry_load.flecs");
+    /// std::fs::write(&path, "prefab SpaceShip { Attack: {50} }").unwrap();
+    ///
+    /// let prefabs = world.prefab_registry().load(&path).unwrap();
+    /// let ship = world.entity().is_a_id(prefabs[0]);
+    ///
+    /// std::fs::write(&path, "prefab SpaceShip { Attack: {75} }").unwrap();
+    /// world.prefab_registry().load(&path).unwrap();
+    ///
+    /// ship.get::<&Attack>(|attack| assert_eq!(attack.value, 75));
+    /// # std::fs::remove_file(&path).unwrap();
+    /// ```
+    pub fn load(&self, path: impl AsRef<Path>) -> Result<Vec<EntityView<'a>>, FlecsError> {
+        let path = path.as_ref();
+        let world = self.world.real_world();
+
+        let known: Vec<Entity> = world
+            .world_ctx()
+            .prefab_files
+            .get(path)
+            .map(|file| file.prefabs.clone())
+            .unwrap_or_default()
+            .into_iter()
+            .filter(|&prefab| world.is_alive(prefab))
+            .collect();
+
+        // a file that fails to load is only loaded again when it's changed
+        world
+            .world_ctx_mut()
+            .prefab_files
+            .get_or_insert(path)
+            .modified = modified(path);
+
+        let source = std::fs::read_to_string(path)
+            .map_err(|err| FlecsError::Io(format!("{}: {err}", path.display())))?;
+
+        let before: Vec<(Entity, Snapshot)> = known
+            .iter()
+            .map(|&prefab| (prefab, snapshot(EntityView::new_from(world, prefab))))
+            .collect();
+        for (prefab, snapshot) in &before {
+            let prefab = EntityView::new_from(world, *prefab);
+            for (id, _) in snapshot {
+                prefab.remove_id(*id);
+            }
+        }
+
+        let existing = all_prefabs(&world);
+        let result = if path.extension().is_some_and(|ext| ext == "json") {
+            world.try_from_json_world(&source, None).map(|_| ())
+        } else {
+            let name = format!("{}", path.display());
+            let (ok, message) = ecs_os_api::capture_errors(|| world.run_code(&name, &source));
+            if ok {
+                Ok(())
+            } else {
+                Err(FlecsError::ParseError(message.unwrap_or_default()))
+            }
+        };
+
+        if let Err(err) = result {
+            for (prefab, snapshot) in &before {
+                restore(EntityView::new_from(world, *prefab), snapshot)?;
+            }
+            return Err(err);
+        }
+
+        let mut prefabs = known;
+        prefabs.extend(
+            all_prefabs(&world)
+                .into_iter()
+                .filter(|prefab| !existing.contains(prefab)),
+        );
+        world
+            .world_ctx_mut()
+            .prefab_files
+            .get_or_insert(path)
+            .prefabs = prefabs.clone();
+
+        for (prefab, old) in &before {
+            let new = snapshot(EntityView::new_from(world, *prefab));
+            if *old != new {
+                update_instances(&world, *prefab, old, &new);
+            }
+        }
+
+        Ok(prefabs
+            .into_iter()
+            .map(|prefab| EntityView::new_from(self.world, prefab))
+            .collect())
+    }
+
+    /// Reload the files that were modified since they were loaded.
+    ///
+    /// # Returns
+    ///
+    /// The prefabs of the reloaded files, or the first error if a file failed to load. The other
+    /// files are reloaded regardless.
+    pub fn reload_changed(&self) -> Result<Vec<EntityView<'a>>, FlecsError> {
+        let changed: Vec<PathBuf> = self
+            .world
+            .world_ctx()
+            .prefab_files
+            .files
+            .iter()
+            .filter(|file| modified(&file.path) != file.modified)
+            .map(|file| file.path.clone())
+            .collect();
+
+        let mut prefabs = Vec::new();
+        let mut error = None;
+        for path in changed {
+            match self.load(&path) {
+                Ok(loaded) => prefabs.extend(loaded),
+                Err(err) => {
+                    error.get_or_insert(err);
+                }
+            }
+        }
+        error.map_or(Ok(prefabs), Err)
+    }
+
+    /// The prefabs defined by a loaded file.
+    pub fn prefabs(&self, path: impl AsRef<Path>) -> Vec<EntityView<'a>> {
+        self.world
+            .world_ctx()
+            .prefab_files
+            .get(path.as_ref())
+            .map(|file| {
+                file.prefabs
+                    .iter()
+                    .map(|&prefab| EntityView::new_from(self.world, prefab))
+                    .collect()
+            })
+            .unwrap_or_default()
+    }
+
+    /// The paths of the loaded files.
+    pub fn files(&self) -> Vec<PathBuf> {
+        self.world
+            .world_ctx()
+            .prefab_files
+            .files
+            .iter()
+            .map(|file| file.path.clone())
+            .collect()
+    }
+
+    /// Create a system that reloads the files that changed every `interval` seconds, see
+    /// [`PrefabRegistry::reload_changed()`]. Errors are logged.
+    ///
+    /// The system runs in the `OnLoad` phase and isn't staged, so it can be added to worlds that
+    /// run systems on multiple threads.
+    #[cfg(feature = "flecs_prefab_watch")]
+    pub fn watch(&self, interval: f32) -> crate::addons::system::System<'_> {
+        self.world
+            .system_named::<()>("flecs::prefab_registry::Watch")
+            .kind::<flecs::pipeline::OnLoad>()
+            .immediate(true)
+            .set_interval(interval)
+            .run(|mut it| {
+                while it.next() {}
+                let world = it.world();
+                if let Err(err) = world.prefab_registry().reload_changed() {
+                    log_error(&format!("failed to reload prefabs: {err}"));
+                }
+            })
+    }
+}
+
+impl World {
+    /// Load prefabs from files and keep them up to date, see [`PrefabRegistry`].
+    pub fn prefab_registry(&self) -> PrefabRegistry<'_> {
+        PrefabRegistry {
+            world: self.world(),
+        }
+    }
+}
@@ -0,0 +1,388 @@
+//! Per-system and per-query timing, enabled with the `flecs_profiler` feature.
+//!
+//! Every world has a [`Profiler`], returned by [`World::profiler()`]. It records each run of a
+//! system and each iteration of a query with [`QueryAPI::each()`], [`QueryAPI::each_entity()`],
+//! [`QueryAPI::each_iter()`] and [`QueryAPI::run()`]: how often it ran, the tables and entities it
+//! iterated and the time it took. [`Profiler::report()`] formats the results as a table.
+//!
+//! Tables and entities are counted when the profiler sees the tables being iterated, which is the
+//! case for systems and queries that use `each`. Systems and queries with a `run` callback iterate
+//! the tables themselves, so only their time is recorded.
+//!
+//! [`Profiler::set_span_hooks()`] registers callbacks that are invoked when a system or query
+//! starts and stops, which can be used to forward the spans to a tracing library. The time spent in
+//! flecs itself, such as merging commands, is traced with the `ecs_os_api` perf trace callbacks
+//! of the `flecs_perf_trace` feature.
+//!
+//! # Example
+//!
+//! ```
+//! use flecs_ecs::prelude::*;
+//!
+//! #[derive(Component)]
+//! struct Position {
+//!     x: f32,
+//!     y: f32,
+//! }
+//!
+//! let world = World::new();
+//! world.entity().set(Position { x: 0.0, y: 0.0 });
+//! world.entity().set(Position { x: 1.0, y: 1.0 });
+//!
+//! let system = world
+//!     .system_named::<&mut Position>("Move")
+//!     .each(|p| p.x += 1.0);
+//!
+//! world.progress();
+//! world.progress();
+//!
+//! let entry = world.profiler().system(system).unwrap();
+//! assert_eq!(entry.invocations, 2);
+//! assert_eq!(entry.entities, 4);
+//!
+//! println!("{}", world.profiler().report());
+//! ```
+
+use core::fmt::Write;
+use core::time::Duration;
+use std::collections::HashMap;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+use std::time::Instant;
+
+use crate::core::*;
+use crate::sys;
+
+use alloc::string::String;
+use alloc::sync::Arc;
+use alloc::vec::Vec;
+
+/// What a [`ProfileEntry`] measured.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ProfileKind {
+    /// Runs of a system.
+    System,
+    /// Iterations of a query.
+    Query,
+}
+
+/// The timings the [`Profiler`] recorded for a system or query.
+#[derive(Debug, Clone)]
+pub struct ProfileEntry {
+    /// The path of the system or query entity, or the query expression of queries without one.
+    pub name: Arc<str>,
+    /// Whether this entry is for a system or a query.
+    pub kind: ProfileKind,
+    /// Number of times the system ran or the query was iterated.
+    pub invocations: u64,
+    /// Tables iterated over all invocations.
+    pub tables: u64,
+    /// Entities iterated over all invocations.
+    pub entities: u64,
+    /// Time spent over all invocations.
+    pub total: Duration,
+}
+
+impl ProfileEntry {
+    /// Average time of an invocation in nanoseconds.
+    pub fn ns_per_invocation(&self) -> u64 {
+        if self.invocations == 0 {
+            0
+        } else {
+            (self.total.as_nanos() / self.invocations as u128) as u64
+        }
+    }
+}
+
+type SpanHook = dyn Fn(&str) + Send + Sync;
+
+struct SpanHooks {
+    enter: Box<SpanHook>,
+    exit: Box<SpanHook>,
+}
+
+// Queries without an entity are identified by their address
+type ProfileKey = (ProfileKind, u64);
+
+/// Records the time spent in the systems and queries of a world.
+///
+/// Created with the world, and returned by [`World::profiler()`].
+#[derive(Default)]
+pub struct Profiler {
+    entries: Mutex<HashMap<ProfileKey, ProfileEntry>>,
+    // The run callbacks of systems that were replaced to time them
+    runs: Mutex<HashMap<u64, sys::ecs_run_action_t>>,
+    hooks: Mutex<Option<Arc<SpanHooks>>>,
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    // A panicking system can poison the lock, the recorded timings are still usable.
+    mutex.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+impl Profiler {
+    /// The timings of all systems and queries, the slowest first.
+    pub fn entries(&self) -> Vec<ProfileEntry> {
+        let mut entries: Vec<ProfileEntry> = lock(&self.entries).values().cloned().collect();
+        entries.sort_by(|a, b| b.total.cmp(&a.total).then_with(|| a.name.cmp(&b.name)));
+        entries
+    }
+
+    /// The timings of a system, or `None` if it hasn't run yet.
+    pub fn system(&self, system: impl Into<Entity>) -> Option<ProfileEntry> {
+        let key = (ProfileKind::System, *system.into());
+        lock(&self.entries).get(&key).cloned()
+    }
+
+    /// The timings of a query, or `None` if it hasn't been iterated yet.
+    pub fn query<T: QueryTuple>(&self, query: &Query<T>) -> Option<ProfileEntry> {
+        let key = query_key(query.query_ptr());
+        lock(&self.entries).get(&key).cloned()
+    }
+
+    /// Clear all recorded timings.
+    pub fn reset(&self) {
+        lock(&self.entries).clear();
+    }
+
+    /// Format the timings of all systems and queries as a table, the slowest first.
+    pub fn report(&self) -> String {
+        let entries = self.entries();
+        let width = entries
+            .iter()
+            .map(|entry| entry.name.len())
+            .max()
+            .unwrap_or(0)
+            .max("name".len());
+
+        let mut report = String::new();
+        let _ = writeln!(
+            report,
+            "{:<width$}  {:<6}  {:>11}  {:>8}  {:>10}  {:>12}  {:>12}",
+            "name", "kind", "invocations", "tables", "entities", "total ms", "ns/call"
+        );
+        for entry in &entries {
+            let kind = match entry.kind {
+                ProfileKind::System => "system",
+                ProfileKind::Query => "query",
+            };
+            let _ = writeln!(
+                report,
+                "{:<width$}  {:<6}  {:>11}  {:>8}  {:>10}  {:>12.3}  {:>12}",
+                entry.name,
+                kind,
+                entry.invocations,
+                entry.tables,
+                entry.entities,
+                entry.total.as_secs_f64() * 1000.0,
+                entry.ns_per_invocation()
+            );
+        }
+        report
+    }
+
+    /// Set callbacks that are invoked with the name of a system or query when it starts and
+    /// stops.
+    ///
+    /// Systems can run on worker threads, so the callbacks can be invoked from any thread, and
+    /// spans of queries iterated inside a system are nested in the span of the system.
+    ///
+    /// # Example
+    ///
+    /// Forwarding the spans to the `tracing` crate:
+    ///
+    /// ```ignore
+    /// thread_local! {
+    ///     static SPANS: RefCell<Vec<tracing::span::EnteredSpan>> = RefCell::default();
+    /// }
+    ///
+    /// world.profiler().set_span_hooks(
+    ///     |name| {
+    ///         let span = tracing::info_span!("flecs", name).entered();
+    ///         SPANS.with(|spans| spans.borrow_mut().push(span));
+    ///     },
+    ///     |_| SPANS.with(|spans| drop(spans.borrow_mut().pop())),
+    /// );
+    /// ```
+    pub fn set_span_hooks(
+        &self,
+        enter: impl Fn(&str) + Send + Sync + 'static,
+        exit: impl Fn(&str) + Send + Sync + 'static,
+    ) {
+        *lock(&self.hooks) = Some(Arc::new(SpanHooks {
+            enter: Box::new(enter),
+            exit: Box::new(exit),
+        }));
+    }
+
+    /// Remove the callbacks set with [`Profiler::set_span_hooks()`].
+    pub fn clear_span_hooks(&self) {
+        *lock(&self.hooks) = None;
+    }
+}
+
+impl core::fmt::Debug for Profiler {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("Profiler")
+            .field("entries", &self.entries())
+            .finish_non_exhaustive()
+    }
+}
+
+impl World {
+    /// Get the profiler of the world, which records the time spent in systems and queries.
+    ///
+    /// See the [`profiler`](crate::addons::profiler) module for what is recorded.
+    pub fn profiler(&self) -> &Profiler {
+        &self.world_ctx().profiler
+    }
+}
+
+fn query_key(query: *const sys::ecs_query_t) -> ProfileKey {
+    let entity = unsafe { (*query).entity };
+    if entity != 0 {
+        (ProfileKind::Query, entity)
+    } else {
+        (ProfileKind::Query, query as u64)
+    }
+}
+
+/// # Safety
+/// `world` must point to a valid world or stage that outlives the returned reference.
+unsafe fn profiler_of<'a>(world: *const sys::ecs_world_t) -> &'a Profiler {
+    unsafe { &(*(sys::ecs_get_binding_ctx(world) as *const WorldCtx)).profiler }
+}
+
+/// Records one invocation of a system or query when dropped.
+pub(crate) struct ProfileScope<'a> {
+    profiler: &'a Profiler,
+    key: ProfileKey,
+    name: Arc<str>,
+    hooks: Option<Arc<SpanHooks>>,
+    tables: u64,
+    entities: u64,
+    start: Instant,
+}
+
+impl<'a> ProfileScope<'a> {
+    fn new(
+        profiler: &'a Profiler,
+        key: ProfileKey,
+        name: impl FnOnce() -> String,
+    ) -> ProfileScope<'a> {
+        let name = lock(&profiler.entries)
+            .entry(key)
+            .or_insert_with(|| ProfileEntry {
+                name: name().into(),
+                kind: key.0,
+                invocations: 0,
+                tables: 0,
+                entities: 0,
+                total: Duration::ZERO,
+            })
+            .name
+            .clone();
+        let hooks = lock(&profiler.hooks).clone();
+        if let Some(hooks) = &hooks {
+            (hooks.enter)(&name);
+        }
+        ProfileScope {
+            profiler,
+            key,
+            name,
+            hooks,
+            tables: 0,
+            entities: 0,
+            start: Instant::now(),
+        }
+    }
+
+    /// Start timing an iteration of `query`.
+    ///
+    /// # Safety
+    /// `world` and `query` must be valid for the lifetime of the scope.
+    pub(crate) unsafe fn query(
+        world: *const sys::ecs_world_t,
+        query: *const sys::ecs_query_t,
+    ) -> ProfileScope<'a> {
+        let key = query_key(query);
+        let profiler = unsafe { profiler_of(world) };
+        ProfileScope::new(profiler, key, || {
+            if key.1 == query as u64 {
+                let expr = unsafe { sys::ecs_query_str(query) };
+                let name = unsafe { core::ffi::CStr::from_ptr(expr) }
+                    .to_string_lossy()
+                    .into_owned();
+                if let Some(free_func) = unsafe { sys::ecs_os_api.free_ } {
+                    unsafe { free_func(expr as *mut _) };
+                }
+                name
+            } else {
+                let world = unsafe { WorldRef::from_ptr(world as *mut _) };
+                EntityView::new_from(world, key.1)
+                    .path()
+                    .unwrap_or_default()
+            }
+        })
+    }
+
+    /// Count a table that was iterated.
+    #[inline]
+    pub(crate) fn table(&mut self, count: i32) {
+        self.tables += 1;
+        self.entities += count as u64;
+    }
+}
+
+impl Drop for ProfileScope<'_> {
+    fn drop(&mut self) {
+        let elapsed = self.start.elapsed();
+        if let Some(entry) = lock(&self.profiler.entries).get_mut(&self.key) {
+            entry.invocations += 1;
+            entry.tables += self.tables;
+            entry.entities += self.entities;
+            entry.total += elapsed;
+        }
+        if let Some(hooks) = &self.hooks {
+            (hooks.exit)(&self.name);
+        }
+    }
+}
+
+/// Replace the run callback of a system with one that times it. Returns the replaced callback,
+/// which has to be passed to [`register_system()`] once the system is created.
+#[cfg(feature = "flecs_system")]
+pub(crate) fn instrument_system(desc: &mut sys::ecs_system_desc_t) -> sys::ecs_run_action_t {
+    desc.run.replace(profiled_run)
+}
+
+#[cfg(feature = "flecs_system")]
+pub(crate) fn register_system(world: &World, system: u64, run: sys::ecs_run_action_t) {
+    lock(&world.profiler().runs).insert(system, run);
+}
+
+#[cfg(feature = "flecs_system")]
+unsafe extern "C-unwind" fn profiled_run(it: *mut sys::ecs_iter_t) {
+    unsafe {
+        let world = (*it).real_world;
+        let system = (*it).system;
+        let profiler = profiler_of(world);
+        let run = lock(&profiler.runs).get(&system).copied().flatten();
+
+        let mut scope = ProfileScope::new(profiler, (ProfileKind::System, system), || {
+            EntityView::new_from(WorldRef::from_ptr(world), system)
+                .path()
+                .unwrap_or_default()
+        });
+
+        if let Some(run) = run {
+            run(it);
+        } else if let Some(action) = (*it).callback {
+            // what flecs does for systems without a run callback, which also cleans up the
+            // iterator of systems that match nothing
+            while sys::ecs_iter_next(it) {
+                scope.table((*it).count);
+                action(it);
+            }
+        }
+    }
+}
@@ -0,0 +1,676 @@
+//! A REST server written in Rust that implements the endpoints used by the
+//! [flecs explorer](https://www.flecs.dev/explorer), enabled with the `flecs_rust_rest` feature.
+//!
+//! This is an alternative to the `flecs_rest` addon of flecs for platforms where the HTTP server
+//! of the C library is undesirable. Requests are received on a background thread and answered on
+//! the thread of the world with the JSON serialization of [`World`], [`EntityView`] and queries,
+//! see [`World::serve_rest()`] and [`RestServer::dequeue()`].
+//!
+//! # Endpoints
+//!
+//! Entity paths use `/` as separator, like `/entity/parent/child`.
+//!
+//! * `GET /entity/<path>` - the entity as JSON, with the same options as the C REST API.
+//! * `PUT /entity/<path>` - create an entity with the path.
+//! * `DELETE /entity/<path>` - delete the entity.
+//! * `GET /component/<path>?component=<id>` - the value of a component as JSON.
+//! * `PUT /component/<path>?component=<id>[&value=<json>]` - add or set a component.
+//! * `DELETE /component/<path>?component=<id>` - remove a component.
+//! * `PUT /toggle/<path>?enable=<bool>[&component=<id>]` - enable or disable an entity or a
+//!   component that can be toggled.
+//! * `GET /query?expr=<query>` or `GET /query?name=<name>` - the results of a query, which can be
+//!   paged with `offset` and `limit`.
+//! * `GET /world` - the whole world as JSON.
+//!
+//! Other endpoints of the C REST API, such as statistics and scripts, are answered with `404`.
+
+use core::net::{IpAddr, Ipv4Addr, SocketAddr};
+use core::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use core::time::Duration;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpListener, TcpStream, ToSocketAddrs};
+use std::sync::mpsc::{self, Receiver, Sender};
+use std::thread::JoinHandle;
+
+use crate::core::*;
+use crate::prelude::json::{EntityToJsonDesc, IterToJsonDesc};
+use crate::sys;
+
+extern crate alloc;
+use alloc::{
+    format,
+    string::{String, ToString},
+    sync::Arc,
+    vec::Vec,
+};
+
+/// The maximum size of the body of a request.
+const MAX_BODY_SIZE: usize = 16 * 1024 * 1024;
+
+/// The maximum size of the request line and headers of a request together.
+const MAX_HEADER_SIZE: u64 = 64 * 1024;
+
+/// The maximum number of headers of a request.
+const MAX_HEADERS: usize = 100;
+
+/// The time a connection may take to send its request.
+const READ_TIMEOUT: Duration = Duration::from_secs(5);
+
+/// The maximum number of connections that are served at the same time. Connections beyond this
+/// are closed right away.
+const MAX_CONNECTIONS: usize = 32;
+
+/// A REST server that answers requests of the flecs explorer, see the [module](self)
+/// documentation for the endpoints.
+///
+/// Connections are accepted and read on background threads, of which at most 32 serve connections
+/// at the same time, but requests are only answered when [`RestServer::dequeue()`] is called with
+/// the world. [`World::serve_rest()`] does this every
+/// frame from a system.
+///
+/// The server stops when it is dropped.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::addons::rest_server::RestServer;
+/// use flecs_ecs::prelude::*;
+///
+/// let world = World::new();
+/// let server = RestServer::bind("127.0.0.1:0").unwrap();
+/// println!("serving on {}", server.local_addr());
+///
+/// // answer the requests that came in since the previous call
+/// server.dequeue(&world);
+/// ```
+pub struct RestServer {
+    addr: SocketAddr,
+    requests: Receiver<(Request, Sender<Response>)>,
+    stop: Arc<AtomicBool>,
+    listener: Option<JoinHandle<()>>,
+}
+
+impl RestServer {
+    /// Start listening for connections on an address.
+    ///
+    /// Use port `0` to let the operating system pick a free port, which is returned by
+    /// [`RestServer::local_addr()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of binding the address, for example when the port is in use.
+    pub fn bind(addr: impl ToSocketAddrs) -> io::Result<Self> {
+        let listener = TcpListener::bind(addr)?;
+        let addr = listener.local_addr()?;
+        let stop = Arc::new(AtomicBool::new(false));
+        let (sender, requests) = mpsc::channel();
+
+        let listener = {
+            let stop = stop.clone();
+            std::thread::Builder::new()
+                .name("flecs-rest".to_string())
+                .spawn(move || listen(listener, sender, &stop))?
+        };
+
+        Ok(Self {
+            addr,
+            requests,
+            stop,
+            listener: Some(listener),
+        })
+    }
+
+    /// The address the server listens on.
+    pub fn local_addr(&self) -> SocketAddr {
+        self.addr
+    }
+
+    /// Answer the requests that were received since the previous call.
+    ///
+    /// Changes made by requests are deferred when this is called while the world is deferred,
+    /// such as from a system.
+    ///
+    /// # Returns
+    ///
+    /// The number of requests that were answered.
+    pub fn dequeue(&self, world: &World) -> usize {
+        let mut count = 0;
+        while let Ok((request, reply)) = self.requests.try_recv() {
+            // the connection may be closed already, which is fine
+            let _ = reply.send(reply_to(world, &request));
+            count += 1;
+        }
+        count
+    }
+}
+
+impl Drop for RestServer {
+    fn drop(&mut self) {
+        self.stop.store(true, Ordering::Release);
+
+        // wake up the listener, which is blocked until the next connection
+        let mut wake = self.addr;
+        if wake.ip().is_unspecified() {
+            wake.set_ip(IpAddr::V4(Ipv4Addr::LOCALHOST));
+        }
+        let _ = TcpStream::connect(wake);
+
+        if let Some(listener) = self.listener.take() {
+            let _ = listener.join();
+        }
+    }
+}
+
+impl World {
+    /// Answer requests of the flecs explorer with a [`RestServer`] on an address.
+    ///
+    /// The requests are answered every frame by a system in the [`flecs::pipeline::OnStore`]
+    /// phase, so [`World::progress()`] has to be called. The server stops when the world is
+    /// dropped.
+    ///
+    /// # Returns
+    ///
+    /// The address the server listens on.
+    ///
+    /// # Errors
+    ///
+    /// Returns the error of binding the address, for example when the port is in use.
+    ///
+    /// # Example
+    ///
+    /// ```no_run
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    /// world.serve_rest("0.0.0.0:27750").unwrap();
+    ///
+    /// while world.progress() {}
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`RestServer`]
+    pub fn serve_rest(&self, addr: impl ToSocketAddrs) -> io::Result<SocketAddr> {
+        let server = RestServer::bind(addr)?;
+        let addr = server.local_addr();
+
+        // requests create queries and entities, which can't be done while the world is readonly
+        self.system::<()>()
+            .kind::<flecs::pipeline::OnStore>()
+            .immediate(true)
+            .run(move |it| {
+                server.dequeue(&it.world());
+            });
+
+        Ok(addr)
+    }
+}
+
+struct Request {
+    method: String,
+    path: String,
+    params: Vec<(String, String)>,
+    body: String,
+}
+
+impl Request {
+    fn param(&self, name: &str) -> Option<&str> {
+        self.params
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+
+    fn bool_param(&self, name: &str, value: &mut bool) {
+        if let Some(param) = self.param(name) {
+            *value = param == "true";
+        }
+    }
+
+    fn int_param(&self, name: &str, value: &mut i32) {
+        if let Some(param) = self.param(name) {
+            *value = param.parse().unwrap_or(0);
+        }
+    }
+}
+
+struct Response {
+    code: u16,
+    body: String,
+}
+
+impl Response {
+    fn ok(body: String) -> Self {
+        Self { code: 200, body }
+    }
+
+    fn error(code: u16, message: &str) -> Self {
+        Self {
+            code,
+            body: format!("{{\"error\":{}}}", json_string(message)),
+        }
+    }
+
+    fn write_to(&self, stream: &mut TcpStream) -> io::Result<()> {
+        let status = match self.code {
+            200 => "OK",
+            400 => "Bad Request",
+            404 => "Not Found",
+            _ => "Internal Server Error",
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {} {status}\r\n\
+             Content-Type: application/json\r\n\
+             Content-Length: {}\r\n\
+             Access-Control-Allow-Origin: *\r\n\
+             Access-Control-Allow-Methods: GET, PUT, DELETE, OPTIONS\r\n\
+             Access-Control-Allow-Headers: *\r\n\
+             Connection: close\r\n\r\n",
+            self.code,
+            self.body.len()
+        )?;
+        stream.write_all(self.body.as_bytes())?;
+        stream.flush()
+    }
+}
+
+/// Counts a connection that is being served, until it is dropped.
+struct ConnectionGuard(Arc<AtomicUsize>);
+
+impl ConnectionGuard {
+    fn acquire(connections: &Arc<AtomicUsize>) -> Option<Self> {
+        connections
+            .fetch_update(Ordering::AcqRel, Ordering::Acquire, |count| {
+                (count < MAX_CONNECTIONS).then_some(count + 1)
+            })
+            .ok()
+            .map(|_| Self(connections.clone()))
+    }
+}
+
+impl Drop for ConnectionGuard {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+fn listen(listener: TcpListener, requests: Sender<(Request, Sender<Response>)>, stop: &AtomicBool) {
+    let connections = Arc::new(AtomicUsize::new(0));
+    for stream in listener.incoming() {
+        if stop.load(Ordering::Acquire) {
+            break;
+        }
+        let Ok(stream) = stream else {
+            continue;
+        };
+        // too many connections, which closes the stream
+        let Some(guard) = ConnectionGuard::acquire(&connections) else {
+            continue;
+        };
+
+        let requests = requests.clone();
+        // a slow client shouldn't hold up other connections
+        let _ = std::thread::Builder::new()
+            .name("flecs-rest-connection".to_string())
+            .spawn(move || {
+                let _guard = guard;
+                serve_connection(stream, &requests);
+            });
+    }
+}
+
+fn serve_connection(mut stream: TcpStream, requests: &Sender<(Request, Sender<Response>)>) {
+    let _ = stream.set_read_timeout(Some(READ_TIMEOUT));
+
+    let response = match read_request(&stream) {
+        Ok(request) if request.method == "OPTIONS" => Response::ok(String::new()),
+        Ok(request) => {
+            let (reply, response) = mpsc::channel();
+            if requests.send((request, reply)).is_err() {
+                return;
+            }
+            // the server is dropped before it answered the request
+            let Ok(response) = response.recv() else {
+                return;
+            };
+            response
+        }
+        Err(_) => Response::error(400, "invalid request"),
+    };
+
+    let _ = response.write_to(&mut stream);
+}
+
+fn read_request(stream: &TcpStream) -> io::Result<Request> {
+    let invalid = || io::Error::from(io::ErrorKind::InvalidData);
+    let mut reader = BufReader::new(stream.take(MAX_HEADER_SIZE));
+
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    let mut request_line = line.split_whitespace();
+    let method = request_line.next().ok_or_else(invalid)?.to_string();
+    let target = request_line.next().ok_or_else(invalid)?;
+
+    let (path, query) = target.split_once('?').unwrap_or((target, ""));
+    let path = percent_decode(path, false);
+    let params = query
+        .split('&')
+        .filter(|param| !param.is_empty())
+        .map(|param| {
+            let (key, value) = param.split_once('=').unwrap_or((param, ""));
+            (percent_decode(key, true), percent_decode(value, true))
+        })
+        .collect();
+
+    let mut content_length = 0;
+    let mut headers = 0;
+    loop {
+        if headers > MAX_HEADERS {
+            return Err(invalid());
+        }
+        headers += 1;
+        line.clear();
+        if reader.read_line(&mut line)? == 0 {
+            return Err(invalid());
+        }
+        let header = line.trim_end();
+        if header.is_empty() {
+            break;
+        }
+        if let Some((name, value)) = header.split_once(':') {
+            if name.eq_ignore_ascii_case("content-length") {
+                content_length = value.trim().parse().map_err(|_| invalid())?;
+            }
+        }
+    }
+
+    if content_length > MAX_BODY_SIZE {
+        return Err(invalid());
+    }
+    // part of the body may already be buffered, so this only bounds what's left to read
+    reader.get_mut().set_limit(content_length as u64);
+    let mut body = alloc::vec![0; content_length];
+    reader.read_exact(&mut body)?;
+
+    Ok(Request {
+        method,
+        path,
+        params,
+        body: String::from_utf8(body).map_err(|_| invalid())?,
+    })
+}
+
+fn percent_decode(text: &str, plus_is_space: bool) -> String {
+    let bytes = text.as_bytes();
+    let mut decoded = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'%' => {
+                let hex = text.get(i + 1..i + 3);
+                if let Some(byte) = hex.and_then(|hex| u8::from_str_radix(hex, 16).ok()) {
+                    decoded.push(byte);
+                    i += 3;
+                    continue;
+                }
+                decoded.push(b'%');
+            }
+            b'+' if plus_is_space => decoded.push(b' '),
+            byte => decoded.push(byte),
+        }
+        i += 1;
+    }
+    String::from_utf8_lossy(&decoded).into_owned()
+}
+
+fn json_string(text: &str) -> String {
+    let mut json = String::with_capacity(text.len() + 2);
+    json.push('"');
+    for c in text.chars() {
+        match c {
+            '"' => json.push_str("\\\""),
+            '\\' => json.push_str("\\\\"),
+            '\n' => json.push_str("\\n"),
+            '\r' => json.push_str("\\r"),
+            '\t' => json.push_str("\\t"),
+            c if c.is_control() => json.push_str(&format!("\\u{:04x}", c as u32)),
+            c => json.push(c),
+        }
+    }
+    json.push('"');
+    json
+}
+
+fn reply_to(world: &World, request: &Request) -> Response {
+    let path = request.path.as_str();
+    let (endpoint, entity_path) = match path.strip_prefix('/') {
+        Some(path) => path.split_once('/').unwrap_or((path, "")),
+        None => return Response::error(404, "endpoint not found"),
+    };
+
+    match (request.method.as_str(), endpoint) {
+        ("GET", "entity") => get_entity(world, request, entity_path),
+        ("PUT", "entity") => put_entity(world, entity_path),
+        ("DELETE", "entity") => with_entity(world, entity_path, |e| {
+            e.destruct();
+            Response::ok(String::new())
+        }),
+        ("GET", "component") => get_component(world, request, entity_path),
+        ("PUT", "component") => put_component(world, request, entity_path),
+        ("DELETE", "component") => with_entity(world, entity_path, |e| {
+            with_component(world, request, |id| {
+                e.remove_id(id);
+                Response::ok(String::new())
+            })
+        }),
+        ("PUT", "toggle") => toggle(world, request, entity_path),
+        ("GET", "query") => get_query(world, request),
+        ("GET", "world") => Response::ok(world.to_json_world(None)),
+        _ => Response::error(404, "endpoint not found"),
+    }
+}
+
+fn with_entity(
+    world: &World,
+    path: &str,
+    func: impl FnOnce(EntityView<'_>) -> Response,
+) -> Response {
+    let entity = if path.is_empty() {
+        None
+    } else {
+        world.try_lookup(&path.replace('/', "::"))
+    };
+    match entity {
+        Some(entity) => func(entity),
+        None => Response::error(404, &format!("entity '{path}' not found")),
+    }
+}
+
+fn with_component(world: &World, request: &Request, func: impl FnOnce(Id) -> Response) -> Response {
+    let Some(component) = request.param("component") else {
+        return Response::error(400, "missing component parameter");
+    };
+
+    let expr = format!("{component}\0");
+    let id = unsafe { sys::ecs_id_from_str(world.world_ptr(), expr.as_ptr() as *const _) };
+    if id == 0 {
+        return Response::error(400, &format!("unresolved component '{component}'"));
+    }
+    func(Id(id))
+}
+
+fn get_entity(world: &World, request: &Request, path: &str) -> Response {
+    with_entity(world, path, |e| {
+        let mut desc = EntityToJsonDesc {
+            serialize_entity_id: false,
+            serialize_doc: false,
+            serialize_full_paths: true,
+            serialize_inherited: false,
+            serialize_values: true,
+            serialize_builtin: false,
+            serialize_type_info: false,
+            serialize_alerts: false,
+            serialize_refs: 0,
+            serialize_matches: false,
+        };
+        request.bool_param("entity_id", &mut desc.serialize_entity_id);
+        request.bool_param("doc", &mut desc.serialize_doc);
+        request.bool_param("full_paths", &mut desc.serialize_full_paths);
+        request.bool_param("inherited", &mut desc.serialize_inherited);
+        request.bool_param("values", &mut desc.serialize_values);
+        request.bool_param("builtin", &mut desc.serialize_builtin);
+        request.bool_param("type_info", &mut desc.serialize_type_info);
+        request.bool_param("matches", &mut desc.serialize_matches);
+        request.bool_param("alerts", &mut desc.serialize_alerts);
+
+        Response::ok(e.to_json(Some(&desc)))
+    })
+}
+
+fn put_entity(world: &World, path: &str) -> Response {
+    if path.is_empty() {
+        return Response::error(400, "missing entity path");
+    }
+    let e = world.entity_named(&path.replace('/', "::"));
+    Response::ok(format!("{{\"id\":\"{}\"}}", e.id()))
+}
+
+fn get_component(world: &World, request: &Request, path: &str) -> Response {
+    with_entity(world, path, |e| {
+        with_component(world, request, |id| {
+            let type_id = world.id_from_id(id).type_id();
+            if *type_id.id() == 0 {
+                return Response::error(400, "component is not a type");
+            }
+
+            let ptr = e.get_untyped(id);
+            if ptr.is_null() {
+                return Response::error(404, "entity doesn't have the component");
+            }
+            Response::ok(world.to_json_id(type_id, ptr))
+        })
+    })
+}
+
+fn put_component(world: &World, request: &Request, path: &str) -> Response {
+    with_entity(world, path, |e| {
+        with_component(world, request, |id| {
+            let value = match request.param("value") {
+                Some(value) => value,
+                None if !request.body.is_empty() => &request.body,
+                None => {
+                    // components without a default value can't be added without a value
+                    let is_data = *world.id_from_id(id).type_id().id() != 0;
+                    if is_data && !has_default_hook(world.world_ptr(), *id) {
+                        return Response::error(400, "component requires a value");
+                    }
+                    e.add_id(id);
+                    return Response::ok(String::new());
+                }
+            };
+
+            match e.try_set_json_id(id, value, None) {
+                Ok(_) => Response::ok(String::new()),
+                Err(FlecsError::NotAComponent) => Response::error(400, "component is not a type"),
+                Err(err) => Response::error(400, &format!("invalid value: {err}")),
+            }
+        })
+    })
+}
+
+fn toggle(world: &World, request: &Request, path: &str) -> Response {
+    with_entity(world, path, |e| {
+        let mut enable = true;
+        request.bool_param("enable", &mut enable);
+
+        if request.param("component").is_none() {
+            if enable {
+                e.enable_self();
+            } else {
+                e.disable_self();
+            }
+            return Response::ok(String::new());
+        }
+
+        with_component(world, request, |id| {
+            let id_view = world.id_from_id(id);
+            let component = if id_view.is_pair() {
+                id_view.first_id()
+            } else {
+                world.entity_from_id(*id)
+            };
+            if !component.has::<flecs::CanToggle>() {
+                return Response::error(400, "component can't be toggled");
+            }
+
+            if enable {
+                e.enable_id(id);
+            } else {
+                e.disable_id(id);
+            }
+            Response::ok(String::new())
+        })
+    })
+}
+
+fn get_query(world: &World, request: &Request) -> Response {
+    let mut is_try = false;
+    request.bool_param("try", &mut is_try);
+
+    let query = if let Some(name) = request.param("name") {
+        match world.try_query_by_name(name) {
+            Some(query) => query,
+            None => return Response::error(404, &format!("unresolved identifier '{name}'")),
+        }
+    } else if let Some(expr) = request.param("expr") {
+        match world.query::<()>().expr(expr).try_build() {
+            Ok(query) => query,
+            // clients that are trying out queries get the error without a failed request
+            Err(err) => return Response::error(if is_try { 200 } else { 400 }, err.message()),
+        }
+    } else {
+        return Response::error(400, "missing parameter 'expr'");
+    };
+
+    let mut desc = IterToJsonDesc {
+        serialize_full_paths: true,
+        serialize_fields: true,
+        serialize_values: true,
+        ..Default::default()
+    };
+    request.bool_param("entity_ids", &mut desc.serialize_entity_ids);
+    request.bool_param("doc", &mut desc.serialize_doc);
+    request.bool_param("full_paths", &mut desc.serialize_full_paths);
+    request.bool_param("inherited", &mut desc.serialize_inherited);
+    request.bool_param("values", &mut desc.serialize_values);
+    request.bool_param("builtin", &mut desc.serialize_builtin);
+    request.bool_param("type_info", &mut desc.serialize_type_info);
+    request.bool_param("field_info", &mut desc.serialize_field_info);
+    request.bool_param("query_info", &mut desc.serialize_query_info);
+    request.bool_param("query_plan", &mut desc.serialize_query_plan);
+    request.bool_param("query_profile", &mut desc.serialize_query_profile);
+    request.bool_param("table", &mut desc.serialize_table);
+    request.bool_param("fields", &mut desc.serialize_fields);
+    let mut results = true;
+    request.bool_param("results", &mut results);
+    desc.dont_serialize_results = !results;
+
+    let mut offset = 0;
+    let mut limit = 1000;
+    request.int_param("offset", &mut offset);
+    request.int_param("limit", &mut limit);
+    if offset < 0 || limit < 0 {
+        return Response::error(400, "invalid offset/limit parameter");
+    }
+
+    match query
+        .iterable()
+        .offset(offset)
+        .limit(limit)
+        .to_json(Some(&desc))
+    {
+        Some(json) => Response::ok(json),
+        None => Response::error(500, "failed to serialize query results"),
+    }
+}
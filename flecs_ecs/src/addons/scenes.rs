@@ -0,0 +1,196 @@
+//! Load and unload groups of entities as a scene, enabled with the `flecs_scenes` feature.
+//!
+//! Entities that are created while a scene is loaded get the pair `(SceneRoot, root)`, which
+//! makes it possible to delete all of them at once when the scene is unloaded, and to save a
+//! single scene to JSON.
+
+use crate::core::*;
+use flecs_ecs_derive::Component;
+
+#[cfg(feature = "flecs_json")]
+extern crate alloc;
+#[cfg(feature = "flecs_json")]
+use alloc::string::String;
+
+/// Relationship between the entities of a scene and the root of the scene.
+///
+/// The root itself is not part of the scene, and stays alive when the scene is unloaded.
+///
+/// # See also
+///
+/// * [`World::load_scene()`]
+/// * [`World::unload_scene()`]
+#[derive(Component, Debug, Default, Clone, Copy)]
+pub struct SceneRoot;
+
+impl World {
+    /// Add all entities created in `func` to the scene of `root`.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The root of the scene.
+    /// * `func` - The function that creates the entities of the scene.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    ///
+    /// let level = world.entity_named("level");
+    ///
+    /// world.load_scene(level, || {
+    ///     let player = world.entity_named("player");
+    ///     world.entity_named("sword").child_of_id(player);
+    /// });
+    ///
+    /// let camera = world.entity_named("camera");
+    ///
+    /// assert_eq!(world.scene_count(level), 2);
+    ///
+    /// world.unload_scene(level);
+    ///
+    /// assert!(world.try_lookup("player").is_none());
+    /// assert!(camera.is_alive());
+    /// assert!(level.is_alive());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::scene_guard()`]
+    /// * [`World::unload_scene()`]
+    pub fn load_scene(&self, root: impl Into<Entity>, func: impl FnMut()) {
+        self.with_id(self.scene_id(root), func);
+    }
+
+    /// Add all entities created while the returned guard is alive to the scene of `root`.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The root of the scene.
+    ///
+    /// # See also
+    ///
+    /// * [`World::load_scene()`]
+    /// * [`World::with_guard_id()`]
+    pub fn scene_guard(&self, root: impl Into<Entity>) -> WithGuard<'_> {
+        self.with_guard_id(self.scene_id(root))
+    }
+
+    /// Delete all entities of the scene of `root`.
+    ///
+    /// The children of the entities are deleted with them, the root stays alive.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The root of the scene.
+    ///
+    /// # See also
+    ///
+    /// * [`World::load_scene()`]
+    /// * [`World::delete_entities_with_id()`]
+    pub fn unload_scene(&self, root: impl Into<Entity>) {
+        self.delete_entities_with_id(self.scene_id(root));
+    }
+
+    /// The number of entities in the scene of `root`.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The root of the scene.
+    pub fn scene_count(&self, root: impl Into<Entity>) -> i32 {
+        self.count_id(self.scene_id(root))
+    }
+
+    /// Invoke `func` for the entities in the scene of `root`, including prefabs and disabled
+    /// entities.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The root of the scene.
+    /// * `func` - The function to invoke for each entity.
+    pub fn each_scene_entity(&self, root: impl Into<Entity>, mut func: impl FnMut(EntityView)) {
+        self.scene_query(root).each_entity(|e, _| func(e));
+    }
+
+    /// Serialize the entities in the scene of `root` to JSON.
+    ///
+    /// The JSON has the format of [`World::to_json_world()`], and can be loaded with
+    /// [`World::scene_from_json()`]. Entities are identified by their path, the root should be
+    /// named so the scene can be loaded into another world.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The root of the scene.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// let world = World::new();
+    ///
+    /// let level = world.entity_named("level");
+    /// world.load_scene(level, || {
+    ///     world.entity_named("goblin").add::<Enemy>();
+    /// });
+    ///
+    /// let json = world.scene_to_json(level);
+    ///
+    /// let world = World::new();
+    /// world.component::<Enemy>();
+    /// world.scene_from_json(&json).unwrap();
+    ///
+    /// let level = world.lookup("level");
+    /// assert_eq!(world.scene_count(level), 1);
+    /// assert!(world.lookup("goblin").has::<Enemy>());
+    /// ```
+    #[cfg(feature = "flecs_json")]
+    pub fn scene_to_json(&self, root: impl Into<Entity>) -> String {
+        let desc = crate::addons::json::IterToJsonDesc {
+            serialize_table: true,
+            serialize_full_paths: true,
+            serialize_entity_ids: true,
+            serialize_values: true,
+            ..Default::default()
+        };
+
+        self.scene_query(root)
+            .to_json(Some(&desc))
+            .unwrap_or_default()
+    }
+
+    /// Load entities serialized with [`World::scene_to_json()`].
+    ///
+    /// The loaded entities keep the pair with the root of the scene, so the scene can be
+    /// unloaded with [`World::unload_scene()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns an error if the JSON is invalid. Entities that were loaded before the error was
+    /// encountered stay in the world.
+    ///
+    /// # See also
+    ///
+    /// * [`World::try_from_json_world()`]
+    #[cfg(feature = "flecs_json")]
+    pub fn scene_from_json(&self, json: &str) -> Result<&Self, FlecsError> {
+        self.component::<SceneRoot>();
+        self.try_from_json_world(json, None)
+    }
+
+    fn scene_id(&self, root: impl Into<Entity>) -> Id {
+        Id(ecs_pair(SceneRoot::id(self), *root.into()))
+    }
+
+    fn scene_query(&self, root: impl Into<Entity>) -> Query<()> {
+        self.query::<()>()
+            .with_id(self.scene_id(root))
+            .query_flags(QueryFlags::MatchPrefab | QueryFlags::MatchDisabled)
+            .build()
+    }
+}
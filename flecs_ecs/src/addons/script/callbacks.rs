@@ -0,0 +1,219 @@
+//! Systems defined in scripts that run Rust callbacks registered by name.
+
+use crate::core::*;
+use crate::sys;
+use flecs_ecs_derive::Component;
+
+extern crate alloc;
+use alloc::{format, rc::Rc, string::String, vec::Vec};
+
+/// A callback that script systems can run, invoked for each entity matched by the system.
+pub type ScriptCallback = Rc<dyn Fn(TableIter<false, ()>, usize)>;
+
+/// Maps names to the Rust callbacks that systems created by scripts run.
+///
+/// Callbacks are registered with [`World::register_callback()`] and the registry of a world is
+/// returned by [`World::callbacks()`].
+#[derive(Default)]
+pub struct CallbackRegistry {
+    callbacks: hashbrown::HashMap<String, ScriptCallback>,
+    observer: Option<Entity>,
+}
+
+impl CallbackRegistry {
+    /// Get the callback registered with `name`.
+    pub fn get(&self, name: &str) -> Option<&ScriptCallback> {
+        self.callbacks.get(name)
+    }
+
+    /// Test if a callback is registered with `name`.
+    pub fn contains(&self, name: &str) -> bool {
+        self.callbacks.contains_key(name)
+    }
+
+    /// The names of the registered callbacks.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.callbacks.keys().map(String::as_str)
+    }
+
+    /// The number of registered callbacks.
+    pub fn len(&self) -> usize {
+        self.callbacks.len()
+    }
+
+    /// Test if no callbacks are registered.
+    pub fn is_empty(&self) -> bool {
+        self.callbacks.is_empty()
+    }
+}
+
+/// Turns the entity into a system that runs a callback registered with
+/// [`World::register_callback()`].
+///
+/// The component is registered as `flecs.rust.ScriptSystem`, so scripts can create systems with:
+///
+/// ```text
+/// move_system {
+///   flecs.rust.ScriptSystem: {callback: "move", query: "Position, Velocity"}
+///   (DependsOn, flecs.pipeline.OnUpdate)
+/// }
+/// ```
+///
+/// The system is created as a child of the entity named `system`, once the callback is
+/// registered. It runs in the phase the entity depends on, or in `OnUpdate` if it has none.
+/// Setting the component again replaces the system. When the query is invalid, an error is
+/// logged and no system is created.
+#[derive(Component, Debug, Default, Clone)]
+#[meta]
+pub struct ScriptSystem {
+    /// The name of the registered callback.
+    pub callback: String,
+    /// The query of the system, in the flecs query language.
+    pub query: String,
+}
+
+/// Create the system for an entity with [`ScriptSystem`], if its callback is registered. A system
+/// that was created before is replaced, so that setting the component again updates it.
+fn build_script_system(entity: EntityView) {
+    let world = entity.world();
+    let script_system = entity.cloned::<&ScriptSystem>();
+    let Some(callback) = world.callbacks().get(&script_system.callback).cloned() else {
+        return;
+    };
+
+    if let Some(system) = entity.try_lookup("system") {
+        system.destruct();
+    }
+
+    let phase = entity
+        .target_id(flecs::DependsOn::ID, 0)
+        .map_or(Entity::new(ECS_ON_UPDATE), |phase| phase.id());
+
+    // the entity with the component can't be turned into a system from the observer, as its
+    // table is locked while observers run
+    let system = world.entity().child_of_id(entity).set_name("system");
+    let desc = sys::ecs_system_desc_t {
+        entity: *system.id(),
+        ..Default::default()
+    };
+    let result = world
+        .system_builder_from_desc::<()>(desc)
+        .expr(&script_system.query)
+        .kind_id(phase)
+        .fallible()
+        .each_iter(move |it, row, ()| callback(it, row));
+
+    // scripts are data, so an invalid query is reported instead of panicking in the observer
+    if let Err(err) = result {
+        log_error(&format!(
+            "script system {}: {err}",
+            entity.path().unwrap_or_default()
+        ));
+    }
+}
+
+impl World {
+    /// Register a callback that systems created by scripts can run by name.
+    ///
+    /// Scripts create a system by adding [`ScriptSystem`] to an entity, which names the callback
+    /// and the query of the system. This allows data-driven content to wire behavior to entities
+    /// without recompiling. Callbacks have to be registered before the scripts that use them are
+    /// run, which also registers the [`ScriptSystem`] component. Script systems of which the
+    /// callback is not yet registered are created once it is.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// #[meta]
+    /// struct Health {
+    ///     value: i32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.component_named::<Health>("Health").meta();
+    ///
+    /// world.register_callback("regenerate", |it, row| {
+    ///     it.entity(row).get::<&mut Health>(|health| health.value += 1);
+    /// });
+    ///
+    /// world.run_code(
+    ///     "regenerate.flecs",
+    ///     r#"
+    ///     using flecs.rust
+    ///
+    ///     regenerate_system {
+    ///       ScriptSystem: {callback: "regenerate", query: "Health"}
+    ///     }
+    ///
+    ///     player {
+    ///       Health: {value: 10}
+    ///     }
+    ///     "#,
+    /// );
+    ///
+    /// world.progress();
+    ///
+    /// world
+    ///     .lookup("player")
+    ///     .get::<&Health>(|health| assert_eq!(health.value, 11));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::callbacks()`]
+    pub fn register_callback(
+        &self,
+        name: &str,
+        callback: impl Fn(TableIter<false, ()>, usize) + 'static,
+    ) -> &Self {
+        self.init_script_systems();
+        self.world_ctx_mut()
+            .script_callbacks
+            .callbacks
+            .insert(String::from(name), Rc::new(callback));
+
+        let mut pending = Vec::new();
+        let id = ScriptSystem::id(self);
+        let mut it = unsafe { sys::ecs_each_id(self.world_ptr(), id) };
+        while unsafe { sys::ecs_each_next(&mut it) } {
+            for i in 0..it.count as usize {
+                pending.push(unsafe { *it.entities.add(i) });
+            }
+        }
+        for entity in pending {
+            let entity = self.entity_from_id(entity);
+            if entity.get::<&ScriptSystem>(|script_system| script_system.callback == name) {
+                build_script_system(entity);
+            }
+        }
+
+        self
+    }
+
+    /// Get the callbacks that systems created by scripts can run.
+    ///
+    /// # See also
+    ///
+    /// * [`World::register_callback()`]
+    pub fn callbacks(&self) -> &CallbackRegistry {
+        &self.world_ctx().script_callbacks
+    }
+
+    fn init_script_systems(&self) {
+        if self.world_ctx().script_callbacks.observer.is_some() {
+            return;
+        }
+
+        self.component_named::<ScriptSystem>("flecs::rust::ScriptSystem")
+            .meta();
+
+        let observer = self
+            .observer::<flecs::OnSet, ()>()
+            .with::<ScriptSystem>()
+            .each_entity(|entity, _| build_script_system(entity));
+
+        self.world_ctx_mut().script_callbacks.observer = Some(observer.id());
+    }
+}
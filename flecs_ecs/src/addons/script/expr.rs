@@ -0,0 +1,255 @@
+//! Evaluate flecs script expressions from Rust, see [`World::eval_expr()`].
+
+use core::ffi::c_void;
+
+use flecs_ecs::core::*;
+use flecs_ecs::sys;
+
+use super::Script;
+
+extern crate alloc;
+use alloc::{ffi::CString, string::String, vec::Vec};
+
+/// Variables that can be used in expressions, as `$name`.
+///
+/// Values are stored in flecs, so their type needs reflection data, which primitives and
+/// [`Entity`] have.
+///
+/// # See also
+///
+/// * [`World::eval_expr()`]
+/// * C API: `ecs_script_vars_t`
+#[doc(alias = "ecs_script_vars_t")]
+pub struct ScriptVars<'a> {
+    vars: *mut sys::ecs_script_vars_t,
+    /// Flecs refers to the names of the variables, so they are kept alive with the variables.
+    names: Vec<CString>,
+    world: WorldRef<'a>,
+}
+
+impl Drop for ScriptVars<'_> {
+    fn drop(&mut self) {
+        unsafe { sys::ecs_script_vars_fini(self.vars) }
+    }
+}
+
+impl<'a> ScriptVars<'a> {
+    /// Create an empty set of variables.
+    ///
+    /// # See also
+    ///
+    /// * C API: `ecs_script_vars_init`
+    #[doc(alias = "ecs_script_vars_init")]
+    pub fn new(world: impl WorldProvider<'a>) -> Self {
+        let world = world.world();
+        Self {
+            vars: unsafe { sys::ecs_script_vars_init(world.world_ptr_mut()) },
+            names: Vec::new(),
+            world,
+        }
+    }
+
+    /// Set the value of a variable, defining it if it doesn't exist yet.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the variable exists with a different type.
+    ///
+    /// # See also
+    ///
+    /// * C API: `ecs_script_vars_define_id`
+    #[doc(alias = "ecs_script_vars_define_id")]
+    pub fn set<T: ComponentId>(&mut self, name: &str, value: T) -> &mut Self {
+        let type_id = T::id(self.world);
+        let name = CString::new(name).expect("variable name contains a nul byte");
+
+        unsafe {
+            let var = sys::ecs_script_vars_lookup(self.vars, name.as_ptr());
+            if var.is_null() {
+                let var = sys::ecs_script_vars_define_id(self.vars, name.as_ptr(), type_id);
+                self.names.push(name);
+                let ptr = (*var).value.ptr as *mut T;
+                if (*(*var).type_info).hooks.ctor.is_some() {
+                    *ptr = value;
+                } else {
+                    ptr.write(value);
+                }
+            } else {
+                assert_eq!(
+                    (*var).value.type_,
+                    type_id,
+                    "variable '{}' already exists with a different type",
+                    name.to_string_lossy()
+                );
+                *((*var).value.ptr as *mut T) = value;
+            }
+        }
+        self
+    }
+
+    /// Set a variable to an entity, so `$name` can be used to access its components.
+    pub fn set_entity(&mut self, name: &str, entity: impl Into<Entity>) -> &mut Self {
+        self.set(name, entity.into())
+    }
+}
+
+/// The result of an expression evaluated with [`World::eval_expr_value()`], in the type the
+/// expression evaluated to.
+pub struct ExprValue<'a> {
+    value: sys::ecs_value_t,
+    world: WorldRef<'a>,
+}
+
+impl Drop for ExprValue<'_> {
+    fn drop(&mut self) {
+        if !self.value.ptr.is_null() {
+            unsafe {
+                sys::ecs_value_free(self.world.world_ptr_mut(), self.value.type_, self.value.ptr)
+            };
+        }
+    }
+}
+
+impl ExprValue<'_> {
+    /// The type of the value.
+    pub fn type_id(&self) -> Entity {
+        Entity::new(self.value.type_)
+    }
+
+    /// A pointer to the value.
+    pub fn as_ptr(&self) -> *const c_void {
+        self.value.ptr
+    }
+
+    /// The value, if it is of type `T`.
+    pub fn get<T: ComponentId>(&self) -> Option<&T> {
+        if self.value.type_ == T::id(self.world) {
+            Some(unsafe { &*(self.value.ptr as *const T) })
+        } else {
+            None
+        }
+    }
+
+    /// The value formatted as a flecs script expression.
+    pub fn to_expr(&self) -> String {
+        Script::to_expr_id(self.world, self.value.type_, self.value.ptr)
+    }
+}
+
+impl World {
+    /// Evaluate a flecs script expression, converting the result to `T`.
+    ///
+    /// Expressions can do arithmetic and comparisons, and read variables and component members
+    /// of entities, such as `$e[Position].x > 10`. This makes it possible to author conditions,
+    /// for example of quests or AI, as strings in data files.
+    ///
+    /// # Arguments
+    ///
+    /// * `expr` - The expression.
+    /// * `vars` - The variables the expression can use.
+    ///
+    /// # Errors
+    ///
+    /// [`FlecsError::ParseError`] when the expression is invalid, or can't be converted to `T`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::addons::script::ScriptVars;
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// #[meta]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.component_named::<Position>("Position").meta();
+    ///
+    /// let player = world.entity().set(Position { x: 20.0, y: 0.0 });
+    ///
+    /// let mut vars = ScriptVars::new(&world);
+    /// vars.set_entity("player", player).set("range", 10.0f32);
+    ///
+    /// let in_range = world.eval_expr::<bool>("$player[Position].x > $range", Some(&vars));
+    /// assert_eq!(in_range, Ok(true));
+    ///
+    /// assert_eq!(world.eval_expr::<i32>("2 * (3 + 4)", None), Ok(14));
+    /// assert!(world.eval_expr::<i32>("2 * (3 +", None).is_err());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::eval_expr_value()`]
+    /// * [`ScriptVars`]
+    /// * C API: `ecs_expr_run`
+    #[doc(alias = "ecs_expr_run")]
+    pub fn eval_expr<T: ComponentId + Default>(
+        &self,
+        expr: &str,
+        vars: Option<&ScriptVars>,
+    ) -> Result<T, FlecsError> {
+        let mut result = T::default();
+        let mut value = sys::ecs_value_t {
+            type_: T::id(self),
+            ptr: &mut result as *mut T as *mut c_void,
+        };
+        self.run_expr(expr, vars, &mut value)?;
+        Ok(result)
+    }
+
+    /// Evaluate a flecs script expression, keeping the type the expression evaluates to.
+    ///
+    /// # Errors
+    ///
+    /// [`FlecsError::ParseError`] when the expression is invalid.
+    ///
+    /// # See also
+    ///
+    /// * [`World::eval_expr()`]
+    /// * C API: `ecs_expr_run`
+    #[doc(alias = "ecs_expr_run")]
+    pub fn eval_expr_value(
+        &self,
+        expr: &str,
+        vars: Option<&ScriptVars>,
+    ) -> Result<ExprValue<'_>, FlecsError> {
+        let mut value = sys::ecs_value_t {
+            type_: 0,
+            ptr: core::ptr::null_mut(),
+        };
+        self.run_expr(expr, vars, &mut value)?;
+        Ok(ExprValue {
+            value,
+            world: self.world(),
+        })
+    }
+
+    fn run_expr(
+        &self,
+        expr: &str,
+        vars: Option<&ScriptVars>,
+        value: &mut sys::ecs_value_t,
+    ) -> Result<(), FlecsError> {
+        let expr = compact_str::format_compact!("{}\0", expr);
+        let desc = sys::ecs_expr_eval_desc_t {
+            vars: vars.map_or(core::ptr::null(), |vars| vars.vars),
+            ..Default::default()
+        };
+
+        let (end, errors) = ecs_os_api::capture_errors(|| unsafe {
+            sys::ecs_expr_run(
+                self.world_ptr_mut(),
+                expr.as_ptr() as *const _,
+                value,
+                &desc,
+            )
+        });
+        if end.is_null() {
+            return Err(FlecsError::ParseError(errors.unwrap_or_default()));
+        }
+        Ok(())
+    }
+}
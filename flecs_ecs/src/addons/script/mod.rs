@@ -1,7 +1,13 @@
+#[cfg(feature = "flecs_system")]
+mod callbacks;
+mod expr;
 mod script_builder;
 mod script_entity_view;
 mod unmanaged_script;
 
+#[cfg(feature = "flecs_system")]
+pub use callbacks::*;
+pub use expr::*;
 pub use script_builder::*;
 pub use script_entity_view::*;
 pub use unmanaged_script::*;
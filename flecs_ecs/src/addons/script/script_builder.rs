@@ -30,7 +30,7 @@ impl<'a> ScriptBuilder<'a> {
     ///
     /// The entity will receive an [`EcsScript`][crate::sys::EcsScript] component.
     pub fn new_named(world: impl WorldProvider<'a>, name: &str) -> Self {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
         let entity_desc = sys::ecs_entity_desc_t {
             name: name.as_ptr() as *const _,
             sep: SEPARATOR.as_ptr(),
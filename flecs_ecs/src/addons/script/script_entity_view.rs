@@ -46,6 +46,37 @@ impl<'a> ScriptEntityView<'a> {
 
     /// Update script with new code.
     ///
+    /// The entities created by the previous version of the script are deleted and created
+    /// again from the new code, which makes it possible to hot reload prefabs that are
+    /// loaded from a script. Entities that are not created by the script (for example an
+    /// instance created in code) lose their relationships with the deleted entities.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// let world = World::new();
+    ///
+    /// let code = |attack: i32| {
+    ///     format!(
+    ///         "using flecs.meta
+    ///         struct Attack {{
+    ///           value = f32
+    ///         }}
+    ///         prefab SpaceShip {{ Attack: {{{attack}}} }}
+    ///         my_ship : SpaceShip {{}}"
+    ///     )
+    /// };
+    ///
+    /// let script = world.script_named("assets").build_from_code(&code(50));
+    /// script.update(&world, None::<Entity>, &code(75));
+    ///
+    /// let attack = world.lookup("Attack");
+    /// let ship = world.lookup("my_ship");
+    /// let value = unsafe { *(ship.get_untyped(attack) as *const f32) };
+    /// assert_eq!(value, 75.0);
+    /// ```
+    ///
     /// # Arguments
     ///
     /// * code - The script code.
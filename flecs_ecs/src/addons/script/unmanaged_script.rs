@@ -59,7 +59,7 @@ impl<'a> Script<'a> {
         code: &str,
         desc: Option<sys::ecs_script_eval_desc_t>,
     ) -> Option<Script<'a>> {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
         let code = compact_str::format_compact!("{}\0", code);
         let world_ptr = world.world_ptr_mut();
 
@@ -131,7 +131,7 @@ impl<'a> Script<'a> {
     /// * C API: `ecs_script_run`
     #[doc(alias = "ecs_script_run")]
     pub fn run_code(world: impl WorldProvider<'a>, name: &str, code: &str) -> bool {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
         let code = compact_str::format_compact!("{}\0", code);
         let world_ptr = world.world_ptr_mut();
 
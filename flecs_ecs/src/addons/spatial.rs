@@ -0,0 +1,310 @@
+//! Find entities by their position, enabled with the `flecs_spatial` feature.
+//!
+//! The positions of entities are stored in a grid that is kept up to date by observers on the
+//! position component, see [`World::enable_spatial()`]. The grid answers which entities are in an
+//! area without iterating all entities, and the results can be combined with regular queries with
+//! [`SpatialQuery::each_matching()`].
+
+use crate::core::*;
+use crate::sys;
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// A component that stores the position of an entity, which can be indexed by
+/// [`World::enable_spatial()`].
+///
+/// Positions are three dimensional, two dimensional positions can return `0.0` as third coordinate.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::addons::spatial::*;
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Position {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// impl SpatialPosition for Position {
+///     fn position(&self) -> [f32; 3] {
+///         [self.x, self.y, 0.0]
+///     }
+/// }
+/// ```
+pub trait SpatialPosition: ComponentId + DataComponent + ComponentType<Struct> {
+    /// The position of the entity.
+    fn position(&self) -> [f32; 3];
+}
+
+type Cell = [i32; 3];
+
+/// Grid of entity positions, stored per world, see [`World::enable_spatial()`].
+pub(crate) struct SpatialGrid {
+    cell_size: f32,
+    cells: hashbrown::HashMap<Cell, Vec<Entity>>,
+    entities: hashbrown::HashMap<Entity, ([f32; 3], Cell)>,
+}
+
+impl SpatialGrid {
+    fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: Default::default(),
+            entities: Default::default(),
+        }
+    }
+
+    fn cell(&self, position: [f32; 3]) -> Cell {
+        position.map(|v| (v / self.cell_size).floor() as i32)
+    }
+
+    fn insert(&mut self, entity: Entity, position: [f32; 3]) {
+        let cell = self.cell(position);
+        match self.entities.insert(entity, (position, cell)) {
+            Some((_, previous)) if previous == cell => return,
+            Some((_, previous)) => self.remove_from_cell(entity, previous),
+            None => {}
+        }
+        self.cells.entry(cell).or_default().push(entity);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some((_, cell)) = self.entities.remove(&entity) {
+            self.remove_from_cell(entity, cell);
+        }
+    }
+
+    fn remove_from_cell(&mut self, entity: Entity, cell: Cell) {
+        if let Some(entities) = self.cells.get_mut(&cell) {
+            if let Some(index) = entities.iter().position(|&e| e == entity) {
+                entities.swap_remove(index);
+            }
+            if entities.is_empty() {
+                self.cells.remove(&cell);
+            }
+        }
+    }
+
+    /// The entities of which the position is inside the box.
+    fn query_aabb(&self, min: [f32; 3], max: [f32; 3]) -> Vec<Entity> {
+        let inside = |p: &[f32; 3]| (0..3).all(|i| p[i] >= min[i] && p[i] <= max[i]);
+        let (min_cell, max_cell) = (self.cell(min), self.cell(max));
+
+        let cell_count = (0..3)
+            .map(|i| (i64::from(max_cell[i]) - i64::from(min_cell[i]) + 1).max(0) as u64)
+            .fold(1u64, u64::saturating_mul);
+
+        // visiting every cell of a large box is slower than checking every entity
+        if cell_count > self.cells.len() as u64 {
+            return self
+                .entities
+                .iter()
+                .filter(|(_, (position, _))| inside(position))
+                .map(|(&entity, _)| entity)
+                .collect();
+        }
+
+        let mut found = Vec::new();
+        for x in min_cell[0]..=max_cell[0] {
+            for y in min_cell[1]..=max_cell[1] {
+                for z in min_cell[2]..=max_cell[2] {
+                    let Some(entities) = self.cells.get(&[x, y, z]) else {
+                        continue;
+                    };
+                    found.extend(
+                        entities
+                            .iter()
+                            .filter(|e| inside(&self.entities[*e].0))
+                            .copied(),
+                    );
+                }
+            }
+        }
+        found
+    }
+}
+
+fn grid_mut<'a>(world: *mut sys::ecs_world_t) -> Option<&'a mut SpatialGrid> {
+    unsafe { (*World::get_context(world)).spatial.as_mut() }
+}
+
+/// Access to the spatial index of a world, created by [`World::spatial()`].
+#[derive(Clone, Copy)]
+pub struct Spatial<'a> {
+    world: WorldRef<'a>,
+}
+
+impl<'a> Spatial<'a> {
+    /// Find the entities of which the position is inside a box.
+    ///
+    /// The bounds are inclusive.
+    ///
+    /// # Arguments
+    ///
+    /// * `min` - The lowest corner of the box.
+    /// * `max` - The highest corner of the box.
+    pub fn query_aabb(&self, min: [f32; 3], max: [f32; 3]) -> SpatialQuery<'a> {
+        let entities = self
+            .world
+            .world_ctx()
+            .spatial
+            .as_ref()
+            .map(|grid| grid.query_aabb(min, max))
+            .unwrap_or_default();
+
+        SpatialQuery {
+            world: self.world,
+            entities,
+        }
+    }
+
+    /// The number of entities in the index.
+    pub fn count(&self) -> usize {
+        self.world
+            .world_ctx()
+            .spatial
+            .as_ref()
+            .map_or(0, |grid| grid.entities.len())
+    }
+}
+
+/// The entities found by a spatial query, created by [`Spatial::query_aabb()`].
+///
+/// The entities are found when the query is created, changes to positions afterwards are not
+/// reflected in the results.
+pub struct SpatialQuery<'a> {
+    world: WorldRef<'a>,
+    entities: Vec<Entity>,
+}
+
+impl<'a> SpatialQuery<'a> {
+    /// Invoke `func` for each entity that was found.
+    pub fn each(&self, mut func: impl FnMut(EntityView<'a>)) {
+        for &entity in &self.entities {
+            func(EntityView::new_from(self.world, entity));
+        }
+    }
+
+    /// Invoke `func` for each entity that was found and is matched by `query`, with the
+    /// components of the query.
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::get_for()`]
+    pub fn each_matching<T: QueryTuple>(
+        &self,
+        query: &Query<T>,
+        mut func: impl FnMut(EntityView<'a>, T::TupleType<'_>),
+    ) {
+        for &entity in &self.entities {
+            query.get_for(entity, |components| {
+                func(EntityView::new_from(self.world, entity), components);
+            });
+        }
+    }
+
+    /// The number of entities that were found.
+    pub fn count(&self) -> usize {
+        self.entities.len()
+    }
+
+    /// Whether no entities were found.
+    pub fn is_empty(&self) -> bool {
+        self.entities.is_empty()
+    }
+}
+
+impl World {
+    /// Keep a grid of the positions of entities with the component `P`, which can be searched with
+    /// [`World::spatial()`].
+    ///
+    /// The grid is updated by observers when `P` is set or removed. Changes to `P` through a
+    /// mutable reference are only picked up after [`EntityView::modified()`] is called.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `P` - The position component.
+    ///
+    /// # Arguments
+    ///
+    /// * `cell_size` - The size of the cells of the grid. A size close to the size of the areas
+    ///   that are searched works best.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the spatial index was already enabled, or if `cell_size` isn't positive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::addons::spatial::*;
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// impl SpatialPosition for Position {
+    ///     fn position(&self) -> [f32; 3] {
+    ///         [self.x, self.y, 0.0]
+    ///     }
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Enemy(u32);
+    ///
+    /// let world = World::new();
+    /// world.enable_spatial::<Position>(10.0);
+    ///
+    /// world.entity().set(Position { x: 1.0, y: 1.0 }).set(Enemy(1));
+    /// world.entity().set(Position { x: 4.0, y: 2.0 });
+    /// world.entity().set(Position { x: 50.0, y: 50.0 }).set(Enemy(2));
+    ///
+    /// let nearby = world.spatial().query_aabb([0.0, 0.0, 0.0], [5.0, 5.0, 0.0]);
+    /// assert_eq!(nearby.count(), 2);
+    ///
+    /// let enemies = world.new_query::<&Enemy>();
+    /// nearby.each_matching(&enemies, |_, enemy| assert_eq!(enemy.0, 1));
+    /// ```
+    pub fn enable_spatial<P: SpatialPosition>(&self, cell_size: f32) {
+        assert!(
+            cell_size > 0.0,
+            "the cell size of the spatial index must be positive"
+        );
+        assert!(
+            self.world_ctx().spatial.is_none(),
+            "the spatial index is already enabled"
+        );
+
+        self.world_ctx_mut().spatial = Some(SpatialGrid::new(cell_size));
+
+        self.observer::<flecs::OnSet, &P>()
+            .add_event::<flecs::OnRemove>()
+            .yield_existing()
+            .each_iter(|it, row, position| {
+                let Some(grid) = grid_mut(it.world().world_ptr_mut()) else {
+                    return;
+                };
+                let entity = it.entity(row).id();
+                if it.event().id() == flecs::OnSet::ID {
+                    grid.insert(entity, position.position());
+                } else {
+                    grid.remove(entity);
+                }
+            });
+    }
+
+    /// Search the positions of entities, which requires [`World::enable_spatial()`].
+    ///
+    /// Searches return no entities when the spatial index isn't enabled.
+    pub fn spatial(&self) -> Spatial<'_> {
+        Spatial {
+            world: self.world(),
+        }
+    }
+}
@@ -0,0 +1,418 @@
+//! State machines with states that are the variants of an enum, see [`World::state_machine()`],
+//! or entities that are the targets of a relationship, see [`World::state_machine_id()`].
+//!
+//! Enums are exclusive relationships, and the relationship of entity states is made exclusive
+//! unless it is a [`flecs::Union`], so an entity is in one state of a state machine at a time:
+//! entering a new state replaces the previous one. Transitions between states are only allowed
+//! when they are defined, and can have a guard that decides whether an entity may take them.
+//! Entering and exiting a state is observed with [`StateMachineBuilder::on_enter()`] and
+//! [`StateMachineBuilder::on_exit()`].
+//!
+//! Union relationships don't emit events when their target changes, so the enter and exit
+//! callbacks of their states only run for [`EntityView::transition_id()`].
+//!
+//! # Example
+//!
+//! ```
+//! use flecs_ecs::prelude::*;
+//!
+//! #[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+//! #[repr(C)]
+//! enum PlayerState {
+//!     Idle,
+//!     Walking,
+//!     Jumping,
+//! }
+//!
+//! #[derive(Component)]
+//! struct Grounded;
+//!
+//! let world = World::new();
+//!
+//! world
+//!     .state_machine::<PlayerState>()
+//!     .transition(PlayerState::Idle, PlayerState::Walking)
+//!     .transition_if(PlayerState::Walking, PlayerState::Jumping, |e| {
+//!         e.has::<Grounded>()
+//!     })
+//!     .on_enter(PlayerState::Jumping, |e| {
+//!         e.remove::<Grounded>();
+//!     });
+//!
+//! let player = world.entity().add::<Grounded>().add_enum(PlayerState::Idle);
+//!
+//! assert!(player.transition(PlayerState::Walking).is_ok());
+//! assert!(player.transition(PlayerState::Jumping).is_ok());
+//! assert!(!player.has::<Grounded>());
+//! assert!(player.transition(PlayerState::Idle).is_err());
+//! assert_eq!(player.state::<PlayerState>(), Some(PlayerState::Jumping));
+//! ```
+
+use core::marker::PhantomData;
+
+use crate::core::*;
+use crate::prelude::*;
+
+extern crate alloc;
+use alloc::{format, sync::Arc, vec::Vec};
+
+/// Decides whether an entity may take a transition.
+type Guard = Arc<dyn Fn(EntityView) -> bool + Send + Sync>;
+
+/// Called when an entity enters or exits a state of a union relationship.
+type Callback = Arc<dyn Fn(EntityView) + Send + Sync>;
+
+struct Transition<S> {
+    from: S,
+    to: S,
+    guard: Option<Guard>,
+}
+
+/// The transitions of a state machine. For enum `S` this is a singleton, see
+/// [`World::state_machine()`], for entity states it is a component of the relationship, see
+/// [`World::state_machine_id()`].
+#[derive(Component)]
+pub struct StateMachine<S: Send + Sync + 'static> {
+    transitions: Vec<Transition<S>>,
+    // run by `transition_id` for union relationships, which are not observable per target
+    on_enter: Vec<(S, Callback)>,
+    on_exit: Vec<(S, Callback)>,
+}
+
+impl<S: Copy + PartialEq + Send + Sync + 'static> StateMachine<S> {
+    fn new() -> Self {
+        Self {
+            transitions: Vec::new(),
+            on_enter: Vec::new(),
+            on_exit: Vec::new(),
+        }
+    }
+
+    fn add(&mut self, from: S, to: S, guard: Option<Guard>) {
+        self.transitions.push(Transition { from, to, guard });
+    }
+
+    /// The guards of the transitions from `from` to `to`, cloned so they can run without the
+    /// state machine being borrowed.
+    fn guards(&self, from: S, to: S) -> Vec<Option<Guard>> {
+        self.transitions
+            .iter()
+            .filter(|transition| transition.from == from && transition.to == to)
+            .map(|transition| transition.guard.clone())
+            .collect()
+    }
+
+    fn callbacks(callbacks: &[(S, Callback)], state: S) -> Vec<Callback> {
+        callbacks
+            .iter()
+            .filter(|(callback_state, _)| *callback_state == state)
+            .map(|(_, callback)| callback.clone())
+            .collect()
+    }
+}
+
+/// Whether `entity` may take one of the transitions with `guards`.
+fn is_allowed(entity: EntityView, guards: Option<Vec<Option<Guard>>>) -> bool {
+    guards.is_some_and(|guards| {
+        guards
+            .iter()
+            .any(|guard| guard.as_ref().is_none_or(|guard| guard(entity)))
+    })
+}
+
+/// The bounds of the enums that are the states of a state machine.
+pub trait State:
+    ComponentId
+    + DataComponent
+    + ComponentType<Enum>
+    + EnumComponentInfo
+    + Copy
+    + PartialEq
+    + Send
+    + Sync
+{
+}
+
+impl<S> State for S where
+    S: ComponentId
+        + DataComponent
+        + ComponentType<Enum>
+        + EnumComponentInfo
+        + Copy
+        + PartialEq
+        + Send
+        + Sync
+{
+}
+
+/// Defines the transitions of a state machine, returned by [`World::state_machine()`].
+pub struct StateMachineBuilder<'a, S: State> {
+    world: WorldRef<'a>,
+    _marker: PhantomData<S>,
+}
+
+impl<S: State> StateMachineBuilder<'_, S> {
+    /// Allow entities to move from state `from` to state `to`.
+    pub fn transition(&self, from: S, to: S) -> &Self {
+        self.add_transition(from, to, None)
+    }
+
+    /// Allow entities to move from state `from` to state `to`, if `guard` returns true for them.
+    pub fn transition_if(
+        &self,
+        from: S,
+        to: S,
+        guard: impl Fn(EntityView) -> bool + Send + Sync + 'static,
+    ) -> &Self {
+        self.add_transition(from, to, Some(Arc::new(guard)))
+    }
+
+    fn add_transition(&self, from: S, to: S, guard: Option<Guard>) -> &Self {
+        self.world
+            .get::<&mut StateMachine<S>>(|machine| machine.add(from, to, guard));
+        self
+    }
+
+    /// Call `callback` when an entity enters `state`.
+    pub fn on_enter(&self, state: S, callback: impl FnMut(EntityView) + 'static) -> &Self {
+        self.observe::<flecs::OnAdd>(state, callback)
+    }
+
+    /// Call `callback` when an entity exits `state`, which is before it enters the next state.
+    pub fn on_exit(&self, state: S, callback: impl FnMut(EntityView) + 'static) -> &Self {
+        self.observe::<flecs::OnRemove>(state, callback)
+    }
+
+    fn observe<Event: ComponentId>(
+        &self,
+        state: S,
+        mut callback: impl FnMut(EntityView) + 'static,
+    ) -> &Self {
+        self.world
+            .observer::<Event, ()>()
+            .with_enum(state)
+            .each_entity(move |e, _| callback(e));
+        self
+    }
+}
+
+impl World {
+    /// Define the state machine of which the states are the variants of enum `S`.
+    ///
+    /// All calls for the same enum define the same state machine.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::transition()`]
+    /// * [`addons::state_machine`](crate::addons::state_machine)
+    pub fn state_machine<S: State>(&self) -> StateMachineBuilder<'_, S> {
+        if !self.has::<StateMachine<S>>() {
+            self.set(StateMachine::<S>::new());
+        }
+        StateMachineBuilder {
+            world: self.world(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Define the state machine of which the states are the targets of `relationship`.
+    ///
+    /// The relationship is made exclusive, unless it already is or it is a [`flecs::Union`].
+    /// All calls for the same relationship define the same state machine.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::transition_id()`]
+    /// * [`addons::state_machine`](crate::addons::state_machine)
+    pub fn state_machine_id(
+        &self,
+        relationship: impl Into<Entity>,
+    ) -> EntityStateMachineBuilder<'_> {
+        let relationship = self.entity_from_id(relationship);
+        if !relationship.has::<StateMachine<Entity>>() {
+            if !relationship.has::<flecs::Exclusive>() && !relationship.has::<flecs::Union>() {
+                relationship.add_trait::<flecs::Exclusive>();
+            }
+            relationship.set(StateMachine::<Entity>::new());
+        }
+        EntityStateMachineBuilder { relationship }
+    }
+}
+
+/// Defines the transitions of a state machine of entity states, returned by
+/// [`World::state_machine_id()`].
+pub struct EntityStateMachineBuilder<'a> {
+    relationship: EntityView<'a>,
+}
+
+impl EntityStateMachineBuilder<'_> {
+    /// Allow entities to move from state `from` to state `to`.
+    pub fn transition(&self, from: impl Into<Entity>, to: impl Into<Entity>) -> &Self {
+        self.add_transition(from.into(), to.into(), None)
+    }
+
+    /// Allow entities to move from state `from` to state `to`, if `guard` returns true for them.
+    pub fn transition_if(
+        &self,
+        from: impl Into<Entity>,
+        to: impl Into<Entity>,
+        guard: impl Fn(EntityView) -> bool + Send + Sync + 'static,
+    ) -> &Self {
+        self.add_transition(from.into(), to.into(), Some(Arc::new(guard)))
+    }
+
+    fn add_transition(&self, from: Entity, to: Entity, guard: Option<Guard>) -> &Self {
+        self.relationship
+            .get::<&mut StateMachine<Entity>>(|machine| machine.add(from, to, guard));
+        self
+    }
+
+    /// Call `callback` when an entity enters `state`.
+    ///
+    /// For a union relationship this is only when the entity enters `state` with
+    /// [`EntityView::transition_id()`].
+    pub fn on_enter(
+        &self,
+        state: impl Into<Entity>,
+        callback: impl Fn(EntityView) + Send + Sync + 'static,
+    ) -> &Self {
+        let state = state.into();
+        if self.relationship.has::<flecs::Union>() {
+            self.relationship
+                .get::<&mut StateMachine<Entity>>(|machine| {
+                    machine.on_enter.push((state, Arc::new(callback)));
+                });
+            return self;
+        }
+        self.observe::<flecs::OnAdd>(state, callback)
+    }
+
+    /// Call `callback` when an entity exits `state`, which is before it enters the next state.
+    ///
+    /// For a union relationship this is only when the entity exits `state` with
+    /// [`EntityView::transition_id()`].
+    pub fn on_exit(
+        &self,
+        state: impl Into<Entity>,
+        callback: impl Fn(EntityView) + Send + Sync + 'static,
+    ) -> &Self {
+        let state = state.into();
+        if self.relationship.has::<flecs::Union>() {
+            self.relationship
+                .get::<&mut StateMachine<Entity>>(|machine| {
+                    machine.on_exit.push((state, Arc::new(callback)));
+                });
+            return self;
+        }
+        self.observe::<flecs::OnRemove>(state, callback)
+    }
+
+    fn observe<Event: ComponentId>(
+        &self,
+        state: Entity,
+        callback: impl Fn(EntityView) + 'static,
+    ) -> &Self {
+        self.relationship
+            .world()
+            .observer::<Event, ()>()
+            .with_id((self.relationship.id(), state))
+            .each_entity(move |e, _| callback(e));
+        self
+    }
+}
+
+impl<'a> EntityView<'a> {
+    /// The state of the entity in the state machine of enum `S`.
+    pub fn state<S: State>(self) -> Option<S> {
+        self.try_get::<&S>(|state| *state)
+    }
+
+    /// Move the entity to state `to` of the state machine of enum `S`.
+    ///
+    /// An entity that isn't in a state yet can enter any state. Otherwise a transition from the
+    /// current state to `to` has to be defined, of which the guard returns true for the entity.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FlecsError::ConstraintViolated`] when the transition isn't allowed, in which case
+    /// the entity stays in its current state.
+    ///
+    /// # See also
+    ///
+    /// * [`World::state_machine()`]
+    pub fn transition<S: State>(self, to: S) -> Result<Self, FlecsError> {
+        if let Some(from) = self.state::<S>() {
+            // guards may access the world, so they run after the state machine is released
+            let guards = self
+                .world()
+                .try_get::<&StateMachine<S>>(|machine| machine.guards(from, to));
+
+            if !is_allowed(self, guards) {
+                return Err(FlecsError::ConstraintViolated(format!(
+                    "transition from {} to {} is not allowed",
+                    from.name_cstr().to_string_lossy(),
+                    to.name_cstr().to_string_lossy()
+                )));
+            }
+        }
+        Ok(self.add_enum(to))
+    }
+    /// The state of the entity in the state machine of `relationship`.
+    pub fn state_id(self, relationship: impl Into<Entity>) -> Option<EntityView<'a>> {
+        self.target_id(relationship, 0)
+    }
+
+    /// Move the entity to state `to` of the state machine of `relationship`.
+    ///
+    /// An entity that isn't in a state yet can enter any state. Otherwise a transition from the
+    /// current state to `to` has to be defined, of which the guard returns true for the entity.
+    /// For a union relationship this also runs the exit and enter callbacks of the states.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FlecsError::ConstraintViolated`] when the transition isn't allowed, in which case
+    /// the entity stays in its current state.
+    ///
+    /// # See also
+    ///
+    /// * [`World::state_machine_id()`]
+    pub fn transition_id(
+        self,
+        relationship: impl Into<Entity>,
+        to: impl Into<Entity>,
+    ) -> Result<Self, FlecsError> {
+        let world = self.world();
+        let relationship = world.entity_from_id(relationship);
+        let to = to.into();
+        let from = self.state_id(relationship);
+        if let Some(from) = from {
+            let guards = relationship
+                .try_get::<&StateMachine<Entity>>(|machine| machine.guards(from.id(), to));
+
+            if !is_allowed(self, guards) {
+                return Err(FlecsError::ConstraintViolated(format!(
+                    "transition from {} to {} is not allowed",
+                    from.name(),
+                    world.entity_from_id(to).name()
+                )));
+            }
+        }
+
+        if !relationship.has::<flecs::Union>() {
+            return Ok(self.add_id((relationship.id(), to)));
+        }
+
+        let (on_exit, on_enter) = relationship
+            .try_get::<&StateMachine<Entity>>(|machine| {
+                let on_exit = from
+                    .map(|from| StateMachine::callbacks(&machine.on_exit, from.id()))
+                    .unwrap_or_default();
+                (on_exit, StateMachine::callbacks(&machine.on_enter, to))
+            })
+            .unwrap_or_default();
+        on_exit.iter().for_each(|callback| callback(self));
+        self.add_id((relationship.id(), to));
+        on_enter.iter().for_each(|callback| callback(self));
+        Ok(self)
+    }
+}
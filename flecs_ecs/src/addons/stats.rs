@@ -18,6 +18,89 @@ pub type SystemStats = sys::EcsSystemStats;
 #[derive(Debug, Clone, Copy, Default)]
 pub struct Stats;
 
+/// Allocation counters of the flecs runtime, created with [`World::allocation_counts()`].
+///
+/// The counters are shared by all worlds in the process and count from process start. They count
+/// allocations, not bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct AllocationCounts {
+    /// Allocations by the OS API.
+    pub alloc_count: i64,
+    /// Reallocations by the OS API.
+    pub realloc_count: i64,
+    /// Frees by the OS API.
+    pub free_count: i64,
+    /// Outstanding allocations by the OS API.
+    pub outstanding_alloc_count: i64,
+    /// Blocks allocated by block allocators.
+    pub block_alloc_count: i64,
+    /// Blocks freed by block allocators.
+    pub block_free_count: i64,
+    /// Outstanding block allocations.
+    pub block_outstanding_alloc_count: i64,
+    /// Pages allocated by stack allocators.
+    pub stack_alloc_count: i64,
+    /// Pages freed by stack allocators.
+    pub stack_free_count: i64,
+    /// Outstanding page allocations.
+    pub stack_outstanding_alloc_count: i64,
+}
+
+impl World {
+    /// Get the allocation counters of the flecs runtime.
+    ///
+    /// The counters are read from the global allocation counters of the OS API and the block and
+    /// stack allocators of flecs.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// let world = World::new();
+    ///
+    /// let before = world.allocation_counts();
+    /// for _ in 0..1000 {
+    ///     world.entity();
+    /// }
+    /// let after = world.allocation_counts();
+    /// assert!(after.alloc_count + after.block_alloc_count > before.alloc_count + before.block_alloc_count);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::entity_count()`]
+    /// * [`World::table_count()`]
+    /// * C API: `ecs_os_api_malloc_count`, `ecs_block_allocator_alloc_count`,
+    ///   `ecs_stack_allocator_alloc_count`
+    #[doc(alias = "ecs_os_api_malloc_count")]
+    #[doc(alias = "ecs_block_allocator_alloc_count")]
+    #[doc(alias = "ecs_stack_allocator_alloc_count")]
+    pub fn allocation_counts(&self) -> AllocationCounts {
+        // SAFETY: the counters are plain integers that are only ever incremented
+        let read = |counter: *const i64| unsafe { counter.read_volatile() };
+        let alloc_count = read(&raw const sys::ecs_os_api_malloc_count)
+            + read(&raw const sys::ecs_os_api_calloc_count);
+        let free_count = read(&raw const sys::ecs_os_api_free_count);
+        let block_alloc_count = read(&raw const sys::ecs_block_allocator_alloc_count);
+        let block_free_count = read(&raw const sys::ecs_block_allocator_free_count);
+        let stack_alloc_count = read(&raw const sys::ecs_stack_allocator_alloc_count);
+        let stack_free_count = read(&raw const sys::ecs_stack_allocator_free_count);
+
+        AllocationCounts {
+            alloc_count,
+            realloc_count: read(&raw const sys::ecs_os_api_realloc_count),
+            free_count,
+            outstanding_alloc_count: alloc_count - free_count,
+            block_alloc_count,
+            block_free_count,
+            block_outstanding_alloc_count: block_alloc_count - block_free_count,
+            stack_alloc_count,
+            stack_free_count,
+            stack_outstanding_alloc_count: stack_alloc_count - stack_free_count,
+        }
+    }
+}
+
 #[cfg(feature = "flecs_module")]
 impl Module for Stats {
     fn module(world: &World) {
@@ -57,13 +57,54 @@ impl<'a> System<'a> {
     ///
     /// * C++ API: `system::system`
     #[doc(alias = "system::system")]
-    pub fn new(world: impl WorldProvider<'a>, desc: sys::ecs_system_desc_t) -> Self {
+    pub fn new(world: impl WorldProvider<'a>, mut desc: sys::ecs_system_desc_t) -> Self {
+        if !desc.multi_threaded {
+            set_deterministic_order(world.world_ptr_mut(), &mut desc.query);
+        }
+        #[cfg(feature = "flecs_profiler")]
+        let run = crate::addons::profiler::instrument_system(&mut desc);
         let id = unsafe { sys::ecs_system_init(world.world_ptr_mut(), &desc) };
+        #[cfg(feature = "flecs_profiler")]
+        crate::addons::profiler::register_system(&world.world(), id, run);
         let entity = EntityView::new_from(world.world(), id);
 
         Self { entity }
     }
 
+    /// Create a new system, returning an error instead of panicking if flecs rejects its query.
+    pub(crate) fn try_new(
+        world: impl WorldProvider<'a>,
+        mut desc: sys::ecs_system_desc_t,
+    ) -> Result<Self, QueryError> {
+        if !desc.multi_threaded {
+            set_deterministic_order(world.world_ptr_mut(), &mut desc.query);
+        }
+        #[cfg(feature = "flecs_profiler")]
+        let run = crate::addons::profiler::instrument_system(&mut desc);
+        let (id, errors) = ecs_os_api::capture_errors(|| unsafe {
+            sys::ecs_system_init(world.world_ptr_mut(), &desc)
+        });
+        if id == 0 {
+            // flecs deletes the system entity, but leaves freeing the callbacks to the caller
+            for (free, ctx) in [
+                (desc.callback_ctx_free, desc.callback_ctx),
+                (desc.run_ctx_free, desc.run_ctx),
+            ] {
+                if let Some(free) = free {
+                    if !ctx.is_null() {
+                        unsafe { free(ctx) };
+                    }
+                }
+            }
+            return Err(QueryError::new(errors));
+        }
+        #[cfg(feature = "flecs_profiler")]
+        crate::addons::profiler::register_system(&world.world(), id, run);
+        let entity = EntityView::new_from(world.world(), id);
+
+        Ok(Self { entity })
+    }
+
     /// Wrap an existing system entity in a system object
     ///
     /// # Arguments
@@ -126,6 +167,41 @@ impl<'a> System<'a> {
         unsafe { Query::<()>::new_from(query) }
     }
 
+    /// Get the total time spent running the system, in seconds.
+    ///
+    /// Time is only measured when system time measurement is enabled with
+    /// [`World::measure_system_time()`]. The number of matched tables and entities can be
+    /// retrieved from [`System::query()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.measure_system_time(true);
+    ///
+    /// let sys = world.system::<&Position>().each(|_p| {});
+    /// world.entity().set(Position { x: 1.0, y: 2.0 });
+    ///
+    /// world.progress();
+    ///
+    /// assert!(sys.time_spent() >= 0.0);
+    /// assert_eq!(sys.query().count(), 1);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::measure_system_time()`]
+    pub fn time_spent(&self) -> f32 {
+        unsafe { (*sys::ecs_system_get(self.world.world_ptr(), *self.id())).time_spent }
+    }
+
     /// Run the system
     ///
     /// # Arguments
@@ -76,7 +76,7 @@ where
 
     /// Create a new system builder with a name
     pub(crate) fn new_named(world: &'a World, name: &str) -> Self {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
 
         let mut obj = Self {
             desc: Default::default(),
@@ -176,6 +176,11 @@ where
 
     /// Specify whether system can run on multiple threads.
     ///
+    /// The access of each term is taken from the system signature: `&T` terms are marked as
+    /// [`InOutKind::In`], `&mut T` terms as [`InOutKind::InOut`] and terms added without a
+    /// reference (e.g. `.with::<T>()`) as [`InOutKind::None`]. The pipeline uses this to decide
+    /// where sync points are needed, so prefer `&T` over `&mut T` when the data is only read.
+    ///
     /// # Arguments
     ///
     /// * `value` - if false, the system will always run on a single thread.
@@ -258,6 +263,39 @@ where
     }
 }
 
+impl<'a, T> TryBuilder<'a> for SystemBuilder<'a, T>
+where
+    T: QueryTuple,
+{
+    /// Build the `system_builder` into a system, returning an error instead of panicking if its
+    /// query is invalid.
+    ///
+    /// Like [`Builder::build()`], this builds the system without a callback. To build a system
+    /// with a callback, see [`SystemAPI::fallible()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// let world = World::new();
+    ///
+    /// let result = world.system::<()>().expr("DoesNotExist").try_build();
+    /// assert!(result.is_err());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`SystemAPI::fallible()`]
+    fn try_build(&mut self) -> Result<System<'a>, QueryError> {
+        let system = System::try_new(self.world(), self.desc);
+        for s in self.term_builder.str_ptrs_to_free.iter_mut() {
+            unsafe { core::mem::ManuallyDrop::drop(s) };
+        }
+        self.term_builder.str_ptrs_to_free.clear();
+        system
+    }
+}
+
 impl<'a, T: QueryTuple> WorldProvider<'a> for SystemBuilder<'a, T> {
     fn world(&self) -> WorldRef<'a> {
         self.world
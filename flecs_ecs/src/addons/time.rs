@@ -0,0 +1,127 @@
+//! Frame timing as singletons, so systems read the same time instead of each computing it from
+//! [`TableIter::delta_time()`](crate::core::TableIter::delta_time).
+//!
+//! Importing [`TimeModule`] adds the [`Time`] and [`RealTime`] singletons, which are updated at
+//! the start of every frame in the `OnLoad` phase.
+//!
+//! # Example
+//!
+//! ```
+//! use flecs_ecs::addons::time::*;
+//! use flecs_ecs::prelude::*;
+//!
+//! #[derive(Component)]
+//! struct Position {
+//!     x: f32,
+//! }
+//!
+//! let world = World::new();
+//! world.import::<TimeModule>();
+//! world.set_time_scale(0.5);
+//!
+//! let e = world.entity().set(Position { x: 0.0 });
+//!
+//! world
+//!     .system::<(&mut Position, &Time)>()
+//!     .term_at(1)
+//!     .singleton()
+//!     .each(|(p, time)| p.x += 10.0 * time.delta);
+//!
+//! world.progress_time(1.0);
+//!
+//! e.get::<&Position>(|p| assert_eq!(p.x, 5.0));
+//! world.get::<&RealTime>(|time| assert_eq!(time.delta, 1.0));
+//! world.get::<&Time>(|time| assert_eq!(time.frame_count, 1));
+//! ```
+
+use crate::core::*;
+use crate::prelude::*;
+
+use super::module::Module;
+
+/// The time of the current frame, scaled by the time scale of the world.
+///
+/// This is the time that gameplay systems should use, it slows down and speeds up with
+/// [`World::set_time_scale()`]. Use [`RealTime`] for time that should keep passing at the same
+/// rate, such as for user interfaces.
+///
+/// This is a singleton that is added and updated by [`TimeModule`].
+///
+/// # See also
+///
+/// * [`Time`]
+/// * [`RealTime`]
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+pub struct ScaledTime {
+    /// The time passed since the previous frame, in seconds.
+    pub delta: f32,
+    /// The time passed since the first frame, in seconds.
+    pub elapsed: f64,
+    /// The number of the current frame, starting at 1 for the first frame.
+    pub frame_count: u64,
+}
+
+/// The time of the current frame, scaled by the time scale of the world.
+///
+/// Same as [`ScaledTime`], which is the time most systems should use.
+pub type Time = ScaledTime;
+
+/// The time of the current frame, not affected by the time scale of the world.
+///
+/// This is a singleton that is added and updated by [`TimeModule`].
+///
+/// # See also
+///
+/// * [`ScaledTime`]
+#[derive(Component, Debug, Clone, Copy, Default, PartialEq)]
+pub struct RealTime {
+    /// The time passed since the previous frame, in seconds.
+    pub delta: f32,
+    /// The time passed since the first frame, in seconds.
+    pub elapsed: f64,
+    /// The number of the current frame, starting at 1 for the first frame.
+    pub frame_count: u64,
+}
+
+/// Module that adds the [`Time`] and [`RealTime`] singletons, imported with
+/// [`World::import()`].
+///
+/// The singletons are updated by a system in the `OnLoad` phase, so they are up to date for all
+/// systems that run in later phases. Frames that aren't run by [`World::progress()`] don't update
+/// the time.
+#[derive(Component)]
+pub struct TimeModule;
+
+impl Module for TimeModule {
+    fn module(world: &World) {
+        world.module::<TimeModule>("flecs::rust::time");
+
+        world.set(ScaledTime::default());
+        world.set(RealTime::default());
+
+        world
+            .system_named::<(&mut ScaledTime, &mut RealTime)>("UpdateTime")
+            .term_at(0)
+            .singleton()
+            .term_at(1)
+            .singleton()
+            .kind::<flecs::pipeline::OnLoad>()
+            .each_iter(|it, _, (scaled, real)| {
+                let info = it.world().info();
+                let frame_count = info.frame_count_total as u64 + 1;
+
+                *scaled = ScaledTime {
+                    delta: info.delta_time,
+                    elapsed: info.world_time_total,
+                    frame_count,
+                };
+                // the raw total of the world is wall clock time, which differs from the sum of
+                // the deltas when frames are run with a fixed delta time
+                *real = RealTime {
+                    delta: info.delta_time_raw,
+                    elapsed: real.elapsed + f64::from(info.delta_time_raw),
+                    frame_count,
+                };
+            });
+    }
+}
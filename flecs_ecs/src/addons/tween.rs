@@ -0,0 +1,255 @@
+//! Animate numeric component members over time, enabled with the `flecs_tween` feature.
+//!
+//! Tweens address the member they animate by path, such as `Position.x`, so they work for any
+//! component with reflection data, see [`EntityView::set_member()`]. A tween is an entity that is
+//! a child of the entity it animates, which is advanced every frame by a system of
+//! [`TweenModule`] and deleted when it is finished, or when the animated entity is deleted.
+//!
+//! # Example
+//!
+//! ```
+//! use flecs_ecs::addons::tween::*;
+//! use flecs_ecs::prelude::*;
+//!
+//! #[derive(Component)]
+//! #[meta]
+//! struct Position {
+//!     x: f32,
+//!     y: f32,
+//! }
+//!
+//! let world = World::new();
+//! world.component_named::<Position>("Position").meta();
+//!
+//! let e = world.entity().set(Position { x: 0.0, y: 0.0 });
+//!
+//! world
+//!     .tween(e, "Position.x")
+//!     .to(10.0)
+//!     .duration(2.0)
+//!     .ease(Ease::InOut);
+//!
+//! world.progress_time(1.0);
+//! e.get::<&Position>(|p| assert_eq!(p.x, 5.0));
+//!
+//! world.progress_time(1.0);
+//! e.get::<&Position>(|p| assert_eq!(p.x, 10.0));
+//! ```
+
+use crate::core::*;
+use crate::prelude::*;
+
+use super::module::Module;
+
+extern crate alloc;
+use alloc::string::{String, ToString};
+
+/// How the progress of a tween maps to the value between its start and end.
+///
+/// The eased variants are cubic.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Ease {
+    /// Change at a constant rate.
+    #[default]
+    Linear,
+    /// Start slow and speed up.
+    In,
+    /// Start fast and slow down.
+    Out,
+    /// Start slow, speed up and slow down again.
+    InOut,
+}
+
+impl Ease {
+    /// Map the progress `t`, between `0.0` and `1.0`, to the eased progress.
+    pub fn apply(self, t: f32) -> f32 {
+        match self {
+            Ease::Linear => t,
+            Ease::In => t * t * t,
+            Ease::Out => 1.0 - (1.0 - t).powi(3),
+            Ease::InOut if t < 0.5 => 4.0 * t * t * t,
+            Ease::InOut => 1.0 - (2.0 - 2.0 * t).powi(3) / 2.0,
+        }
+    }
+}
+
+/// The state of a tween, stored on the tween entity, see [`World::tween()`].
+#[derive(Component, Debug, Clone)]
+pub struct TweenState {
+    path: String,
+    from: f64,
+    to: f64,
+    duration: f32,
+    elapsed: f32,
+    ease: Ease,
+    tick_source: u64,
+}
+
+impl TweenState {
+    /// The path of the animated member, such as `Position.x`.
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+
+    /// The progress of the tween, between `0.0` and `1.0`.
+    pub fn progress(&self) -> f32 {
+        if self.duration > 0.0 {
+            (self.elapsed / self.duration).min(1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// The value of the member at the current progress.
+    fn value(&self) -> f64 {
+        let t = f64::from(self.ease.apply(self.progress()));
+        self.from + (self.to - self.from) * t
+    }
+}
+
+/// A tween created by [`World::tween()`], of which the methods configure the animation.
+///
+/// The [`TweenState`] is set on the tween entity once, when the `Tween` is dropped, so the tween
+/// doesn't need to be finished with a final call.
+pub struct Tween<'a> {
+    entity: EntityView<'a>,
+    state: TweenState,
+}
+
+impl Drop for Tween<'_> {
+    fn drop(&mut self) {
+        if self.entity.is_alive() {
+            self.entity.set(self.state.clone());
+        }
+    }
+}
+
+impl<'a> Tween<'a> {
+    fn update(mut self, f: impl FnOnce(&mut TweenState)) -> Self {
+        f(&mut self.state);
+        self
+    }
+
+    /// The tween entity.
+    pub fn entity(&self) -> EntityView<'a> {
+        self.entity
+    }
+
+    /// Set the value the tween starts at, which is the value of the member when the tween is
+    /// created by default.
+    pub fn from(self, value: f64) -> Self {
+        self.update(|state| state.from = value)
+    }
+
+    /// Set the value the tween ends at.
+    pub fn to(self, value: f64) -> Self {
+        self.update(|state| state.to = value)
+    }
+
+    /// Set how long the tween takes in seconds, which is `1.0` by default.
+    pub fn duration(self, seconds: f32) -> Self {
+        self.update(|state| state.duration = seconds)
+    }
+
+    /// Set the easing of the tween, which is [`Ease::Linear`] by default.
+    pub fn ease(self, ease: Ease) -> Self {
+        self.update(|state| state.ease = ease)
+    }
+
+    /// Advance the tween with a tick source, such as a timer, instead of every frame.
+    ///
+    /// The tween is advanced by the time passed since the previous tick when the tick source
+    /// ticks, so it can run at a lower rate than the frame rate.
+    ///
+    /// # See also
+    ///
+    /// * [`World::timer()`]
+    pub fn tick_source(self, tick_source: impl Into<Entity>) -> Self {
+        let tick_source = *tick_source.into();
+        self.update(|state| state.tick_source = tick_source)
+    }
+}
+
+impl World {
+    /// Animate a numeric member of a component of an entity, such as `Position.x`.
+    ///
+    /// Imports [`TweenModule`] when it isn't imported yet. The tween starts at the current
+    /// value of the member, and is advanced during the `OnUpdate` phase. Values are converted to
+    /// the type of the member, so integer members can be animated too.
+    ///
+    /// # Arguments
+    ///
+    /// * `entity` - The entity to animate.
+    /// * `path` - The path of the member, starting with the name of the component.
+    ///
+    /// # Panics
+    ///
+    /// Panics when the entity doesn't have the member, see [`EntityView::get_member()`].
+    ///
+    /// # See also
+    ///
+    /// * [`Tween`]
+    /// * [`addons::tween`](crate::addons::tween)
+    pub fn tween(&self, entity: impl Into<Entity>, path: &str) -> Tween<'_> {
+        self.import::<TweenModule>();
+
+        let target = self.entity_from_id(entity);
+        let from = target.get_member::<f64>(path);
+        Tween {
+            entity: self.entity().child_of_id(target),
+            state: TweenState {
+                path: path.to_string(),
+                from,
+                to: from,
+                duration: 1.0,
+                elapsed: 0.0,
+                ease: Ease::Linear,
+                tick_source: 0,
+            },
+        }
+    }
+}
+
+/// Module that advances tweens, imported by [`World::tween()`].
+///
+/// The `AdvanceTweens` system writes the animated members through the parent of each tween, so
+/// the components it writes aren't part of its query. It therefore runs single-threaded, and
+/// must not be made multi-threaded.
+#[derive(Component)]
+pub struct TweenModule;
+
+impl Module for TweenModule {
+    fn module(world: &World) {
+        world.module::<TweenModule>("flecs::rust::tween");
+
+        world
+            .system_named::<&mut TweenState>("AdvanceTweens")
+            .kind::<flecs::pipeline::OnUpdate>()
+            // writes members of the components of the tweened entity, which aren't declared by
+            // the query, so the system can't be split over worker threads
+            .each_iter(|it, row, tween| {
+                let e = it.entity(row);
+                let delta = if tween.tick_source == 0 {
+                    Some(it.delta_time())
+                } else {
+                    it.world()
+                        .entity_from_id(tween.tick_source)
+                        .try_get::<&flecs::system::TickSource>(|source| {
+                            source.tick.then_some(source.time_elapsed)
+                        })
+                        .flatten()
+                };
+                let Some(delta) = delta else {
+                    return;
+                };
+
+                tween.elapsed += delta;
+                let applied = e.parent().is_some_and(|target| {
+                    target.try_set_member(&tween.path, tween.value()).is_ok()
+                });
+                if !applied || tween.progress() >= 1.0 {
+                    e.destruct();
+                }
+            });
+    }
+}
@@ -0,0 +1,105 @@
+//! Record which thread borrows each component column and where, enabled with the
+//! `flecs_safety_access_journal` feature.
+//!
+//! The read write locks of `flecs_safety_readwrite_locks` panic when a column is borrowed while a
+//! conflicting borrow is alive, but only know about the borrow that failed. Flecs can't see these
+//! races, as Rust code accesses the columns through raw pointers. The journal remembers the thread
+//! and source location of every borrow that is alive, so the panic can point at both sides.
+//!
+//! Borrows made while systems and observers iterate their queries are not recorded, flecs
+//! schedules those so they don't conflict.
+
+use core::fmt;
+use core::panic::Location;
+use std::thread::{self, ThreadId};
+
+use dashmap::DashMap;
+use foldhash::fast::RandomState;
+use smallvec::SmallVec;
+
+extern crate alloc;
+use alloc::{format, string::String};
+
+/// A borrow of a component column that is alive.
+struct Access {
+    thread: ThreadId,
+    thread_name: Option<String>,
+    write: bool,
+    location: &'static Location<'static>,
+}
+
+impl Access {
+    #[track_caller]
+    fn current(write: bool) -> Self {
+        let thread = thread::current();
+        Self {
+            thread: thread.id(),
+            thread_name: thread.name().map(String::from),
+            write,
+            location: Location::caller(),
+        }
+    }
+}
+
+impl fmt::Display for Access {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let kind = if self.write { "write" } else { "read" };
+        match &self.thread_name {
+            Some(name) => write!(f, "{kind} on thread '{name}'")?,
+            None => write!(f, "{kind} on thread {:?}", self.thread)?,
+        }
+        write!(f, " at {}", self.location)
+    }
+}
+
+/// The borrows that are alive, per component and table.
+pub(crate) struct AccessJournal {
+    accesses: DashMap<u128, SmallVec<[Access; 2]>, RandomState>,
+}
+
+impl AccessJournal {
+    pub(crate) fn new() -> Self {
+        Self {
+            accesses: DashMap::with_hasher(RandomState::default()),
+        }
+    }
+
+    /// Record a borrow by the caller on the current thread.
+    #[track_caller]
+    pub(crate) fn record(&self, key: u128, write: bool) {
+        self.accesses
+            .entry(key)
+            .or_default()
+            .push(Access::current(write));
+    }
+
+    /// Remove a borrow that ended, preferably one made by the current thread.
+    pub(crate) fn release(&self, key: u128, write: bool) {
+        let thread = thread::current().id();
+        self.accesses.remove_if_mut(&key, |_, accesses| {
+            let index = accesses
+                .iter()
+                .rposition(|access| access.write == write && access.thread == thread)
+                .or_else(|| accesses.iter().rposition(|access| access.write == write));
+            if let Some(index) = index {
+                accesses.remove(index);
+            }
+            accesses.is_empty()
+        });
+    }
+
+    /// Describe the borrow by the caller that failed, and the borrows it conflicts with.
+    #[track_caller]
+    pub(crate) fn report(&self, key: u128, write: bool) -> String {
+        let mut report = format!("\n  {}", Access::current(write));
+        match self.accesses.get(&key) {
+            Some(accesses) => {
+                for access in accesses.iter() {
+                    report.push_str(&format!("\n  conflicts with {access}"));
+                }
+            }
+            None => report.push_str("\n  conflicts with a borrow by a system or observer"),
+        }
+        report
+    }
+}
@@ -3,6 +3,7 @@
 use core::{
     ffi::CStr,
     fmt::{Debug, Display},
+    ops::Index,
     ptr::NonNull,
 };
 
@@ -52,6 +53,14 @@ impl Debug for Archetype<'_> {
     }
 }
 
+impl Index<usize> for Archetype<'_> {
+    type Output = Id;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.type_vec[index]
+    }
+}
+
 impl<'a> Archetype<'a> {
     pub(crate) fn new(world: impl WorldProvider<'a>, type_vec: &'a [Id]) -> Self {
         Archetype {
@@ -155,4 +164,112 @@ impl<'a> Archetype<'a> {
             None
         }
     }
+
+    /// Iterate over the [ids](IdView) in the type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Likes;
+    ///
+    /// let world = World::new();
+    ///
+    /// let bob = world.entity();
+    /// let e = world
+    ///     .entity()
+    ///     .set(Position { x: 1.0, y: 2.0 })
+    ///     .add_first::<Likes>(bob);
+    ///
+    /// let archetype = e.archetype();
+    /// assert_eq!(archetype.iter_ids().count(), 2);
+    /// assert!(archetype.iter_ids().any(|id| id.is_pair()));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`Archetype::as_slice()`]
+    pub fn iter_ids(&self) -> impl Iterator<Item = IdView<'a>> + '_ {
+        let world = self.world;
+        self.type_vec
+            .iter()
+            .map(move |&id| IdView::new_from_id(world, id))
+    }
+
+    /// Test if the type contains the specified id.
+    ///
+    /// Wildcards are supported, so `(Likes, *)` matches any `Likes` pair in the type.
+    /// Unlike [`EntityView::has_id()`], this does not take inherited components into account.
+    ///
+    /// # See also
+    ///
+    /// * [`Archetype::contains()`]
+    /// * [`Archetype::contains_pair()`]
+    pub fn contains_id(&self, id: impl IntoId) -> bool {
+        let pattern = *id.into();
+        self.type_vec
+            .iter()
+            .any(|&id| unsafe { sys::ecs_id_match(*id, pattern) })
+    }
+
+    /// Test if the type contains the specified component or pair.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Velocity {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Likes;
+    ///
+    /// let world = World::new();
+    ///
+    /// let bob = world.entity();
+    /// let e = world
+    ///     .entity()
+    ///     .set(Position { x: 1.0, y: 2.0 })
+    ///     .add_first::<Likes>(bob);
+    ///
+    /// let archetype = e.archetype();
+    /// assert!(archetype.contains::<Position>());
+    /// assert!(!archetype.contains::<Velocity>());
+    /// assert!(archetype.contains::<(Likes, flecs::Wildcard)>());
+    /// assert!(archetype.contains_pair(world.component_id::<Likes>(), bob));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`Archetype::contains_id()`]
+    /// * [`Archetype::contains_pair()`]
+    pub fn contains<T: ComponentOrPairId>(&self) -> bool {
+        self.contains_id(T::get_id(self.world))
+    }
+
+    /// Test if the type contains the specified pair.
+    ///
+    /// # See also
+    ///
+    /// * [`Archetype::contains()`]
+    /// * [`Archetype::contains_id()`]
+    pub fn contains_pair(&self, first: impl Into<Entity>, second: impl Into<Entity>) -> bool {
+        self.contains_id((first.into(), second.into()))
+    }
 }
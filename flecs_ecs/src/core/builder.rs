@@ -1,7 +1,14 @@
 #![doc(hidden)]
 
+use crate::core::QueryError;
+
 pub trait Builder<'a> {
     type BuiltType;
 
     fn build(&mut self) -> Self::BuiltType;
 }
+
+/// A [`Builder`] that returns an error instead of panicking when its query is invalid.
+pub trait TryBuilder<'a>: Builder<'a> {
+    fn try_build(&mut self) -> Result<Self::BuiltType, QueryError>;
+}
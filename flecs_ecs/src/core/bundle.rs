@@ -0,0 +1,143 @@
+//! Groups of components that are spawned together, see [`Bundle`].
+
+use crate::core::*;
+use crate::sys;
+
+/// A group of components that can be spawned on an entity with a single archetype move.
+///
+/// Implement this with `#[derive(Bundle)]` on a struct where each field is a component. The
+/// derive also implements [`EntityBundle`] and [`BundleContains`] for the fields, so the bundle
+/// can be used as the marker of a [`TypedEntity`].
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Position {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// #[derive(Component)]
+/// struct Player;
+///
+/// #[derive(Bundle)]
+/// struct PlayerBundle {
+///     position: Position,
+///     health: Health,
+///     player: Player,
+/// }
+///
+/// let world = World::new();
+///
+/// let player = world.spawn(PlayerBundle {
+///     position: Position { x: 1.0, y: 2.0 },
+///     health: Health(100),
+///     player: Player,
+/// });
+///
+/// assert!(player.has::<Player>());
+/// assert_eq!(player.typed::<PlayerBundle>().get::<Health>(|h| h.0), 100);
+/// ```
+pub trait Bundle {
+    /// Add the components of the bundle to the entity.
+    ///
+    /// When the world is not deferred, each component moves the entity to a new table. Use
+    /// [`World::spawn()`] or [`EntityView::insert()`] to add all components with one move.
+    fn insert_into(self, entity: EntityView);
+}
+
+/// Add a component of a [`Bundle`] to an entity, used by `#[derive(Bundle)]`.
+#[doc(hidden)]
+pub fn bundle_insert<T: ComponentId>(entity: EntityView, value: T) {
+    let world = entity.world.world_ptr_mut();
+    let id = T::id(entity.world);
+
+    if T::IS_TAG {
+        unsafe { sys::ecs_add_id(world, *entity.id, id) };
+        return;
+    }
+
+    unsafe {
+        if T::NEEDS_DROP && !T::IMPLS_DEFAULT {
+            // the component can't be constructed before it is written, which emplace avoids at
+            // the cost of not being batched with the other components of the bundle
+            let mut is_new = false;
+            let ptr = sys::ecs_emplace_id(world, *entity.id, id, &mut is_new) as *mut T;
+            if !is_new {
+                core::ptr::drop_in_place(ptr);
+            }
+            core::ptr::write(ptr, value);
+            sys::ecs_modified_id(world, *entity.id, id);
+        } else {
+            let ptr = sys::ecs_ensure_modified_id(world, *entity.id, id) as *mut T;
+            core::ptr::drop_in_place(ptr);
+            core::ptr::write(ptr, value);
+        }
+    }
+}
+
+impl<'a> EntityView<'a> {
+    /// Add the components of a bundle to the entity.
+    ///
+    /// The operations are deferred until all components are added, so the entity moves to its
+    /// new table once instead of once per component.
+    ///
+    /// # See also
+    ///
+    /// * [`World::spawn()`]
+    pub fn insert(self, bundle: impl Bundle) -> Self {
+        self.world.defer_begin();
+        bundle.insert_into(self);
+        self.world.defer_end();
+        self
+    }
+}
+
+impl World {
+    /// Create an entity with the components of a bundle.
+    ///
+    /// All components are added with a single archetype move, instead of moving the entity to a
+    /// new table for every chained [`EntityView::set()`]. Components that implement [`Drop`] but
+    /// not [`Default`] can't be batched and are added with a move of their own.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Velocity {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// #[derive(Bundle)]
+    /// struct Moving(Position, Velocity);
+    ///
+    /// let world = World::new();
+    ///
+    /// let e = world.spawn(Moving(Position { x: 0.0, y: 0.0 }, Velocity { x: 1.0, y: 1.0 }));
+    /// assert!(e.has::<Position>() && e.has::<Velocity>());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`Bundle`]
+    /// * [`EntityView::insert()`]
+    #[cfg_attr(feature = "flecs_entity_audit", track_caller)]
+    pub fn spawn(&self, bundle: impl Bundle) -> EntityView<'_> {
+        self.entity().insert(bundle)
+    }
+}
@@ -0,0 +1,359 @@
+//! Queue entity operations from other threads, see [`World::command_sender()`].
+
+extern crate std;
+
+use crate::core::*;
+use crate::sys;
+
+extern crate alloc;
+use alloc::{boxed::Box, format, string::String, sync::Arc, vec::Vec};
+use std::sync::{Mutex, MutexGuard};
+
+/// An entity that operations sent with a [`CommandSender`] apply to.
+///
+/// This is either an entity that already exists in the world, or one created by
+/// [`CommandSender::create()`] that may not exist yet. Use [`World::sent_entity()`] to get the
+/// entity once the commands are applied.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SentEntity(Target);
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+enum Target {
+    Alive(Entity),
+    Pending(u64),
+}
+
+impl From<Entity> for SentEntity {
+    fn from(entity: Entity) -> Self {
+        SentEntity(Target::Alive(entity))
+    }
+}
+
+impl From<EntityView<'_>> for SentEntity {
+    fn from(entity: EntityView<'_>) -> Self {
+        SentEntity(Target::Alive(entity.id()))
+    }
+}
+
+enum Command {
+    Create {
+        handle: u64,
+        name: Option<String>,
+    },
+    Set {
+        target: SentEntity,
+        apply: Box<dyn FnOnce(EntityView) + Send>,
+    },
+    SetBytes {
+        target: SentEntity,
+        component: Entity,
+        bytes: Vec<u8>,
+    },
+    Delete(SentEntity),
+}
+
+#[derive(Default)]
+struct CommandQueue {
+    commands: Vec<Command>,
+    next_handle: u64,
+    created: hashbrown::HashMap<u64, Entity>,
+}
+
+/// A handle to queue entity operations from any thread, created by [`World::command_sender()`].
+///
+/// The handle can be cloned and sent to other threads, also when the world has no stages. The
+/// operations are applied in the order they were sent, at the start of the next frame, or when
+/// [`World::apply_sent_commands()`] is called.
+#[derive(Clone, Default)]
+pub struct CommandSender {
+    queue: Arc<Mutex<CommandQueue>>,
+}
+
+impl core::fmt::Debug for CommandSender {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("CommandSender")
+            .field("pending", &self.lock().commands.len())
+            .finish()
+    }
+}
+
+impl CommandSender {
+    fn lock(&self) -> MutexGuard<'_, CommandQueue> {
+        self.queue
+            .lock()
+            .unwrap_or_else(std::sync::PoisonError::into_inner)
+    }
+
+    fn push(&self, command: Command) {
+        self.lock().commands.push(command);
+    }
+
+    /// Queue the creation of an entity.
+    ///
+    /// The returned handle can be used in later operations sent with any clone of the sender.
+    pub fn create(&self) -> SentEntity {
+        self.create_entity(None)
+    }
+
+    /// Queue the creation of an entity with a name.
+    ///
+    /// # See also
+    ///
+    /// * [`World::entity_named()`]
+    pub fn create_named(&self, name: &str) -> SentEntity {
+        self.create_entity(Some(String::from(name)))
+    }
+
+    fn create_entity(&self, name: Option<String>) -> SentEntity {
+        let mut queue = self.lock();
+        let handle = queue.next_handle;
+        queue.next_handle += 1;
+        queue.commands.push(Command::Create { handle, name });
+        SentEntity(Target::Pending(handle))
+    }
+
+    /// Queue setting the value of a component.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::set()`]
+    pub fn set<T>(&self, entity: impl Into<SentEntity>, value: T) -> &Self
+    where
+        T: ComponentId + DataComponent + Send,
+    {
+        self.run(entity, move |entity| {
+            entity.set(value);
+        })
+    }
+
+    /// Queue a function that is called with the entity when the operations are applied.
+    pub(crate) fn run(
+        &self,
+        entity: impl Into<SentEntity>,
+        apply: impl FnOnce(EntityView) + Send + 'static,
+    ) -> &Self {
+        self.push(Command::Set {
+            target: entity.into(),
+            apply: Box::new(apply),
+        });
+        self
+    }
+
+    /// Queue setting a component from its serialized bytes.
+    ///
+    /// The bytes are copied into the component as is. The command is skipped with an error when
+    /// the number of bytes doesn't match the size of the component.
+    ///
+    /// # Safety
+    ///
+    /// The bytes have to be a valid value of the type of the component.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::set_ptr()`]
+    pub unsafe fn set_bytes(
+        &self,
+        entity: impl Into<SentEntity>,
+        component: impl Into<Entity>,
+        bytes: impl Into<Vec<u8>>,
+    ) -> &Self {
+        self.push(Command::SetBytes {
+            target: entity.into(),
+            component: component.into(),
+            bytes: bytes.into(),
+        });
+        self
+    }
+
+    /// Queue the deletion of an entity.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::destruct()`]
+    pub fn delete(&self, entity: impl Into<SentEntity>) -> &Self {
+        self.push(Command::Delete(entity.into()));
+        self
+    }
+
+    /// The number of operations that are not yet applied.
+    pub fn len(&self) -> usize {
+        self.lock().commands.len()
+    }
+
+    /// Test if all sent operations are applied.
+    pub fn is_empty(&self) -> bool {
+        self.lock().commands.is_empty()
+    }
+
+    fn resolve(&self, entity: SentEntity) -> Option<Entity> {
+        match entity.0 {
+            Target::Alive(entity) => Some(entity),
+            Target::Pending(handle) => self.lock().created.get(&handle).copied(),
+        }
+    }
+}
+
+impl World {
+    /// Get a handle to queue entity operations from other threads.
+    ///
+    /// The world can only be accessed from the thread it was created on. The [`CommandSender`]
+    /// can be cloned and moved to other threads, for example to stream in entities from a
+    /// background loader. Sent operations are applied at the start of the next frame, by a system
+    /// in the `OnLoad` phase, or by calling [`World::apply_sent_commands()`].
+    ///
+    /// All calls return a handle to the same queue.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.component::<Position>();
+    ///
+    /// let sender = world.command_sender();
+    /// let loaded = std::thread::spawn(move || {
+    ///     let e = sender.create_named("loaded");
+    ///     sender.set(e, Position { x: 1.0, y: 2.0 });
+    ///     e
+    /// })
+    /// .join()
+    /// .unwrap();
+    ///
+    /// world.progress();
+    ///
+    /// let e = world.sent_entity(loaded).unwrap();
+    /// assert_eq!(e.name(), "loaded");
+    /// assert!(e.has::<Position>());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::apply_sent_commands()`]
+    /// * [`World::sent_entity()`]
+    pub fn command_sender(&self) -> CommandSender {
+        if let Some(sender) = &self.world_ctx().command_sender {
+            return sender.clone();
+        }
+
+        let sender = CommandSender::default();
+        self.world_ctx_mut().command_sender = Some(sender.clone());
+
+        #[cfg(feature = "flecs_pipeline")]
+        self.system_named::<()>("flecs::rust::ApplySentCommands")
+            .kind::<flecs::pipeline::OnLoad>()
+            .run(|it| {
+                it.world().apply_sent_commands();
+            });
+
+        sender
+    }
+
+    /// Apply the operations queued with the [`CommandSender`] of the world.
+    ///
+    /// This is done at the start of every frame, so it only has to be called directly when the
+    /// world is not progressed. Operations on entities that were deleted are ignored.
+    ///
+    /// # See also
+    ///
+    /// * [`World::command_sender()`]
+    pub fn apply_sent_commands(&self) {
+        let Some(sender) = self.world_ctx().command_sender.clone() else {
+            return;
+        };
+
+        // commands are taken from the queue first, so that hooks and observers can send new ones
+        let commands = {
+            let mut queue = sender.lock();
+            if !queue.commands.is_empty() {
+                // forget created entities that were deleted other than by a sent command
+                queue.created.retain(|_, entity| self.is_alive(*entity));
+            }
+            core::mem::take(&mut queue.commands)
+        };
+        let alive = |target| {
+            sender
+                .resolve(target)
+                .filter(|&entity| self.is_alive(entity))
+                .map(|entity| self.entity_from_id(entity))
+        };
+
+        for command in commands {
+            match command {
+                Command::Create { handle, name } => {
+                    let entity = match name {
+                        Some(name) => self.entity_named(&name),
+                        None => self.entity(),
+                    };
+                    sender.lock().created.insert(handle, entity.id());
+                }
+                Command::Set { target, apply } => {
+                    if let Some(entity) = alive(target) {
+                        apply(entity);
+                    }
+                }
+                Command::SetBytes {
+                    target,
+                    component,
+                    bytes,
+                } => {
+                    if let Some(entity) = alive(target) {
+                        let size = unsafe {
+                            sys::ecs_get_type_info(self.world_ptr(), *component)
+                                .as_ref()
+                                .map(|type_info| type_info.size as usize)
+                        };
+                        if size != Some(bytes.len()) {
+                            log_error(&format!(
+                                "{} bytes sent for component {:?} of size {:?}",
+                                bytes.len(),
+                                component,
+                                size
+                            ));
+                            continue;
+                        }
+                        unsafe {
+                            entity.set_ptr_w_size(component, bytes.len(), bytes.as_ptr().cast());
+                        }
+                    }
+                }
+                Command::Delete(target) => {
+                    if let Some(entity) = alive(target) {
+                        entity.destruct();
+                    }
+                    if let Target::Pending(handle) = target.0 {
+                        sender.lock().created.remove(&handle);
+                    }
+                }
+            }
+        }
+    }
+
+    /// Get the entity that operations sent with a [`CommandSender`] apply to.
+    ///
+    /// Returns `None` if the entity is not yet created, or no longer alive.
+    ///
+    /// # See also
+    ///
+    /// * [`CommandSender::create()`]
+    pub fn sent_entity(&self, entity: impl Into<SentEntity>) -> Option<EntityView<'_>> {
+        let entity = match entity.into().0 {
+            Target::Alive(entity) => entity,
+            Target::Pending(handle) => {
+                let mut queue = self.world_ctx().command_sender.as_ref()?.lock();
+                let entity = *queue.created.get(&handle)?;
+                if !self.is_alive(entity) {
+                    queue.created.remove(&handle);
+                    return None;
+                }
+                entity
+            }
+        };
+        self.is_alive(entity).then(|| self.entity_from_id(entity))
+    }
+}
@@ -0,0 +1,71 @@
+//! Register groups of components up front, see [`World::register_components()`].
+
+use crate::core::*;
+use flecs_ecs_derive::tuples;
+
+/// A tuple of components that are registered together by [`World::register_components()`].
+///
+/// Implemented for tuples of up to 32 components.
+pub trait ComponentSet {
+    /// Register the components of the set with the world.
+    #[doc(hidden)]
+    fn register_with(world: &World);
+}
+
+macro_rules! impl_component_set {
+    ($($t:ident),*) => {
+        impl<$($t: ComponentId),*> ComponentSet for ($($t,)*) {
+            fn register_with(world: &World) {
+                $(
+                    world.component::<$t>();
+                )*
+            }
+        }
+    }
+}
+
+tuples!(impl_component_set, 1, 32);
+
+impl World {
+    /// Register a set of components in one pass.
+    ///
+    /// Components are registered the first time they are used, which can cause a hitch when that
+    /// happens in the middle of gameplay. Registering the components when the world is set up
+    /// avoids this. Components that are already registered are skipped.
+    ///
+    /// The components are registered in order, so their ids are allocated one after the other
+    /// from the range that flecs reserves for components, for as long as it has room.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Velocity {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Player;
+    ///
+    /// let world = World::new();
+    /// world.register_components::<(Position, Velocity, Player)>();
+    ///
+    /// assert!(Velocity::is_registered_with_world(&world));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::component()`]
+    pub fn register_components<T: ComponentSet>(&self) -> &Self {
+        T::register_with(self);
+        self
+    }
+}
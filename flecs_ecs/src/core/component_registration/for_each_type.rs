@@ -0,0 +1,112 @@
+//! Register generic systems once per component type, see [`register_for_each_type!`] and
+//! [`World::on_reflected_component()`].
+
+#[cfg(feature = "flecs_meta")]
+use crate::core::*;
+
+/// Call a generic registration function once for each type in a list.
+///
+/// Systems and observers that work the same way for many components, such as serialization or
+/// replication, can be written once as a generic function that takes the world and then be
+/// registered for each concrete component. The type parameter of the function can be named in
+/// the macro invocation for readability, it's replaced by each type of the list in turn.
+///
+/// The functions are called in the order of the list.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+/// use flecs_ecs::register_for_each_type;
+///
+/// #[derive(Component, Default)]
+/// struct Position(f32);
+///
+/// #[derive(Component, Default)]
+/// struct Rotation(f32);
+///
+/// #[derive(Component)]
+/// struct Dirty;
+///
+/// fn mark_dirty<T: ComponentId>(world: &World) {
+///     world
+///         .observer::<flecs::OnSet, &T>()
+///         .each_entity(|e, _| {
+///             e.add::<Dirty>();
+///         });
+/// }
+///
+/// let world = World::new();
+/// register_for_each_type!(world, mark_dirty::<T>, [Position, Rotation]);
+///
+/// let e = world.entity().set(Rotation(1.0));
+/// assert!(e.has::<Dirty>());
+/// ```
+///
+/// # See also
+///
+/// * [`World::register_components()`]
+/// * [`World::on_reflected_component()`]
+#[macro_export]
+macro_rules! register_for_each_type {
+    (@call $world:ident, [$($func:tt)*], []) => {};
+    (@call $world:ident, [$($func:tt)*], [$t:ty $(, $rest:ty)*]) => {
+        $($func)*::<$t>($world);
+        $crate::register_for_each_type!(@call $world, [$($func)*], [$($rest),*]);
+    };
+    ($world:expr, $($func:ident)::+ $(::<$param:ident>)?, [$($t:ty),* $(,)?] $(,)?) => {{
+        let world: &$crate::core::World = &$world;
+        $crate::register_for_each_type!(@call world, [$($func)::+], [$($t),*]);
+    }};
+}
+
+#[cfg(feature = "flecs_meta")]
+impl World {
+    /// Invoke `func` for each component that gets reflection data from now on.
+    ///
+    /// This is the runtime counterpart of [`register_for_each_type!`], for components that aren't
+    /// known when the code is compiled, such as components created by scripts or by
+    /// [`World::component_named()`]. A component is reflected once its type information is set,
+    /// for example by deriving `Component` with `#[flecs(meta)]` or by adding members to it.
+    ///
+    /// Components that were already reflected before this is called are not passed to `func`.
+    ///
+    /// # Returns
+    ///
+    /// The observer that invokes `func`, which can be destructed to stop receiving components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    /// use std::cell::RefCell;
+    /// use std::rc::Rc;
+    ///
+    /// let world = World::new();
+    ///
+    /// let seen = Rc::new(RefCell::new(Vec::new()));
+    /// let seen_by_observer = seen.clone();
+    /// world.on_reflected_component(move |component| {
+    ///     seen_by_observer.borrow_mut().push(component.id());
+    /// });
+    ///
+    /// let health = world
+    ///     .component_untyped_named("Health")
+    ///     .member::<f32>("value");
+    ///
+    /// assert!(seen.borrow().contains(&health.id()));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`register_for_each_type!`]
+    pub fn on_reflected_component(
+        &self,
+        mut func: impl FnMut(UntypedComponent<'_>) + 'static,
+    ) -> Observer<'_> {
+        self.observer::<flecs::OnSet, &flecs::meta::Type>()
+            .each_entity(move |e, _| {
+                func(UntypedComponent::new_from(e.world(), e));
+            })
+    }
+}
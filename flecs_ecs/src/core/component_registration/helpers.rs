@@ -17,6 +17,29 @@ pub(crate) fn create_component_desc(
     }
 }
 
+/// Largest alignment of components, see [`UntypedComponent::layout()`].
+///
+/// Storage is aligned to at most `ECS_MAX_ALIGN` bytes, or to what the allocator of the OS
+/// guarantees when flecs uses it directly.
+pub(crate) const MAX_COMPONENT_ALIGN: usize = if cfg!(feature = "flecs_use_os_alloc") {
+    16
+} else {
+    sys::ECS_MAX_ALIGN as usize
+};
+
+/// Panics if flecs can't store values of `T` at the alignment it requires, which would
+/// otherwise result in misaligned references.
+#[inline]
+fn assert_component_align<T>() {
+    assert!(
+        core::mem::align_of::<T>() <= MAX_COMPONENT_ALIGN,
+        "component `{}` requires an alignment of {} bytes, components can be aligned to at most {} bytes",
+        core::any::type_name::<T>(),
+        core::mem::align_of::<T>(),
+        MAX_COMPONENT_ALIGN
+    );
+}
+
 #[inline(never)]
 pub(crate) fn create_type_info<T, const ALLOCATE_TAG: bool>() -> flecs_ecs_sys::ecs_type_info_t
 where
@@ -28,6 +51,7 @@ where
     };
 
     let alignment = if size != 0 {
+        assert_component_align::<T>();
         core::mem::align_of::<T>()
     } else {
         0
@@ -46,6 +70,9 @@ where
     // on rust side we check for safety and panic if the component does not implement the default trait where needed.
     if T::IMPLS_DEFAULT {
         T::__register_default_hooks(&mut hooks);
+    } else if size != 0 && T::NEEDS_DROP {
+        // flecs zeroes the memory, which isn't a value that can be dropped when it's assigned to
+        flecs_ecs::core::lifecycle_traits::register_ctor_zeroed_lifecycle_actions::<T>(&mut hooks);
     }
 
     T::__register_clone_hooks(&mut hooks);
@@ -98,6 +125,7 @@ pub(crate) fn create_entity_desc(
 pub(crate) fn external_create_type_info<T>() -> flecs_ecs_sys::ecs_type_info_t {
     let size = core::mem::size_of::<T>();
     let alignment = if size != 0 {
+        assert_component_align::<T>();
         core::mem::align_of::<T>()
     } else {
         0
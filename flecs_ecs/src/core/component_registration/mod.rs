@@ -1,10 +1,13 @@
 //! Contains types and traits that define what a component is and how it is registered.
 
+mod component_set;
+mod for_each_type;
 mod helpers;
 mod registration;
 pub mod registration_traits;
 pub mod registration_types;
 
+pub use component_set::ComponentSet;
 pub(crate) use helpers::*;
 #[doc(hidden)]
 pub use registration::*;
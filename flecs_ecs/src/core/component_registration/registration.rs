@@ -64,7 +64,7 @@ pub(crate) fn try_register_component_named<'a, const COMPONENT_REGISTRATION: boo
 where
     T: ComponentId,
 {
-    let name = compact_str::format_compact!("{}\0", name);
+    let name = SmallCString::new(name);
     const NAMED: bool = true;
     internal_register_component::<NAMED, COMPONENT_REGISTRATION, T>(
         world,
@@ -200,6 +200,32 @@ pub(crate) fn external_register_component_data<const COMPONENT_REGISTRATION: boo
     id
 }
 
+/// Looks up a component that was already registered for `T`, for example by another crate
+/// or dynamic library with its own component index.
+///
+/// Components are registered with the full type name as symbol, so the same type resolves to the
+/// same entity regardless of the name it was registered with. If `allow_short_name` is set, a
+/// component that was registered outside of Rust with just the type name as symbol is matched too.
+fn lookup_component_symbol<T>(
+    world: *mut sys::ecs_world_t,
+    symbol: *const c_char,
+    allow_short_name: bool,
+) -> sys::ecs_entity_t {
+    let prev_scope = unsafe { sys::ecs_set_scope(world, 0) };
+    let mut id = unsafe { sys::ecs_lookup_symbol(world, symbol, false, false) };
+
+    if id == 0 && allow_short_name {
+        let only_type_name = crate::core::get_only_type_name::<T>();
+        let only_type_name = SmallCString::new(only_type_name);
+        id = unsafe {
+            sys::ecs_lookup_symbol(world, only_type_name.as_ptr() as *const _, false, false)
+        };
+    }
+
+    unsafe { sys::ecs_set_scope(world, prev_scope) };
+    id
+}
+
 /// registers the component with the world.
 #[inline(never)]
 pub(crate) fn register_componment_data_explicit<T, const ALLOCATE_TAG: bool>(
@@ -209,28 +235,15 @@ pub(crate) fn register_componment_data_explicit<T, const ALLOCATE_TAG: bool>(
 where
     T: ComponentId,
 {
-    let only_type_name = crate::core::get_only_type_name::<T>();
-    let only_type_name = compact_str::format_compact!("{}\0", only_type_name);
-
-    // If no name was provided first check if a type with the provided
-    // symbol was already registered.
-    let id = if name.is_null() {
-        let prev_scope = unsafe { sys::ecs_set_scope(world, 0) };
-        let id = unsafe {
-            sys::ecs_lookup_symbol(world, only_type_name.as_ptr() as *const _, false, false)
-        };
-        unsafe { sys::ecs_set_scope(world, prev_scope) };
-        id
-    } else {
-        0
-    };
+    let type_name = crate::core::type_name_cstring::<T>();
+    let type_name_ptr = type_name.as_ptr();
+
+    let id = lookup_component_symbol::<T>(world, type_name_ptr, name.is_null());
     if id != 0 {
+        rebind_hooks(world, id, || create_type_info::<T, ALLOCATE_TAG>().hooks);
         return id;
     }
 
-    let type_name = crate::core::type_name_cstring::<T>();
-    let type_name_ptr = type_name.as_ptr();
-
     let name = if name.is_null() { type_name_ptr } else { name };
 
     let entity_desc = create_entity_desc(name, type_name_ptr);
@@ -256,28 +269,15 @@ pub(crate) fn external_register_componment_data_explicit<T>(
     world: *mut sys::ecs_world_t,
     name: *const c_char,
 ) -> sys::ecs_entity_t {
-    let only_type_name = crate::core::get_only_type_name::<T>();
-    let only_type_name = compact_str::format_compact!("{}\0", only_type_name);
-
-    // If no name was provided first check if a type with the provided
-    // symbol was already registered.
-    let id = if name.is_null() {
-        let prev_scope = unsafe { sys::ecs_set_scope(world, 0) };
-        let id = unsafe {
-            sys::ecs_lookup_symbol(world, only_type_name.as_ptr() as *const _, false, false)
-        };
-        unsafe { sys::ecs_set_scope(world, prev_scope) };
-        id
-    } else {
-        0
-    };
+    let type_name = crate::core::type_name_cstring::<T>();
+    let type_name_ptr = type_name.as_ptr();
+
+    let id = lookup_component_symbol::<T>(world, type_name_ptr, name.is_null());
     if id != 0 {
+        rebind_hooks(world, id, || external_create_type_info::<T>().hooks);
         return id;
     }
 
-    let type_name = crate::core::type_name_cstring::<T>();
-    let type_name_ptr = type_name.as_ptr();
-
     let name = if name.is_null() { type_name_ptr } else { name };
 
     let entity_desc = create_entity_desc(name, type_name_ptr);
@@ -6,8 +6,6 @@ use core::{
 use crate::core::*;
 use crate::sys;
 
-use hashbrown::hash_map::Entry;
-
 #[cfg(feature = "flecs_query_rust_traits")]
 pub trait RustTrait {}
 
@@ -126,6 +124,11 @@ pub trait OnComponentRegistration {
 /// If the world doesn't, this implies the component was registered by a different world.
 /// In such a case, the component is registered with the present world using the pre-existing ID.
 /// If the ID is already known, the trait takes care of the component registration and checks for consistency in the input.
+///
+/// Each component type gets an index the first time it is used, which is stored in a static of
+/// the type and shared by all worlds. Worlds map the index to the id of the component, so once
+/// the component is registered, [`ComponentId::id()`] is an atomic load and an array lookup.
+/// Generic components are looked up by their [`TypeId`](core::any::TypeId) instead.
 #[diagnostic::on_unimplemented(
     message = "`{Self}` is not a flecs component.",
     label = "use `#[derive(Component)]` on `{Self}` to mark it as one."
@@ -140,108 +143,14 @@ pub trait ComponentId:
 
     /// attempts to register the component with the world. If it's already registered, it does nothing.
     #[doc(hidden)]
-    #[inline(never)]
+    #[inline(always)]
     fn __register_or_get_id<'a, const MANUAL_REGISTRATION_CHECK: bool>(
         world: impl WorldProvider<'a>,
     ) -> sys::ecs_entity_t {
-        if !Self::IS_GENERIC {
-            let index = Self::index() as usize;
-            let world = world.world();
-            let components_array = world.components_array();
-            let len = components_array.len();
-
-            if len > index {
-                if components_array[index] == 0 {
-                    if MANUAL_REGISTRATION_CHECK {
-                        #[cfg(feature = "flecs_manual_registration")]
-                        {
-                            ecs_assert!(
-                                false,
-                                FlecsErrorCode::InvalidOperation,
-                                "Component {} is not registered with the world before usage",
-                                Self::name()
-                            );
-                        }
-                    }
-
-                    let id = registration_traits::try_register_component::<
-                        MANUAL_REGISTRATION_CHECK,
-                        Self,
-                    >(world);
-
-                    components_array[index] = id;
-                    #[cfg(feature = "flecs_meta")]
-                    {
-                        world
-                            .components_map()
-                            .insert(core::any::TypeId::of::<Self>(), id);
-                    }
-
-                    Self::on_component_registration(world, Entity::new(id));
-
-                    return id;
-                }
-                components_array[index]
-            } else {
-                components_array.reserve(len);
-                let capacity = components_array.capacity();
-                unsafe {
-                    core::ptr::write_bytes(
-                        components_array.as_mut_ptr().add(len),
-                        0,
-                        capacity - len,
-                    );
-                    components_array.set_len(capacity);
-                }
-
-                let id = registration_traits::try_register_component::<
-                    MANUAL_REGISTRATION_CHECK,
-                    Self,
-                >(world);
-
-                components_array[index] = id;
-
-                #[cfg(feature = "flecs_meta")]
-                {
-                    world
-                        .components_map()
-                        .insert(core::any::TypeId::of::<Self>(), id);
-                }
-
-                Self::on_component_registration(world, Entity::new(id));
-
-                id
-            }
-        } else {
-            let world = world.world();
-            let components_map = world.components_map();
-
-            *match components_map.entry(core::any::TypeId::of::<Self>()) {
-                Entry::Occupied(entry) => entry.into_mut(),
-                Entry::Vacant(entry) => {
-                    if MANUAL_REGISTRATION_CHECK {
-                        #[cfg(feature = "flecs_manual_registration")]
-                        {
-                            ecs_assert!(
-                                false,
-                                FlecsErrorCode::InvalidOperation,
-                                "Component {} is not registered with the world before usage",
-                                Self::name()
-                            );
-                        }
-                    }
-
-                    let id = registration_traits::try_register_component::<
-                        MANUAL_REGISTRATION_CHECK,
-                        Self,
-                    >(world);
-
-                    let id = entry.insert(id);
-
-                    Self::on_component_registration(world, Entity::new(*id));
-                    id
-                }
-            }
+        let world = world.world();
+        match Self::__registered_id(world) {
+            Some(id) => id,
+            None => Self::__register_id::<MANUAL_REGISTRATION_CHECK>(world, None),
         }
     }
 
@@ -252,127 +161,93 @@ pub trait ComponentId:
         world: impl WorldProvider<'a>,
         name: &str,
     ) -> sys::ecs_entity_t {
+        let world = world.world();
+        match Self::__registered_id(world) {
+            Some(id) => id,
+            None => Self::__register_id::<MANUAL_REGISTRATION_CHECK>(world, Some(name)),
+        }
+    }
+
+    /// The id of the component in the world, if it is registered.
+    ///
+    /// Components that are not generic have an index that is assigned once per binary, which is
+    /// used to look up the id in an array of the world. Generic components can't have a static
+    /// of their own, so they are looked up by [`TypeId`](core::any::TypeId).
+    #[doc(hidden)]
+    #[inline(always)]
+    fn __registered_id(world: WorldRef) -> Option<sys::ecs_entity_t> {
+        let id = if !Self::IS_GENERIC {
+            world
+                .components_array()
+                .get(Self::index() as usize)
+                .copied()
+                .unwrap_or(0)
+        } else {
+            world
+                .components_map()
+                .get(&core::any::TypeId::of::<Self>())
+                .copied()
+                .unwrap_or(0)
+        };
+        (id != 0).then_some(id)
+    }
+
+    /// registers the component with the world, after [`Self::__registered_id`] didn't find it.
+    #[doc(hidden)]
+    #[cold]
+    #[inline(never)]
+    fn __register_id<const MANUAL_REGISTRATION_CHECK: bool>(
+        world: WorldRef,
+        name: Option<&str>,
+    ) -> sys::ecs_entity_t {
+        if MANUAL_REGISTRATION_CHECK {
+            #[cfg(feature = "flecs_manual_registration")]
+            {
+                ecs_assert!(
+                    false,
+                    FlecsErrorCode::InvalidOperation,
+                    "Component {} is not registered with the world before usage",
+                    Self::name()
+                );
+            }
+        }
+
+        let id = match name {
+            Some(name) => registration_traits::try_register_component_named::<
+                MANUAL_REGISTRATION_CHECK,
+                Self,
+            >(world, name),
+            None => registration_traits::try_register_component::<MANUAL_REGISTRATION_CHECK, Self>(
+                world,
+            ),
+        };
+
         if !Self::IS_GENERIC {
             let index = Self::index() as usize;
-            let world = world.world();
             let components_array = world.components_array();
             let len = components_array.len();
+            if len <= index {
+                components_array.resize((index + 1).max(len * 2), 0);
+            }
+            components_array[index] = id;
+        }
 
-            if len > index {
-                if components_array[index] == 0 {
-                    if MANUAL_REGISTRATION_CHECK {
-                        #[cfg(feature = "flecs_manual_registration")]
-                        {
-                            ecs_assert!(
-                                false,
-                                FlecsErrorCode::InvalidOperation,
-                                "Component {} is not registered with the world before usage",
-                                Self::name()
-                            );
-                        }
-                    }
-
-                    let id = registration_traits::try_register_component_named::<
-                        MANUAL_REGISTRATION_CHECK,
-                        Self,
-                    >(world, name);
-
-                    components_array[index] = id;
-
-                    #[cfg(feature = "flecs_meta")]
-                    {
-                        world
-                            .components_map()
-                            .insert(core::any::TypeId::of::<Self>(), id);
-                    }
-
-                    Self::on_component_registration(world, Entity::new(id));
-
-                    return id;
-                }
-                components_array[index]
-            } else {
-                components_array.reserve(len);
-                let capacity = components_array.capacity();
-                unsafe {
-                    core::ptr::write_bytes(
-                        components_array.as_mut_ptr().add(len),
-                        0,
-                        capacity - len,
-                    );
-                    components_array.set_len(capacity);
-                }
-                let id = registration_traits::try_register_component_named::<
-                    MANUAL_REGISTRATION_CHECK,
-                    Self,
-                >(world, name);
-
-                components_array[index] = id;
-
-                #[cfg(feature = "flecs_meta")]
-                {
-                    world
-                        .components_map()
-                        .insert(core::any::TypeId::of::<Self>(), id);
-                }
+        if Self::IS_GENERIC || cfg!(feature = "flecs_meta") {
+            world
+                .components_map()
+                .insert(core::any::TypeId::of::<Self>(), id);
+        }
 
-                Self::on_component_registration(world, Entity::new(id));
+        Self::on_component_registration(world, Entity::new(id));
 
-                id
-            }
-        } else {
-            let world = world.world();
-            let components_map = world.components_map();
-
-            *match components_map.entry(core::any::TypeId::of::<Self>()) {
-                Entry::Occupied(entry) => entry.into_mut(),
-                Entry::Vacant(entry) => {
-                    if MANUAL_REGISTRATION_CHECK {
-                        #[cfg(feature = "flecs_manual_registration")]
-                        {
-                            ecs_assert!(
-                                false,
-                                FlecsErrorCode::InvalidOperation,
-                                "Component {} is not registered with the world before usage",
-                                Self::name()
-                            );
-                        }
-                    }
-
-                    let id = registration_traits::try_register_component_named::<
-                        MANUAL_REGISTRATION_CHECK,
-                        Self,
-                    >(world, name);
-
-                    let id = entry.insert(id);
-
-                    Self::on_component_registration(world, Entity::new(*id));
-                    id
-                }
-            }
-        }
+        id
     }
 
     /// checks if the component is registered with a world.
     #[allow(clippy::not_unsafe_ptr_arg_deref)]
     #[inline(always)]
     fn is_registered_with_world<'a>(world: impl WorldProvider<'a>) -> bool {
-        if !Self::IS_GENERIC {
-            let index = Self::index();
-            let world = world.world();
-            let components_array = world.components_array();
-            let len = components_array.len();
-
-            if len > index as usize {
-                components_array[index as usize] != 0
-            } else {
-                false
-            }
-        } else {
-            let world = world.world();
-            let components_map = world.components_map();
-            components_map.contains_key(&core::any::TypeId::of::<Self>())
-        }
+        Self::__registered_id(world.world()).is_some()
     }
 
     /// returns the component id registered with a particular world. If the component is not registered, it will register it.
@@ -410,11 +285,11 @@ pub trait ComponentId:
         if hooks.is_null() {
             let mut hooks = Default::default();
             register_ctor_lifecycle_actions::<Self>(&mut hooks);
-            unsafe { flecs_ecs_sys::ecs_set_hooks_id(world_ptr, id, &hooks) }
+            set_hooks(world_ptr, id, &hooks);
         } else {
             let hooks = &mut unsafe { *hooks };
             register_ctor_lifecycle_actions::<Self>(hooks);
-            unsafe { flecs_ecs_sys::ecs_set_hooks_id(world_ptr, id, hooks) }
+            set_hooks(world_ptr, id, hooks);
         }
     }
 
@@ -433,11 +308,11 @@ pub trait ComponentId:
         if hooks.is_null() {
             let mut hooks = Default::default();
             register_copy_lifecycle_action::<Self>(&mut hooks);
-            unsafe { flecs_ecs_sys::ecs_set_hooks_id(world_ptr, id, &hooks) }
+            set_hooks(world_ptr, id, &hooks);
         } else {
             let hooks = &mut unsafe { *hooks };
             register_copy_lifecycle_action::<Self>(hooks);
-            unsafe { flecs_ecs_sys::ecs_set_hooks_id(world_ptr, id, hooks) }
+            set_hooks(world_ptr, id, hooks);
         }
     }
 
@@ -451,6 +326,16 @@ pub trait ComponentId:
     #[doc(hidden)]
     #[inline(always)]
     fn get_or_init_index(id: &AtomicU32) -> u32 {
+        let index = id.load(Ordering::Acquire);
+        if index != u32::MAX {
+            return index;
+        }
+        Self::init_index(id)
+    }
+
+    #[doc(hidden)]
+    #[cold]
+    fn init_index(id: &AtomicU32) -> u32 {
         match id.fetch_update(Ordering::Acquire, Ordering::Relaxed, |v| {
             if v != u32::MAX {
                 None
@@ -88,6 +88,17 @@ impl<'a, T: ComponentId + DataComponent> CachedRef<'a, T> {
         .map(callback)
     }
 
+    /// Get component from ref.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entity no longer has the component.
+    ///
+    /// # See also
+    ///
+    /// * [`CachedRef::try_get()`]
+    /// * C++ API: `ref::get`
+    #[doc(alias = "ref::get")]
     pub fn get<R>(&mut self, callback: impl FnOnce(&mut T) -> R) -> R {
         let mut ref_comp = NonNull::new(unsafe {
             sys::ecs_ref_get_id(
@@ -102,15 +113,31 @@ impl<'a, T: ComponentId + DataComponent> CachedRef<'a, T> {
     }
 
     /// Return entity associated with reference.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `ref::entity`
+    #[doc(alias = "ref::entity")]
     pub fn entity(&self) -> EntityView<'a> {
         EntityView::new_from(self.world, self.component_ref.entity)
     }
 
     /// Return component associated with reference.
-    fn component(&self) -> IdView<'a> {
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `ref::component`
+    #[doc(alias = "ref::component")]
+    pub fn component(&self) -> IdView<'a> {
         IdView::new_from_id(self.world, self.component_ref.id)
     }
 
+    /// Test if the entity still has the referenced component.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `ref::has`
+    #[doc(alias = "ref::has")]
     pub fn has(&mut self) -> bool {
         !unsafe {
             sys::ecs_ref_get_id(
@@ -59,7 +59,7 @@ impl<'a, T: ComponentId> Component<'a, T> {
     ///
     /// * C++ API: `component::component`
     #[doc(alias = "component::component")]
-    #[inline(never)]
+    #[inline]
     pub fn new(world: impl WorldProvider<'a>) -> Self {
         let world = world.world();
         let id = T::__register_or_get_id::<false>(world);
@@ -127,7 +127,6 @@ impl<'a, T> Component<'a, T> {
     #[doc(alias = "component::component")]
     #[cfg(feature = "flecs_meta")]
     pub fn new_named_id(world: impl WorldProvider<'a>, id: FetchedId<T>, name: &str) -> Self {
-        let _name = compact_str::format_compact!("{}\0", name);
         let world = world.world();
         let entity = world.entity_from_id(id.id());
         entity.get_name().map_or_else(
@@ -157,6 +156,27 @@ impl<'a, T> Component<'a, T> {
         self.base.entity
     }
 
+    /// Store the component in sparse storage, see [`flecs::Sparse`].
+    ///
+    /// This has to be done before the component is used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// let world = World::new();
+    /// world.component::<Health>().sparse();
+    ///
+    /// assert!(world.component::<Health>().has::<flecs::Sparse>());
+    /// ```
+    pub fn sparse(self) -> Self {
+        self.entity().add_trait::<flecs::Sparse>();
+        self
+    }
+
     /// Get the binding context for the component.
     ///
     /// # Arguments
@@ -205,7 +225,7 @@ impl<'a, T> Component<'a, T> {
     unsafe extern "C-unwind" fn binding_ctx_drop(ptr: *mut c_void) {
         let ptr_struct: *mut ComponentBindingCtx = ptr as *mut ComponentBindingCtx;
         unsafe {
-            ptr::drop_in_place(ptr_struct);
+            drop(Box::from_raw(ptr_struct));
         }
     }
 
@@ -234,7 +254,7 @@ impl<'a, T> Component<'a, T> {
         binding_ctx.on_add = Some(static_ref as *mut _ as *mut c_void);
         binding_ctx.free_on_add = Some(Self::on_add_drop::<Func>);
         type_hooks.on_add = Some(Self::run_add::<Func>);
-        unsafe { sys::ecs_set_hooks_id(self.world.world_ptr_mut(), *self.id, &type_hooks) };
+        set_hooks(self.world.world_ptr_mut(), *self.id, &type_hooks);
         self
     }
 
@@ -263,7 +283,7 @@ impl<'a, T> Component<'a, T> {
         binding_ctx.on_remove = Some(static_ref as *mut _ as *mut c_void);
         binding_ctx.free_on_remove = Some(Self::on_remove_drop::<Func>);
         type_hooks.on_remove = Some(Self::run_remove::<Func>);
-        unsafe { sys::ecs_set_hooks_id(self.world.world_ptr_mut(), *self.id, &type_hooks) };
+        set_hooks(self.world.world_ptr_mut(), *self.id, &type_hooks);
         self
     }
 
@@ -292,7 +312,69 @@ impl<'a, T> Component<'a, T> {
         binding_ctx.on_set = Some(static_ref as *mut _ as *mut c_void);
         binding_ctx.free_on_set = Some(Self::on_set_drop::<Func>);
         type_hooks.on_set = Some(Self::run_set::<Func>);
-        unsafe { sys::ecs_set_hooks_id(self.world.world_ptr_mut(), *self.id, &type_hooks) };
+        set_hooks(self.world.world_ptr_mut(), *self.id, &type_hooks);
+        self
+    }
+
+    /// Register a copy hook, which creates the copy of a component.
+    ///
+    /// Copies are made when a prefab is instantiated with [`flecs::IsA`], when entities are
+    /// duplicated and when an inherited component is overridden. Without a copy hook, components
+    /// are copied with [`Clone::clone`] when they implement it. Components without `Clone` are
+    /// copied bitwise when they don't need to be dropped, and copying them panics when they do.
+    /// The hook replaces either of those.
+    ///
+    /// This has to be done before the component is used.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Inventory {
+    ///     items: Vec<String>,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .component::<Inventory>()
+    ///     .on_copy(|inventory| Inventory {
+    ///         items: inventory.items.clone(),
+    ///     })
+    ///     .add_trait::<(flecs::OnInstantiate, flecs::Override)>();
+    ///
+    /// let chest = world.prefab().set(Inventory {
+    ///     items: vec!["sword".to_string()],
+    /// });
+    ///
+    /// let e = world.entity().is_a_id(chest);
+    /// e.get::<&mut Inventory>(|inventory| inventory.items.push("shield".to_string()));
+    ///
+    /// chest.get::<&Inventory>(|inventory| assert_eq!(inventory.items.len(), 1));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`ComponentId::register_clone_hook()`]
+    pub fn on_copy<Func>(&mut self, func: Func) -> &mut Self
+    where
+        Func: FnMut(&T) -> T + 'static,
+    {
+        let mut type_hooks: sys::ecs_type_hooks_t = self.get_hooks();
+
+        let binding_ctx = Self::get_binding_context(&mut type_hooks);
+        if let (Some(on_copy), Some(free_on_copy)) = (binding_ctx.on_copy, binding_ctx.free_on_copy)
+        {
+            unsafe { free_on_copy(on_copy) };
+        }
+        let boxed_func = Box::new(func);
+        let static_ref = Box::leak(boxed_func);
+        binding_ctx.on_copy = Some(static_ref as *mut _ as *mut c_void);
+        binding_ctx.free_on_copy = Some(Self::on_copy_drop::<Func>);
+        type_hooks.copy = Some(Self::run_copy::<Func>);
+        type_hooks.copy_ctor = Some(Self::run_copy_ctor::<Func>);
+        set_hooks(self.world.world_ptr_mut(), *self.id, &type_hooks);
         self
     }
 
@@ -329,6 +411,17 @@ impl<'a, T> Component<'a, T> {
         }
     }
 
+    /// Function to free the copy hook.
+    unsafe extern "C-unwind" fn on_copy_drop<Func>(func: *mut c_void)
+    where
+        Func: FnMut(&T) -> T + 'static,
+    {
+        let ptr_func: *mut Func = func as *mut Func;
+        unsafe {
+            ptr::drop_in_place(ptr_func);
+        }
+    }
+
     /// Function to run the on add hook.
     unsafe extern "C-unwind" fn run_add<Func>(iter: *mut sys::ecs_iter_t)
     where
@@ -377,6 +470,46 @@ impl<'a, T> Component<'a, T> {
             on_remove(entity, &mut *component);
         }
     }
+
+    /// Function to run the copy hook, which assigns the copies to initialized components.
+    unsafe extern "C-unwind" fn run_copy<Func>(
+        dst_ptr: *mut c_void,
+        src_ptr: *const c_void,
+        count: i32,
+        type_info: *const sys::ecs_type_info_t,
+    ) where
+        Func: FnMut(&T) -> T + 'static,
+    {
+        unsafe {
+            let ctx: *mut ComponentBindingCtx = (*type_info).hooks.binding_ctx as *mut _;
+            let on_copy = &mut *((*ctx).on_copy.unwrap() as *mut Func);
+            let dst_arr = dst_ptr as *mut T;
+            let src_arr = src_ptr as *const T;
+            for i in 0..count as usize {
+                *dst_arr.add(i) = on_copy(&*src_arr.add(i));
+            }
+        }
+    }
+
+    /// Function to run the copy hook, which writes the copies to uninitialized memory.
+    unsafe extern "C-unwind" fn run_copy_ctor<Func>(
+        dst_ptr: *mut c_void,
+        src_ptr: *const c_void,
+        count: i32,
+        type_info: *const sys::ecs_type_info_t,
+    ) where
+        Func: FnMut(&T) -> T + 'static,
+    {
+        unsafe {
+            let ctx: *mut ComponentBindingCtx = (*type_info).hooks.binding_ctx as *mut _;
+            let on_copy = &mut *((*ctx).on_copy.unwrap() as *mut Func);
+            let dst_arr = dst_ptr as *mut T;
+            let src_arr = src_ptr as *const T;
+            for i in 0..count as usize {
+                ptr::write(dst_arr.add(i), on_copy(&*src_arr.add(i)));
+            }
+        }
+    }
 }
 
 mod eq_operations {
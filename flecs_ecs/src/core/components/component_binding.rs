@@ -1,20 +1,24 @@
 #![doc(hidden)]
 use core::ffi::c_void;
 
+use crate::core::utility::is_thread_panicking;
+
 type EcsCtxFreeT = unsafe extern "C-unwind" fn(*mut c_void);
 
 pub(crate) struct ComponentBindingCtx {
     pub(crate) on_add: Option<*mut c_void>,
     pub(crate) on_remove: Option<*mut c_void>,
     pub(crate) on_set: Option<*mut c_void>,
+    pub(crate) on_copy: Option<*mut c_void>,
     pub(crate) free_on_add: Option<EcsCtxFreeT>,
     pub(crate) free_on_remove: Option<EcsCtxFreeT>,
     pub(crate) free_on_set: Option<EcsCtxFreeT>,
+    pub(crate) free_on_copy: Option<EcsCtxFreeT>,
 }
 
 impl Drop for ComponentBindingCtx {
     fn drop(&mut self) {
-        if std::thread::panicking() {
+        if is_thread_panicking() {
             return;
         }
 
@@ -33,6 +37,11 @@ impl Drop for ComponentBindingCtx {
                 unsafe { free_on_set(on_set) };
             }
         }
+        if let Some(on_copy) = self.on_copy {
+            if let Some(free_on_copy) = self.free_on_copy {
+                unsafe { free_on_copy(on_copy) };
+            }
+        }
     }
 }
 
@@ -43,9 +52,11 @@ impl Default for ComponentBindingCtx {
             on_add: None,
             on_remove: None,
             on_set: None,
+            on_copy: None,
             free_on_add: None,
             free_on_remove: None,
             free_on_set: None,
+            free_on_copy: None,
         }
     }
 }
@@ -54,17 +65,21 @@ impl ComponentBindingCtx {
         on_add: Option<*mut c_void>,
         on_remove: Option<*mut c_void>,
         on_set: Option<*mut c_void>,
+        on_copy: Option<*mut c_void>,
         free_on_add: Option<EcsCtxFreeT>,
         free_on_remove: Option<EcsCtxFreeT>,
         free_on_set: Option<EcsCtxFreeT>,
+        free_on_copy: Option<EcsCtxFreeT>,
     ) -> Self {
         Self {
             on_add,
             on_remove,
             on_set,
+            on_copy,
             free_on_add,
             free_on_remove,
             free_on_set,
+            free_on_copy,
         }
     }
 }
@@ -1,9 +1,11 @@
 use core::{
+    alloc::Layout,
     fmt::{Debug, Display},
     ops::Deref,
 };
 
 use crate::core::*;
+use crate::sys;
 
 /// Untyped component class.
 #[derive(Clone, Copy)]
@@ -93,6 +95,84 @@ impl<'a> UntypedComponent<'a> {
     pub fn as_entity(&self) -> EntityView<'a> {
         self.entity
     }
+
+    /// Returns true if the component is a tag, which means it has no data.
+    ///
+    /// Zero sized types are registered as tags, so they don't take up storage in tables.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// let world = World::new();
+    ///
+    /// assert!(world.component::<Enemy>().is_tag());
+    /// assert!(!world.component::<Health>().is_tag());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`UntypedComponent::size()`]
+    pub fn is_tag(&self) -> bool {
+        unsafe { sys::ecs_get_typeid(self.world_ptr(), *self.entity.id) == 0 }
+    }
+
+    /// Returns the size of the component in bytes, which is 0 for tags.
+    ///
+    /// # See also
+    ///
+    /// * [`UntypedComponent::is_tag()`]
+    pub fn size(&self) -> usize {
+        let type_info = unsafe { sys::ecs_get_type_info(self.world_ptr(), *self.entity.id) };
+        if type_info.is_null() {
+            0
+        } else {
+            unsafe { (*type_info).size as usize }
+        }
+    }
+
+    /// Returns the size and alignment with which values of the component are stored.
+    ///
+    /// For components registered from Rust this is the layout of the type, including for
+    /// over-aligned types such as `#[repr(align(64))]` SIMD types. Components can be aligned to
+    /// at most 128 bytes, or 16 bytes with the `flecs_use_os_alloc` feature. Tags have a size of
+    /// 0 and an alignment of 1.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// # use core::alloc::Layout;
+    /// #[derive(Component)]
+    /// #[repr(align(64))]
+    /// struct Lanes([f32; 16]);
+    ///
+    /// let world = World::new();
+    ///
+    /// let layout = world.component::<Lanes>().layout();
+    /// assert_eq!(layout, Layout::new::<Lanes>());
+    /// assert_eq!(layout.align(), 64);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`UntypedComponent::size()`]
+    pub fn layout(&self) -> Layout {
+        let type_info = unsafe { sys::ecs_get_type_info(self.world_ptr(), *self.entity.id) };
+        if type_info.is_null() {
+            return Layout::new::<()>();
+        }
+
+        let (size, alignment) = unsafe { ((*type_info).size, (*type_info).alignment) };
+        Layout::from_size_align(size as usize, alignment as usize)
+            .expect("flecs stores components with a valid layout")
+    }
 }
 
 #[cfg(feature = "flecs_meta")]
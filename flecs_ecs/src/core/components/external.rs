@@ -0,0 +1,217 @@
+//! Components of which the data is stored outside of flecs, see [`External`].
+
+use core::{
+    fmt::Debug,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+};
+
+use flecs_ecs_derive::Component;
+
+/// A component that refers to data that is stored outside of flecs, such as the bodies of a
+/// physics library.
+///
+/// Only the key of the value is stored in flecs, the value itself is found with a resolve
+/// function whenever it is accessed. This lets entities be queried and iterated together with
+/// the external data without copying it in and out of flecs every frame. The value is accessed
+/// through [`Deref`] and [`DerefMut`].
+///
+/// Each entity refers to its own value:
+///
+/// * [`External::from_ptr()`] stores a pointer to a value that doesn't move.
+/// * [`External::new()`] stores a key, such as an index into an external array, which is
+///   resolved to a pointer on every access. This keeps working when the array is reallocated.
+///
+/// Removing the component doesn't free the external value. Values that aren't laid out as `T`,
+/// or that can only be read and written through functions of the external library, are accessed
+/// with [`ExternalAccessor`] instead.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// struct Body {
+///     velocity: f32,
+/// }
+///
+/// // the storage of a physics library
+/// let mut bodies = vec![Body { velocity: 1.0 }, Body { velocity: 2.0 }];
+///
+/// let world = World::new();
+/// for body in &mut bodies {
+///     // SAFETY: the bodies don't move and outlive the world
+///     world.entity().set(unsafe { External::from_ptr(body as *mut Body) });
+/// }
+///
+/// world
+///     .new_query::<&mut External<Body>>()
+///     .each(|body| body.velocity *= 2.0);
+///
+/// drop(world);
+/// assert_eq!(bodies[1].velocity, 4.0);
+/// ```
+#[derive(Component)]
+pub struct External<T: Send + Sync + 'static> {
+    key: usize,
+    resolve: fn(usize) -> *mut T,
+    _marker: PhantomData<T>,
+}
+
+impl<T: Send + Sync + 'static> External<T> {
+    /// Refer to an external value by a key that is resolved on every access.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the value, such as its index in an external array.
+    /// * `resolve` - Returns a pointer to the value of a key.
+    ///
+    /// # Safety
+    ///
+    /// For as long as the component exists, `resolve(key)` must return a non-null pointer to a
+    /// valid `T` that isn't accessed through anything but this component while it is borrowed.
+    /// Two components must not resolve to the same value.
+    pub unsafe fn new(key: usize, resolve: fn(usize) -> *mut T) -> Self {
+        Self {
+            key,
+            resolve,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Refer to an external value that doesn't move.
+    ///
+    /// # Safety
+    ///
+    /// Same as [`External::new()`]: `ptr` must point to a valid `T` for as long as the component
+    /// exists, and the value must not be accessed otherwise while it is borrowed.
+    pub unsafe fn from_ptr(ptr: *mut T) -> Self {
+        unsafe { Self::new(ptr as usize, |key| key as *mut T) }
+    }
+
+    /// The key of the value, which is its address for [`External::from_ptr()`].
+    pub fn key(&self) -> usize {
+        self.key
+    }
+
+    fn ptr(&self) -> *mut T {
+        let ptr = (self.resolve)(self.key);
+        assert!(!ptr.is_null(), "external component resolved to null");
+        ptr
+    }
+}
+
+impl<T: Send + Sync + 'static> Deref for External<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: guaranteed by the constructors
+        unsafe { &*self.ptr() }
+    }
+}
+
+impl<T: Send + Sync + 'static> DerefMut for External<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: guaranteed by the constructors
+        unsafe { &mut *self.ptr() }
+    }
+}
+
+impl<T: Debug + Send + Sync + 'static> Debug for External<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_tuple("External").field(&**self).finish()
+    }
+}
+
+/// A component that reads and writes a value stored outside of flecs through a getter and a
+/// setter, for external data that can't be borrowed in place as a `T`.
+///
+/// Only the key of the value is stored in flecs. [`ExternalAccessor::get()`] calls the getter
+/// with the key and returns a copy of the value, [`ExternalAccessor::set()`] passes a new value to
+/// the setter, and [`ExternalAccessor::update()`] does both around a closure that modifies the
+/// value. The value is never cached, so changes made by the external library are seen on the next
+/// access.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+/// use std::sync::Mutex;
+///
+/// // a physics library that only exposes its bodies through functions
+/// static VELOCITIES: Mutex<Vec<f32>> = Mutex::new(Vec::new());
+///
+/// fn get_velocity(body: usize) -> f32 {
+///     VELOCITIES.lock().unwrap()[body]
+/// }
+///
+/// fn set_velocity(body: usize, velocity: f32) {
+///     VELOCITIES.lock().unwrap()[body] = velocity;
+/// }
+///
+/// *VELOCITIES.lock().unwrap() = vec![1.0, 2.0];
+///
+/// let world = World::new();
+/// for body in 0..2 {
+///     world
+///         .entity()
+///         .set(ExternalAccessor::new(body, get_velocity, set_velocity));
+/// }
+///
+/// world
+///     .new_query::<&mut ExternalAccessor<f32>>()
+///     .each(|velocity| velocity.update(|v| *v *= 2.0));
+///
+/// assert_eq!(*VELOCITIES.lock().unwrap(), [2.0, 4.0]);
+/// ```
+#[derive(Component)]
+pub struct ExternalAccessor<T: 'static> {
+    key: usize,
+    get: fn(usize) -> T,
+    set: fn(usize, T),
+}
+
+impl<T: 'static> ExternalAccessor<T> {
+    /// Refer to an external value by a key that is passed to the getter and setter.
+    ///
+    /// # Arguments
+    ///
+    /// * `key` - The key of the value, such as the handle of a body in a physics library.
+    /// * `get` - Returns the value of a key.
+    /// * `set` - Replaces the value of a key.
+    pub fn new(key: usize, get: fn(usize) -> T, set: fn(usize, T)) -> Self {
+        Self { key, get, set }
+    }
+
+    /// The key of the value.
+    pub fn key(&self) -> usize {
+        self.key
+    }
+
+    /// Read the value with the getter.
+    pub fn get(&self) -> T {
+        (self.get)(self.key)
+    }
+
+    /// Write the value with the setter.
+    pub fn set(&mut self, value: T) {
+        (self.set)(self.key, value);
+    }
+
+    /// Read the value, modify it with `func` and write it back.
+    pub fn update<R>(&mut self, func: impl FnOnce(&mut T) -> R) -> R {
+        let mut value = self.get();
+        let result = func(&mut value);
+        self.set(value);
+        result
+    }
+}
+
+impl<T: Debug + 'static> Debug for ExternalAccessor<T> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        f.debug_struct("ExternalAccessor")
+            .field("key", &self.key)
+            .field("value", &self.get())
+            .finish()
+    }
+}
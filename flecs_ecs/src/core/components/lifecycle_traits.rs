@@ -45,7 +45,12 @@
 // Note: C does the same, where the user needs to opt in for non trivial types. We can do the same.
 // Note2: zerobit pattern
 
-use core::{ffi::c_void, mem::MaybeUninit, ptr};
+use core::{
+    ffi::c_void,
+    mem::MaybeUninit,
+    ptr,
+    sync::atomic::{AtomicPtr, Ordering},
+};
 
 use crate::core::*;
 use crate::sys;
@@ -66,15 +71,92 @@ pub(crate) unsafe extern "C-unwind" fn register_panic_hooks_free_ctx(ctx: *mut c
     let _box = unsafe { Box::from_raw(ctx as *mut RegistersPanicHooks) };
 }
 
+/// The lifecycle context of types with the hooks of [`register_lifecycle_actions`].
+///
+/// Flecs expects the hooks of a type to leave a valid value behind when moving out of it, and
+/// constructs values before assigning to them. Neither is possible for every Rust type, so the
+/// hooks track the value that was last left vacant, which the next hook that overwrites or
+/// destructs it doesn't drop:
+///
+/// * `move_ctor` moves an entity to another table, after which `move_dtor` moves the last entity
+///   of the old table into the vacant row.
+/// * the ctor of types without [`Default`] zeroes the value, which is assigned to right after,
+///   such as when a component is overridden.
+///
+/// `vacant` is a single slot shared by all values of the type in a world, not one per table. This
+/// relies on the hook that leaves a value vacant being directly followed by the hook that
+/// overwrites it, such as `move_dtor` after `move_ctor`, and on structural changes only happening
+/// on the main thread, as other threads defer them. A hook of the same type running in between
+/// would replace the slot and leave the first value to be dropped.
+#[derive(Default)]
+pub(crate) struct LifecycleCtx {
+    vacant: AtomicPtr<c_void>,
+}
+
+impl LifecycleCtx {
+    fn take_vacant(&self, ptr: *mut c_void) -> bool {
+        let is_vacant = self.vacant.load(Ordering::Relaxed) == ptr;
+        if is_vacant {
+            self.vacant.store(ptr::null_mut(), Ordering::Relaxed);
+        }
+        is_vacant
+    }
+}
+
+pub(crate) unsafe extern "C-unwind" fn lifecycle_ctx_free(ctx: *mut c_void) {
+    let _box = unsafe { Box::from_raw(ctx as *mut LifecycleCtx) };
+}
+
+/// Forget the vacant value of a type, after moving out of memory that isn't a component value.
+pub(crate) fn forget_vacant(type_info: *const sys::ecs_type_info_t) {
+    if let Some(ctx) = lifecycle_ctx(type_info) {
+        ctx.vacant.store(ptr::null_mut(), Ordering::Relaxed);
+    }
+}
+
+fn lifecycle_ctx<'a>(type_info: *const sys::ecs_type_info_t) -> Option<&'a LifecycleCtx> {
+    if type_info.is_null() {
+        return None;
+    }
+    let hooks = unsafe { &(*type_info).hooks };
+    let is_rust_ctx = hooks.lifecycle_ctx_free.is_some_and(|free| {
+        core::ptr::fn_addr_eq(
+            free,
+            lifecycle_ctx_free as unsafe extern "C-unwind" fn(*mut c_void),
+        )
+    });
+    if is_rust_ctx && !hooks.lifecycle_ctx.is_null() {
+        Some(unsafe { &*(hooks.lifecycle_ctx as *const LifecycleCtx) })
+    } else {
+        None
+    }
+}
+
+/// Whether the value at `ptr` was left vacant, in which case it must not be dropped.
+fn take_vacant(type_info: *const sys::ecs_type_info_t, ptr: *mut c_void) -> bool {
+    lifecycle_ctx(type_info).is_some_and(|ctx| ctx.take_vacant(ptr))
+}
+
+fn set_vacant(type_info: *const sys::ecs_type_info_t, ptr: *mut c_void) {
+    if let Some(ctx) = lifecycle_ctx(type_info) {
+        ctx.vacant.store(ptr, Ordering::Relaxed);
+    }
+}
+
 #[allow(clippy::not_unsafe_ptr_arg_deref)]
 pub fn register_lifecycle_actions<T>(type_hooks: &mut sys::ecs_type_hooks_t) {
     //type_hooks.ctor = Some(ctor::<T>);
     type_hooks.dtor = Some(dtor::<T>);
-    type_hooks.move_dtor = Some(move_dtor::<T>); //same implementation as ctor_move_dtor
+    type_hooks.move_dtor = Some(move_dtor::<T>);
 
-    //type_hooks.move_ctor = Some(move_ctor::<T>);
+    type_hooks.move_ctor = Some(move_ctor::<T>);
     type_hooks.ctor_move_dtor = Some(ctor_move_dtor::<T>);
 
+    if type_hooks.lifecycle_ctx.is_null() {
+        type_hooks.lifecycle_ctx = Box::into_raw(Box::<LifecycleCtx>::default()) as *mut c_void;
+        type_hooks.lifecycle_ctx_free = Some(lifecycle_ctx_free);
+    }
+
     //TODO we could potentially add an autoamtic check if the type is unmoveable to add
     //a sparse component tag
 }
@@ -83,6 +165,12 @@ pub fn register_ctor_lifecycle_actions<T: Default>(type_hooks: &mut sys::ecs_typ
     type_hooks.ctor = Some(ctor::<T>);
 }
 
+/// Register the ctor of types that need drop but don't implement [`Default`], which zeroes the
+/// value and leaves it vacant, see [`LifecycleCtx`].
+pub fn register_ctor_zeroed_lifecycle_actions<T>(type_hooks: &mut sys::ecs_type_hooks_t) {
+    type_hooks.ctor = Some(zeroed_ctor);
+}
+
 pub fn register_ctor_panic_lifecycle_actions<T>(type_hooks: &mut sys::ecs_type_hooks_t) {
     type_hooks.ctor = Some(panic_ctor::<T>);
 }
@@ -128,6 +216,25 @@ extern "C-unwind" fn ctor<T: Default>(
     }
 }
 
+/// Zero the memory of a type without [`Default`], which is assigned to right after.
+///
+/// The value is left vacant, so assigning to it or destructing it doesn't drop it. The ctor isn't
+/// generic, so [`has_default_hook()`](crate::core::has_default_hook) can tell it apart from the
+/// ctor of [`Default`] types.
+pub(crate) extern "C-unwind" fn zeroed_ctor(
+    ptr: *mut c_void,
+    count: i32,
+    type_info: *const sys::ecs_type_info_t,
+) {
+    ecs_assert!(!type_info.is_null(), FlecsErrorCode::InternalError);
+
+    let size = unsafe { (*type_info).size } as usize;
+    unsafe { ptr::write_bytes(ptr as *mut u8, 0, size * count as usize) };
+    if count == 1 {
+        set_vacant(type_info, ptr);
+    }
+}
+
 /// Runs the destructor for the type.
 ///
 /// # Arguments
@@ -153,7 +260,9 @@ extern "C-unwind" fn dtor<T>(
     for i in 0..count as isize {
         unsafe {
             let item = arr.offset(i);
-            ptr::drop_in_place(item);
+            if !take_vacant(_type_info, item as *mut c_void) {
+                ptr::drop_in_place(item);
+            }
         }
     }
 }
@@ -182,7 +291,9 @@ extern "C-unwind" fn copy<T: Clone>(
         unsafe {
             let src_value = &*(src_arr.offset(i)); //get value of src
             let dst_value = dst_arr.offset(i); // get ptr to dest
-            core::ptr::drop_in_place(dst_value); //calls destructor
+            if !take_vacant(_type_info, dst_value as *mut c_void) {
+                core::ptr::drop_in_place(dst_value); //calls destructor
+            }
             core::ptr::write(dst_value, src_value.clone()); //overwrite the memory of dest with new value
         }
     }
@@ -265,7 +376,9 @@ extern "C-unwind" fn move_dtor<T>(
             let src_value = src_arr.offset(i); //get value of src
             let dst_value = dst_arr.offset(i); // get ptr to dest
 
-            core::ptr::drop_in_place(dst_value); //calls destructor on dest
+            if !take_vacant(_type_info, dst_value as *mut c_void) {
+                core::ptr::drop_in_place(dst_value); //calls destructor on dest
+            }
 
             //memcpy the bytes of src to dest
             //src value and dest value point to the same thing
@@ -299,6 +412,11 @@ extern "C-unwind" fn move_ctor<T>(
             core::ptr::copy_nonoverlapping(src_arr.offset(i), dst_arr.offset(i), 1);
         }
     }
+
+    // src is left vacant, so move_dtor doesn't drop it when it's overwritten
+    if count == 1 {
+        set_vacant(_type_info, src_ptr);
+    }
 }
 
 extern "C-unwind" fn ctor_move_dtor<T>(
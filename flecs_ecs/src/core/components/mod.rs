@@ -4,6 +4,7 @@ mod cached_ref;
 mod component;
 mod component_binding;
 mod component_untyped;
+mod external;
 #[doc(hidden)]
 pub mod lifecycle_traits;
 
@@ -11,5 +12,6 @@ pub use cached_ref::*;
 pub use component::*;
 pub(crate) use component_binding::*;
 pub use component_untyped::*;
+pub use external::{External, ExternalAccessor};
 #[doc(hidden)]
 pub use lifecycle_traits::*;
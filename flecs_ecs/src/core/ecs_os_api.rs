@@ -8,21 +8,30 @@
 #[cfg(feature = "std")]
 extern crate std;
 
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+mod wasm;
+
 extern crate alloc;
+#[cfg(feature = "std")]
 use alloc::{boxed::Box, vec::Vec};
 
+#[cfg(feature = "std")]
 use std::sync::LazyLock;
+#[cfg(feature = "std")]
 use std::sync::Mutex;
 
+#[cfg(feature = "std")]
 struct OsApiHook(Box<dyn FnOnce(&mut flecs_ecs::sys::ecs_os_api_t)>);
 
 /// SAFETY: the OS API hooks are only ever used once, from behind a [`Mutex`]
+#[cfg(feature = "std")]
 unsafe impl Send for OsApiHook {}
 
 /// List of hooks to run during initialization of the Flecs OS API from Rust.
 ///
 /// Run automatically, once and only once, when the first [`super::World`]
 /// is created, or [`ensure_initialized`] is called directly.
+#[cfg(feature = "std")]
 static OS_API_HOOKS: LazyLock<Mutex<Option<Vec<OsApiHook>>>> =
     LazyLock::new(|| Mutex::new(Some(Default::default())));
 
@@ -34,6 +43,7 @@ static OS_API_HOOKS: LazyLock<Mutex<Option<Vec<OsApiHook>>>> =
 /// This function is called from [`super::World`] constructors.
 ///
 /// See also: [`add_init_hook`]
+#[cfg(feature = "std")]
 pub fn ensure_initialized() {
     let Some(hooks) = OS_API_HOOKS
         .lock()
@@ -48,14 +58,258 @@ pub fn ensure_initialized() {
         flecs_ecs::sys::ecs_os_set_api_defaults();
         flecs_ecs::sys::ecs_os_get_api()
     };
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    wasm::set_api(&mut api);
     for h in hooks {
         (h.0)(&mut api);
     }
+    let _ = PREV_LOG.set(api.log_);
+    api.log_ = Some(capture_log);
+    let _ = PREV_ABORT.set(api.abort_);
+    api.abort_ = Some(panic_or_abort);
     unsafe {
         flecs_ecs::sys::ecs_os_set_api(&mut api as *mut _);
     };
 }
 
+/// Log function that was set before [`capture_log`] was installed.
+#[cfg(feature = "std")]
+static PREV_LOG: std::sync::OnceLock<flecs_ecs::sys::ecs_os_api_log_t> = std::sync::OnceLock::new();
+
+/// Abort function that was set before [`panic_or_abort`] was installed.
+#[cfg(feature = "std")]
+static PREV_ABORT: std::sync::OnceLock<flecs_ecs::sys::ecs_os_api_abort_t> =
+    std::sync::OnceLock::new();
+
+/// Number of worlds that turn flecs asserts into panics on every thread, see
+/// [`super::World::set_panic_on_assert`].
+#[cfg(feature = "std")]
+static PANIC_ON_ASSERT: core::sync::atomic::AtomicUsize = core::sync::atomic::AtomicUsize::new(0);
+
+#[cfg(feature = "std")]
+std::thread_local! {
+    static CAPTURED_ERRORS: core::cell::RefCell<Option<alloc::string::String>> =
+        const { core::cell::RefCell::new(None) };
+
+    /// The last fatal message flecs logged on this thread, which precedes an abort.
+    static LAST_FATAL: core::cell::RefCell<Option<alloc::string::String>> =
+        const { core::cell::RefCell::new(None) };
+
+    /// Number of callers that turn flecs asserts into panics on this thread only, such as
+    /// [`catch_assert`].
+    static THREAD_PANIC_ON_ASSERT: core::cell::Cell<usize> = const { core::cell::Cell::new(0) };
+
+    /// The message of the assert that panicked on this thread, for [`catch_assert`].
+    static PANICKED_ASSERT: core::cell::RefCell<Option<alloc::string::String>> =
+        const { core::cell::RefCell::new(None) };
+
+    /// Warnings and errors logged on this thread while logs are captured, see [`capture_logs`].
+    static CAPTURED_LOGS: core::cell::RefCell<Option<Vec<(i32, alloc::string::String)>>> =
+        const { core::cell::RefCell::new(None) };
+}
+
+/// Forwards log messages to the previous log function, unless errors are being captured on
+/// the current thread with [`capture_errors`].
+#[cfg(feature = "std")]
+unsafe extern "C-unwind" fn capture_log(
+    level: i32,
+    file: *const core::ffi::c_char,
+    line: i32,
+    msg: *const core::ffi::c_char,
+) {
+    if level == -4 && !msg.is_null() {
+        let msg = unsafe { core::ffi::CStr::from_ptr(msg) }.to_string_lossy();
+        let fatal = if file.is_null() {
+            alloc::string::String::from(msg)
+        } else {
+            let file = unsafe { core::ffi::CStr::from_ptr(file) }.to_string_lossy();
+            alloc::format!("{file}:{line}: {msg}")
+        };
+        LAST_FATAL.set(Some(fatal));
+    }
+
+    if level == -3 && !msg.is_null() {
+        let captured = CAPTURED_ERRORS.with_borrow_mut(|errors| {
+            let Some(errors) = errors else {
+                return false;
+            };
+            if !errors.is_empty() {
+                errors.push('\n');
+            }
+            errors.push_str(&unsafe { core::ffi::CStr::from_ptr(msg) }.to_string_lossy());
+            true
+        });
+
+        if captured {
+            return;
+        }
+    }
+
+    if (level == -2 || level == -3) && !msg.is_null() {
+        let captured = CAPTURED_LOGS.with_borrow_mut(|logs| {
+            let Some(logs) = logs else {
+                return false;
+            };
+            let msg = unsafe { core::ffi::CStr::from_ptr(msg) }.to_string_lossy();
+            logs.push((level, msg.into()));
+            true
+        });
+
+        if captured {
+            return;
+        }
+    }
+
+    if let Some(Some(log)) = PREV_LOG.get() {
+        unsafe { log(level, file, line, msg) };
+    }
+}
+
+/// Run `f` while collecting the error messages flecs logs on the current thread instead of
+/// printing them.
+#[cfg(feature = "std")]
+pub(crate) fn capture_errors<R>(f: impl FnOnce() -> R) -> (R, Option<alloc::string::String>) {
+    let prev = CAPTURED_ERRORS.replace(Some(Default::default()));
+    let result = f();
+    let errors = CAPTURED_ERRORS
+        .replace(prev)
+        .filter(|errors| !errors.is_empty());
+    (result, errors)
+}
+
+/// Panics with the last fatal message flecs logged when asserts are turned into panics, otherwise
+/// forwards to the previous abort function.
+#[cfg(feature = "std")]
+unsafe extern "C-unwind" fn panic_or_abort() {
+    let message = LAST_FATAL.take();
+    if PANIC_ON_ASSERT.load(core::sync::atomic::Ordering::Acquire) > 0
+        || THREAD_PANIC_ON_ASSERT.get() > 0
+    {
+        let message = message.unwrap_or_else(|| "flecs aborted".into());
+        PANICKED_ASSERT.set(Some(message.clone()));
+        panic!("{message}");
+    }
+
+    match PREV_ABORT.get() {
+        Some(Some(abort)) => unsafe { abort() },
+        _ => std::process::abort(),
+    }
+}
+
+/// Enable or disable turning flecs asserts into panics for one world. Asserts panic while any
+/// world has it enabled, because the abort function is shared by all worlds.
+#[cfg(feature = "std")]
+pub(crate) fn set_panic_on_assert(enabled: bool) {
+    use core::sync::atomic::Ordering;
+    if enabled {
+        PANIC_ON_ASSERT.fetch_add(1, Ordering::AcqRel);
+    } else {
+        PANIC_ON_ASSERT.fetch_sub(1, Ordering::AcqRel);
+    }
+}
+
+/// Enable or disable turning flecs asserts into panics on the current thread only. Other threads
+/// keep aborting, unless a world enabled [`set_panic_on_assert`].
+#[cfg(feature = "std")]
+pub(crate) fn set_thread_panic_on_assert(enabled: bool) {
+    THREAD_PANIC_ON_ASSERT.set(if enabled {
+        THREAD_PANIC_ON_ASSERT.get() + 1
+    } else {
+        THREAD_PANIC_ON_ASSERT.get() - 1
+    });
+}
+
+/// Test if flecs asserts panic on the current thread, because of [`set_thread_panic_on_assert`].
+#[cfg(feature = "std")]
+pub(crate) fn is_thread_panic_on_assert() -> bool {
+    THREAD_PANIC_ON_ASSERT.get() > 0
+}
+
+/// Run `f`, returning the message of the flecs assert that panicked in it as error.
+///
+/// Asserts only panic on the current thread while `f` runs. Panics that aren't caused by flecs
+/// asserts are resumed.
+#[cfg(feature = "std")]
+pub(crate) fn catch_assert<R>(f: impl FnOnce() -> R) -> Result<R, alloc::string::String> {
+    set_thread_panic_on_assert(true);
+    PANICKED_ASSERT.take();
+    let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(f));
+    set_thread_panic_on_assert(false);
+
+    result.or_else(|payload| match PANICKED_ASSERT.take() {
+        Some(message) => Err(message),
+        None => std::panic::resume_unwind(payload),
+    })
+}
+
+/// Start collecting the warnings and errors flecs logs on the current thread instead of printing
+/// them. Returns the logs collected before, which [`restore_logs`] puts back.
+#[cfg(feature = "std")]
+pub(crate) fn capture_logs() -> Option<Vec<(i32, alloc::string::String)>> {
+    CAPTURED_LOGS.replace(Some(Vec::new()))
+}
+
+/// Stop collecting logs, restoring the logs returned by [`capture_logs`].
+#[cfg(feature = "std")]
+pub(crate) fn restore_logs(prev: Option<Vec<(i32, alloc::string::String)>>) {
+    CAPTURED_LOGS.set(prev);
+}
+
+/// The logs with `level` collected on the current thread, removing them when `take` is set.
+#[cfg(feature = "std")]
+pub(crate) fn captured_logs(level: i32, take: bool) -> Vec<alloc::string::String> {
+    CAPTURED_LOGS.with_borrow_mut(|logs| {
+        let Some(logs) = logs else {
+            return Vec::new();
+        };
+        let found = logs
+            .iter()
+            .filter(|(l, _)| *l == level)
+            .map(|(_, msg)| msg.clone())
+            .collect();
+        if take {
+            logs.retain(|(l, _)| *l != level);
+        }
+        found
+    })
+}
+
+/// Without `std` errors can't be captured, they are logged as usual.
+#[cfg(not(feature = "std"))]
+pub(crate) fn capture_errors<R>(f: impl FnOnce() -> R) -> (R, Option<alloc::string::String>) {
+    (f(), None)
+}
+
+/// Initialize the Flecs OS API if not initialized already.
+///
+/// Without `std` no hooks can be registered. To customize the OS API, set it with
+/// [`ecs_os_set_api`](flecs_ecs::sys::ecs_os_set_api) before the first [`super::World`] is created.
+///
+/// On `wasm32-unknown-unknown` the time, logging and abort functions are replaced with ones that
+/// use the browser, with or without `std`.
+///
+/// This function is called from [`super::World`] constructors.
+#[cfg(not(feature = "std"))]
+pub fn ensure_initialized() {
+    use core::sync::atomic::{AtomicBool, Ordering};
+
+    static INITIALIZED: AtomicBool = AtomicBool::new(false);
+    if INITIALIZED.swap(true, Ordering::AcqRel) {
+        return;
+    }
+
+    unsafe {
+        flecs_ecs::sys::ecs_os_set_api_defaults();
+    }
+
+    #[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+    unsafe {
+        let mut api = flecs_ecs::sys::ecs_os_get_api();
+        wasm::set_api(&mut api);
+        flecs_ecs::sys::ecs_os_set_api(&mut api);
+    }
+}
+
 /// Add a hook for modifying the Flecs OS API structure,
 /// which runs during [`ensure_initialized`].
 ///
@@ -83,6 +337,7 @@ pub fn ensure_initialized() {
 ///     api.abort_ = Some(abort_);
 /// }));
 /// ```
+#[cfg(feature = "std")]
 pub fn add_init_hook(f: Box<dyn FnOnce(&mut flecs_ecs::sys::ecs_os_api_t)>) {
     if let Err(e) = try_add_init_hook(f) {
         panic!("{e}");
@@ -118,6 +373,7 @@ impl core::error::Error for AddInitHookError {}
 /// for modifying it, which runs during [`ensure_initialized`].
 ///
 /// See also: [`add_init_hook`]
+#[cfg(feature = "std")]
 pub fn try_add_init_hook(
     f: Box<dyn FnOnce(&mut flecs_ecs::sys::ecs_os_api_t)>,
 ) -> Result<(), AddInitHookError> {
@@ -0,0 +1,77 @@
+//! OS API for `wasm32-unknown-unknown`, which has no OS for the OS API implementation of flecs.
+//!
+//! Time is read with `performance.now()` and logs go to the browser console. There are no
+//! threads, so the OS API has no threading functions and systems run on the main thread. Aborts
+//! trap, which shows up as an `unreachable` error in the console after the fatal message.
+
+use core::ffi::{CStr, c_char};
+
+use flecs_ecs::sys;
+use wasm_bindgen::prelude::wasm_bindgen;
+
+extern crate alloc;
+use alloc::format;
+
+#[wasm_bindgen]
+extern "C" {
+    #[wasm_bindgen(js_namespace = performance, js_name = now)]
+    fn performance_now() -> f64;
+
+    #[wasm_bindgen(js_namespace = console, js_name = log)]
+    fn console_log(message: &str);
+
+    #[wasm_bindgen(js_namespace = console, js_name = warn)]
+    fn console_warn(message: &str);
+
+    #[wasm_bindgen(js_namespace = console, js_name = error)]
+    fn console_error(message: &str);
+}
+
+/// Time since the page was loaded in nanoseconds.
+unsafe extern "C-unwind" fn now() -> u64 {
+    (performance_now() * 1_000_000.0) as u64
+}
+
+unsafe extern "C-unwind" fn get_time(time_out: *mut sys::ecs_time_t) {
+    let nanos = unsafe { now() };
+    unsafe {
+        (*time_out).sec = (nanos / 1_000_000_000) as u32;
+        (*time_out).nanosec = (nanos % 1_000_000_000) as u32;
+    }
+}
+
+/// The main thread of a browser can't block, a frame is limited with `requestAnimationFrame`
+/// instead of sleeping.
+unsafe extern "C-unwind" fn sleep(_sec: i32, _nanosec: i32) {}
+
+unsafe extern "C-unwind" fn abort() {
+    core::arch::wasm32::unreachable()
+}
+
+unsafe extern "C-unwind" fn log(level: i32, file: *const c_char, line: i32, msg: *const c_char) {
+    if msg.is_null() {
+        return;
+    }
+    let msg = unsafe { CStr::from_ptr(msg) }.to_string_lossy();
+    let message = if file.is_null() || level > -2 {
+        msg.into_owned()
+    } else {
+        let file = unsafe { CStr::from_ptr(file) }.to_string_lossy();
+        format!("{file}:{line}: {msg}")
+    };
+
+    match level {
+        ..=-3 => console_error(&message),
+        -2 => console_warn(&message),
+        _ => console_log(&message),
+    }
+}
+
+/// Replace the parts of the default OS API that need an OS.
+pub(super) fn set_api(api: &mut sys::ecs_os_api_t) {
+    api.now_ = Some(now);
+    api.get_time_ = Some(get_time);
+    api.sleep_ = Some(sleep);
+    api.abort_ = Some(abort);
+    api.log_ = Some(log);
+}
@@ -5,6 +5,7 @@ use core::ops::Deref;
 use core::ops::{BitAnd, BitOr};
 
 use crate::core::*;
+use crate::sys;
 
 /// An identifier that represents an entity.
 ///
@@ -33,6 +34,28 @@ impl Entity {
         self.0 != 0
     }
 
+    /// Returns the index of the entity id, which is the id without the generation.
+    ///
+    /// The index is stable for the lifetime of the entity but is reused once the
+    /// entity is deleted and the id gets recycled.
+    #[inline]
+    pub fn index(&self) -> u32 {
+        (self.0 & sys::ECS_ENTITY_MASK as u64) as u32
+    }
+
+    /// Returns the generation of the entity id.
+    ///
+    /// The generation is increased every time the index of the entity is recycled,
+    /// which makes it possible to tell apart entities that share the same index.
+    ///
+    /// # See also
+    ///
+    /// * [`get_generation`]
+    #[inline]
+    pub fn generation(&self) -> u32 {
+        get_generation(*self)
+    }
+
     /// Convert the entity id to an [`EntityView`] with the given world.
     ///
     /// # Safety
@@ -128,6 +151,93 @@ impl Display for Entity {
     }
 }
 
+/// An entity id split into its index and generation.
+///
+/// Unlike [`Entity`], which displays as the raw `u64`, an `EntityId` displays as
+/// `generation:index`, which makes recycled ids easy to tell apart in logs and when
+/// sending ids over the network.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// let world = World::new();
+///
+/// let e = world.entity();
+/// let index = e.id().index();
+/// e.destruct();
+///
+/// let recycled = EntityId::from(world.entity().id());
+/// assert_eq!(recycled.index(), index);
+/// assert_eq!(recycled.generation(), 1);
+/// assert_eq!(recycled.to_string(), format!("1:{index}"));
+/// ```
+#[derive(Default, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct EntityId(Entity);
+
+impl EntityId {
+    /// Create an entity id from an index and a generation.
+    #[inline]
+    pub fn new(index: u32, generation: u32) -> Self {
+        Self(Entity(((generation as u64) << 32) | index as u64))
+    }
+
+    /// Returns the index of the entity id, see [`Entity::index()`].
+    #[inline]
+    pub fn index(&self) -> u32 {
+        self.0.index()
+    }
+
+    /// Returns the generation of the entity id, see [`Entity::generation()`].
+    #[inline]
+    pub fn generation(&self) -> u32 {
+        self.0.generation()
+    }
+
+    /// Returns the entity id as an [`Entity`].
+    #[inline]
+    pub fn entity(&self) -> Entity {
+        self.0
+    }
+}
+
+impl From<Entity> for EntityId {
+    #[inline]
+    fn from(entity: Entity) -> Self {
+        Self(entity)
+    }
+}
+
+impl From<EntityId> for Entity {
+    #[inline]
+    fn from(id: EntityId) -> Self {
+        id.0
+    }
+}
+
+impl From<EntityView<'_>> for EntityId {
+    #[inline]
+    fn from(entity: EntityView<'_>) -> Self {
+        Self(entity.id())
+    }
+}
+
+impl Display for EntityId {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}:{}", self.generation(), self.index())
+    }
+}
+
+impl core::fmt::Debug for EntityId {
+    #[inline]
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "EntityId({self})")
+    }
+}
+
 mod bit_operations {
     use super::*;
 
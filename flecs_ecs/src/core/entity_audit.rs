@@ -0,0 +1,94 @@
+//! Track where entities are created and deleted, enabled with the `flecs_entity_audit` feature.
+
+use core::panic::Location;
+
+use crate::core::*;
+use crate::sys;
+
+/// Source locations of entity creation and deletion, stored per world.
+#[derive(Default)]
+pub(crate) struct EntityAudit {
+    created: hashbrown::HashMap<u64, &'static Location<'static>>,
+    deleted: hashbrown::HashMap<u64, &'static Location<'static>>,
+}
+
+/// Record the caller as the location where `entity` was created.
+#[track_caller]
+pub(crate) fn audit_created(world: *mut sys::ecs_world_t, entity: Entity) {
+    if let Some(audit) = audit_mut(world) {
+        audit.created.insert(*entity, Location::caller());
+        audit.deleted.remove(&*entity);
+    }
+}
+
+/// Record the caller as the location where `entity` was deleted.
+#[track_caller]
+pub(crate) fn audit_deleted(world: *mut sys::ecs_world_t, entity: Entity) {
+    if let Some(audit) = audit_mut(world) {
+        audit.deleted.insert(*entity, Location::caller());
+    }
+}
+
+fn audit_mut<'a>(world: *mut sys::ecs_world_t) -> Option<&'a mut EntityAudit> {
+    // worker threads run on the other stages at the same time as the first stage
+    if unsafe { sys::ecs_stage_get_id(world) } != 0 {
+        return None;
+    }
+    Some(unsafe { &mut (*World::get_context(world)).entity_audit })
+}
+
+impl World {
+    /// Get the source location where the entity was created.
+    ///
+    /// Only entities created with the Rust API, such as [`World::entity()`] and
+    /// [`World::entity_named()`], are tracked. Entities created by multi threaded systems are only
+    /// tracked on the first stage. The id includes the generation, so the location of a recycled id
+    /// is the location where that generation was created.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// let world = World::new();
+    ///
+    /// let e = world.entity();
+    /// let location = world.who_created(e).unwrap();
+    /// assert_eq!(location.line(), line!() - 2);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::who_deleted()`]
+    pub fn who_created(&self, entity: impl Into<Entity>) -> Option<&'static Location<'static>> {
+        let entity = entity.into();
+        self.world_ctx().entity_audit.created.get(&*entity).copied()
+    }
+
+    /// Get the source location where the entity was deleted.
+    ///
+    /// Use this to find out who deleted an entity when an operation on it fails because it is no
+    /// longer alive. Only deletions with [`EntityView::destruct()`] are tracked.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// let world = World::new();
+    ///
+    /// let e = world.entity();
+    /// assert!(world.who_deleted(e).is_none());
+    ///
+    /// let id = e.id();
+    /// e.destruct();
+    /// let location = world.who_deleted(id).unwrap();
+    /// assert_eq!(location.line(), line!() - 2);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::who_created()`]
+    pub fn who_deleted(&self, entity: impl Into<Entity>) -> Option<&'static Location<'static>> {
+        let entity = entity.into();
+        self.world_ctx().entity_audit.deleted.get(&*entity).copied()
+    }
+}
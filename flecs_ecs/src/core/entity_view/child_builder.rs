@@ -0,0 +1,86 @@
+//! Declarative construction of hierarchies, see [`EntityView::with_children()`].
+
+use crate::core::*;
+
+/// Creates children of an entity, created by [`EntityView::with_children()`].
+pub struct ChildBuilder<'a> {
+    parent: EntityView<'a>,
+}
+
+impl<'a> ChildBuilder<'a> {
+    /// The entity that children are created for.
+    pub fn parent(&self) -> EntityView<'a> {
+        self.parent
+    }
+
+    /// Create a child of the parent.
+    ///
+    /// # See also
+    ///
+    /// * [`World::entity()`]
+    #[cfg_attr(feature = "flecs_entity_audit", track_caller)]
+    pub fn entity(&self) -> EntityView<'a> {
+        EntityView::new_child(self.parent.world(), self.parent, None)
+    }
+
+    /// Create a named child of the parent.
+    ///
+    /// The name is relative to the parent, so `"turret::barrel"` creates `barrel` as a child of
+    /// `turret`, which is created as child of the parent if it doesn't exist. When the parent
+    /// already has a child with the name, that child is returned.
+    ///
+    /// # See also
+    ///
+    /// * [`World::entity_named()`]
+    #[cfg_attr(feature = "flecs_entity_audit", track_caller)]
+    pub fn entity_named(&self, name: &str) -> EntityView<'a> {
+        EntityView::new_child(self.parent.world(), self.parent, Some(name))
+    }
+}
+
+impl<'a> EntityView<'a> {
+    /// Create children of the entity in `func`.
+    ///
+    /// The entities created with the [`ChildBuilder`] get the `(ChildOf, self)` pair, which
+    /// makes it possible to declare a hierarchy in the shape it has. Children can create children
+    /// of their own by calling `with_children` again.
+    ///
+    /// Unlike [`EntityView::scope()`], the scope of the world isn't changed, so lookups and
+    /// entities created with the world in `func` aren't affected.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Damage(f32);
+    ///
+    /// #[derive(Component)]
+    /// struct Armor(f32);
+    ///
+    /// let world = World::new();
+    ///
+    /// let tank = world.entity_named("tank").with_children(|tank| {
+    ///     tank.entity_named("hull").set(Armor(100.0));
+    ///     tank.entity_named("turret").with_children(|turret| {
+    ///         turret.entity_named("gun").set(Damage(50.0));
+    ///     });
+    /// });
+    ///
+    /// let gun = world.lookup("tank::turret::gun");
+    /// assert_eq!(gun.parent().unwrap().parent().unwrap(), tank);
+    /// assert!(gun.has::<Damage>());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::child_of_id()`]
+    /// * [`EntityView::scope()`]
+    /// * C++ API: `entity_builder::scope`
+    #[doc(alias = "entity_builder::scope")]
+    pub fn with_children(self, func: impl FnOnce(&ChildBuilder<'a>)) -> Self {
+        func(&ChildBuilder { parent: self });
+        self
+    }
+}
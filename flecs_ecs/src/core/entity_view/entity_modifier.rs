@@ -0,0 +1,124 @@
+//! Batch operations on an entity into a single archetype move, see [`EntityView::modify()`].
+
+use crate::core::*;
+
+/// Operations on an entity that are applied together, created by [`EntityView::modify()`].
+pub struct EntityModifier<'a> {
+    entity: EntityView<'a>,
+}
+
+impl<'a> EntityModifier<'a> {
+    /// The entity that is modified.
+    pub fn entity(&self) -> EntityView<'a> {
+        self.entity
+    }
+
+    /// Add a component, tag or pair to the entity.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::add()`]
+    pub fn add<T: ComponentOrPairId>(&mut self) -> &mut Self {
+        self.entity.add::<T>();
+        self
+    }
+
+    /// Add an id to the entity.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::add_id()`]
+    pub fn add_id(&mut self, id: impl IntoId) -> &mut Self {
+        self.entity.add_id(id);
+        self
+    }
+
+    /// Remove a component, tag or pair from the entity.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::remove()`]
+    pub fn remove<T: ComponentOrPairId>(&mut self) -> &mut Self {
+        self.entity.remove::<T>();
+        self
+    }
+
+    /// Remove an id from the entity.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::remove_id()`]
+    pub fn remove_id(&mut self, id: impl IntoId) -> &mut Self {
+        self.entity.remove_id(id);
+        self
+    }
+
+    /// Set the value of a component.
+    ///
+    /// The value is assigned once the entity moved to its new table, after which `OnSet`
+    /// observers run. A component that implements [`Drop`] but not [`Default`], and that the
+    /// entity doesn't have yet, is emplaced instead, which isn't part of the batched move: the
+    /// entity moves to a table with the component separately when the operations are applied.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::set()`]
+    pub fn set<T: ComponentId + DataComponent>(&mut self, component: T) -> &mut Self {
+        self.entity.set(component);
+        self
+    }
+}
+
+impl<'a> EntityView<'a> {
+    /// Apply several operations on the entity with a single archetype move.
+    ///
+    /// Chaining [`EntityView::add()`], [`EntityView::remove()`] and [`EntityView::set()`] moves
+    /// the entity to a new table for every call. The operations done in `func` are deferred and
+    /// batched instead, so the entity only moves once, to the table it ends up in. `OnAdd` and
+    /// `OnRemove` observers run after the move, `OnSet` observers after the values are assigned.
+    ///
+    /// When the world is already deferred, the operations are batched with the other commands of
+    /// the entity when the world is merged.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Walking;
+    ///
+    /// #[derive(Component)]
+    /// struct Idle;
+    ///
+    /// let world = World::new();
+    ///
+    /// let e = world.entity().add::<Idle>();
+    ///
+    /// e.modify(|ops| {
+    ///     ops.remove::<Idle>()
+    ///         .add::<Walking>()
+    ///         .set(Position { x: 1.0, y: 2.0 });
+    /// });
+    ///
+    /// assert!(!e.has::<Idle>());
+    /// assert!(e.has::<Walking>());
+    /// assert!(e.has::<Position>());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::defer()`]
+    pub fn modify(self, func: impl FnOnce(&mut EntityModifier<'a>)) -> Self {
+        self.world.defer_begin();
+        func(&mut EntityModifier { entity: self });
+        self.world.defer_end();
+        self
+    }
+}
@@ -0,0 +1,181 @@
+//! Reuse the record of an entity for several operations, see [`EntityView::with_record()`].
+
+use crate::core::*;
+use crate::sys;
+use core::ptr::NonNull;
+
+/// The record of an entity, which locates its table and row, created by
+/// [`EntityView::with_record()`].
+///
+/// Operations on an [`EntityView`] look up the record of the entity every time. Operations on the
+/// record use the one that was looked up when it was created.
+pub struct EntityRecord<'a> {
+    entity: EntityView<'a>,
+    record: *mut sys::ecs_record_t,
+}
+
+impl<'a> EntityRecord<'a> {
+    /// The entity of the record.
+    pub fn entity(&self) -> EntityView<'a> {
+        self.entity
+    }
+
+    /// The table the entity is stored in, if it has any components.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::table()`]
+    pub fn table(&self) -> Option<Table<'a>> {
+        let table = unsafe { (*self.record).table };
+        NonNull::new(table).map(|table| Table::new(self.entity.world, table))
+    }
+
+    /// Test if the entity has a component, tag or pair.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::has()`]
+    pub fn has<T: ComponentOrPairId>(&self) -> bool {
+        self.has_id(T::get_id(self.entity.world))
+    }
+
+    /// Test if the entity has an id.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::has_id()`]
+    pub fn has_id(&self, id: impl IntoId) -> bool {
+        unsafe {
+            sys::ecs_record_has_id(self.entity.world.world_ptr_mut(), self.record, *id.into())
+        }
+    }
+
+    /// Get references to components of the entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entity doesn't have a component that isn't wrapped in [`Option`].
+    ///
+    /// # See also
+    ///
+    /// * [`EntityViewGet::get()`]
+    pub fn get<T: GetTuple, Return>(
+        &self,
+        callback: impl for<'e> FnOnce(T::TupleType<'e>) -> Return,
+    ) -> Return {
+        self.entity
+            .get_from_record::<T, Return>(self.record, callback)
+    }
+
+    /// Get references to components of the entity, if it has all of them.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityViewGet::try_get()`]
+    pub fn try_get<T: GetTuple, Return>(
+        &self,
+        callback: impl for<'e> FnOnce(T::TupleType<'e>) -> Return,
+    ) -> Option<Return> {
+        self.entity
+            .try_get_from_record::<T, Return>(self.record, callback)
+    }
+
+    /// Set the value of a component.
+    ///
+    /// When the entity already has the component and the world isn't deferred, the value is
+    /// assigned in place.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::set()`]
+    pub fn set<T: ComponentId + DataComponent>(&mut self, component: T) -> &mut Self {
+        let world = self.entity.world.world_ptr_mut();
+        let id = T::id(self.entity.world);
+
+        unsafe {
+            if !sys::ecs_is_deferred(world) {
+                let ptr = sys::ecs_record_ensure_id(world, self.record, id) as *mut T;
+                if !ptr.is_null() {
+                    *ptr = component;
+                    sys::ecs_modified_id(world, *self.entity.id, id);
+                    return self;
+                }
+            }
+        }
+
+        self.entity.set(component);
+        self
+    }
+
+    /// Add a component, tag or pair to the entity.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::add()`]
+    pub fn add<T: ComponentOrPairId>(&mut self) -> &mut Self {
+        self.entity.add::<T>();
+        self
+    }
+
+    /// Remove a component, tag or pair from the entity.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::remove()`]
+    pub fn remove<T: ComponentOrPairId>(&mut self) -> &mut Self {
+        self.entity.remove::<T>();
+        self
+    }
+}
+
+impl<'a> EntityView<'a> {
+    /// Look up the record of the entity once, and use it for several operations.
+    ///
+    /// Every operation on an [`EntityView`] finds the table and row of the entity in the entity
+    /// index first. Operations done on the [`EntityRecord`] passed to `func` skip that lookup,
+    /// which adds up for code that does many operations on the same entity.
+    ///
+    /// The record stays valid when the entity moves to another table, but not when it's deleted.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the entity is not alive.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// #[derive(Component)]
+    /// struct Dead;
+    ///
+    /// let world = World::new();
+    ///
+    /// let e = world.entity().set(Health(10));
+    ///
+    /// e.with_record(|record| {
+    ///     let health = record.get::<&Health, _>(|health| health.0);
+    ///     record.set(Health(health.saturating_sub(20)));
+    ///     if record.get::<&Health, _>(|health| health.0) == 0 {
+    ///         record.add::<Dead>();
+    ///     }
+    /// });
+    ///
+    /// assert!(e.has::<Dead>());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::modify()`]
+    pub fn with_record<Return>(self, func: impl FnOnce(&mut EntityRecord<'a>) -> Return) -> Return {
+        let record = unsafe { sys::ecs_record_find(self.world.world_ptr(), *self.id) };
+        assert!(!record.is_null(), "entity {:?} is not alive", self.id);
+        func(&mut EntityRecord {
+            entity: self,
+            record,
+        })
+    }
+}
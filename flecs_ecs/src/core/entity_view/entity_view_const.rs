@@ -121,11 +121,24 @@ impl core::fmt::Display for EntityView<'_> {
 
 impl core::fmt::Debug for EntityView<'_> {
     fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
-        let entity_display = match self.get_name() {
-            Some(name_str) => alloc::format!("Entity: #{} | \"{}\"", self.id, name_str),
+        let entity_display = match self.get_name().and(self.path()) {
+            Some(path) => alloc::format!("Entity: #{} | \"{}\"", self.id, path),
             None => format!("Entity: #{}", self.id),
         };
-        let archetype_types_str = debug_separate_archetype_types_into_strings(&self.archetype());
+        let archetype = self.archetype();
+        #[allow(unused_mut)]
+        let mut archetype_types_str = debug_separate_archetype_types_into_strings(&archetype);
+
+        // append the values of components with reflection data
+        #[cfg(feature = "flecs_json")]
+        if archetype_types_str.len() == archetype.count() {
+            for (type_str, &id) in archetype_types_str.iter_mut().zip(archetype.as_slice()) {
+                if let Some(value) = crate::addons::json::value_to_json(*self, id) {
+                    type_str.push_str(" = ");
+                    type_str.push_str(&value);
+                }
+            }
+        }
 
         let mut children = alloc::vec![];
         self.each_child(|child| {
@@ -177,6 +190,7 @@ impl<'a> EntityView<'a> {
     /// * [`World::entity()`] - Preferred way to create entities
     #[doc(alias = "entity::entity")]
     #[allow(clippy::not_unsafe_ptr_arg_deref)]
+    #[cfg_attr(feature = "flecs_entity_audit", track_caller)]
     pub(crate) fn new(world: impl WorldProvider<'a>) -> Self {
         let world_ptr = world.world_ptr_mut();
         let id = if unsafe { sys::ecs_get_scope(world_ptr) == 0 && ecs_get_with(world_ptr) == 0 } {
@@ -185,6 +199,8 @@ impl<'a> EntityView<'a> {
             let desc = sys::ecs_entity_desc_t::default();
             unsafe { sys::ecs_entity_init(world_ptr, &desc) }
         };
+        #[cfg(feature = "flecs_entity_audit")]
+        audit_created(world_ptr, id.into());
         Self {
             world: world.world(),
             id: id.into(),
@@ -248,8 +264,9 @@ impl<'a> EntityView<'a> {
     /// * [`EntityView::path()`] - Get full hierarchical path
     /// * [`World::lookup()`] - Look up named entities
     #[doc(alias = "entity::entity")]
+    #[cfg_attr(feature = "flecs_entity_audit", track_caller)]
     pub(crate) fn new_named(world: impl WorldProvider<'a>, name: &str) -> Self {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
 
         let desc = sys::ecs_entity_desc_t {
             name: name.as_ptr() as *const _,
@@ -265,12 +282,15 @@ impl<'a> EntityView<'a> {
             set: core::ptr::null(),
         };
         let id = unsafe { sys::ecs_entity_init(world.world_ptr_mut(), &desc) };
+        #[cfg(feature = "flecs_entity_audit")]
+        audit_created(world.world_ptr_mut(), id.into());
         Self {
             world: world.world(),
             id: id.into(),
         }
     }
 
+    #[cfg_attr(feature = "flecs_entity_audit", track_caller)]
     pub(crate) fn new_named_cstr(world: impl WorldProvider<'a>, name: &CStr) -> Self {
         let desc = sys::ecs_entity_desc_t {
             name: name.as_ptr(),
@@ -286,6 +306,36 @@ impl<'a> EntityView<'a> {
             set: core::ptr::null(),
         };
         let id = unsafe { sys::ecs_entity_init(world.world_ptr_mut(), &desc) };
+        #[cfg(feature = "flecs_entity_audit")]
+        audit_created(world.world_ptr_mut(), id.into());
+        Self {
+            world: world.world(),
+            id: id.into(),
+        }
+    }
+
+    /// Create a child of `parent`. A name is relative to the parent, and returns the existing
+    /// child when the parent already has a child with that name.
+    #[cfg_attr(feature = "flecs_entity_audit", track_caller)]
+    pub(crate) fn new_child(
+        world: impl WorldProvider<'a>,
+        parent: impl Into<Entity>,
+        name: Option<&str>,
+    ) -> Self {
+        let name = name.map(SmallCString::new);
+
+        let desc = sys::ecs_entity_desc_t {
+            parent: *parent.into(),
+            name: name
+                .as_ref()
+                .map_or(core::ptr::null(), SmallCString::as_ptr),
+            sep: SEPARATOR.as_ptr(),
+            root_sep: SEPARATOR.as_ptr(),
+            ..Default::default()
+        };
+        let id = unsafe { sys::ecs_entity_init(world.world_ptr_mut(), &desc) };
+        #[cfg(feature = "flecs_entity_audit")]
+        audit_created(world.world_ptr_mut(), id.into());
         Self {
             world: world.world(),
             id: id.into(),
@@ -378,7 +428,7 @@ impl<'a> EntityView<'a> {
     /// let entity = world.entity();
     /// assert!(entity.is_alive());
     ///
-    /// world.defer(|| {
+    /// world.defer(|_| {
     ///     entity.destruct(); // Deferred deletion
     ///     assert!(entity.is_alive()); // still alive since deferred
     /// });
@@ -483,17 +533,47 @@ impl<'a> EntityView<'a> {
 
     /// Returns the entity symbol.
     ///
+    /// If the entity has no symbol, this will return an empty string.
+    ///
     /// # See also
     ///
+    /// * [`EntityView::get_symbol()`]
     /// * C++ API: `entity_view::symbol`
     #[doc(alias = "entity_view::symbol")]
     pub fn symbol(self) -> String {
-        //self.symbol_cstr().to_str().unwrap()
-        let cstr = unsafe { CStr::from_ptr(sys::ecs_get_symbol(self.world.world_ptr(), *self.id)) };
-        cstr.to_str()
-            .ok()
-            .map(ToString::to_string)
-            .unwrap_or_default()
+        self.get_symbol().unwrap_or_default()
+    }
+
+    /// Returns the entity symbol as an Option.
+    ///
+    /// Similar to [`symbol()`][EntityView::symbol] but returns None if the entity has no symbol.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// let component = world.component::<Position>();
+    /// assert!(component.get_symbol().is_some());
+    ///
+    /// assert_eq!(world.entity().get_symbol(), None);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::symbol()`]
+    #[doc(alias = "entity_view::symbol")]
+    pub fn get_symbol(self) -> Option<String> {
+        NonNull::new(unsafe { sys::ecs_get_symbol(self.world.world_ptr(), *self.id) } as *mut _)
+            .map(|s| unsafe { CStr::from_ptr(s.as_ptr()) })
+            .and_then(|s| s.to_str().ok().map(ToString::to_string))
     }
 
     /// Return the hierarchical entity path.
@@ -602,8 +682,8 @@ impl<'a> EntityView<'a> {
         sep: &str,
         init_sep: &str,
     ) -> Option<String> {
-        let sep = compact_str::format_compact!("{}\0", sep);
-        let init_sep = compact_str::format_compact!("{}\0", init_sep);
+        let sep = SmallCString::new(sep);
+        let init_sep = SmallCString::new(init_sep);
 
         NonNull::new(unsafe {
             sys::ecs_get_path_w_sep(
@@ -1507,15 +1587,86 @@ pub trait EntityViewGet<Return> {
     /// });
     /// ```
     fn get<T: GetTuple>(self, callback: impl for<'e> FnOnce(T::TupleType<'e>) -> Return) -> Return;
+
+    /// Like [`EntityViewGet::try_get()`], but returns why the callback didn't run:
+    /// [`FlecsError::NotAlive`] if the entity is not alive, or [`FlecsError::MissingComponent`]
+    /// with the requested type if it doesn't have the components.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// pub struct Position {
+    ///     pub x: f32,
+    ///     pub y: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// pub struct Velocity {
+    ///     pub x: f32,
+    ///     pub y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// let entity = world.entity().set(Position { x: 10.0, y: 20.0 });
+    ///
+    /// assert_eq!(entity.get_or_err::<&Position>(|pos| pos.x), Ok(10.0));
+    /// assert!(matches!(
+    ///     entity.get_or_err::<&Velocity>(|vel| vel.x),
+    ///     Err(FlecsError::MissingComponent(_))
+    /// ));
+    ///
+    /// entity.destruct();
+    /// assert_eq!(
+    ///     entity.get_or_err::<&Position>(|pos| pos.x),
+    ///     Err(FlecsError::NotAlive)
+    /// );
+    /// ```
+    fn get_or_err<T: GetTuple>(
+        self,
+        callback: impl for<'e> FnOnce(T::TupleType<'e>) -> Return,
+    ) -> Result<Return, FlecsError>;
 }
 
 impl<Return> EntityViewGet<Return> for EntityView<'_> {
+    #[track_caller]
     fn try_get<T: GetTuple>(
         self,
         callback: impl for<'e> FnOnce(T::TupleType<'e>) -> Return,
     ) -> Option<Return> {
         let record = unsafe { sys::ecs_record_find(self.world.world_ptr(), *self.id) };
+        self.try_get_from_record::<T, Return>(record, callback)
+    }
+
+    #[track_caller]
+    fn get<T: GetTuple>(self, callback: impl for<'e> FnOnce(T::TupleType<'e>) -> Return) -> Return {
+        let record = unsafe { sys::ecs_record_find(self.world.world_ptr(), *self.id) };
+        self.get_from_record::<T, Return>(record, callback)
+    }
+
+    #[track_caller]
+    fn get_or_err<T: GetTuple>(
+        self,
+        callback: impl for<'e> FnOnce(T::TupleType<'e>) -> Return,
+    ) -> Result<Return, FlecsError> {
+        if !self.is_alive() {
+            return Err(FlecsError::NotAlive);
+        }
+        self.try_get::<T>(callback)
+            .ok_or(FlecsError::MissingComponent(core::any::type_name::<T>()))
+    }
+}
 
+impl EntityView<'_> {
+    /// [`EntityViewGet::try_get()`] with the record of the entity already looked up.
+    #[track_caller]
+    pub(crate) fn try_get_from_record<T: GetTuple, Return>(
+        self,
+        record: *const sys::ecs_record_t,
+        callback: impl for<'e> FnOnce(T::TupleType<'e>) -> Return,
+    ) -> Option<Return> {
         if unsafe { (*record).table.is_null() } {
             return None;
         }
@@ -1554,9 +1705,13 @@ impl<Return> EntityViewGet<Return> for EntityView<'_> {
         }
     }
 
-    fn get<T: GetTuple>(self, callback: impl for<'e> FnOnce(T::TupleType<'e>) -> Return) -> Return {
-        let record = unsafe { sys::ecs_record_find(self.world.world_ptr(), *self.id) };
-
+    /// [`EntityViewGet::get()`] with the record of the entity already looked up.
+    #[track_caller]
+    pub(crate) fn get_from_record<T: GetTuple, Return>(
+        self,
+        record: *const sys::ecs_record_t,
+        callback: impl for<'e> FnOnce(T::TupleType<'e>) -> Return,
+    ) -> Return {
         if unsafe { (*record).table.is_null() } {
             panic!("Entity does not have any components");
         }
@@ -1741,6 +1896,17 @@ impl<'a> EntityView<'a> {
         }
     }
 
+    /// Like [`EntityView::try_cloned()`], but returns [`FlecsError::NotAlive`] if the entity is not
+    /// alive, or [`FlecsError::MissingComponent`] with the requested type if it doesn't have the
+    /// components.
+    pub fn cloned_or_err<T: ClonedTuple>(self) -> Result<T::TupleType<'a>, FlecsError> {
+        if !self.is_alive() {
+            return Err(FlecsError::NotAlive);
+        }
+        self.try_cloned::<T>()
+            .ok_or(FlecsError::MissingComponent(core::any::type_name::<T>()))
+    }
+
     /// Get component value or pair as untyped pointer
     ///
     /// # Arguments
@@ -2077,6 +2243,51 @@ impl<'a> EntityView<'a> {
         self.target_for_id(relationship, T::get_id(self.world))
     }
 
+    /// Get the target for a given component and relationship type.
+    /// Typed version of [`EntityView::target_for()`].
+    ///
+    /// Returns the first entity that has `T` by following the `Rel` relationship,
+    /// which can be used to find out through which base or parent a component is provided.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Position(f32, f32, f32);
+    /// let world = World::new();
+    /// world
+    ///     .component::<Position>()
+    ///     .add_trait::<(flecs::OnInstantiate, flecs::Inherit)>();
+    ///
+    /// let base = world.prefab().set(Position(1.0, 2.0, 3.0));
+    /// let inst = world.entity().is_a_id(base);
+    ///
+    /// // Position is inherited from the prefab, not owned by the instance
+    /// let provider = inst.target_for_rel::<flecs::IsA, Position>().unwrap();
+    /// assert_eq!(provider, base);
+    /// ```
+    ///
+    /// # Type Parameters
+    ///
+    /// * `Rel` - The relationship to follow.
+    /// * `T` - The component type to use for deriving the id.
+    ///
+    /// # Returns
+    ///
+    /// * The entity for which the target has been found.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::target_for()`]
+    /// * [`EntityView::target_for_id()`]
+    /// * C++ API: `entity_view::target_for`
+    #[doc(alias = "entity_view::target_for")]
+    #[inline(always)]
+    pub fn target_for_rel<Rel: ComponentId, T: ComponentOrPairId>(self) -> Option<EntityView<'a>> {
+        self.target_for_id(Rel::id(self.world), T::get_id(self.world))
+    }
+
     // TODO this needs a better name and documentation, the rest of the cpp functions still have to be done as well
     // TODO, I removed the second template parameter and changed the fn parameter second to entityT, check validity
     /// Get the target for a given pair of components and a relationship.
@@ -2195,7 +2406,7 @@ impl<'a> EntityView<'a> {
     #[doc(alias = "entity_view::lookup")]
     #[inline(always)]
     fn try_lookup_impl(self, name: &str, recursively: bool) -> Option<EntityView<'a>> {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
 
         ecs_assert!(
             self.id != 0,
@@ -2266,6 +2477,24 @@ impl<'a> EntityView<'a> {
         self.try_lookup_impl(name, false)
     }
 
+    /// Lookup an entity by name, only in the current scope of the entity.
+    ///
+    /// Like [`EntityView::try_lookup()`], but returns [`FlecsError::Unresolved`] with the name
+    /// when no entity is found.
+    pub fn lookup_or_err(&self, name: &str) -> Result<EntityView<'_>, FlecsError> {
+        self.try_lookup(name)
+            .ok_or_else(|| FlecsError::Unresolved(name.into()))
+    }
+
+    /// Lookup an entity by name, recursively traversing up the tree until found.
+    ///
+    /// Like [`EntityView::try_lookup_recursive()`], but returns [`FlecsError::Unresolved`] with
+    /// the name when no entity is found.
+    pub fn lookup_recursive_or_err(&self, name: &str) -> Result<EntityView<'_>, FlecsError> {
+        self.try_lookup_recursive(name)
+            .ok_or_else(|| FlecsError::Unresolved(name.into()))
+    }
+
     /// Lookup an entity by name.
     /// The entity is searched recursively recursively traversing
     /// up the tree until found.
@@ -2675,14 +2904,7 @@ impl<'a> EntityView<'a> {
     #[inline(always)]
     pub fn duplicate(self, copy_value: bool) -> EntityView<'a> {
         let dest_entity = EntityView::new(self.world());
-        unsafe {
-            sys::ecs_clone(
-                self.world.world_ptr_mut(),
-                *dest_entity.id,
-                *self.id,
-                copy_value,
-            )
-        };
+        self.clone_to(*dest_entity.id, copy_value);
         dest_entity
     }
 
@@ -2719,10 +2941,44 @@ impl<'a> EntityView<'a> {
         }
 
         let dest_entity = EntityView::new_from(self.world, dest_id);
-        unsafe { sys::ecs_clone(self.world.world_ptr_mut(), dest_id, *self.id, copy_value) };
+        self.clone_to(dest_id, copy_value);
         dest_entity
     }
 
+    /// Clone the entity to `dest_id`, which has no components.
+    ///
+    /// When flecs copies the values while cloning, components that are overridden from a prefab
+    /// are assigned the value of the prefab before they are constructed. Outside of deferred mode
+    /// the values are therefore copied after cloning, when the components are constructed.
+    fn clone_to(self, dest_id: u64, copy_value: bool) {
+        let world = self.world.world_ptr_mut();
+        if !copy_value || unsafe { sys::ecs_is_deferred(world) } {
+            unsafe { sys::ecs_clone(world, dest_id, *self.id, copy_value) };
+            return;
+        }
+
+        unsafe { sys::ecs_clone(world, dest_id, *self.id, false) };
+        let name = ecs_pair(flecs::Identifier::ID, flecs::Name::ID);
+        let ids: Vec<Id> = self.archetype().as_slice().to_vec();
+        for id in ids {
+            let id = *id;
+            if id == name {
+                continue;
+            }
+            unsafe {
+                let type_info = sys::ecs_get_type_info(world, id);
+                if type_info.is_null() {
+                    continue;
+                }
+                // the value is looked up again, as OnSet observers may have moved the entity
+                let value = sys::ecs_get_id(world, *self.id, id);
+                if !value.is_null() {
+                    sys::ecs_set_id(world, dest_id, id, (*type_info).size as usize, value);
+                }
+            }
+        }
+    }
+
     /// Returns a mutable entity handle for the current stage.
     ///
     /// When an entity handle created from the world is used while the world is
@@ -2894,7 +3150,7 @@ impl EntityView<'_> {
     ///     height: i32,
     /// }
     ///
-    /// world.defer(|| {
+    /// world.defer(|_| {
     ///     entity.enqueue(Resize {
     ///         width: 10,
     ///         height: 20,
@@ -3057,6 +3313,13 @@ impl EntityView<'_> {
         Func: FnMut(&C) + 'static,
         C: ComponentId,
     {
+        const {
+            assert!(
+                !C::IS_REF && !C::IS_MUT,
+                "The payload is already passed by reference, use `observe_payload::<T>` instead of `observe_payload::<&T>`."
+            );
+        }
+
         let new_binding_ctx = Box::<ObserverEntityBindingCtx>::default();
         let binding_ctx = Box::leak(new_binding_ctx);
 
@@ -3109,6 +3372,13 @@ impl EntityView<'_> {
         Func: FnMut(&mut EntityView, &C) + 'static,
         C: ComponentId,
     {
+        const {
+            assert!(
+                !C::IS_REF && !C::IS_MUT,
+                "The payload is already passed by reference, use `observe_payload_entity::<T>` instead of `observe_payload_entity::<&T>`."
+            );
+        }
+
         let new_binding_ctx = Box::<ObserverEntityBindingCtx>::default();
         let binding_ctx = Box::leak(new_binding_ctx);
 
@@ -3168,15 +3438,9 @@ impl EntityView<'_> {
             let ctx: *mut ObserverEntityBindingCtx = (*iter).callback_ctx as *mut _;
             let empty = (*ctx).empty.unwrap();
             let empty = &mut *(empty as *mut Func);
-            let iter_count = (*iter).count as usize;
-
-            sys::ecs_table_lock((*iter).world, (*iter).table);
-
-            for _i in 0..iter_count {
-                empty();
-            }
-
-            sys::ecs_table_unlock((*iter).world, (*iter).table);
+            // the observed entity is matched as term source, so `count` is 0 and the
+            // callback is invoked once per event, like the C++ delegates do.
+            empty();
         }
     }
 
@@ -3198,19 +3462,11 @@ impl EntityView<'_> {
             let ctx: *mut ObserverEntityBindingCtx = (*iter).callback_ctx as *mut _;
             let empty = (*ctx).empty_entity.unwrap();
             let empty = &mut *(empty as *mut Func);
-            let iter_count = (*iter).count as usize;
-
-            sys::ecs_table_lock((*iter).world, (*iter).table);
-
-            for _i in 0..iter_count {
-                let world = WorldRef::from_ptr((*iter).world);
-                empty(&mut EntityView::new_from(
-                    world,
-                    sys::ecs_field_src(iter, 0),
-                ));
-            }
-
-            sys::ecs_table_unlock((*iter).world, (*iter).table);
+            let world = WorldRef::from_ptr((*iter).world);
+            empty(&mut EntityView::new_from(
+                world,
+                sys::ecs_field_src(iter, 0),
+            ));
         }
     }
 
@@ -3232,17 +3488,9 @@ impl EntityView<'_> {
             let ctx: *mut ObserverEntityBindingCtx = (*iter).callback_ctx as *mut _;
             let empty = (*ctx).payload.unwrap();
             let empty = &mut *(empty as *mut Func);
-            let iter_count = (*iter).count as usize;
-
-            sys::ecs_table_lock((*iter).world, (*iter).table);
-
-            for _i in 0..iter_count {
-                let data = (*iter).param as *mut C;
-                let data_ref = &mut *data;
-                empty(data_ref);
-            }
-
-            sys::ecs_table_unlock((*iter).world, (*iter).table);
+            let data = (*iter).param as *mut C;
+            let data_ref = &mut *data;
+            empty(data_ref);
         }
     }
 
@@ -3264,21 +3512,13 @@ impl EntityView<'_> {
             let ctx: *mut ObserverEntityBindingCtx = (*iter).callback_ctx as *mut _;
             let empty = (*ctx).payload_entity.unwrap();
             let empty = &mut *(empty as *mut Func);
-            let iter_count = (*iter).count as usize;
-
-            sys::ecs_table_lock((*iter).world, (*iter).table);
-
-            for _i in 0..iter_count {
-                let data = (*iter).param as *mut C;
-                let data_ref = &mut *data;
-                let world = WorldRef::from_ptr((*iter).world);
-                empty(
-                    &mut EntityView::new_from(world, sys::ecs_field_src(iter, 0)),
-                    data_ref,
-                );
-            }
-
-            sys::ecs_table_unlock((*iter).world, (*iter).table);
+            let data = (*iter).param as *mut C;
+            let data_ref = &mut *data;
+            let world = WorldRef::from_ptr((*iter).world);
+            empty(
+                &mut EntityView::new_from(world, sys::ecs_field_src(iter, 0)),
+                data_ref,
+            );
         }
     }
 
@@ -3318,7 +3558,7 @@ impl EntityView<'_> {
     pub(crate) extern "C-unwind" fn binding_entity_ctx_drop(ptr: *mut c_void) {
         let ptr_struct: *mut ObserverEntityBindingCtx = ptr as *mut ObserverEntityBindingCtx;
         unsafe {
-            ptr::drop_in_place(ptr_struct);
+            drop(Box::from_raw(ptr_struct));
         }
     }
 }
@@ -1,5 +1,7 @@
 use core::ffi::c_void;
 
+extern crate alloc;
+
 use flecs_ecs::core::*;
 use sys::EcsIsA;
 
@@ -39,6 +41,78 @@ impl<'a> EntityView<'a> {
         self
     }
 
+    /// Adds an ID to the entity, checking the constraints of the relationship first.
+    ///
+    /// Flecs aborts when a constraint is violated, such as a target of a [`flecs::OneOf`]
+    /// relationship that is not a child of the relationship's scope. Use this when the id comes
+    /// from user input or data files, to handle the error instead.
+    ///
+    /// # Errors
+    ///
+    /// [`FlecsError::ConstraintViolated`] if the id violates a `OneOf` constraint, such as when
+    /// the target isn't alive or isn't a child of the scope of the relationship.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::add_id()`]
+    /// * [`EntityView::add_one_of_id()`]
+    pub fn try_add_id(self, id: impl IntoId) -> Result<Self, FlecsError> {
+        let id = *id.into();
+        check_one_of(self.world, id)?;
+        Ok(self.add_id(id))
+    }
+
+    /// Adds a component or pair to the entity, checking the constraints of the relationship
+    /// first. See [`EntityView::try_add_id()`].
+    ///
+    /// # Errors
+    ///
+    /// [`FlecsError::ConstraintViolated`] if the pair violates a `OneOf` constraint, such as when
+    /// the target isn't alive or isn't a child of the scope of the relationship.
+    pub fn try_add<T>(self) -> Result<Self, FlecsError>
+    where
+        T: ComponentOrPairId,
+    {
+        let world = self.world;
+        self.try_add_id(T::get_id(world))
+    }
+
+    /// Adds a component to the entity by its name, for tooling and scripting code that only knows
+    /// components at run time.
+    ///
+    /// The component is found with [`World::try_lookup_component()`], so the name is its path.
+    ///
+    /// # Errors
+    ///
+    /// * [`FlecsError::Unresolved`] with the name if there is no component with that name.
+    /// * [`FlecsError::ConstraintViolated`] like [`EntityView::try_add_id()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Enemy;
+    ///
+    /// let world = World::new();
+    /// world.component_named::<Enemy>("Enemy");
+    ///
+    /// let e = world.entity().add_by_name("Enemy").unwrap();
+    /// assert!(e.has::<Enemy>());
+    ///
+    /// assert_eq!(
+    ///     e.add_by_name("Friend"),
+    ///     Err(FlecsError::Unresolved("Friend".into()))
+    /// );
+    /// ```
+    pub fn add_by_name(self, name: &str) -> Result<Self, FlecsError> {
+        let component = self
+            .world
+            .try_lookup_component(name)
+            .ok_or_else(|| FlecsError::Unresolved(name.into()))?;
+        self.try_add_id(component.id())
+    }
+
     /// Adds an ID to the entity unchecked. Useful for run-time components.
     ///
     /// The provided `id` can represent various types, including a component, a pair, a tag, or another entity.
@@ -91,6 +165,62 @@ impl<'a> EntityView<'a> {
         unsafe { self.add_id_unchecked(T::get_id(world)) }
     }
 
+    /// Set the cleanup policy for when this entity is deleted.
+    ///
+    /// The policy is applied to entities that have this entity as component, tag or
+    /// relationship. Shorthand for adding the `(OnDelete, policy)` pair.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Tag;
+    ///
+    /// let world = World::new();
+    /// world.component::<Tag>().on_delete(flecs::Delete);
+    ///
+    /// let e = world.entity().add::<Tag>();
+    /// world.component::<Tag>().destruct();
+    /// assert!(!e.is_alive());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::on_delete_target()`]
+    pub fn on_delete<Policy: flecs::CleanupPolicy>(self, _policy: Policy) -> Self {
+        unsafe { self.add_id_unchecked((flecs::OnDelete::ID, Policy::ID)) }
+    }
+
+    /// Set the cleanup policy for when a target of this relationship is deleted.
+    ///
+    /// The policy is applied to entities that have a `(self, target)` pair when `target`
+    /// is deleted. Shorthand for adding the `(OnDeleteTarget, policy)` pair.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Owns;
+    ///
+    /// let world = World::new();
+    /// world.component::<Owns>().on_delete_target(flecs::Delete);
+    ///
+    /// let owner = world.entity();
+    /// let owned = world.entity().add_first::<Owns>(owner);
+    ///
+    /// owner.destruct();
+    /// assert!(!owned.is_alive());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::on_delete()`]
+    pub fn on_delete_target<Policy: flecs::CleanupPolicy>(self, _policy: Policy) -> Self {
+        unsafe { self.add_id_unchecked((flecs::OnDeleteTarget::ID, Policy::ID)) }
+    }
+
     /// Override a component on an entity.
     /// This is useful if you want to override a component that is inherited by a prefab on a per entity basis
     ///
@@ -651,6 +781,121 @@ impl<'a> EntityView<'a> {
         self.slot_of_id(T::id(world))
     }
 
+    /// Shortcut for `add_trait::<(flecs::With, entity)>()`.
+    ///
+    /// Adding this component or relationship to an entity also adds `second`. When this is a
+    /// relationship, `second` is added as a pair with the same target.
+    ///
+    /// # Arguments
+    ///
+    /// * `second`: The component that is added together with this one.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::add_with()`]
+    /// * C++ API: `entity_builder::add`
+    #[doc(alias = "entity_builder::add")]
+    pub fn add_with_id(self, second: impl Into<Entity>) -> Self {
+        unsafe { self.add_id_unchecked((ECS_WITH, second.into())) }
+    }
+
+    /// Shortcut for `add_trait::<(flecs::With, T)>()`.
+    ///
+    /// Adding this component or relationship to an entity also adds `T`, which encodes that
+    /// one component requires another. `T` must be a tag or implement [`Default`], as it is
+    /// added without a value.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T`: The component that is added together with this one.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component, Default)]
+    /// struct Ammo {
+    ///     count: u32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Weapon;
+    ///
+    /// let world = World::new();
+    /// world.component::<Ammo>().add_with::<Weapon>();
+    ///
+    /// let gun = world.entity().set(Ammo { count: 6 });
+    /// assert!(gun.has::<Weapon>());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::add_with_id()`]
+    /// * C++ API: `entity_builder::add`
+    #[doc(alias = "entity_builder::add")]
+    pub fn add_with<T: ComponentId>(self) -> Self {
+        let world = self.world;
+        self.add_with_id(T::id(world))
+    }
+
+    /// Shortcut for `add_trait::<(flecs::OneOf, entity)>()`.
+    ///
+    /// Requires the targets of this relationship to be children of `scope`, which makes the
+    /// relationship behave like an enumeration of the children. Targets in query expressions
+    /// are looked up in `scope`. Adding a target outside of `scope` is an error, see
+    /// [`EntityView::try_add_id()`]. Use `add_trait::<flecs::OneOf>()` to require the targets
+    /// to be children of the relationship itself.
+    ///
+    /// # Arguments
+    ///
+    /// * `scope`: The parent of the valid targets.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    /// let colors = world.entity_named("Colors");
+    /// let red = world.entity_named("Red").child_of_id(colors);
+    /// let tall = world.entity_named("Tall");
+    ///
+    /// let color = world.entity_named("Color").add_one_of_id(colors);
+    ///
+    /// let e = world.entity().add_id((color, red));
+    /// assert!(e.try_add_id((color, tall)).is_err());
+    ///
+    /// // targets are looked up in the scope
+    /// let query = world.query::<()>().expr("(Color, Red)").build();
+    /// assert_eq!(query.count(), 1);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::add_one_of()`]
+    /// * C++ API: `entity_builder::add`
+    #[doc(alias = "entity_builder::add")]
+    pub fn add_one_of_id(self, scope: impl Into<Entity>) -> Self {
+        unsafe { self.add_id_unchecked((ECS_ONE_OF, scope.into())) }
+    }
+
+    /// Shortcut for `add_trait::<(flecs::OneOf, T)>()`.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T`: The parent of the valid targets.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::add_one_of_id()`]
+    /// * C++ API: `entity_builder::add`
+    #[doc(alias = "entity_builder::add")]
+    pub fn add_one_of<T: ComponentId>(self) -> Self {
+        let world = self.world;
+        self.add_one_of_id(T::id(world))
+    }
+
     /// Shortcut for `add_id((flecs::SlotOf::ID, target(ChildOf)))`.
     ///
     /// # See also
@@ -929,6 +1174,88 @@ impl<'a> EntityView<'a> {
         self
     }
 
+    /// Emplaces a component of type `T` on the entity.
+    ///
+    /// Unlike [`EntityView::set`], the component is never default constructed, not even when the
+    /// world is deferred: `component` is moved straight into the component storage. This makes it
+    /// the preferred way to add large components or components that don't implement `Default`.
+    ///
+    /// If the entity already has the component, the old value is dropped and replaced.
+    ///
+    /// # Arguments
+    ///
+    /// * `component` - The component to move into the entity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Inventory {
+    ///     items: Vec<u32>,
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// let e = world.entity().emplace(Inventory {
+    ///     items: vec![1, 2, 3],
+    /// });
+    ///
+    /// e.get::<&Inventory>(|inv| {
+    ///     assert_eq!(inv.items.len(), 3);
+    /// });
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::emplace_id`]
+    /// * [`EntityView::set`]
+    /// * C++ API: `entity_builder::emplace`
+    #[doc(alias = "entity_builder::emplace")]
+    pub fn emplace<T: ComponentId + DataComponent>(self, component: T) -> Self {
+        emplace_helper(
+            self.world.world_ptr_mut(),
+            *self.id,
+            component,
+            T::id(self.world),
+        );
+        self
+    }
+
+    /// Emplaces the data of the specified id. Can be a pair or Component.
+    ///
+    /// See [`EntityView::emplace`] for how this differs from [`EntityView::set_id`].
+    ///
+    /// # Panics
+    ///
+    /// Panics when the type of `data` doesn't match the data type of `id`.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::emplace`]
+    /// * [`EntityView::set_id`]
+    /// * C++ API: `entity_builder::emplace`
+    #[doc(alias = "entity_builder::emplace")]
+    pub fn emplace_id<T>(self, data: T, id: impl IntoId) -> Self
+    where
+        T: ComponentId + DataComponent,
+    {
+        let world = self.world.world_ptr_mut();
+        let id = *id.into();
+        let data_id = T::id(self.world);
+        let id_data_id = unsafe { sys::ecs_get_typeid(world, id) };
+
+        if data_id != id_data_id {
+            panic!(
+                "Data type does not match id type. For pairs this is the first element occurrence that is not a zero-sized type (ZST)."
+            );
+        }
+
+        emplace_helper(world, *self.id, data, id);
+        self
+    }
+
     /// Set a pair for an entity.
     /// This operation sets the pair value, and uses the first non tag / ZST as type.
     /// If the data is an flecs enum (Repr(C)), it will use the enum variant id.
@@ -1143,16 +1470,28 @@ impl<'a> EntityView<'a> {
 
     /// Sets the name of the entity.
     ///
+    /// When the entity already had a different name, [`Renamed`] is emitted for the entity.
+    ///
     /// # Arguments
     ///
     /// * `name` - A string slice that holds the name to be set.
     ///
     /// # See also
     ///
+    /// * [`World::rename()`]
     /// * C++ API: `entity_builder::set_name`
     #[doc(alias = "entity_builder::set_name")]
     pub fn set_name(self, name: &str) -> Self {
-        let name = compact_str::format_compact!("{}\0", name);
+        // only emit when someone could be observing the event
+        let previous = if Renamed::is_registered_with_world(self.world) {
+            unsafe { self.get_name_cstr() }
+                .filter(|previous| previous.to_bytes() != name.as_bytes())
+                .map(|previous| previous.to_string_lossy().into_owned())
+        } else {
+            None
+        };
+
+        let name = SmallCString::new(name);
 
         unsafe {
             sys::ecs_set_name(
@@ -1161,6 +1500,10 @@ impl<'a> EntityView<'a> {
                 name.as_ptr() as *const _,
             );
         }
+
+        if let Some(previous) = previous {
+            self.emit(&Renamed { previous });
+        }
         self
     }
 
@@ -1183,7 +1526,7 @@ impl<'a> EntityView<'a> {
     /// * C++ API: `entity_builder::set_alias`
     #[doc(alias = "entity_builder::set_alias")]
     pub fn set_alias(self, name: &str) -> Self {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
 
         unsafe {
             sys::ecs_set_alias(
@@ -1293,6 +1636,75 @@ impl<'a> EntityView<'a> {
         self
     }
 
+    /// Enables the entity and its children, recursively.
+    ///
+    /// Children that were disabled on their own are enabled as well.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::disable_recursive()`]
+    /// * [`EntityView::enable_self()`]
+    pub fn enable_recursive(self) -> Self {
+        self.set_enabled_recursive(true);
+        self
+    }
+
+    /// Disables the entity and its children, recursively.
+    ///
+    /// Flecs doesn't propagate [`flecs::Disabled`] over the `ChildOf` hierarchy, so disabling a
+    /// parent with [`EntityView::disable_self()`] leaves its children matched by queries. This
+    /// disables the whole subtree, for example to hide a level or a UI panel. Disabled entities
+    /// can still be matched by queries with [`QueryFlags::MatchDisabled`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// let level = world.entity().set(Position { x: 0.0, y: 0.0 });
+    /// world
+    ///     .entity()
+    ///     .child_of_id(level)
+    ///     .set(Position { x: 1.0, y: 0.0 });
+    ///
+    /// let query = world.new_query::<&Position>();
+    /// assert_eq!(query.count(), 2);
+    ///
+    /// level.disable_recursive();
+    /// assert_eq!(query.count(), 0);
+    ///
+    /// level.enable_recursive();
+    /// assert_eq!(query.count(), 2);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::enable_recursive()`]
+    /// * [`EntityView::disable_self()`]
+    pub fn disable_recursive(self) -> Self {
+        self.set_enabled_recursive(false);
+        self
+    }
+
+    fn set_enabled_recursive(self, enabled: bool) {
+        // toggling moves the entities to other tables, defer it so the children can be iterated
+        // and the subtree is updated in one go
+        self.world.defer_begin();
+        let mut stack = alloc::vec![self.id];
+        while let Some(id) = stack.pop() {
+            unsafe { sys::ecs_enable(self.world.world_ptr_mut(), *id, enabled) }
+            EntityView::new_from(self.world, id).each_child(|child| stack.push(child.id));
+        }
+        self.world.defer_end();
+    }
+
     /// Disables an ID which represents a component or pair.
     ///
     /// This sets the enabled bit for this ID. If this is the first time the ID is
@@ -1617,11 +2029,41 @@ impl<'a> EntityView<'a> {
     ///
     /// A reference allows for quick and safe access to a component value, and is
     /// a faster alternative to repeatedly calling `get` for the same component.
+    /// The reference caches the location of the component and stays valid when the
+    /// entity moves to a different table.
     ///
     /// - `T`: Component for which to get a reference.
     ///
     /// Returns: The reference component.
     ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Player;
+    ///
+    /// let world = World::new();
+    ///
+    /// let player = world.entity().set(Position { x: 1.0, y: 2.0 });
+    /// let mut pos = player.get_ref::<Position>();
+    ///
+    /// // moving the entity to another table doesn't invalidate the reference
+    /// player.add::<Player>();
+    ///
+    /// pos.get(|p| p.x += 1.0);
+    /// player.get::<&Position>(|p| assert_eq!(p.x, 2.0));
+    ///
+    /// player.remove::<Position>();
+    /// assert!(pos.try_get(|p| p.x).is_none());
+    /// ```
+    ///
     /// # See also
     ///
     /// * C++ API: `entity::get_ref`
@@ -1740,7 +2182,64 @@ impl<'a> EntityView<'a> {
     ///
     /// * C++ API: `entity::destruct`
     #[doc(alias = "entity::destruct")]
+    #[cfg_attr(feature = "flecs_entity_audit", track_caller)]
     pub fn destruct(self) {
+        #[cfg(feature = "flecs_entity_audit")]
+        audit_deleted(self.world.world_ptr_mut(), self.id);
         unsafe { sys::ecs_delete(self.world.world_ptr_mut(), *self.id) }
     }
 }
+
+/// The entity the targets of `relationship` must be children of, or 0 if it has no
+/// [`flecs::OneOf`] constraint.
+fn one_of_scope(world: WorldRef, relationship: Entity) -> Entity {
+    let world_ptr = world.world_ptr();
+    unsafe {
+        if !sys::ecs_is_alive(world_ptr, *relationship) {
+            Entity::null()
+        } else if sys::ecs_has_id(world_ptr, *relationship, ECS_ONE_OF) {
+            relationship
+        } else {
+            Entity::new(sys::ecs_get_target(world_ptr, *relationship, ECS_ONE_OF, 0))
+        }
+    }
+}
+
+/// Check that the target of a pair is a child of the scope of its [`flecs::OneOf`] relationship.
+fn check_one_of(world: WorldRef, id: u64) -> Result<(), FlecsError> {
+    let id = Id::new(id);
+    if !id.is_pair() {
+        return Ok(());
+    }
+    let (relationship, target) = (id.first(), id.second());
+    if target == flecs::Wildcard::ID || target == flecs::Any::ID {
+        return Ok(());
+    }
+
+    let scope = one_of_scope(world, relationship);
+    if scope == 0 {
+        return Ok(());
+    }
+    let world_ptr = world.world_ptr();
+    // the pair only stores the index of the target, so look up its current version
+    let alive = unsafe { sys::ecs_get_alive(world_ptr, *target) };
+    if alive == 0 {
+        return Err(FlecsError::ConstraintViolated(alloc::format!(
+            "OneOf constraint violated: target #{} is not alive",
+            *target
+        )));
+    }
+    if unsafe { sys::ecs_has_id(world_ptr, alive, ecs_pair(ECS_CHILD_OF, *scope)) } {
+        return Ok(());
+    }
+
+    let path = |e: Entity| {
+        EntityView::new_from(world, e)
+            .path()
+            .unwrap_or_else(|| alloc::format!("#{}", *e))
+    };
+    let (relationship, target, scope) = (path(relationship), path(target), path(scope));
+    Err(FlecsError::ConstraintViolated(alloc::format!(
+        "OneOf constraint violated: ({relationship}, {target}): '{target}' is not a child of '{scope}'"
+    )))
+}
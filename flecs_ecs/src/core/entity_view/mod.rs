@@ -1,10 +1,18 @@
 //! `EntityViews` are wrappers around an [`Entity`][super::Entity] id with the world. It provides methods to build and interact with entities.
 
 mod bulk_entity_builder;
+mod child_builder;
+mod entity_modifier;
+mod entity_record;
 mod entity_view_const;
 mod entity_view_impl;
 mod entity_view_mut;
 mod macros;
+mod typed_entity;
 
+pub use child_builder::ChildBuilder;
+pub use entity_modifier::EntityModifier;
+pub use entity_record::EntityRecord;
 pub use entity_view_const::EntityView;
 pub use entity_view_const::EntityViewGet;
+pub use typed_entity::{BundleContains, EntityBundle, TypedEntity, TypedEntityGet};
@@ -0,0 +1,229 @@
+//! Entity handles that are guaranteed to have a set of components.
+
+use core::marker::PhantomData;
+
+use crate::core::*;
+
+/// A set of components an entity is guaranteed to have, used as the marker of a [`TypedEntity`].
+///
+/// Implement this with the [`entity_bundle!`](crate::entity_bundle) macro, which also implements
+/// [`BundleContains`] for each of the components.
+pub trait EntityBundle {
+    /// Test if the entity has all components of the bundle.
+    fn is_matched_by(entity: EntityView) -> bool;
+}
+
+/// Marks that the bundle contains component `C`, which allows getting `C` from a [`TypedEntity`]
+/// without checking if the entity has it.
+pub trait BundleContains<C: ComponentOrPairId>: EntityBundle {}
+
+/// Implement [`EntityBundle`] and [`BundleContains`] for a marker type.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::entity_bundle;
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// #[derive(Component)]
+/// struct Alive;
+///
+/// struct Player;
+///
+/// entity_bundle!(Player: Health, Alive);
+/// ```
+#[macro_export]
+macro_rules! entity_bundle {
+    ($bundle:ty: $($component:ty),+ $(,)?) => {
+        impl $crate::core::EntityBundle for $bundle {
+            fn is_matched_by(entity: $crate::core::EntityView) -> bool {
+                true $(&& entity.has::<$component>())+
+            }
+        }
+
+        $(
+            impl $crate::core::BundleContains<$component> for $bundle {}
+        )+
+    };
+}
+
+/// An entity handle that is guaranteed to have the components of bundle `B`.
+///
+/// The components are checked once when the handle is created with [`EntityView::try_typed()`]
+/// or [`EntityView::typed()`], after which the components of the bundle can be read with
+/// [`TypedEntityGet::get()`] without going through an [`Option`]. This allows functions to express
+/// the components they rely on in their signature, e.g. `fn heal(player: TypedEntity<Player>)`.
+///
+/// The handle stores the entity id including its generation. Removing a component of the bundle
+/// or deleting the entity while holding the handle breaks the guarantee, in which case getting the
+/// component panics like [`EntityViewGet::get()`] does.
+pub struct TypedEntity<'a, B: EntityBundle> {
+    entity: EntityView<'a>,
+    _bundle: PhantomData<fn() -> B>,
+}
+
+impl<B: EntityBundle> Clone for TypedEntity<'_, B> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<B: EntityBundle> Copy for TypedEntity<'_, B> {}
+
+impl<'a, B: EntityBundle> TypedEntity<'a, B> {
+    /// Get the entity view of the handle.
+    pub fn entity(self) -> EntityView<'a> {
+        self.entity
+    }
+}
+
+impl<'a, B: EntityBundle> core::ops::Deref for TypedEntity<'a, B> {
+    type Target = EntityView<'a>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        &self.entity
+    }
+}
+
+impl<'a, B: EntityBundle> From<TypedEntity<'a, B>> for EntityView<'a> {
+    fn from(typed: TypedEntity<'a, B>) -> Self {
+        typed.entity
+    }
+}
+
+impl<B: EntityBundle> From<TypedEntity<'_, B>> for Entity {
+    #[inline]
+    fn from(typed: TypedEntity<'_, B>) -> Self {
+        typed.entity.id()
+    }
+}
+
+impl<B: EntityBundle> core::fmt::Debug for TypedEntity<'_, B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{:?}", self.entity)
+    }
+}
+
+impl<B: EntityBundle> core::fmt::Display for TypedEntity<'_, B> {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        write!(f, "{}", self.entity)
+    }
+}
+
+/// Get the components of the bundle of a [`TypedEntity`].
+pub trait TypedEntityGet<Return> {
+    /// The bundle of the handle.
+    type Bundle: EntityBundle;
+
+    /// Get an immutable reference to a component of the bundle in a callback.
+    ///
+    /// # Panics
+    ///
+    /// - When the component was removed from the entity after the handle was created.
+    /// - When the entity is moved to another table inside the callback, see [`EntityViewGet::get()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::entity_bundle;
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// struct Player;
+    ///
+    /// entity_bundle!(Player: Health);
+    ///
+    /// fn health(player: TypedEntity<Player>) -> u32 {
+    ///     player.get::<Health>(|health| health.0)
+    /// }
+    ///
+    /// let world = World::new();
+    ///
+    /// let player = world.entity().set(Health(100));
+    /// assert_eq!(health(player.typed::<Player>()), 100);
+    ///
+    /// assert!(world.entity().try_typed::<Player>().is_none());
+    /// ```
+    fn get<C>(self, callback: impl FnOnce(&C::CastType) -> Return) -> Return
+    where
+        C: ComponentOrPairId + DataComponent,
+        Self::Bundle: BundleContains<C>;
+
+    /// Get a mutable reference to a component of the bundle in a callback.
+    ///
+    /// # Panics
+    ///
+    /// - When the component was removed from the entity after the handle was created.
+    /// - When the entity is moved to another table inside the callback, see [`EntityViewGet::get()`].
+    fn get_mut<C>(self, callback: impl FnOnce(&mut C::CastType) -> Return) -> Return
+    where
+        C: ComponentOrPairId + DataComponent,
+        Self::Bundle: BundleContains<C>;
+}
+
+impl<Return, B: EntityBundle> TypedEntityGet<Return> for TypedEntity<'_, B> {
+    type Bundle = B;
+
+    fn get<C>(self, callback: impl FnOnce(&C::CastType) -> Return) -> Return
+    where
+        C: ComponentOrPairId + DataComponent,
+        B: BundleContains<C>,
+    {
+        self.entity.get::<&C>(|component| callback(component))
+    }
+
+    fn get_mut<C>(self, callback: impl FnOnce(&mut C::CastType) -> Return) -> Return
+    where
+        C: ComponentOrPairId + DataComponent,
+        B: BundleContains<C>,
+    {
+        self.entity.get::<&mut C>(|component| callback(component))
+    }
+}
+
+impl<'a> EntityView<'a> {
+    /// Create a handle that is guaranteed to have the components of bundle `B`.
+    ///
+    /// Returns [`None`] if the entity is not alive or misses a component of the bundle.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::typed()`]
+    /// * [`TypedEntity`]
+    pub fn try_typed<B: EntityBundle>(self) -> Option<TypedEntity<'a, B>> {
+        if self.is_alive() && B::is_matched_by(self) {
+            Some(TypedEntity {
+                entity: self,
+                _bundle: PhantomData,
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Create a handle that is guaranteed to have the components of bundle `B`.
+    ///
+    /// # Panics
+    ///
+    /// If the entity is not alive or misses a component of the bundle.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::try_typed()`]
+    /// * [`TypedEntity`]
+    pub fn typed<B: EntityBundle>(self) -> TypedEntity<'a, B> {
+        self.try_typed().unwrap_or_else(|| {
+            panic!(
+                "entity {} does not have all components of `{}`",
+                self.id,
+                core::any::type_name::<B>()
+            )
+        })
+    }
+}
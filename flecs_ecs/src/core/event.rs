@@ -241,6 +241,8 @@ impl<'a, T: ComponentId> EventBuilder<'a, T> {
         unsafe {
             sys::ecs_enqueue(world.world_ptr_mut(), desc);
             if !T::IS_TAG {
+                // the command moved out of the data, which is freed instead of overwritten
+                forget_vacant(sys::ecs_get_type_info(world.world_ptr(), desc.event));
                 dealloc(desc.param as *mut u8, Layout::new::<T>());
             }
         };
@@ -8,12 +8,16 @@ use crate::sys;
 
 pub trait FlecsTrait {}
 
+/// Cleanup policy used with [`OnDelete`] and [`OnDeleteTarget`].
+/// Implemented by [`Remove`], [`Delete`] and [`Panic`].
+pub trait CleanupPolicy: FlecsConstantId {}
+
 macro_rules! create_pre_registered_component {
     ($struct_name:ident, $const_name:ident) => {
         create_pre_registered_component!($struct_name, $const_name, "");
     };
     ($struct_name:ident, $const_name:ident, $doc:tt) => {
-        #[derive(Debug, Default)]
+        #[derive(Debug, Default, Clone, Copy)]
         #[allow(clippy::empty_docs)]
         #[doc = $doc]
         pub struct $struct_name;
@@ -31,6 +35,17 @@ macro_rules! create_pre_registered_component {
             }
         }
 
+        impl From<$struct_name> for flecs_ecs::core::Id {
+            #[inline]
+            fn from(_view: $struct_name) -> Self {
+                flecs_ecs::core::Id($struct_name::ID)
+            }
+        }
+
+        impl flecs_ecs::core::IntoId for $struct_name {
+            const IS_PAIR: bool = false;
+        }
+
         impl Deref for $struct_name {
             type Target = u64;
             #[inline(always)]
@@ -357,6 +372,10 @@ create_pre_registered_component!(Remove, ECS_REMOVE);
 create_pre_registered_component!(Delete, ECS_DELETE);
 create_pre_registered_component!(Panic, ECS_PANIC);
 
+impl CleanupPolicy for Remove {}
+impl CleanupPolicy for Delete {}
+impl CleanupPolicy for Panic {}
+
 // Builtin relationships
 create_pre_registered_component!(
     ChildOf,
@@ -432,7 +451,10 @@ pub use script::Script;
 create_pre_registered_component!(
     Sparse,
     ECS_SPARSE,
-    "Component trait. Configures a component to use sparse storage."
+    "Component trait. Configures a component to use sparse storage. \
+    Values of sparse components are stored outside of tables, so their address doesn't change \
+    when the entity moves to another table. The trait must be added before the component is \
+    used, for example from [`OnComponentRegistration`](crate::core::OnComponentRegistration)."
 );
 create_pre_registered_component!(
     Union,
@@ -0,0 +1,421 @@
+//! Keep a world alive across a reload of the library that registers its components, systems and
+//! observers, see [`World::detach_callbacks()`] and [`World::rebind_callbacks()`].
+
+use crate::core::*;
+use crate::sys;
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// Callbacks of a world that were detached by [`World::detach_callbacks()`].
+#[derive(Default)]
+pub(crate) struct DetachedCallbacks {
+    /// Systems and observers of which the callbacks were detached.
+    callbacks: Vec<Entity>,
+    /// The systems and observers in `callbacks` that were enabled before they were detached.
+    enabled: Vec<Entity>,
+    /// Components of which the hooks were detached.
+    components: Vec<Entity>,
+    /// The components in `components` of which the hooks were registered again.
+    rebound: Vec<Entity>,
+}
+
+fn detached_mut<'a>(world: *mut sys::ecs_world_t) -> Option<&'a mut DetachedCallbacks> {
+    unsafe { (*World::get_context(world)).detached_callbacks.as_mut() }
+}
+
+/// Set the hooks of a component.
+///
+/// A component of which the hooks were detached is usually in use already, which
+/// `ecs_set_hooks_id` doesn't allow, so its hooks are replaced directly.
+pub(crate) fn set_hooks(
+    world: *mut sys::ecs_world_t,
+    id: sys::ecs_entity_t,
+    hooks: &sys::ecs_type_hooks_t,
+) {
+    let is_detached =
+        detached_mut(world).is_some_and(|detached| detached.components.contains(&Entity::new(id)));
+    if is_detached {
+        unsafe { replace_hooks(world, id, hooks) };
+    } else {
+        unsafe { sys::ecs_set_hooks_id(world, id, hooks) };
+    }
+}
+
+/// Register the hooks of an existing component again, if they were detached.
+pub(crate) fn rebind_hooks(
+    world: *mut sys::ecs_world_t,
+    id: sys::ecs_entity_t,
+    hooks: impl FnOnce() -> sys::ecs_type_hooks_t,
+) {
+    let Some(detached) = detached_mut(world) else {
+        return;
+    };
+    let id = Entity::new(id);
+    if !detached.components.contains(&id) || detached.rebound.contains(&id) {
+        return;
+    }
+    detached.rebound.push(id);
+    unsafe { replace_hooks(world, *id, &hooks()) };
+}
+
+/// Copy the hooks that are set, like `ecs_set_hooks_id` does, without the check that the
+/// component isn't in use. Hooks that flecs derived from the others stay as they are.
+unsafe fn replace_hooks(
+    world: *mut sys::ecs_world_t,
+    id: sys::ecs_entity_t,
+    hooks: &sys::ecs_type_hooks_t,
+) {
+    let type_info = unsafe { sys::ecs_get_type_info(world, id) } as *mut sys::ecs_type_info_t;
+    if type_info.is_null() {
+        return;
+    }
+    let current = unsafe { &mut (*type_info).hooks };
+
+    macro_rules! replace {
+        ($($field:ident),*) => {
+            $(if hooks.$field.is_some() {
+                current.$field = hooks.$field;
+            })*
+        };
+    }
+    replace!(
+        ctor,
+        dtor,
+        copy,
+        move_,
+        copy_ctor,
+        move_ctor,
+        ctor_move_dtor,
+        move_dtor,
+        on_add,
+        on_set,
+        on_remove,
+        binding_ctx_free,
+        lifecycle_ctx_free
+    );
+    if !hooks.binding_ctx.is_null() {
+        current.binding_ctx = hooks.binding_ctx;
+    }
+    if !hooks.lifecycle_ctx.is_null() {
+        current.lifecycle_ctx = hooks.lifecycle_ctx;
+    }
+    current.flags = lifecycle_flags(current);
+}
+
+/// The flags flecs sets for the ctor, dtor, copy and move hooks that are set.
+fn lifecycle_flags(hooks: &sys::ecs_type_hooks_t) -> sys::ecs_flags32_t {
+    let set = [
+        (hooks.ctor.is_some(), sys::ECS_TYPE_HOOK_CTOR),
+        (hooks.dtor.is_some(), sys::ECS_TYPE_HOOK_DTOR),
+        (hooks.copy.is_some(), sys::ECS_TYPE_HOOK_COPY),
+        (hooks.move_.is_some(), sys::ECS_TYPE_HOOK_MOVE),
+        (hooks.copy_ctor.is_some(), sys::ECS_TYPE_HOOK_COPY_CTOR),
+        (hooks.move_ctor.is_some(), sys::ECS_TYPE_HOOK_MOVE_CTOR),
+        (
+            hooks.ctor_move_dtor.is_some(),
+            sys::ECS_TYPE_HOOK_CTOR_MOVE_DTOR,
+        ),
+        (hooks.move_dtor.is_some(), sys::ECS_TYPE_HOOK_MOVE_DTOR),
+    ];
+    set.into_iter()
+        .filter(|&(is_set, _)| is_set)
+        .fold(hooks.flags & !sys::ECS_TYPE_HOOKS, |flags, (_, flag)| {
+            flags | flag
+        })
+}
+
+/// Free the Rust closures of a system or observer and clear its callbacks, if it was created from
+/// Rust.
+macro_rules! detach_poly {
+    ($poly:expr, $callback:ident) => {{
+        let poly = unsafe { &mut *$poly };
+        let is_rust = poly.callback_ctx_free.is_some() || poly.run_ctx_free.is_some();
+        if is_rust {
+            if let Some(free) = poly.callback_ctx_free.take() {
+                if !poly.callback_ctx.is_null() {
+                    unsafe { free(poly.callback_ctx) };
+                }
+            }
+            if let Some(free) = poly.run_ctx_free.take() {
+                if !poly.run_ctx.is_null() {
+                    unsafe { free(poly.run_ctx) };
+                }
+            }
+            poly.callback_ctx = core::ptr::null_mut();
+            poly.run_ctx = core::ptr::null_mut();
+            poly.$callback = None;
+            poly.run = None;
+        }
+        is_rust
+    }};
+}
+
+fn entities_with(world: *mut sys::ecs_world_t, id: sys::ecs_id_t) -> Vec<Entity> {
+    let mut entities = Vec::new();
+    unsafe {
+        let mut it = sys::ecs_each_id(world, id);
+        while sys::ecs_each_next(&mut it) {
+            entities.extend((0..it.count as usize).map(|i| Entity::new(*it.entities.add(i))));
+        }
+    }
+    entities
+}
+
+/// Detach the callback of a system or observer. Returns false if it wasn't created from Rust.
+fn detach_callback(world: *mut sys::ecs_world_t, entity: Entity) -> bool {
+    #[cfg(feature = "flecs_system")]
+    {
+        let system = unsafe { sys::ecs_system_get(world, *entity) } as *mut sys::ecs_system_t;
+        if !system.is_null() {
+            return detach_poly!(system, action);
+        }
+    }
+    let observer = unsafe { sys::ecs_observer_get(world, *entity) } as *mut sys::ecs_observer_t;
+    !observer.is_null() && detach_poly!(observer, callback)
+}
+
+/// Whether a detached system or observer was created again.
+fn is_bound(world: *mut sys::ecs_world_t, entity: Entity) -> bool {
+    #[cfg(feature = "flecs_system")]
+    {
+        let system = unsafe { sys::ecs_system_get(world, *entity) };
+        if !system.is_null() {
+            return unsafe { (*system).action.is_some() || (*system).run.is_some() };
+        }
+    }
+    let observer = unsafe { sys::ecs_observer_get(world, *entity) };
+    !observer.is_null() && unsafe { (*observer).callback.is_some() || (*observer).run.is_some() }
+}
+
+/// Free the closures of the hooks of a component and clear the hooks that run them.
+///
+/// The ctor, dtor, copy and move hooks are cleared as well, so flecs copies the memory of values
+/// instead until the component is registered again. The ctor flecs sets for types without one is
+/// kept, as registering the component again doesn't set it.
+unsafe fn detach_hooks(type_info: *mut sys::ecs_type_info_t) {
+    let hooks = unsafe { &mut (*type_info).hooks };
+
+    let is_flecs_ctor = hooks.ctor.is_some_and(|ctor| {
+        core::ptr::fn_addr_eq(
+            ctor,
+            sys::flecs_default_ctor
+                as unsafe extern "C-unwind" fn(
+                    *mut core::ffi::c_void,
+                    i32,
+                    *const sys::ecs_type_info_t,
+                ),
+        )
+    });
+    if !is_flecs_ctor {
+        hooks.ctor = None;
+    }
+    hooks.dtor = None;
+    hooks.copy = None;
+    hooks.move_ = None;
+    hooks.copy_ctor = None;
+    hooks.move_ctor = None;
+    hooks.ctor_move_dtor = None;
+    hooks.move_dtor = None;
+    hooks.flags = lifecycle_flags(hooks);
+
+    if let Some(free) = hooks.binding_ctx_free.take() {
+        if !hooks.binding_ctx.is_null() {
+            unsafe { free(hooks.binding_ctx) };
+        }
+    }
+    hooks.binding_ctx = core::ptr::null_mut();
+    hooks.on_add = None;
+    hooks.on_set = None;
+    hooks.on_remove = None;
+
+    if let Some(free) = hooks.lifecycle_ctx_free.take() {
+        if !hooks.lifecycle_ctx.is_null() {
+            unsafe { free(hooks.lifecycle_ctx) };
+        }
+    }
+    hooks.lifecycle_ctx = core::ptr::null_mut();
+}
+
+impl World {
+    /// Detach the Rust callbacks of the world, before the library that created them is unloaded.
+    ///
+    /// The closures of systems, observers and component hooks are dropped while their code is
+    /// still loaded, and the function pointers to them are cleared. Systems and observers are
+    /// disabled until [`World::rebind_callbacks()`] binds them again. Entities, component values
+    /// and component ids stay as they are.
+    ///
+    /// Components are looked up again when they are used after this, so a type keeps its id even
+    /// though a reloaded library numbers its types from scratch. State of addons that is kept up
+    /// to date by callbacks, like the guid and name indices, is dropped as well, so these addons
+    /// have to be enabled again.
+    ///
+    /// Constructors, destructors, copies and moves of components are detached too, and flecs
+    /// copies the memory of values instead. The world should still not be progressed or modified
+    /// until the callbacks are bound again, since values that are removed in the meantime aren't
+    /// dropped and values that are added aren't constructed.
+    ///
+    /// # See also
+    ///
+    /// * [`World::rebind_callbacks()`]
+    pub fn detach_callbacks(&self) {
+        let world = self.world_ptr_mut();
+        let mut detached = self
+            .world_ctx_mut()
+            .detached_callbacks
+            .take()
+            .unwrap_or_default();
+
+        #[allow(unused_mut)]
+        let mut polys = entities_with(world, ecs_pair(flecs::Poly::ID, flecs::Observer::ID));
+        #[cfg(feature = "flecs_system")]
+        polys.extend(entities_with(
+            world,
+            ecs_pair(flecs::Poly::ID, flecs::system::System::ID),
+        ));
+
+        for entity in polys {
+            let is_rust = detach_callback(world, entity);
+            if !is_rust {
+                continue;
+            }
+
+            if !unsafe { sys::ecs_has_id(world, *entity, flecs::Disabled::ID) } {
+                unsafe { sys::ecs_enable(world, *entity, false) };
+                detached.enabled.push(entity);
+            }
+            detached.callbacks.push(entity);
+        }
+
+        let ctx = self.world_ctx_mut();
+        let mut components: Vec<Entity> = ctx
+            .components_array
+            .iter()
+            .chain(ctx.components.values())
+            .filter(|&&id| id != 0)
+            .map(|&id| Entity::new(id))
+            .collect();
+        components.sort_unstable();
+        components.dedup();
+        for id in components {
+            if !unsafe { sys::ecs_is_alive(world, *id) } {
+                continue;
+            }
+            let type_info =
+                unsafe { sys::ecs_get_type_info(world, *id) } as *mut sys::ecs_type_info_t;
+            if type_info.is_null() || detached.components.contains(&id) {
+                continue;
+            }
+            unsafe { detach_hooks(type_info) };
+            detached.components.push(id);
+            detached.rebound.retain(|&rebound| rebound != id);
+        }
+
+        ctx.components_array.fill(0);
+        ctx.components.clear();
+
+        ctx.name_index = None;
+        ctx.deferred_runs.clear();
+        #[cfg(feature = "flecs_spatial")]
+        {
+            ctx.spatial = None;
+        }
+        #[cfg(feature = "flecs_json")]
+        {
+            ctx.migrations.clear();
+            ctx.guids = None;
+        }
+        #[cfg(all(feature = "flecs_script", feature = "flecs_system"))]
+        {
+            ctx.script_callbacks = Default::default();
+        }
+        #[cfg(feature = "std")]
+        {
+            ctx.command_sender = None;
+        }
+        #[cfg(feature = "flecs_async")]
+        {
+            ctx.task_spawner = None;
+        }
+
+        ctx.detached_callbacks = Some(detached);
+    }
+
+    /// Bind the Rust callbacks of the world again after [`World::detach_callbacks()`], once the
+    /// library that creates them is loaded again.
+    ///
+    /// `register` registers the components, systems and observers again, usually by calling the
+    /// same code that set up the world before the reload. Components keep their ids and get the
+    /// hooks of the reloaded code. Systems and observers that are created with the name they had
+    /// before, and modules that are imported again, keep their entities and get the callbacks of
+    /// the reloaded code. Systems and observers that aren't created again, which includes all
+    /// anonymous ones, are deleted.
+    ///
+    /// Components that aren't registered again by `register` get their hooks back when they are
+    /// registered later, and must not be used before that.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    /// }
+    ///
+    /// fn register(world: &World) {
+    ///     world
+    ///         .system_named::<&mut Position>("Move")
+    ///         .each(|p| p.x += 1.0);
+    /// }
+    ///
+    /// let world = World::new();
+    /// register(&world);
+    /// let e = world.entity().set(Position { x: 0.0 });
+    /// let position = world.component_id::<Position>();
+    ///
+    /// world.detach_callbacks();
+    /// // unload and load the library
+    /// world.rebind_callbacks(register);
+    ///
+    /// assert_eq!(world.component_id::<Position>(), position);
+    /// world.progress();
+    /// e.get::<&Position>(|p| assert_eq!(p.x, 1.0));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::detach_callbacks()`]
+    pub fn rebind_callbacks(&self, register: impl FnOnce(&World)) {
+        ecs_assert!(
+            self.world_ctx().detached_callbacks.is_some(),
+            FlecsErrorCode::InvalidOperation,
+            "callbacks have to be detached before they are bound again"
+        );
+
+        register(self);
+
+        let world = self.world_ptr_mut();
+        let Some(mut detached) = self.world_ctx_mut().detached_callbacks.take() else {
+            return;
+        };
+
+        for entity in core::mem::take(&mut detached.callbacks) {
+            if !unsafe { sys::ecs_is_alive(world, *entity) } {
+                continue;
+            }
+            if !is_bound(world, entity) {
+                unsafe { sys::ecs_delete(world, *entity) };
+            } else if detached.enabled.contains(&entity) {
+                unsafe { sys::ecs_enable(world, *entity, true) };
+            }
+        }
+        detached.enabled.clear();
+
+        let rebound = core::mem::take(&mut detached.rebound);
+        detached.components.retain(|id| !rebound.contains(id));
+        if !detached.components.is_empty() {
+            self.world_ctx_mut().detached_callbacks = Some(detached);
+        }
+    }
+}
@@ -6,6 +6,7 @@ use core::{
 };
 
 use crate::core::*;
+use crate::sys;
 
 /// An Identifier for what could represent either what [`Entity`]
 /// as well as an ECS relationship pair and can have optional id flags.
@@ -20,6 +21,178 @@ impl Id {
         Self(id)
     }
 
+    /// Create a pair id from a relationship and a target.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// let world = World::new();
+    /// let likes = world.entity();
+    /// let bob = world.entity();
+    ///
+    /// let pair = Id::pair(likes, bob);
+    /// assert!(pair.is_pair());
+    /// assert_eq!(pair.first(), likes);
+    /// assert_eq!(pair.second(), bob);
+    ///
+    /// let alice = world.entity().add_id(pair);
+    /// assert!(alice.has_id((likes, bob)));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::pair`
+    #[doc(alias = "world::pair")]
+    /// * C API: `ecs_pair`
+    #[doc(alias = "ecs_pair")]
+    #[inline]
+    pub fn pair(first: impl Into<Entity>, second: impl Into<Entity>) -> Self {
+        Self(ecs_pair(*first.into(), *second.into()))
+    }
+
+    /// Return the id with the auto override flag added.
+    ///
+    /// Adding the resulting id to a prefab makes instances get their own copy of the component.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::auto_override_id()`]
+    /// * C API: `ECS_AUTO_OVERRIDE`
+    #[doc(alias = "ECS_AUTO_OVERRIDE")]
+    #[inline]
+    pub fn with_override(id: impl IntoId) -> Self {
+        Self(ECS_AUTO_OVERRIDE | *id.into())
+    }
+
+    /// Return the id with the toggle flag added.
+    ///
+    /// Adding the resulting id to an entity allows the component to be enabled and disabled.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::enable_id()`]
+    /// * C API: `ECS_TOGGLE`
+    #[doc(alias = "ECS_TOGGLE")]
+    #[inline]
+    pub fn toggle(id: impl IntoId) -> Self {
+        Self(ECS_TOGGLE | *id.into())
+    }
+
+    /// Get the flags of the id, or 0 if the id has no flags.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `id::flags`
+    #[doc(alias = "id::flags")]
+    #[inline]
+    pub fn flags(self) -> Id {
+        Self(self.0 & RUST_ecs_id_FLAGS_MASK)
+    }
+
+    /// Test if the id has all of the specified flags.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `id::has_flags`
+    #[doc(alias = "id::has_flags")]
+    #[inline]
+    pub fn has_flags(self, flags: impl IntoId) -> bool {
+        let flags = *flags.into();
+        self.0 & flags == flags
+    }
+
+    /// Return the id without its flags.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `id::remove_flags`
+    #[doc(alias = "id::remove_flags")]
+    #[inline]
+    pub fn remove_flags(self) -> Id {
+        Self(self.0 & RUST_ECS_COMPONENT_MASK)
+    }
+
+    /// Test if the id is a pair.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `id::is_pair`
+    #[doc(alias = "id::is_pair")]
+    /// * C API: `ecs_id_is_pair`
+    #[doc(alias = "ecs_id_is_pair")]
+    #[inline]
+    pub fn is_pair(self) -> bool {
+        ecs_is_pair(self)
+    }
+
+    /// Test if the id has the auto override flag.
+    #[inline]
+    pub fn is_auto_override(self) -> bool {
+        self.0 & RUST_ecs_id_FLAGS_MASK == ECS_AUTO_OVERRIDE
+    }
+
+    /// Test if the id has the toggle flag.
+    #[inline]
+    pub fn is_toggle(self) -> bool {
+        self.0 & RUST_ecs_id_FLAGS_MASK == ECS_TOGGLE
+    }
+
+    /// Test if the id is a wildcard, or a pair with a wildcard.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `id::is_wildcard`
+    #[doc(alias = "id::is_wildcard")]
+    /// * C API: `ecs_id_is_wildcard`
+    #[doc(alias = "ecs_id_is_wildcard")]
+    #[inline]
+    pub fn is_wildcard(self) -> bool {
+        unsafe { sys::ecs_id_is_wildcard(self.0) }
+    }
+
+    /// Get the relationship of a pair.
+    ///
+    /// The returned entity doesn't have a generation, use [`IdView::first_id()`]
+    /// to get the alive entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug mode if the id is not a pair.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `id::first`
+    #[doc(alias = "id::first")]
+    /// * C API: `ECS_PAIR_FIRST`
+    #[doc(alias = "ECS_PAIR_FIRST")]
+    #[inline]
+    pub fn first(self) -> Entity {
+        ecs_assert!(self.is_pair(), FlecsErrorCode::InvalidOperation);
+        ecs_first(self)
+    }
+
+    /// Get the target of a pair.
+    ///
+    /// The returned entity doesn't have a generation, use [`IdView::second_id()`]
+    /// to get the alive entity.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug mode if the id is not a pair.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `id::second`
+    #[doc(alias = "id::second")]
+    /// * C API: `ECS_PAIR_SECOND`
+    #[doc(alias = "ECS_PAIR_SECOND")]
+    #[inline]
+    pub fn second(self) -> Entity {
+        ecs_assert!(self.is_pair(), FlecsErrorCode::InvalidOperation);
+        ecs_second(self)
+    }
+
     /// Convert the entity id to an entity with the given world.
     ///
     /// # Safety
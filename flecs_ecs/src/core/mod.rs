@@ -1,27 +1,39 @@
+#[cfg(feature = "flecs_safety_access_journal")]
+mod access_journal;
 mod archetype;
 pub mod builder;
+mod bundle;
 pub mod c_types;
 pub(crate) mod cloned_tuple;
+#[cfg(feature = "std")]
+mod command_sender;
 pub mod component_registration;
 mod components;
 pub mod ecs_os_api;
 mod entity;
+#[cfg(feature = "flecs_entity_audit")]
+mod entity_audit;
 mod entity_view;
 mod event;
 pub mod flecs;
 pub(crate) mod get_tuple;
+mod hot_reload;
 mod id;
 mod id_view;
+mod name_index;
 mod observer;
 mod observer_builder;
 mod query;
 pub mod query_builder;
+mod query_extract;
+mod query_filter_map;
 mod query_iter;
 pub(crate) mod query_tuple;
 #[cfg(feature = "flecs_safety_readwrite_locks")]
 mod read_write_safety_map;
 pub mod table;
 pub mod term;
+mod traversal;
 pub mod utility;
 mod world;
 pub(crate) mod world_ctx;
@@ -29,25 +41,42 @@ pub(crate) mod world_ctx;
 pub use archetype::Archetype;
 #[doc(hidden)]
 pub use builder::*;
+pub use bundle::*;
 #[doc(hidden)]
 pub use c_types::*;
 pub(crate) use cloned_tuple::*;
+#[cfg(feature = "std")]
+pub use command_sender::{CommandSender, SentEntity};
 #[doc(hidden)]
 pub use component_registration::*;
 #[doc(inline)]
 pub use components::*;
-pub use entity::Entity;
+pub use entity::{Entity, EntityId};
+#[cfg(feature = "flecs_entity_audit")]
+pub(crate) use entity_audit::*;
+pub use entity_view::ChildBuilder;
+pub use entity_view::EntityModifier;
+pub use entity_view::EntityRecord;
 pub use entity_view::EntityView;
 pub use entity_view::EntityViewGet;
+pub use entity_view::{BundleContains, EntityBundle, TypedEntity, TypedEntityGet};
 pub use event::EventBuilder;
 pub(crate) use get_tuple::*;
+pub(crate) use hot_reload::{DetachedCallbacks, rebind_hooks, set_hooks};
 pub use id::Id;
 pub use id_view::IdView;
+pub(crate) use name_index::NameIndex;
+pub use name_index::Renamed;
 pub use observer::Observer;
 pub use observer_builder::ObserverBuilder;
-pub use query::Query;
+#[cfg(feature = "flecs_system")]
+pub(crate) use query::set_deterministic_order;
+pub use query::{Query, QueryError};
 #[doc(hidden)]
 pub use query_builder::*;
+pub use query_extract::ExtractColumns;
+pub(crate) use query_extract::field_of;
+pub(crate) use query_filter_map::QueryFilterMap;
 pub use query_iter::QueryIter;
 #[doc(hidden)]
 pub use query_tuple::*;
@@ -57,9 +86,11 @@ pub(crate) use read_write_safety_map::*;
 pub use table::*;
 #[doc(hidden)]
 pub use term::*;
+pub use traversal::Traversal;
 #[doc(hidden)]
 pub use utility::*;
 pub(crate) use world::FlecsArray;
+pub use world::WithGuard;
 pub use world::World;
 pub use world::WorldGet;
 pub(crate) use world_ctx::*;
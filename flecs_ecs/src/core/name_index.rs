@@ -0,0 +1,196 @@
+//! Search entities by name, see [`World::find_by_name_prefix()`].
+
+use crate::core::*;
+use crate::sys;
+use flecs_ecs_derive::Component;
+
+extern crate alloc;
+use alloc::{collections::BTreeSet, string::String, vec::Vec};
+
+/// Event emitted for an entity when its name changes, with the previous name as payload.
+///
+/// Only emitted when an entity that already has a name gets a different one, not when an entity
+/// is named for the first time.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// let world = World::new();
+///
+/// let e = world.entity_named("goblin");
+/// e.observe_payload::<Renamed>(|renamed| assert_eq!(renamed.previous, "goblin"));
+///
+/// world.rename(e, "orc");
+/// ```
+///
+/// # See also
+///
+/// * [`World::rename()`]
+/// * [`EntityView::set_name()`]
+#[derive(Component, Debug, Clone)]
+pub struct Renamed {
+    /// The name of the entity before it was renamed.
+    pub previous: String,
+}
+
+/// Names of entities in lowercase, stored per world, see [`World::enable_name_index()`].
+#[derive(Default)]
+pub(crate) struct NameIndex {
+    names: BTreeSet<(String, Entity)>,
+    entities: hashbrown::HashMap<Entity, String>,
+}
+
+impl NameIndex {
+    fn insert(&mut self, entity: Entity, name: &str) {
+        self.remove(entity);
+        let name = name.to_lowercase();
+        self.names.insert((name.clone(), entity));
+        self.entities.insert(entity, name);
+    }
+
+    fn remove(&mut self, entity: Entity) {
+        if let Some(name) = self.entities.remove(&entity) {
+            self.names.remove(&(name, entity));
+        }
+    }
+
+    /// Entities of which the lowercase name starts with `prefix`, which is lowercase.
+    fn starting_with<'a>(&'a self, prefix: &'a str) -> impl Iterator<Item = Entity> + 'a {
+        self.names
+            .range((String::from(prefix), Entity::null())..)
+            .take_while(move |(name, _)| name.starts_with(prefix))
+            .map(|&(_, entity)| entity)
+    }
+}
+
+fn index_mut<'a>(world: *mut sys::ecs_world_t) -> Option<&'a mut NameIndex> {
+    unsafe { (*World::get_context(world)).name_index.as_mut() }
+}
+
+impl World {
+    /// Rename an entity.
+    ///
+    /// Emits [`Renamed`] for the entity when it had a different name before.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::set_name()`]
+    pub fn rename(&self, entity: impl Into<Entity>, name: &str) -> EntityView<'_> {
+        EntityView::new_from(self, entity.into()).set_name(name)
+    }
+
+    /// Keep an index of the names of entities, which speeds up [`World::find_by_name_prefix()`]
+    /// and [`World::find_by_name_ignore_case()`].
+    ///
+    /// The index is kept up to date by observers, which adds a cost to naming and deleting named
+    /// entities. Without the index the searches iterate all named entities.
+    pub fn enable_name_index(&self) {
+        if self.world_ctx().name_index.is_some() {
+            return;
+        }
+
+        let mut index = NameIndex::default();
+        self.each_named(|e| {
+            if let Some(name) = e.get_name() {
+                index.insert(e.id(), &name);
+            }
+        });
+        self.world_ctx_mut().name_index = Some(index);
+
+        self.observer::<flecs::OnSet, ()>()
+            .add_event::<flecs::OnRemove>()
+            .with_id((flecs::Identifier::ID, flecs::Name::ID))
+            .each_iter(|it, row, _| {
+                let Some(index) = index_mut(it.world().world_ptr_mut()) else {
+                    return;
+                };
+                let entity = it.entity(row);
+                match entity.get_name() {
+                    Some(name) if it.event().id() == flecs::OnSet::ID => {
+                        index.insert(entity.id(), &name);
+                    }
+                    _ => index.remove(entity.id()),
+                }
+            });
+    }
+
+    /// Find the entities of which the name starts with `prefix`.
+    ///
+    /// Only the name is matched, not the path of the entity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    /// world.enable_name_index();
+    ///
+    /// world.entity_named("enemy_goblin");
+    /// world.entity_named("enemy_orc");
+    /// world.entity_named("player");
+    ///
+    /// assert_eq!(world.find_by_name_prefix("enemy_").len(), 2);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::enable_name_index()`]
+    /// * [`World::find_by_name_ignore_case()`]
+    pub fn find_by_name_prefix(&self, prefix: &str) -> Vec<EntityView<'_>> {
+        let mut found = Vec::new();
+        let mut push_if_matches = |e: EntityView<'_>| {
+            if e.get_name().is_some_and(|name| name.starts_with(prefix)) {
+                found.push(EntityView::new_from(self, e.id()));
+            }
+        };
+
+        match &self.world_ctx().name_index {
+            Some(index) => index
+                .starting_with(&prefix.to_lowercase())
+                .for_each(|e| push_if_matches(EntityView::new_from(self, e))),
+            None => self.each_named(push_if_matches),
+        }
+        found
+    }
+
+    /// Find the entities of which the name is equal to `name`, ignoring case.
+    ///
+    /// Only the name is matched, not the path of the entity.
+    ///
+    /// # See also
+    ///
+    /// * [`World::enable_name_index()`]
+    /// * [`World::find_by_name_prefix()`]
+    pub fn find_by_name_ignore_case(&self, name: &str) -> Vec<EntityView<'_>> {
+        let name = name.to_lowercase();
+
+        match &self.world_ctx().name_index {
+            Some(index) => index
+                .starting_with(&name)
+                .filter(|e| index.entities.get(e).is_some_and(|n| *n == name))
+                .map(|e| EntityView::new_from(self, e))
+                .collect(),
+            None => {
+                let mut found = Vec::new();
+                self.each_named(|e| {
+                    if e.get_name().is_some_and(|n| n.to_lowercase() == name) {
+                        found.push(EntityView::new_from(self, e.id()));
+                    }
+                });
+                found
+            }
+        }
+    }
+
+    /// Invoke `func` for all named entities, including prefabs and disabled entities.
+    fn each_named(&self, mut func: impl FnMut(EntityView)) {
+        self.query::<()>()
+            .with_id((flecs::Identifier::ID, flecs::Name::ID))
+            .query_flags(QueryFlags::MatchPrefab | QueryFlags::MatchDisabled)
+            .build()
+            .each_entity(|e, _| func(e));
+    }
+}
@@ -98,6 +98,41 @@ impl<'a> Observer<'a> {
         Self { entity }
     }
 
+    /// Create a new observer, returning an error instead of panicking if flecs rejects its query.
+    pub(crate) fn try_new(
+        world: impl WorldProvider<'a>,
+        desc: sys::ecs_observer_desc_t,
+    ) -> Result<Self, QueryError> {
+        // flecs can't recover from an observer of which the query fails to initialize, so the
+        // query is created on its own first, the same way the observer creates it
+        let mut query_desc = desc.query;
+        query_desc.entity = 0;
+        query_desc.cache_kind = QueryCacheKind::None as sys::ecs_query_cache_kind_t;
+        let (query, errors) = ecs_os_api::capture_errors(|| unsafe {
+            sys::ecs_query_init(world.world_ptr_mut(), &query_desc)
+        });
+        if query.is_null() {
+            for (free, ctx) in [
+                (desc.callback_ctx_free, desc.callback_ctx),
+                (desc.run_ctx_free, desc.run_ctx),
+            ] {
+                if let Some(free) = free {
+                    if !ctx.is_null() {
+                        unsafe { free(ctx) };
+                    }
+                }
+            }
+            // like flecs does for systems, delete the entity the observer would have been
+            if desc.entity != 0 {
+                unsafe { sys::ecs_delete(world.world_ptr_mut(), desc.entity) };
+            }
+            return Err(QueryError::new(errors));
+        }
+        unsafe { sys::ecs_query_fini(query) };
+
+        Ok(Self::new(world, desc))
+    }
+
     /// Wrap an existing observer entity in an observer object
     pub(crate) fn new_from_existing(observer_entity: EntityView<'a>) -> Self {
         Self {
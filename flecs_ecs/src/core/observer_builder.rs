@@ -62,7 +62,7 @@ impl<'a, P: ComponentId, T: QueryTuple> ObserverBuilder<'a, P, T> {
     /// * C++ API: `node_builder::node_builder`
     #[doc(alias = "node_builder::node_builder")]
     pub fn new_named(world: impl WorldProvider<'a>, name: &str) -> Self {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
 
         let desc = Default::default();
         let mut obj = Self {
@@ -262,6 +262,29 @@ where
     }
 }
 
+impl<'a, P, T> TryBuilder<'a> for ObserverBuilder<'a, P, T>
+where
+    T: QueryTuple,
+{
+    /// Build the `observer_builder` into an `observer`, returning an error instead of panicking
+    /// if its query is invalid.
+    ///
+    /// Observers need a callback, so this is called by the callback methods of [`SystemAPI`] when
+    /// the builder is made fallible with [`SystemAPI::fallible()`].
+    ///
+    /// # See also
+    ///
+    /// * [`SystemAPI::fallible()`]
+    fn try_build(&mut self) -> Result<Observer<'a>, QueryError> {
+        let observer = Observer::try_new(self.world(), self.desc);
+        for s in self.term_builder.str_ptrs_to_free.iter_mut() {
+            unsafe { core::mem::ManuallyDrop::drop(s) };
+        }
+        self.term_builder.str_ptrs_to_free.clear();
+        observer
+    }
+}
+
 impl<'a, P, T: QueryTuple> WorldProvider<'a> for ObserverBuilder<'a, P, T> {
     fn world(&self) -> WorldRef<'a> {
         self.world
@@ -9,6 +9,9 @@ use sys::ecs_get_alive;
 use crate::core::*;
 use crate::sys;
 
+extern crate alloc;
+use alloc::string::String;
+
 /// Queries quickly find entities that match a list of conditions, and are at the core of many Flecs features like [systems], [observers], [tooling] and serialization.
 ///
 /// Flecs queries can do anything from returning entities that match a simple list of components, to matching complex patterns against entity graphs.
@@ -208,6 +211,41 @@ where
         world: impl WorldProvider<'a>,
         desc: &mut sys::ecs_query_desc_t,
     ) -> Self {
+        Self::from_desc(world, desc, |world_ptr, desc| unsafe {
+            sys::ecs_query_init(world_ptr, desc)
+        })
+        .unwrap_or_else(|| {
+            panic!(
+                "Failed to create query, this is due to the user creating an invalid query. Most likely by using `expr` with a wrong expression."
+            )
+        })
+    }
+
+    /// Create a new query from a query descriptor, returning an error if the query desc is faulty.
+    ///
+    /// # Arguments
+    ///
+    /// * `world` - The world to create the query in
+    /// * `desc` - The query descriptor to create the query from
+    pub(crate) fn try_new_from_desc<'a>(
+        world: impl WorldProvider<'a>,
+        desc: &mut sys::ecs_query_desc_t,
+    ) -> Result<Self, QueryError> {
+        let mut message = None;
+        Self::from_desc(world, desc, |world_ptr, desc| {
+            let (query_ptr, errors) =
+                ecs_os_api::capture_errors(|| unsafe { sys::ecs_query_init(world_ptr, desc) });
+            message = errors;
+            query_ptr
+        })
+        .ok_or_else(|| QueryError::new(message))
+    }
+
+    fn from_desc<'a>(
+        world: impl WorldProvider<'a>,
+        desc: &mut sys::ecs_query_desc_t,
+        init: impl FnOnce(*mut sys::ecs_world_t, &sys::ecs_query_desc_t) -> *mut sys::ecs_query_t,
+    ) -> Option<Self> {
         if desc.entity != 0 && desc.terms[0].id == 0 {
             let world_ptr = world.world_ptr();
             let query_poly = unsafe {
@@ -226,22 +264,21 @@ where
                     (*world_ctx).inc_query_ref_count();
                     let world_ctx = NonNull::new_unchecked(world_ctx);
 
-                    return Self {
+                    return Some(Self {
                         query,
                         world_ctx,
                         _phantom: PhantomData,
-                    };
+                    });
                 }
             }
         }
         let world_ptr = world.world_ptr_mut();
+        set_deterministic_order(world_ptr, desc);
 
-        let query_ptr = unsafe { sys::ecs_query_init(world_ptr, desc) };
+        let query_ptr = init(world_ptr, desc);
 
         if query_ptr.is_null() {
-            panic!(
-                "Failed to create query, this is due to the user creating an invalid query. Most likely by using `expr` with a wrong expression."
-            );
+            return None;
         }
 
         unsafe {
@@ -251,11 +288,11 @@ where
 
             let query = NonNull::new_unchecked(query_ptr);
 
-            Self {
+            Some(Self {
                 query,
                 world_ctx,
                 _phantom: PhantomData,
-            }
+            })
         }
     }
 
@@ -358,6 +395,138 @@ where
         unsafe { sys::ecs_query_changed(self.query.as_ptr()) }
     }
 
+    /// Modify a term of an uncached query, and compile the query again.
+    ///
+    /// This makes a query reusable when only a parameter changes between runs, such as the
+    /// fixed source of a term, without building it from scratch.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The index of the term to modify.
+    /// * `func` - Changes the term through a [`TermRefMut`].
+    ///
+    /// # Panics
+    ///
+    /// Panics when the query can't be modified or the modified term is invalid, see
+    /// [`Query::try_modify_term()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Health(i32);
+    ///
+    /// #[derive(Component)]
+    /// struct Damage(i32);
+    ///
+    /// let world = World::new();
+    /// let red = world.entity().set(Health(10));
+    /// let blue = world.entity().set(Health(20));
+    /// world.entity().set(Damage(5));
+    ///
+    /// // the health of a single entity, passed as parameter
+    /// let mut query = world
+    ///     .query::<(&Damage, &Health)>()
+    ///     .term_at(1)
+    ///     .set_src_id(red)
+    ///     .build();
+    /// query.each(|(_, health)| assert_eq!(health.0, 10));
+    ///
+    /// query.modify_term(1, |term| {
+    ///     term.set_src_id(blue);
+    /// });
+    /// query.each(|(_, health)| assert_eq!(health.0, 20));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`Query::try_modify_term()`]
+    /// * [`QueryAPI::term()`]
+    pub fn modify_term(&mut self, index: usize, func: impl FnOnce(&mut TermRefMut)) {
+        if let Err(err) = self.try_modify_term(index, func) {
+            panic!("{err}");
+        }
+    }
+
+    /// Modify a term of an uncached query, and compile the query again.
+    ///
+    /// # Errors
+    ///
+    /// Returns an error, leaving the query unchanged, when:
+    /// - the query is cached or named, as other code may refer to it by its entity
+    /// - the query is shared with a clone
+    /// - the query has a context
+    /// - the modified term is invalid
+    ///
+    /// # Panics
+    ///
+    /// Panics when `index` is out of range.
+    ///
+    /// # See also
+    ///
+    /// * [`Query::modify_term()`]
+    pub fn try_modify_term(
+        &mut self,
+        index: usize,
+        func: impl FnOnce(&mut TermRefMut),
+    ) -> Result<(), QueryError> {
+        let query = unsafe { self.query.as_ref() };
+        let error = |message: &str| {
+            Err(QueryError {
+                message: message.into(),
+            })
+        };
+        if query.entity != 0 || query.cache_kind != sys::ecs_query_cache_kind_t_EcsQueryCacheNone {
+            return error("only uncached queries without a name can be modified");
+        }
+        if unsafe { (*(self.query.as_ptr() as *const sys::ecs_header_t)).refcount } > 1 {
+            return error("queries shared with a clone can't be modified");
+        }
+        if !query.ctx.is_null() || !query.binding_ctx.is_null() {
+            return error("queries with a context can't be modified");
+        }
+        assert!(
+            index < query.term_count as usize,
+            "term index {index} out of range {}",
+            query.term_count
+        );
+
+        let mut desc = sys::ecs_query_desc_t {
+            cache_kind: query.cache_kind,
+            flags: query.flags & QueryFlags::all().bits(),
+            ..Default::default()
+        };
+        for (dst, src) in desc
+            .terms
+            .iter_mut()
+            .zip(&query.terms[..query.term_count as usize])
+        {
+            *dst = *src;
+            // recomputed when the query is compiled
+            dst.field_index = 0;
+            dst.flags_ = 0;
+        }
+        func(&mut TermRefMut::new(&mut desc.terms[index]));
+
+        let (query_ptr, errors) =
+            ecs_os_api::capture_errors(|| unsafe { sys::ecs_query_init(query.world, &desc) });
+        let Some(query_ptr) = NonNull::new(query_ptr) else {
+            return Err(QueryError {
+                message: errors.unwrap_or_default(),
+            });
+        };
+
+        unsafe {
+            if sys::flecs_poly_release_(self.query.as_ptr() as *mut c_void) == 0 {
+                sys::ecs_query_fini(self.query.as_ptr());
+            }
+        }
+        self.query = query_ptr;
+        Ok(())
+    }
+
     /// Get info for group
     ///
     /// # Arguments
@@ -407,3 +576,76 @@ impl<T: QueryTuple> From<&Query<T>> for NonNull<sys::ecs_query_t> {
         q.query
     }
 }
+
+extern "C-unwind" fn compare_entity(
+    e1: sys::ecs_entity_t,
+    _ptr1: *const c_void,
+    e2: sys::ecs_entity_t,
+    _ptr2: *const c_void,
+) -> i32 {
+    (e1 > e2) as i32 - (e1 < e2) as i32
+}
+
+/// Sort the query by entity id if the world has deterministic iteration enabled, see
+/// [`World::set_deterministic()`].
+pub(crate) fn set_deterministic_order(
+    world: *mut sys::ecs_world_t,
+    desc: &mut sys::ecs_query_desc_t,
+) {
+    let world_ctx = unsafe { &*World::get_context(world) };
+    if !world_ctx.is_deterministic
+        || desc.order_by_callback.is_some()
+        || desc.cache_kind == sys::ecs_query_cache_kind_t_EcsQueryCacheNone
+    {
+        return;
+    }
+
+    // only queries that match entities can be sorted
+    let matches_this = desc
+        .terms
+        .iter()
+        .take_while(|term| unsafe { sys::ecs_term_is_initialized(*term) })
+        .any(|term| {
+            let src = term.src.id & RUST_ECS_COMPONENT_MASK;
+            term.src.name.is_null() && (src == 0 || src == flecs::This_::ID)
+        });
+
+    if matches_this {
+        desc.order_by_callback = Some(compare_entity);
+    }
+}
+
+/// Error returned by [`TryBuilder::try_build()`] when flecs rejects the query of a query, system
+/// or observer, for example because of a syntax error in an expression or an identifier that
+/// can't be resolved.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QueryError {
+    message: String,
+}
+
+impl QueryError {
+    pub(crate) fn new(message: Option<String>) -> Self {
+        Self {
+            message: message.unwrap_or_default(),
+        }
+    }
+
+    /// The error message logged by flecs while creating the query.
+    ///
+    /// This is empty when the message couldn't be captured, which is the case without `std`.
+    pub fn message(&self) -> &str {
+        &self.message
+    }
+}
+
+impl core::fmt::Display for QueryError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        if self.message.is_empty() {
+            write!(f, "invalid query")
+        } else {
+            write!(f, "invalid query: {}", self.message)
+        }
+    }
+}
+
+impl core::error::Error for QueryError {}
@@ -110,7 +110,7 @@ where
     /// * C++ API: `query_builder::query_builder`
     #[doc(alias = "query_builder::query_builder")]
     pub fn new_named(world: &'a World, name: &str) -> Self {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
 
         let desc = Default::default();
 
@@ -263,6 +263,44 @@ where
     }
 }
 
+impl<'a, T> TryBuilder<'a> for QueryBuilder<'a, T>
+where
+    T: QueryTuple,
+{
+    /// Build the `query_builder` into a query, returning an error instead of panicking if
+    /// the query is invalid.
+    ///
+    /// This is useful for queries created from user input, such as expressions passed to
+    /// [`expr`](QueryBuilderImpl::expr) by tooling.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// let world = World::new();
+    ///
+    /// let result = world.query::<()>().expr("Position(").try_build();
+    /// assert!(result.is_err());
+    ///
+    /// match world.query::<()>().expr("DoesNotExist").try_build() {
+    ///     Ok(_) => unreachable!(),
+    ///     Err(err) => assert!(err.message().contains("DoesNotExist")),
+    /// }
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`Builder::build()`]
+    fn try_build(&mut self) -> Result<Query<T>, QueryError> {
+        let query = Query::<T>::try_new_from_desc(self.world, &mut self.desc);
+        for s in self.term_builder.str_ptrs_to_free.iter_mut() {
+            unsafe { ManuallyDrop::drop(s) };
+        }
+        self.term_builder.str_ptrs_to_free.clear();
+        query
+    }
+}
+
 // Assuming some imports and definitions from your previous example, and adding the required ones for this example.
 type GroupByFn = extern "C-unwind" fn(
     *mut sys::ecs_world_t,
@@ -275,7 +313,7 @@ type GroupByFn = extern "C-unwind" fn(
 pub trait QueryBuilderImpl<'a>: TermBuilderImpl<'a> {
     /// set the name of the query-like object
     fn named(&mut self, name: &str) -> &mut Self {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
         let world_ptr = self.world_ptr_mut();
 
         let entity_desc: sys::ecs_entity_desc_t = sys::ecs_entity_desc_t {
@@ -526,6 +564,20 @@ pub trait QueryBuilderImpl<'a>: TermBuilderImpl<'a> {
         self
     }
 
+    /// set term with a pair of runtime ids
+    ///
+    /// Same as `with_id((first, second))`, for queries of which the components are only known at
+    /// run time.
+    ///
+    /// # See also
+    ///
+    /// * [`QueryBuilderImpl::with_id()`]
+    /// * C++ API: `query_builder_i::with`
+    #[doc(alias = "query_builder_i::with")]
+    fn with_pair_ids(&mut self, first: impl Into<Entity>, second: impl Into<Entity>) -> &mut Self {
+        self.with_id((first.into(), second.into()))
+    }
+
     /* Without methods, shorthand for .with(...).not() */
 
     /// set term without Id
@@ -538,6 +590,20 @@ pub trait QueryBuilderImpl<'a>: TermBuilderImpl<'a> {
         self.with_id(id).not()
     }
 
+    /// set term without a pair of runtime ids
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `query_builder_i::without`
+    #[doc(alias = "query_builder_i::without")]
+    fn without_pair_ids(
+        &mut self,
+        first: impl Into<Entity>,
+        second: impl Into<Entity>,
+    ) -> &mut Self {
+        self.with_pair_ids(first, second).not()
+    }
+
     /// set term without type
     ///
     /// # See also
@@ -0,0 +1,99 @@
+//! Access the component columns of query results, see [`QueryAPI::extract()`] and
+//! [`QueryAPI::column_chunks()`].
+
+use crate::core::*;
+use crate::sys;
+use flecs_ecs_derive::tuples;
+
+extern crate alloc;
+use alloc::{format, string::String, vec::Vec};
+
+/// Buffers that the components of query results are copied into by [`QueryAPI::extract()`].
+///
+/// Implemented for `Vec<T>` of components that implement [`Copy`], and for tuples of those.
+pub trait ExtractColumns: Default {
+    /// Append the components of the entities in the current result of `iter`.
+    ///
+    /// Returns the reason when the components can't be extracted, so that the caller can finish
+    /// the iterator before it panics.
+    #[doc(hidden)]
+    fn extend_from<'a>(
+        &mut self,
+        world: impl WorldProvider<'a>,
+        iter: &sys::ecs_iter_t,
+    ) -> Result<(), String>;
+}
+
+impl<T> ExtractColumns for Vec<T>
+where
+    T: ComponentId + DataComponent + Copy,
+{
+    fn extend_from<'a>(
+        &mut self,
+        world: impl WorldProvider<'a>,
+        iter: &sys::ecs_iter_t,
+    ) -> Result<(), String> {
+        let id = T::id(world);
+        let count = iter.count as usize;
+        let size = core::mem::size_of::<T>();
+
+        let field = field_of::<T>(iter, id)?;
+
+        if !unsafe { sys::ecs_field_is_set(iter, field) } {
+            return Err(format!(
+                "optional component `{}` is not set for all results",
+                core::any::type_name::<T>()
+            ));
+        }
+
+        unsafe {
+            let ptr = sys::ecs_field_w_size(iter, size, field) as *const T;
+            if ptr.is_null() {
+                // sparse components are not stored in table columns
+                self.extend((0..count).map(|row| {
+                    *(sys::ecs_field_at_w_size(iter, size, field, row as i32) as *const T)
+                }));
+            } else if sys::ecs_field_is_self(iter, field) {
+                self.extend_from_slice(core::slice::from_raw_parts(ptr, count));
+            } else {
+                // shared components, such as from a parent or prefab, are the same for every row
+                self.extend(core::iter::repeat_n(*ptr, count));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Find the field of the query that matched component `T` with id `id`.
+pub(crate) fn field_of<T>(iter: &sys::ecs_iter_t, id: sys::ecs_id_t) -> Result<i8, String> {
+    (0..iter.field_count)
+        .find(|&index| unsafe { sys::ecs_field_id(iter, index) } == id)
+        .ok_or_else(|| {
+            format!(
+                "component `{}` is not a field of the query",
+                core::any::type_name::<T>()
+            )
+        })
+}
+
+macro_rules! impl_extract_columns {
+    ($($t:ident),*) => {
+        impl<$($t: ExtractColumns),*> ExtractColumns for ($($t,)*) {
+            #[allow(non_snake_case)]
+            fn extend_from<'a>(
+                &mut self,
+                world: impl WorldProvider<'a>,
+                iter: &sys::ecs_iter_t,
+            ) -> Result<(), String> {
+                let world = world.world();
+                let ($($t,)*) = self;
+                $(
+                    $t.extend_from(world, iter)?;
+                )*
+                Ok(())
+            }
+        }
+    }
+}
+
+tuples!(impl_extract_columns, 1, 12);
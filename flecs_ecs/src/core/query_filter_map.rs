@@ -0,0 +1,149 @@
+//! Lazy iterator over values computed from the matches of a query, returned by
+//! [`QueryAPI::map()`], [`QueryAPI::filter_map()`] and [`QueryAPI::filter()`].
+
+use alloc::boxed::Box;
+
+use crate::core::*;
+use crate::sys;
+
+/// Iterates the matches of a query one entity at a time, passing each to `func` and yielding the
+/// values it returns.
+///
+/// The table of the current entity stays locked until the iterator moves on to the next table or
+/// is dropped, like the table of an `each` callback.
+pub(crate) struct QueryFilterMap<'q, T, F>
+where
+    T: QueryTuple,
+{
+    // boxed so the iterator doesn't move once iteration started
+    iter: Box<sys::ecs_iter_t>,
+    iter_next: unsafe extern "C-unwind" fn(*mut sys::ecs_iter_t) -> bool,
+    world: WorldRef<'q>,
+    // components of the current table, `None` between tables
+    data: Option<T::Pointers>,
+    row: usize,
+    count: usize,
+    done: bool,
+    func: F,
+}
+
+impl<'q, T, F, R> QueryFilterMap<'q, T, F>
+where
+    T: QueryTuple,
+    F: for<'t> FnMut(EntityView<'q>, T::TupleType<'t>) -> Option<R>,
+{
+    pub(crate) fn new<'w: 'q, P>(query: &'q (impl QueryAPI<'w, P, T> + ?Sized), func: F) -> Self {
+        let mut iter = Box::new(query.retrieve_iter());
+        iter.flags |= sys::EcsIterCppEach;
+        Self {
+            iter,
+            iter_next: query.iter_next_func(),
+            world: query.world(),
+            data: None,
+            row: 0,
+            count: 0,
+            done: false,
+            func,
+        }
+    }
+
+    fn begin_table(&mut self) {
+        let iter = &*self.iter;
+        self.data = Some(T::create_ptrs(iter));
+        self.row = 0;
+        self.count = if iter.count == 0 && iter.table.is_null() {
+            1
+        } else {
+            iter.count as usize
+        };
+
+        #[cfg(feature = "flecs_safety_readwrite_locks")]
+        do_read_write_locks::<INCREMENT>(
+            iter,
+            self.world.components_access_map(),
+            T::COUNT as usize,
+            &self.world,
+        );
+
+        unsafe { sys::ecs_table_lock(self.world.world_ptr_mut(), iter.table) };
+    }
+
+    fn end_table(&mut self) {
+        let iter = &*self.iter;
+        unsafe { sys::ecs_table_unlock(self.world.world_ptr_mut(), iter.table) };
+
+        #[cfg(feature = "flecs_safety_readwrite_locks")]
+        do_read_write_locks::<DECREMENT>(
+            iter,
+            self.world.components_access_map(),
+            T::COUNT as usize,
+            &self.world,
+        );
+
+        self.data = None;
+    }
+}
+
+impl<'q, T, F, R> Iterator for QueryFilterMap<'q, T, F>
+where
+    T: QueryTuple,
+    F: for<'t> FnMut(EntityView<'q>, T::TupleType<'t>) -> Option<R>,
+{
+    type Item = R;
+
+    fn next(&mut self) -> Option<R> {
+        loop {
+            if let Some(data) = &mut self.data {
+                while self.row < self.count {
+                    let row = self.row;
+                    self.row += 1;
+                    let entity = if self.iter.entities.is_null() {
+                        0
+                    } else {
+                        unsafe { *self.iter.entities.add(row) }
+                    };
+                    let entity = EntityView::new_from(self.world, entity);
+                    let tuple = data.get_tuple(&self.iter, row);
+                    if let Some(value) = (self.func)(entity, tuple) {
+                        return Some(value);
+                    }
+                }
+                self.end_table();
+            }
+
+            if self.done {
+                return None;
+            }
+            if !unsafe { (self.iter_next)(&mut *self.iter) } {
+                self.done = true;
+                return None;
+            }
+            self.begin_table();
+        }
+    }
+}
+
+impl<T, F> Drop for QueryFilterMap<'_, T, F>
+where
+    T: QueryTuple,
+{
+    fn drop(&mut self) {
+        if self.done {
+            return;
+        }
+        if self.data.is_some() {
+            let iter = &*self.iter;
+            unsafe { sys::ecs_table_unlock(self.world.world_ptr_mut(), iter.table) };
+
+            #[cfg(feature = "flecs_safety_readwrite_locks")]
+            do_read_write_locks::<DECREMENT>(
+                iter,
+                self.world.components_access_map(),
+                T::COUNT as usize,
+                &self.world,
+            );
+        }
+        // the iterator is not iterated until completion, so it has to be cleaned up
+        unsafe { sys::ecs_iter_fini(&mut *self.iter) };
+    }
+}
@@ -1,4 +1,5 @@
 //! Class that extends the capabilities of a [`Query`] by providing additional operations on the query's iterator.
+use core::cell::UnsafeCell;
 use core::ffi::c_void;
 
 use crate::core::*;
@@ -8,8 +9,13 @@ pub struct QueryIter<'a, P, T>
 where
     T: QueryTuple,
 {
-    iter: sys::ecs_iter_t,
+    iter: UnsafeCell<sys::ecs_iter_t>,
+    // copy of `iter` that a page iterator chains to and advances through a pointer, so `iter`
+    // itself is never advanced, like when it isn't paged
+    chain: UnsafeCell<sys::ecs_iter_t>,
     iter_next: unsafe extern "C-unwind" fn(*mut sys::ecs_iter_t) -> bool,
+    offset: i32,
+    limit: i32,
     _phantom: core::marker::PhantomData<&'a (P, T)>,
 }
 
@@ -22,8 +28,11 @@ where
         iter_next: unsafe extern "C-unwind" fn(*mut sys::ecs_iter_t) -> bool,
     ) -> Self {
         Self {
-            iter,
+            iter: UnsafeCell::new(iter),
+            chain: UnsafeCell::new(iter),
             iter_next,
+            offset: 0,
+            limit: 0,
             _phantom: core::marker::PhantomData,
         }
     }
@@ -39,7 +48,7 @@ where
     /// * C++ API: `iter_iterable::set_group`
     #[doc(alias = "iter_iterable::set_group")]
     pub fn set_group_id(&mut self, group_id: impl Into<Entity>) -> &mut Self {
-        unsafe { sys::ecs_iter_set_group(&mut self.iter, *group_id.into()) }
+        unsafe { sys::ecs_iter_set_group(self.iter.get_mut(), *group_id.into()) }
         self
     }
 
@@ -54,8 +63,8 @@ where
     /// * C++ API: `iter_iterable::set_group`
     #[doc(alias = "iter_iterable::set_group")]
     pub fn set_group<Group: ComponentId>(&mut self) -> &mut Self {
-        let world = unsafe { WorldRef::from_ptr(self.iter.real_world) };
-        unsafe { sys::ecs_iter_set_group(&mut self.iter, Group::id(world)) }
+        let world = unsafe { WorldRef::from_ptr(self.iter.get_mut().real_world) };
+        unsafe { sys::ecs_iter_set_group(self.iter.get_mut(), Group::id(world)) }
         self
     }
 
@@ -73,7 +82,7 @@ where
     #[doc(alias = "iter_iterable::set_var")]
     pub fn set_var(&mut self, var_id: i32, value: impl Into<Entity>) -> &mut Self {
         ecs_assert!(var_id != -1, FlecsErrorCode::InvalidParameter, 0);
-        unsafe { sys::ecs_iter_set_var(&mut self.iter, var_id, *value.into()) };
+        unsafe { sys::ecs_iter_set_var(self.iter.get_mut(), var_id, *value.into()) };
         self
     }
 
@@ -91,7 +100,7 @@ where
     #[doc(alias = "iter_iterable::set_var")]
     pub fn set_var_table(&mut self, var_id: i32, table: impl IntoTableRange) -> &mut Self {
         ecs_assert!(var_id != -1, FlecsErrorCode::InvalidParameter, 0);
-        unsafe { sys::ecs_iter_set_var_as_range(&mut self.iter, var_id, &table.range_raw()) };
+        unsafe { sys::ecs_iter_set_var_as_range(self.iter.get_mut(), var_id, &table.range_raw()) };
         self
     }
 
@@ -107,16 +116,12 @@ where
     /// * C++ API: `iter_iterable::set_var`
     #[doc(alias = "iter_iterable::set_var")]
     pub fn set_var_expr(&mut self, name: &str, value: impl Into<Entity>) -> &mut Self {
-        let name = compact_str::format_compact!("{}\0", name);
+        let c_name = SmallCString::new(name);
 
-        let qit = unsafe { &mut self.iter.priv_.iter.query };
-        let var_id = unsafe { sys::ecs_query_find_var(qit.query, name.as_ptr() as *const _) };
-        ecs_assert!(
-            var_id != -1,
-            FlecsErrorCode::InvalidParameter,
-            name.as_str()
-        );
-        unsafe { sys::ecs_iter_set_var(&mut self.iter, var_id, *value.into()) };
+        let qit = unsafe { &mut self.iter.get_mut().priv_.iter.query };
+        let var_id = unsafe { sys::ecs_query_find_var(qit.query, c_name.as_ptr() as *const _) };
+        ecs_assert!(var_id != -1, FlecsErrorCode::InvalidParameter, name);
+        unsafe { sys::ecs_iter_set_var(self.iter.get_mut(), var_id, *value.into()) };
         self
     }
 
@@ -132,18 +137,90 @@ where
     /// * C++ API: `iter_iterable::set_var`
     #[doc(alias = "iter_iterable::set_var")]
     pub fn set_var_table_expr(&mut self, name: &str, table: impl IntoTableRange) -> &mut Self {
-        let name = compact_str::format_compact!("{}\0", name);
+        let c_name = SmallCString::new(name);
+
+        let qit = unsafe { &mut self.iter.get_mut().priv_.iter.query };
+        let var_id = unsafe { sys::ecs_query_find_var(qit.query, c_name.as_ptr() as *const _) };
+        ecs_assert!(var_id != -1, FlecsErrorCode::InvalidParameter, name);
+        unsafe { sys::ecs_iter_set_var_as_range(self.iter.get_mut(), var_id, &table.range_raw()) };
+        self
+    }
 
-        let qit = unsafe { &mut self.iter.priv_.iter.query };
-        let var_id = unsafe { sys::ecs_query_find_var(qit.query, name.as_ptr() as *const _) };
+    /// Skip the first `offset` entities that match the query.
+    ///
+    /// Combined with [`QueryIter::limit()`] this iterates a page of the results, without counting
+    /// entities inside the callback. The iterable is consumed by iterating a page, so create a
+    /// new one with [`QueryAPI::iterable()`] for the next page.
+    ///
+    /// # Arguments
+    ///
+    /// * `offset`: the number of entities to skip
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Score(u32);
+    ///
+    /// let world = World::new();
+    ///
+    /// for i in 0..10 {
+    ///     world.entity().set(Score(i));
+    /// }
+    ///
+    /// let query = world.new_query::<&Score>();
+    ///
+    /// let mut page = Vec::new();
+    /// query
+    ///     .iterable()
+    ///     .offset(4)
+    ///     .limit(3)
+    ///     .each(|score| page.push(score.0));
+    ///
+    /// assert_eq!(page, [4, 5, 6]);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryIter::limit()`]
+    /// * C++ API: `iter_iterable::page`
+    #[doc(alias = "iter_iterable::page")]
+    pub fn offset(&mut self, offset: i32) -> &mut Self {
+        ecs_assert!(
+            offset >= 0,
+            FlecsErrorCode::InvalidParameter,
+            "offset can't be negative"
+        );
+        self.offset = offset;
+        self
+    }
+
+    /// Iterate at most `limit` entities that match the query.
+    ///
+    /// # Arguments
+    ///
+    /// * `limit`: the maximum number of entities to iterate, or 0 to iterate all
+    ///
+    /// # See also
+    ///
+    /// * [`QueryIter::offset()`]
+    /// * C++ API: `iter_iterable::page`
+    #[doc(alias = "iter_iterable::page")]
+    pub fn limit(&mut self, limit: i32) -> &mut Self {
         ecs_assert!(
-            var_id != -1,
+            limit >= 0,
             FlecsErrorCode::InvalidParameter,
-            name.as_str()
+            "limit can't be negative"
         );
-        unsafe { sys::ecs_iter_set_var_as_range(&mut self.iter, var_id, &table.range_raw()) };
+        self.limit = limit;
         self
     }
+
+    fn is_paged(&self) -> bool {
+        self.offset != 0 || self.limit != 0
+    }
 }
 
 #[doc(hidden)]
@@ -152,7 +229,14 @@ where
     T: QueryTuple,
 {
     fn retrieve_iter(&self) -> sys::ecs_iter_t {
-        self.iter
+        if self.is_paged() {
+            unsafe {
+                *self.chain.get() = *self.iter.get();
+                sys::ecs_page_iter(self.chain.get(), self.offset, self.limit)
+            }
+        } else {
+            unsafe { *self.iter.get() }
+        }
     }
 
     fn retrieve_iter_stage<'w>(&self, _stage: impl WorldProvider<'w>) -> sys::ecs_iter_t {
@@ -162,15 +246,19 @@ where
     }
 
     fn iter_next(&self, iter: &mut sys::ecs_iter_t) -> bool {
-        unsafe { (self.iter_next)(iter) }
+        unsafe { (self.iter_next_func())(iter) }
     }
 
     fn query_ptr(&self) -> *const sys::ecs_query_t {
-        self.iter.query
+        unsafe { (*self.iter.get()).query }
     }
 
     fn iter_next_func(&self) -> unsafe extern "C-unwind" fn(*mut sys::ecs_iter_t) -> bool {
-        self.iter_next
+        if self.is_paged() {
+            sys::ecs_page_next
+        } else {
+            self.iter_next
+        }
     }
 }
 
@@ -180,9 +268,10 @@ where
     Self: WorldProvider<'a>,
 {
     fn entity(&self) -> EntityView {
-        let world = unsafe { WorldRef::from_ptr(self.iter.real_world) };
+        let iter = unsafe { &*self.iter.get() };
+        let world = unsafe { WorldRef::from_ptr(iter.real_world) };
         EntityView::new_from(world, unsafe {
-            sys::ecs_get_entity(self.iter.query as *const c_void)
+            sys::ecs_get_entity(iter.query as *const c_void)
         })
     }
 }
@@ -192,8 +281,8 @@ where
     T: QueryTuple,
 {
     fn world(&self) -> WorldRef<'a> {
-        unsafe { WorldRef::from_ptr(self.iter.world) }
+        unsafe { WorldRef::from_ptr((*self.iter.get()).world) }
     }
 }
 
-// TODO : worker_iterable and page_iterable not implemented yet
+// TODO : worker_iterable not implemented yet
@@ -8,6 +8,11 @@ use foldhash::fast::RandomState;
 use smallvec::{SmallVec, smallvec};
 
 use super::ReadWriteId;
+#[cfg(feature = "flecs_safety_access_journal")]
+use super::access_journal::AccessJournal;
+
+extern crate alloc;
+use alloc::string::String;
 
 /// Reserve the highest bit as the write flag.
 const WRITE_FLAG: u16 = 1 << 15;
@@ -107,12 +112,45 @@ impl core::panic::RefUnwindSafe for ReadWriteComponentsMap {}
 pub(crate) struct ReadWriteComponentsMap {
     // Maps entity ID to number of readers
     pub(crate) read_write: DashMap<ComponentOrPairIdAndTableId, ReadWriteCounter, RandomState>,
+    #[cfg(feature = "flecs_safety_access_journal")]
+    journal: AccessJournal,
 }
 
 impl ReadWriteComponentsMap {
     pub(crate) fn new() -> Self {
         Self {
             read_write: DashMap::with_hasher(RandomState::default()),
+            #[cfg(feature = "flecs_safety_access_journal")]
+            journal: AccessJournal::new(),
+        }
+    }
+
+    /// Describe the conflicting borrows for the panic message of a failed borrow.
+    #[cfg(feature = "flecs_safety_access_journal")]
+    #[track_caller]
+    fn conflicts(&self, id: ComponentOrPairIdAndTableId, write: bool) -> String {
+        self.journal.report(id, write)
+    }
+
+    #[cfg(not(feature = "flecs_safety_access_journal"))]
+    fn conflicts(&self, _id: ComponentOrPairIdAndTableId, _write: bool) -> String {
+        String::new()
+    }
+
+    #[cfg_attr(not(feature = "flecs_safety_access_journal"), allow(unused_variables))]
+    #[track_caller]
+    fn record(&self, id: ComponentOrPairIdAndTableId, write: bool, journal: bool) {
+        #[cfg(feature = "flecs_safety_access_journal")]
+        if journal {
+            self.journal.record(id, write);
+        }
+    }
+
+    #[cfg_attr(not(feature = "flecs_safety_access_journal"), allow(unused_variables))]
+    fn release(&self, id: ComponentOrPairIdAndTableId, write: bool, journal: bool) {
+        #[cfg(feature = "flecs_safety_access_journal")]
+        if journal {
+            self.journal.release(id, write);
         }
     }
 
@@ -128,17 +166,29 @@ impl ReadWriteComponentsMap {
         self.read_write.remove(&combone_ids(id, table_id));
     }
 
+    #[track_caller]
     pub(crate) fn increment_read(
         &self,
         comp_id: ComponentOrPairId,
         table_id: TableId,
         world: &WorldRef,
+    ) {
+        self.increment_read_journaled(comp_id, table_id, world, true);
+    }
+
+    #[track_caller]
+    fn increment_read_journaled(
+        &self,
+        comp_id: ComponentOrPairId,
+        table_id: TableId,
+        world: &WorldRef,
+        journal: bool,
     ) {
         let id = combone_ids(comp_id, table_id);
         if let Some(counter) = self.read_write.get(&id) {
             if counter.increment_read().is_err() {
                 panic!(
-                    "Cannot increment read: write already set for component: {} with table id: {}",
+                    "Cannot increment read: write already set for component: {} with table id: {}{}",
                     {
                         let id = IdView::new_from_id(world, comp_id);
                         if id.is_pair() {
@@ -151,7 +201,8 @@ impl ReadWriteComponentsMap {
                             format!("{}", id.entity_view())
                         }
                     },
-                    table_id
+                    table_id,
+                    self.conflicts(id, false)
                 );
             }
         } else {
@@ -159,25 +210,44 @@ impl ReadWriteComponentsMap {
             let _ = counter.increment_read();
             self.add_entry_with(id, counter);
         }
+        self.record(id, false, journal);
     }
 
     pub(crate) fn decrement_read(&self, id: ComponentOrPairId, table_id: TableId) {
-        if let Some(counter) = self.read_write.get(&combone_ids(id, table_id)) {
+        self.decrement_read_journaled(id, table_id, true);
+    }
+
+    fn decrement_read_journaled(&self, id: ComponentOrPairId, table_id: TableId, journal: bool) {
+        let id = combone_ids(id, table_id);
+        if let Some(counter) = self.read_write.get(&id) {
             counter.decrement_read();
         }
+        self.release(id, false, journal);
     }
 
+    #[track_caller]
     pub(crate) fn set_write(
         &self,
         comp_id: ComponentOrPairId,
         table_id: TableId,
         world: &WorldRef,
+    ) {
+        self.set_write_journaled(comp_id, table_id, world, true);
+    }
+
+    #[track_caller]
+    fn set_write_journaled(
+        &self,
+        comp_id: ComponentOrPairId,
+        table_id: TableId,
+        world: &WorldRef,
+        journal: bool,
     ) {
         let id = combone_ids(comp_id, table_id);
         if let Some(counter) = self.read_write.get(&id) {
             if counter.set_write().is_err() {
                 panic!(
-                    "Cannot set write: reads already present or write already set for component: {} with table id: {}",
+                    "Cannot set write: reads already present or write already set for component: {} with table id: {}{}",
                     {
                         let id = IdView::new_from_id(world, comp_id);
                         if id.is_pair() {
@@ -190,7 +260,8 @@ impl ReadWriteComponentsMap {
                             format!("{}", id.entity_view())
                         }
                     },
-                    table_id
+                    table_id,
+                    self.conflicts(id, true)
                 );
             }
         } else {
@@ -198,12 +269,19 @@ impl ReadWriteComponentsMap {
             let _ = counter.set_write();
             self.add_entry_with(id, counter);
         }
+        self.record(id, true, journal);
     }
 
     pub(crate) fn clear_write(&self, id: ComponentOrPairId, table_id: TableId) {
-        if let Some(counter) = self.read_write.get(&combone_ids(id, table_id)) {
+        self.clear_write_journaled(id, table_id, true);
+    }
+
+    fn clear_write_journaled(&self, id: ComponentOrPairId, table_id: TableId, journal: bool) {
+        let id = combone_ids(id, table_id);
+        if let Some(counter) = self.read_write.get(&id) {
             counter.clear_write();
         }
+        self.release(id, true, journal);
     }
 
     pub(crate) fn increment_counters_from_iter(
@@ -257,6 +335,7 @@ impl ReadWriteComponentsMap {
         }
     }
 
+    #[track_caller]
     pub(crate) fn increment_counters_from_id(
         &self,
         id: ReadWriteId,
@@ -273,6 +352,7 @@ impl ReadWriteComponentsMap {
         }
     }
 
+    #[track_caller]
     pub(crate) fn increment_counters_from_ids(
         &self,
         ids: &[ReadWriteId],
@@ -338,6 +418,10 @@ pub(crate) fn do_read_write_locks<const INCREMENT: bool>(
     count: usize,
     world: &WorldRef,
 ) {
+    // systems and observers are scheduled by flecs, only borrows made by the application are
+    // recorded in the journal
+    let journal = iter.system == 0;
+
     unsafe {
         for i in 0..count {
             if !sys::ecs_field_is_set(iter, i as i8) {
@@ -364,19 +448,32 @@ pub(crate) fn do_read_write_locks<const INCREMENT: bool>(
             if !sys::ecs_id_is_wildcard(component_id) {
                 if sys::ecs_field_is_readonly(iter, i as i8) {
                     if INCREMENT {
-                        components_access.increment_read(
+                        components_access.increment_read_journaled(
                             component_id,
                             sys::ecs_rust_table_id(table),
                             world,
+                            journal,
                         );
                     } else {
-                        components_access
-                            .decrement_read(component_id, sys::ecs_rust_table_id(table));
+                        components_access.decrement_read_journaled(
+                            component_id,
+                            sys::ecs_rust_table_id(table),
+                            journal,
+                        );
                     }
                 } else if INCREMENT {
-                    components_access.set_write(component_id, sys::ecs_rust_table_id(table), world);
+                    components_access.set_write_journaled(
+                        component_id,
+                        sys::ecs_rust_table_id(table),
+                        world,
+                        journal,
+                    );
                 } else {
-                    components_access.clear_write(component_id, sys::ecs_rust_table_id(table));
+                    components_access.clear_write_journaled(
+                        component_id,
+                        sys::ecs_rust_table_id(table),
+                        journal,
+                    );
                 }
             }
         }
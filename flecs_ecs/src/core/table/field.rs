@@ -75,6 +75,7 @@ impl<'a, T> Field<'a, T> {
     /// * C++ API: `field::field`
     #[doc(alias = "field::field")]
     #[cfg(feature = "flecs_safety_readwrite_locks")]
+    #[track_caller]
     pub(crate) fn new(
         slice_components: &'a [T],
         is_shared: bool,
@@ -179,6 +180,7 @@ impl<'a, T> FieldMut<'a, T> {
     /// * C++ API: `field::field`
     #[doc(alias = "field::field")]
     #[cfg(feature = "flecs_safety_readwrite_locks")]
+    #[track_caller]
     pub(crate) fn new(
         slice_components: &'a mut [T],
         is_shared: bool,
@@ -266,6 +268,7 @@ impl<'a, T> FieldAt<'a, T> {
     }
 
     #[cfg(feature = "flecs_safety_readwrite_locks")]
+    #[track_caller]
     pub(crate) fn new(
         component: &'a T,
         id: Entity,
@@ -336,6 +339,7 @@ impl<'a, T> FieldAtMut<'a, T> {
     }
 
     #[cfg(feature = "flecs_safety_readwrite_locks")]
+    #[track_caller]
     pub(crate) fn new(
         component: &'a mut T,
         id: Entity,
@@ -389,6 +393,78 @@ impl FieldUntyped {
         }
     }
 
+    /// The number of values in the field, which is 1 for shared fields.
+    pub fn len(&self) -> usize {
+        self.count
+    }
+
+    /// Whether the field has no values.
+    pub fn is_empty(&self) -> bool {
+        self.count == 0
+    }
+
+    /// The size of the component type in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Whether the field is shared, for example when it is inherited from a prefab.
+    pub fn is_shared(&self) -> bool {
+        self.is_shared
+    }
+
+    /// The number of bytes between the values of two consecutive entities.
+    ///
+    /// This is the size of the component for owned fields, and 0 for shared fields, as all
+    /// entities share the single value. The value of the entity at `row` starts at
+    /// `as_ptr() + row * stride()`.
+    pub fn stride(&self) -> usize {
+        if self.is_shared { 0 } else { self.size }
+    }
+
+    /// A pointer to the first value of the column.
+    ///
+    /// The pointer is null for tags, which have no data.
+    pub fn as_ptr(&self) -> *const c_void {
+        self.array
+    }
+
+    /// A mutable pointer to the first value of the column.
+    ///
+    /// The pointer is null for tags, which have no data.
+    pub fn as_mut_ptr(&mut self) -> *mut c_void {
+        self.array
+    }
+
+    /// The memory of the values in the field, `len() * size()` bytes.
+    ///
+    /// This lets generic code, such as serialization or replication, copy the values of
+    /// components without knowing their type.
+    ///
+    /// # Safety
+    ///
+    /// The component must not contain padding or other uninitialized bytes.
+    pub unsafe fn as_bytes(&self) -> &[u8] {
+        if self.array.is_null() {
+            return &[];
+        }
+        unsafe { core::slice::from_raw_parts(self.array as *const u8, self.count * self.size) }
+    }
+
+    /// The mutable memory of the values in the field, `len() * size()` bytes.
+    ///
+    /// # Safety
+    ///
+    /// The component must not contain padding or other uninitialized bytes, and the bytes
+    /// written must be a valid value of the component. Shared fields belong to another entity,
+    /// such as a prefab.
+    pub unsafe fn as_bytes_mut(&mut self) -> &mut [u8] {
+        if self.array.is_null() {
+            return &mut [];
+        }
+        unsafe { core::slice::from_raw_parts_mut(self.array as *mut u8, self.count * self.size) }
+    }
+
     pub fn at(&self, index: usize) -> *const c_void {
         ecs_assert!(
             index < self.count,
@@ -7,6 +7,9 @@ use crate::sys;
 
 use super::field::{FieldAt, FieldAtMut, FieldMut};
 
+extern crate alloc;
+use alloc::{string::String, vec::Vec};
+
 pub struct TableIter<'a, const IS_RUN: bool = true, P = ()> {
     pub(crate) iter: &'a mut sys::ecs_iter_t,
     marker: PhantomData<P>,
@@ -151,6 +154,11 @@ where
         self.iter.count as usize
     }
 
+    /// Whether the iterator has no entities, for example when the query only matches singletons.
+    pub fn is_empty(&self) -> bool {
+        self.iter.count == 0
+    }
+
     /// Return the delta time stored in the iterator.
     ///
     /// This is the time since the last frame.
@@ -234,26 +242,98 @@ where
     ///
     /// # Arguments
     ///
-    /// * `var_id` - The variable id
+    /// * `name` - The name of the variable
+    ///
+    /// # Panics
+    ///
+    /// Panics if the query has no variable with the name.
+    /// Use the [`TableIter::try_get_var_by_name()`] variant otherwise.
     ///
     /// # See also
     ///
     /// * C++ API: `iter::get_var`
     #[doc(alias = "iter::get_var")]
     pub fn get_var_by_name(&self, name: &str) -> EntityView<'a> {
-        let name = compact_str::format_compact!("{}\0", name);
+        self.try_get_var_by_name(name)
+            .unwrap_or_else(|| panic!("query has no variable {}", name))
+    }
 
-        let world = self.world();
-        let rule_query = unsafe { self.iter.priv_.iter.query.query };
-        let var_id = unsafe { sys::ecs_query_find_var(rule_query, name.as_ptr() as *const _) };
-        ecs_assert!(
-            var_id != -1,
-            FlecsErrorCode::InvalidParameter,
-            name.as_str()
-        );
-        EntityView::new_from(world, unsafe {
-            sys::ecs_iter_get_var(self.iter as *const _ as *mut _, var_id)
-        })
+    /// Get the variable of the iterator by name, if the query has a variable with the name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the variable
+    ///
+    /// # See also
+    ///
+    /// * [`TableIter::get_var_by_name()`]
+    /// * [`TableIter::vars()`]
+    pub fn try_get_var_by_name(&self, name: &str) -> Option<EntityView<'a>> {
+        let name = SmallCString::new(name);
+
+        let query = unsafe { self.iter.priv_.iter.query.query };
+        let var_id = unsafe { sys::ecs_query_find_var(query, name.as_ptr() as *const _) };
+        if var_id == -1 {
+            return None;
+        }
+
+        Some(self.get_var(var_id))
+    }
+
+    /// Get the values of the named entity variables of the query, for the current result.
+    ///
+    /// The `this` variable, which is iterated as a table, is not included.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Eats;
+    ///
+    /// let world = World::new();
+    ///
+    /// let apples = world.entity_named("apples");
+    /// world.entity_named("bob").add_first::<Eats>(apples);
+    ///
+    /// world
+    ///     .query::<()>()
+    ///     .with::<&Eats>()
+    ///     .second()
+    ///     .set_var("food")
+    ///     .build()
+    ///     .run(|mut it| {
+    ///         while it.next() {
+    ///             let vars = it.vars();
+    ///             assert_eq!(vars.len(), 1);
+    ///             assert_eq!(vars[0].0, "food");
+    ///             assert_eq!(vars[0].1, apples);
+    ///         }
+    ///     });
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`TableIter::try_get_var_by_name()`]
+    pub fn vars(&self) -> Vec<(&'a str, EntityView<'a>)> {
+        let query = self.iter.query;
+        if query.is_null() {
+            return Vec::new();
+        }
+
+        // the first variable is always `this`
+        (1..self.iter.variable_count as i32)
+            .filter(|&var_id| unsafe { sys::ecs_query_var_is_entity(query, var_id) })
+            .filter_map(|var_id| {
+                let name = unsafe { sys::ecs_query_var_name(query, var_id) };
+                if name.is_null() {
+                    return None;
+                }
+                let name = unsafe { CStr::from_ptr(name) }.to_str().ok()?;
+                Some((name, self.get_var(var_id)))
+            })
+            .collect()
     }
 
     /// Access ctx.
@@ -427,10 +507,54 @@ where
     /// Obtain pair id matched for field.
     /// This operation will return `None` if the field is not a pair.
     ///
+    /// For wildcard terms such as `(Eats, flecs::Wildcard)` this returns the concrete
+    /// pair that was matched. An entity with multiple `(Eats, *)` pairs is returned once
+    /// for every pair, with the field data pointing to the matched pair.
+    ///
     /// # Arguments
     ///
     /// * `index` - The field index.
     ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Eats {
+    ///     amount: i32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Apples;
+    ///
+    /// #[derive(Component)]
+    /// struct Pears;
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .entity()
+    ///     .set_pair::<Eats, Apples>(Eats { amount: 10 })
+    ///     .set_pair::<Eats, Pears>(Eats { amount: 5 });
+    ///
+    /// let mut total = 0;
+    /// world
+    ///     .new_query::<&(Eats, flecs::Wildcard)>()
+    ///     .each_iter(|it, _index, eats| {
+    ///         let pair = it.pair(0).unwrap();
+    ///         let food = pair.second_id();
+    ///         if food == world.component_id::<Apples>() {
+    ///             assert_eq!(eats.amount, 10);
+    ///         } else {
+    ///             assert_eq!(food, world.component_id::<Pears>());
+    ///             assert_eq!(eats.amount, 5);
+    ///         }
+    ///         total += eats.amount;
+    ///     });
+    ///
+    /// assert_eq!(total, 15);
+    /// ```
+    ///
     /// # See also
     ///
     /// * C++ API: `iter::pair`
@@ -446,6 +570,64 @@ where
         }
     }
 
+    /// Obtain the target of the pair matched for a field with relationship `First`.
+    ///
+    /// This is a shorthand for `it.pair(index).unwrap().second_id()` that also checks the
+    /// relationship, which is useful for queries and observers on wildcard pairs such as
+    /// `(Buff, flecs::Wildcard)` to find out which pair was matched.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The field index.
+    ///
+    /// # Panics
+    ///
+    /// If the field didn't match a pair with relationship `First`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Buff {
+    ///     duration: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Haste;
+    ///
+    /// let world = World::new();
+    ///
+    /// world
+    ///     .observer::<flecs::OnSet, &(Buff, flecs::Wildcard)>()
+    ///     .each_iter(|it, _index, buff| {
+    ///         let kind = it.pair_second::<Buff>(0);
+    ///         assert_eq!(kind, it.world().component_id::<Haste>());
+    ///         assert_eq!(buff.duration, 5.0);
+    ///     });
+    ///
+    /// world
+    ///     .entity()
+    ///     .set_pair::<Buff, Haste>(Buff { duration: 5.0 });
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`TableIter::pair()`]
+    /// * [`IdView::second_id()`]
+    pub fn pair_second<First: ComponentId>(&self, index: i8) -> EntityView<'a> {
+        let world = self.world();
+        let id = unsafe { sys::ecs_field_id(self.iter, index) };
+        assert!(
+            ecs_is_pair(id) && ecs_first(id) == First::id(world),
+            "field {} did not match a pair with relationship `{}`",
+            index,
+            core::any::type_name::<First>()
+        );
+        let second = unsafe { sys::ecs_get_alive(world.world_ptr(), *ecs_second(id)) };
+        EntityView::new_from(world, second)
+    }
+
     /// Obtain column index for field.
     ///
     /// # Arguments
@@ -475,10 +657,14 @@ where
     ///
     /// * C++ API: `iter::str`
     #[doc(alias = "iter::str")]
-    pub fn to_str(&self) -> &'a CStr {
+    pub fn to_str(&self) -> String {
         let c_str = unsafe { sys::ecs_iter_str(self.iter) };
         ecs_assert!(!c_str.is_null(), FlecsErrorCode::InvalidParameter);
-        unsafe { CStr::from_ptr(c_str) }
+        unsafe {
+            let result = CStr::from_ptr(c_str).to_string_lossy().into_owned();
+            sys::ecs_os_api.free_.expect("os api is missing")(c_str as *mut c_void);
+            result
+        }
     }
 
     /// Get read access to field data.
@@ -511,6 +697,7 @@ where
     ///
     /// * C++ API: `iter::field`
     #[doc(alias = "iter::field")]
+    #[track_caller]
     pub unsafe fn field_unchecked<T>(&self, index: i8) -> Field<T> {
         ecs_assert!(
             index < self.iter.field_count,
@@ -550,6 +737,7 @@ where
     /// # Returns
     ///
     /// Returns a column object that can be used to access the field data.
+    #[track_caller]
     pub unsafe fn field_unchecked_mut<T>(&self, index: i8) -> FieldMut<T> {
         ecs_assert!(
             index < self.iter.field_count,
@@ -571,88 +759,67 @@ where
         unsafe { self.field_internal_mut::<T>(index, Entity(id)).unwrap() }
     }
 
-    fn field_checked_mut<T: ComponentId>(&self, index: i8) -> Option<FieldMut<T::UnderlyingType>> {
-        let id = <T::UnderlyingType as ComponentId>::id(self.world());
-
-        if index > self.iter.field_count {
+    /// Returns the id matched for the field if its data can be accessed as `T`.
+    ///
+    /// For pairs (including wildcard pairs such as `(Eats, flecs::Wildcard)`) the type of the
+    /// matched pair is compared, which is either the first or the second element of the pair.
+    fn matched_field_id<T: ComponentId>(&self, index: i8) -> Option<u64> {
+        if index >= self.iter.field_count {
             return None;
         }
 
+        let id = T::id(self.world());
         let term_id = unsafe { sys::ecs_field_id(self.iter, index) };
-        let is_pair = unsafe { sys::ecs_id_is_pair(term_id) };
-        let is_id_correct = id == term_id;
 
-        if is_id_correct || is_pair {
-            return unsafe { self.field_internal_mut::<T::UnderlyingType>(index, Entity(term_id)) };
+        if id == term_id {
+            return Some(term_id);
+        }
+
+        let is_pair = unsafe { sys::ecs_id_is_pair(term_id) };
+        if is_pair && unsafe { sys::ecs_get_typeid(self.iter.world, term_id) } == id {
+            return Some(term_id);
         }
 
         None
     }
 
+    #[track_caller]
+    fn field_checked_mut<T: ComponentId>(
+        &self,
+        index: i8,
+    ) -> Option<FieldMut<'_, T::UnderlyingType>> {
+        let term_id = self.matched_field_id::<T::UnderlyingType>(index)?;
+
+        unsafe { self.field_internal_mut::<T::UnderlyingType>(index, Entity(term_id)) }
+    }
+
+    #[track_caller]
     fn field_at_checked_mut<T: ComponentId>(
         &self,
         index: i8,
         row: usize,
     ) -> Option<FieldAtMut<T::UnderlyingType>> {
-        let id = <T::UnderlyingType as ComponentId>::id(self.world());
-
-        if index > self.iter.field_count {
-            return None;
-        }
-
-        let term_id = unsafe { sys::ecs_field_id(self.iter, index) };
-        let is_pair = unsafe { sys::ecs_id_is_pair(term_id) };
-        let is_id_correct = id == term_id;
-
-        if is_id_correct || is_pair {
-            return unsafe {
-                self.field_at_internal_mut::<T::UnderlyingType>(index, row, Entity(term_id))
-            };
-        }
+        let term_id = self.matched_field_id::<T::UnderlyingType>(index)?;
 
-        None
+        unsafe { self.field_at_internal_mut::<T::UnderlyingType>(index, row, Entity(term_id)) }
     }
 
+    #[track_caller]
     fn field_checked<T: ComponentId>(&self, index: i8) -> Option<Field<T::UnderlyingType>> {
-        let id = <T::UnderlyingType as ComponentId>::id(self.world());
-
-        if index > self.iter.field_count {
-            return None;
-        }
-
-        let term_id = unsafe { sys::ecs_field_id(self.iter, index) };
-        let is_pair = unsafe { sys::ecs_id_is_pair(term_id) };
-        let is_id_correct = id == term_id;
+        let term_id = self.matched_field_id::<T::UnderlyingType>(index)?;
 
-        if is_id_correct || is_pair {
-            return unsafe { self.field_internal::<T::UnderlyingType>(index, Entity(term_id)) };
-        }
-
-        None
+        unsafe { self.field_internal::<T::UnderlyingType>(index, Entity(term_id)) }
     }
 
+    #[track_caller]
     fn field_at_checked<T: ComponentId>(
         &self,
         index: i8,
         row: usize,
     ) -> Option<FieldAt<T::UnderlyingType>> {
-        let id = <T::UnderlyingType as ComponentId>::id(self.world());
-
-        if index > self.iter.field_count {
-            return None;
-        }
-
-        let term_id = unsafe { sys::ecs_field_id(self.iter, index) };
-        let is_pair = unsafe { sys::ecs_id_is_pair(term_id) };
-        let is_id_correct = id == term_id;
-
-        if is_id_correct || is_pair {
-            return unsafe {
-                self.field_at_internal::<T::UnderlyingType>(index, row, Entity(term_id))
-            };
-        }
+        let term_id = self.matched_field_id::<T::UnderlyingType>(index)?;
 
-        None
+        unsafe { self.field_at_internal::<T::UnderlyingType>(index, row, Entity(term_id)) }
     }
 
     /// Get read access to field data.
@@ -682,6 +849,7 @@ where
     /// # See also
     ///
     /// * C++ API: `iter::field`
+    #[track_caller]
     pub fn field<T: ComponentId>(&self, index: i8) -> Option<Field<T::UnderlyingType>> {
         ecs_assert!(
             (self.iter.flags & sys::EcsIterCppEach == 0)
@@ -720,6 +888,7 @@ where
     /// # See also
     ///
     /// * C++ API: `iter::field`
+    #[track_caller]
     pub fn field_mut<T: ComponentId>(&self, index: i8) -> Option<FieldMut<T::UnderlyingType>> {
         ecs_assert!(
             (self.iter.flags & sys::EcsIterCppEach == 0)
@@ -752,7 +921,9 @@ where
     ///
     /// # Returns
     ///
-    /// Returns an `FieldUntyped` object that can be used to access the field data.
+    /// Returns an `FieldUntyped` object that can be used to access the field data. It exposes the
+    /// raw column pointer with [`FieldUntyped::as_ptr()`], and the size, count and stride of the
+    /// values, so generic code can copy component memory without knowing the Rust type.
     ///
     /// # See also
     ///
@@ -821,6 +992,7 @@ where
     /// # See also
     ///
     /// * C++ API: `iter::field_at`
+    #[track_caller]
     pub fn field_at_mut<T>(
         &self,
         index: i8,
@@ -865,6 +1037,7 @@ where
     /// # See also
     ///
     /// * C++ API: `iter::field_at`
+    #[track_caller]
     pub fn field_at<T>(&self, index: i8, row: usize) -> Option<FieldAt<T::UnderlyingType>>
     where
         T: ComponentId,
@@ -948,32 +1121,93 @@ where
     ///
     /// # Returns
     ///
-    /// The entity ids.
+    /// The ids of the entities being iterated, one for each row.
     ///
     /// # See also
     ///
     /// * C++ API: `iter::entities`
     #[doc(alias = "iter::entities")]
-    pub fn entities(&self) -> Field<Entity> {
-        let slice = unsafe {
-            core::slice::from_raw_parts_mut(
-                self.iter.entities as *mut Entity,
+    pub fn entities(&self) -> &[Entity] {
+        if self.iter.count == 0 || self.iter.entities.is_null() {
+            return &[];
+        }
+
+        unsafe {
+            core::slice::from_raw_parts(
+                self.iter.entities as *const Entity,
                 self.iter.count as usize,
             )
-        };
-
-        #[cfg(not(feature = "flecs_safety_readwrite_locks"))]
-        {
-            Field::<Entity>::new(slice, false)
         }
+    }
 
-        #[cfg(feature = "flecs_safety_readwrite_locks")]
-        {
-            let world_ref = unsafe { WorldRef::from_ptr(self.iter.world) };
-            let components_access = world_ref.component_access;
-            //Dummy id and table id since we're not accessing any data
-            Field::<Entity>::new(slice, false, Entity(0), 0, components_access, &world_ref)
-        }
+    /// Invoke `func` for each entity of the current table with the value of a mutable field,
+    /// spread over multiple threads.
+    ///
+    /// The rows of the table are split in chunks, which are processed by the global rayon thread
+    /// pool and have finished when this returns. Small tables are processed as a single chunk.
+    /// Requires the `flecs_parallel` feature.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T` - The component of the field.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The field index, which must be an owned field of type `T`.
+    /// * `func` - The function that is invoked with the entity and its component.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the field is not set, not of type `T`, or shared.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// for i in 0..10_000 {
+    ///     world.entity().set(Position { x: i as f32 });
+    /// }
+    ///
+    /// world.new_query::<&mut Position>().run(|mut it| {
+    ///     while it.next() {
+    ///         it.par_for_each::<Position>(0, |_, p| p.x += 1.0);
+    ///     }
+    /// });
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`TableIter::field_mut()`]
+    #[cfg(feature = "flecs_parallel")]
+    pub fn par_for_each<T>(&self, index: i8, func: impl Fn(Entity, &mut T::UnderlyingType) + Sync)
+    where
+        T: ComponentId,
+        T::UnderlyingType: Send,
+    {
+        use rayon::prelude::*;
+
+        const MIN_ROWS_PER_TASK: usize = 256;
+
+        let field = self
+            .field_mut::<T>(index)
+            .unwrap_or_else(|| panic!("field {index} is not set or is not of the given type"));
+        assert!(
+            !field.is_shared,
+            "field {index} is shared, so its value can't be split over threads"
+        );
+
+        self.entities()
+            .par_iter()
+            .zip(field.slice_components.par_iter_mut())
+            .with_min_len(MIN_ROWS_PER_TASK)
+            .for_each(|(&entity, value)| func(entity, value));
     }
 
     /// Check if the current table has changed since the last iteration.
@@ -1049,6 +1283,7 @@ where
         self.iter.group_id
     }
 
+    #[track_caller]
     unsafe fn field_internal<T>(&self, index: i8, _id: Entity) -> Option<Field<T>> {
         unsafe {
             let is_shared = !self.is_self(index);
@@ -1099,6 +1334,7 @@ where
         }
     }
 
+    #[track_caller]
     unsafe fn field_at_internal<T>(&self, index: i8, row: usize, _id: Entity) -> Option<FieldAt<T>>
     where
         T: ComponentId,
@@ -1140,6 +1376,7 @@ where
         }
     }
 
+    #[track_caller]
     unsafe fn field_internal_mut<T>(&self, index: i8, _id: Entity) -> Option<FieldMut<T>> {
         unsafe {
             let is_shared = !self.is_self(index);
@@ -1190,6 +1427,7 @@ where
         }
     }
 
+    #[track_caller]
     unsafe fn field_at_internal_mut<T>(
         &self,
         index: i8,
@@ -1513,14 +1751,75 @@ where
             }
         };
 
-        self.iter.flags |= sys::EcsIterIsValid;
-        if result && !self.iter.table.is_null() {
+        if result {
+            self.iter.flags |= sys::EcsIterIsValid;
+            if !self.iter.table.is_null() {
+                unsafe {
+                    sys::ecs_table_lock(self.iter.world, self.iter.table);
+                };
+            }
+        }
+
+        result
+    }
+
+    /// Serialize the remaining results of the iterator to JSON.
+    ///
+    /// This uses the same format as the results of the REST api, and iterates the iterator until
+    /// completion, so it replaces the calls to [`TableIter::next()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// #[meta]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.component::<Position>().meta();
+    ///
+    /// world.entity_named("player").set(Position { x: 1.0, y: 2.0 });
+    ///
+    /// world.new_query::<&Position>().run(|mut it| {
+    ///     let json = it.to_json(None).unwrap();
+    ///     assert!(json.contains("\"player\""));
+    /// });
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::to_json()`]
+    /// * C++ API: `iter::to_json`
+    #[doc(alias = "iter::to_json")]
+    #[cfg(feature = "flecs_json")]
+    pub fn to_json(
+        &mut self,
+        desc: Option<&crate::prelude::json::IterToJsonDesc>,
+    ) -> Option<String> {
+        if self.iter.flags & sys::EcsIterIsValid != 0 && !self.iter.table.is_null() {
             unsafe {
-                sys::ecs_table_lock(self.iter.world, self.iter.table);
+                sys::ecs_table_unlock(self.iter.world, self.iter.table);
             };
         }
 
-        result
+        let desc_ptr = desc
+            .map(|d| d as *const crate::prelude::json::IterToJsonDesc)
+            .unwrap_or(core::ptr::null());
+
+        unsafe {
+            let json_ptr = sys::ecs_iter_to_json(self.iter, desc_ptr);
+            if json_ptr.is_null() {
+                return None;
+            }
+            let json = CStr::from_ptr(json_ptr).to_string_lossy().into_owned();
+            sys::ecs_os_api.free_.expect("os api is missing")(json_ptr as *mut c_void);
+            Some(json)
+        }
     }
 
     /// Free iterator resources.
@@ -1528,7 +1827,7 @@ where
     /// until completion (e.g. the last call to `next()` did not return false).
     ///
     /// Failing to call this operation on an unfinished iterator will throw a
-    /// `fatal LEAK_DETECTED` error.
+    /// `fatal LEAK_DETECTED` error. [`QueryAPI::run()`] does this when the callback returns.
     ///
     /// # Example
     ///
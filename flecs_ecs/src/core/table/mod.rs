@@ -772,7 +772,7 @@ impl<'a> TableLock<'a> {
 
 impl Drop for TableLock<'_> {
     fn drop(&mut self) {
-        if std::thread::panicking() {
+        if is_thread_panicking() {
             return;
         }
 
@@ -63,6 +63,84 @@ impl<'a> TermRef<'a> {
     }
 }
 
+/// A mutable reference to a term of an uncached query, see [`Query::modify_term()`].
+///
+/// Only the source and traversal of a term can be changed, so the term keeps matching the
+/// component types of the query.
+pub struct TermRefMut<'a> {
+    term: &'a mut sys::ecs_term_t,
+}
+
+impl<'a> TermRefMut<'a> {
+    pub(crate) fn new(term: &'a mut sys::ecs_term_t) -> Self {
+        Self { term }
+    }
+
+    /// Get the term as a [`TermRef`], to inspect its properties.
+    pub fn as_term_ref(&self) -> TermRef<'_> {
+        TermRef::new(self.term)
+    }
+
+    /// Match the term on a fixed entity instead of its current source.
+    ///
+    /// # See also
+    ///
+    /// * [`TermBuilderImpl::set_src_id()`]
+    pub fn set_src_id(&mut self, id: impl Into<Entity>) -> &mut Self {
+        let traversal = self.term.src.id & ECS_TRAVERSE_FLAGS;
+        self.term.src.id = *id.into() | ECS_IS_ENTITY | traversal;
+        self.term.src.name = core::ptr::null();
+        self
+    }
+
+    /// Match the term on the entities of the query again, after [`TermRefMut::set_src_id()`].
+    pub fn set_src_this(&mut self) -> &mut Self {
+        let traversal = self.term.src.id & ECS_TRAVERSE_FLAGS;
+        self.term.src.id = flecs::This_::ID | ECS_IS_VARIABLE | traversal;
+        self.term.src.name = core::ptr::null();
+        self
+    }
+
+    /// Only match the component on the source itself.
+    ///
+    /// # See also
+    ///
+    /// * [`TermBuilderImpl::self_()`]
+    pub fn self_(&mut self) -> &mut Self {
+        self.set_traversal(ECS_SELF, 0)
+    }
+
+    /// Only match the component on the parent of the source.
+    ///
+    /// # See also
+    ///
+    /// * [`TermBuilderImpl::up()`]
+    pub fn up(&mut self) -> &mut Self {
+        self.set_traversal(ECS_UP, ECS_CHILD_OF)
+    }
+
+    /// Only match the component by traversing `traverse_relationship` upwards from the source.
+    ///
+    /// # See also
+    ///
+    /// * [`TermBuilderImpl::up_id()`]
+    pub fn up_id(&mut self, traverse_relationship: impl Into<Entity>) -> &mut Self {
+        self.set_traversal(ECS_UP, *traverse_relationship.into())
+    }
+
+    /// Match the component on the source, or by traversing `traverse_relationship` upwards when
+    /// the source doesn't have it.
+    pub fn self_up_id(&mut self, traverse_relationship: impl Into<Entity>) -> &mut Self {
+        self.set_traversal(ECS_SELF | ECS_UP, *traverse_relationship.into())
+    }
+
+    fn set_traversal(&mut self, flags: u64, traverse_relationship: u64) -> &mut Self {
+        self.term.src.id = (self.term.src.id & !ECS_TRAVERSE_FLAGS) | flags;
+        self.term.trav = traverse_relationship;
+        self
+    }
+}
+
 #[doc(hidden)]
 pub mod internals {
     use crate::core::*;
@@ -421,16 +499,16 @@ pub trait TermBuilderImpl<'a>: Sized + WorldProvider<'a> + internals::QueryConfi
     ///
     /// # Arguments
     ///
-    /// * `flags` - The flags to set.
+    /// * `flags` - The flags to set, such as [`flecs::IsName`].
     ///
     /// # See also
     ///
     /// * C++ API: `term_builder_i::flags`
     #[doc(alias = "term_builder_i::flags")]
-    fn flags(&mut self, flags: u64) -> &mut Self {
+    fn flags(&mut self, flags: impl Into<Entity>) -> &mut Self {
         check_term_access_validity(self);
 
-        self.term_ref_mut().id = flags;
+        self.term_ref_mut().id = *flags.into();
         self
     }
 
@@ -0,0 +1,220 @@
+//! Walk the graph formed by a relationship, see [`World::traversal()`].
+
+use crate::core::*;
+
+extern crate alloc;
+use alloc::{collections::VecDeque, vec::Vec};
+
+/// Walks the graph formed by a relationship, created by [`World::traversal()`].
+///
+/// An entity with the pair `(R, target)` is a child of `target`, the same way [`flecs::ChildOf`]
+/// forms a hierarchy. This makes it possible to walk custom graphs, such as power grids or
+/// ownership, in the same way as the hierarchy.
+///
+/// The graph may contain cycles, each entity is only visited once by the walks.
+///
+/// # Queries
+///
+/// Queries can match components on the targets of any relationship, with
+/// [`up_type()`](TermBuilderImpl::up_type) and [`cascade_type()`](TermBuilderImpl::cascade_type).
+/// This requires the relationship to be traversable, see [`Traversal::make_traversable()`].
+#[derive(Clone, Copy)]
+pub struct Traversal<'a> {
+    world: WorldRef<'a>,
+    relationship: Entity,
+}
+
+impl<'a> Traversal<'a> {
+    /// The relationship that is traversed.
+    pub fn relationship(&self) -> EntityView<'a> {
+        EntityView::new_from(self.world, self.relationship)
+    }
+
+    /// Add the [`flecs::Traversable`] trait to the relationship, so that queries can match
+    /// components on the targets of the relationship.
+    ///
+    /// This has to be done before the relationship is used. Traversable relationships can't have
+    /// cycles.
+    ///
+    /// # See also
+    ///
+    /// * [`TermBuilderImpl::up_id()`]
+    /// * [`TermBuilderImpl::cascade_id()`]
+    pub fn make_traversable(&self) -> &Self {
+        self.relationship().add::<flecs::Traversable>();
+        self
+    }
+
+    /// The first target of the relationship for an entity.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::target_id()`]
+    pub fn parent(&self, entity: impl Into<Entity>) -> Option<EntityView<'a>> {
+        EntityView::new_from(self.world, entity.into()).target_id(self.relationship, 0)
+    }
+
+    /// Invoke `func` for the targets of an entity, following the first target of the
+    /// relationship up to the root.
+    ///
+    /// The nearest target is visited first.
+    pub fn ancestors(&self, entity: impl Into<Entity>, mut func: impl FnMut(EntityView<'a>)) {
+        let mut visited = hashbrown::HashSet::new();
+        let mut entity = entity.into();
+        visited.insert(entity);
+
+        while let Some(parent) = self.parent(entity) {
+            if !visited.insert(parent.id()) {
+                break;
+            }
+            func(parent);
+            entity = parent.id();
+        }
+    }
+
+    /// Invoke `func` for the entities that have the relationship with `entity` as target.
+    ///
+    /// # See also
+    ///
+    /// * [`EntityView::each_child_of_id()`]
+    pub fn children(&self, entity: impl Into<Entity>, func: impl FnMut(EntityView)) -> bool {
+        EntityView::new_from(self.world, entity.into()).each_child_of_id(self.relationship, func)
+    }
+
+    /// The number of targets between an entity and the root.
+    ///
+    /// Unlike [`EntityView::depth_id()`], this doesn't require the relationship to be acyclic.
+    pub fn depth(&self, entity: impl Into<Entity>) -> usize {
+        let mut depth = 0;
+        self.ancestors(entity, |_| depth += 1);
+        depth
+    }
+
+    /// Invoke `func` for the descendants of `root`, depth first.
+    ///
+    /// Entities are visited before their children, the depth passed to `func` is `1` for the
+    /// children of `root`.
+    ///
+    /// # See also
+    ///
+    /// * [`Traversal::breadth_first()`]
+    pub fn depth_first(
+        &self,
+        root: impl Into<Entity>,
+        mut func: impl FnMut(EntityView<'a>, usize),
+    ) {
+        let root = root.into();
+        let mut visited = hashbrown::HashSet::new();
+        visited.insert(root);
+
+        let mut stack = Vec::new();
+        let mut children = Vec::new();
+        self.push_children(root, 1, &mut visited, &mut children);
+        stack.extend(children.drain(..).rev());
+
+        while let Some((entity, depth)) = stack.pop() {
+            func(EntityView::new_from(self.world, entity), depth);
+            self.push_children(entity, depth + 1, &mut visited, &mut children);
+            stack.extend(children.drain(..).rev());
+        }
+    }
+
+    /// Invoke `func` for the descendants of `root`, breadth first.
+    ///
+    /// All entities at a depth are visited before the entities at the next depth, the depth
+    /// passed to `func` is `1` for the children of `root`.
+    ///
+    /// # See also
+    ///
+    /// * [`Traversal::depth_first()`]
+    pub fn breadth_first(
+        &self,
+        root: impl Into<Entity>,
+        mut func: impl FnMut(EntityView<'a>, usize),
+    ) {
+        let root = root.into();
+        let mut visited = hashbrown::HashSet::new();
+        visited.insert(root);
+
+        let mut queue = VecDeque::new();
+        let mut children = Vec::new();
+        self.push_children(root, 1, &mut visited, &mut children);
+        queue.extend(children.drain(..));
+
+        while let Some((entity, depth)) = queue.pop_front() {
+            func(EntityView::new_from(self.world, entity), depth);
+            self.push_children(entity, depth + 1, &mut visited, &mut children);
+            queue.extend(children.drain(..));
+        }
+    }
+
+    fn push_children(
+        &self,
+        entity: Entity,
+        depth: usize,
+        visited: &mut hashbrown::HashSet<Entity>,
+        children: &mut Vec<(Entity, usize)>,
+    ) {
+        self.children(entity, |child| {
+            if visited.insert(child.id()) {
+                children.push((child.id(), depth));
+            }
+        });
+    }
+}
+
+impl World {
+    /// Walk the graph formed by a relationship.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `R` - The relationship to traverse.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct PoweredBy;
+    ///
+    /// let world = World::new();
+    ///
+    /// let generator = world.entity_named("generator");
+    /// let substation = world.entity_named("substation").add_first::<PoweredBy>(generator);
+    /// let house = world.entity_named("house").add_first::<PoweredBy>(substation);
+    /// let factory = world.entity_named("factory").add_first::<PoweredBy>(substation);
+    ///
+    /// let grid = world.traversal::<PoweredBy>();
+    ///
+    /// let mut powered = Vec::new();
+    /// grid.breadth_first(generator, |e, depth| powered.push((e.name(), depth)));
+    /// assert_eq!(powered[0], ("substation".to_string(), 1));
+    /// assert_eq!(powered.len(), 3);
+    ///
+    /// assert_eq!(grid.depth(house), 2);
+    /// assert_eq!(grid.parent(factory).unwrap(), substation);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::traversal_id()`]
+    pub fn traversal<R: ComponentId>(&self) -> Traversal<'_> {
+        self.traversal_id(R::id(self))
+    }
+
+    /// Walk the graph formed by a relationship.
+    ///
+    /// # Arguments
+    ///
+    /// * `relationship` - The relationship to traverse.
+    ///
+    /// # See also
+    ///
+    /// * [`World::traversal()`]
+    pub fn traversal_id(&self, relationship: impl Into<Entity>) -> Traversal<'_> {
+        Traversal {
+            world: self.world(),
+            relationship: relationship.into(),
+        }
+    }
+}
@@ -1,6 +1,9 @@
 #![doc(hidden)]
 use core::fmt::{Display, Formatter};
 
+extern crate alloc;
+use alloc::string::String;
+
 #[doc(hidden)]
 /// Enum representing the error codes that can be used by `ecs_asserts` and `ecs_abort`
 pub enum FlecsErrorCode {
@@ -126,6 +129,59 @@ impl FlecsErrorCode {
     }
 }
 
+/// Error returned by fallible operations, such as the `try_` variants of JSON deserialization.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[non_exhaustive]
+pub enum FlecsError {
+    /// A parameter passed to the operation is invalid.
+    InvalidParameter,
+    /// The id is not a component, or has no type information.
+    NotAComponent,
+    /// The entity is not alive.
+    NotAlive,
+    /// Parsing an expression, query or JSON failed. Contains the error message logged by flecs,
+    /// which is empty if it couldn't be captured.
+    ParseError(String),
+    /// A flecs assert failed, see [`World::catch_assert()`](crate::core::World::catch_assert).
+    /// Contains the message logged by flecs.
+    Assert(String),
+    /// The operation violates a constraint of a relationship, such as
+    /// [`flecs::OneOf`](crate::core::flecs::OneOf). Contains a description of the violation.
+    ConstraintViolated(String),
+    /// A reference to an entity, such as a path, doesn't resolve to an entity in this world.
+    /// Returned when a lookup by name finds nothing. Contains the reference.
+    Unresolved(String),
+    /// The entity doesn't have the component(s) that were requested. Contains the requested type.
+    MissingComponent(&'static str),
+    /// Reading a file failed. Contains the path and the error of the OS.
+    Io(String),
+}
+
+impl Display for FlecsError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FlecsError::InvalidParameter => write!(f, "invalid parameter"),
+            FlecsError::NotAComponent => write!(f, "id is not a component"),
+            FlecsError::NotAlive => write!(f, "entity is not alive"),
+            FlecsError::ParseError(message) if message.is_empty() => write!(f, "parse error"),
+            FlecsError::ParseError(message) => write!(f, "parse error: {message}"),
+            FlecsError::Assert(message) => write!(f, "flecs assert failed: {message}"),
+            FlecsError::ConstraintViolated(message) => write!(f, "{message}"),
+            FlecsError::Unresolved(reference) => write!(f, "unresolved reference: {reference}"),
+            FlecsError::MissingComponent(name) => write!(f, "entity does not have {name}"),
+            FlecsError::Io(message) => write!(f, "io error: {message}"),
+        }
+    }
+}
+
+impl core::error::Error for FlecsError {}
+
+impl From<crate::core::QueryError> for FlecsError {
+    fn from(err: crate::core::QueryError) -> Self {
+        FlecsError::ParseError(err.message().into())
+    }
+}
+
 /// Macro to assert a condition.
 /// In release mode, the condition is not checked.
 /// Can be turned off by disabling the `flecs_ecs_asserts` feature
@@ -161,61 +217,56 @@ macro_rules! ecs_assert {
     ($($args:tt)*) => {};
 }
 
+/// Print the message and abort the application.
+///
+/// Without `std` there is no way to print or abort, so this panics instead. What happens then is
+/// up to the `#[panic_handler]` of the application, which should not return to flecs: flecs
+/// doesn't expect an abort to return, and its state can't be relied on after it.
+#[cold]
+#[allow(clippy::print_stderr)]
+pub(crate) fn abort_with_message(message: core::fmt::Arguments) -> ! {
+    #[cfg(feature = "std")]
+    {
+        std::eprintln!("{message}");
+        std::process::abort();
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        panic!("{message}");
+    }
+}
+
 /// Macro to abort the application when an error occurs.
 #[allow(unused_macros)]
 macro_rules! ecs_abort {
     ($error_code:expr $(,)?) => {
-        #[cfg(feature = "std")]
-        extern crate std;
-
-        extern crate alloc;
-
-        let file = file!();
-        let line = line!();
-
-        eprintln!("{}:{}: {}", file, line, $error_code);
-
-        //unsafe {
-        //    if let Some(abort_func) = ecs_os_api.abort_ {
-        //        abort_func();
-        //    }
-        //};
-
-        std::process::abort();
+        $crate::core::utility::abort_with_message(format_args!(
+            "{}:{}: {}",
+            file!(),
+            line!(),
+            $error_code
+        ))
     };
     ($error_code:expr, $msg:expr $(,)?) => {
-        eprintln!("{}: {}", $error_code, $msg);
-        //unsafe {
-        //    if let Some(abort_func) = ecs_os_api.abort_ {
-        //        abort_func();
-        //    }
-        //};
-        std::process::abort();
+        $crate::core::utility::abort_with_message(format_args!("{}: {}", $error_code, $msg))
     };
     ($error_code:expr, $arg:ident: *const c_char $(,)?) => {
-        eprintln!("{}: {}",
+        $crate::core::utility::abort_with_message(format_args!(
+            "{}: {}",
             $error_code,
             if $arg.is_null() {
                 "<null>"
             } else {
                 unsafe { CStr::from_ptr($arg).to_str().unwrap_or("<invalid>") }
             }
-        );
-        //unsafe {
-        //    if let Some(abort_func) = ecs_os_api.abort_ {
-        //        abort_func();
-        //    }
-        //};
-        std::process::abort();
+        ))
     };
     ($error_code:expr, $fmt:expr, $($arg:tt)+) => {
-        eprintln!("{}: {}", $error_code, format!($fmt, $($arg)+));
-        //unsafe {
-        //    if let Some(abort_func) = ecs_os_api.abort_ {
-        //        abort_func();
-        //    }
-        //};
-        std::process::abort();
+        $crate::core::utility::abort_with_message(format_args!(
+            "{}: {}",
+            $error_code,
+            format_args!($fmt, $($arg)+)
+        ))
     };
 }
 
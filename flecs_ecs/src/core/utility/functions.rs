@@ -18,6 +18,22 @@ use alloc::{
 
 const ECS_GENERATION_MASK: u64 = u32::MAX as u64;
 
+/// Returns true if the current thread is unwinding because of a panic.
+///
+/// Without `std` there is no way to tell, so this always returns false, even when the panic
+/// handler of the application unwinds.
+#[inline(always)]
+pub(crate) fn is_thread_panicking() -> bool {
+    #[cfg(feature = "std")]
+    {
+        std::thread::panicking()
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        false
+    }
+}
+
 /// Combines two 32 bit integers into a 64 bit integer.
 ///
 /// # Arguments
@@ -310,6 +326,41 @@ pub(crate) fn set_helper<T: ComponentId>(
     }
 }
 
+/// Moves `value` into the storage of the component, without constructing it first.
+///
+/// Unlike [`set_helper`], this doesn't use `ecs_ensure_modified_id` when deferred, so the
+/// component is never default constructed and the value is written exactly once.
+///
+/// # Arguments
+///
+/// * `entity`: The ID of the entity.
+/// * `value`: The value to move into the component storage.
+/// * `id`: The ID of the component type.
+pub(crate) fn emplace_helper<T: ComponentId>(
+    world: *mut sys::ecs_world_t,
+    entity: u64,
+    value: T,
+    id: u64,
+) {
+    const {
+        assert!(
+            core::mem::size_of::<T>() != 0,
+            "cannot emplace zero-sized-type / tag components"
+        );
+    };
+
+    let mut is_new = false;
+    unsafe {
+        let ptr = sys::ecs_emplace_id(world, entity, id, &mut is_new) as *mut T;
+
+        if !is_new {
+            core::ptr::drop_in_place(ptr);
+        }
+        core::ptr::write(ptr, value);
+        sys::ecs_modified_id(world, entity, id);
+    }
+}
+
 /// Remove generation from entity id.
 ///
 /// # Arguments
@@ -524,18 +575,16 @@ pub(crate) fn check_add_id_validity(world: *const sys::ecs_world_t, id: u64) {
 #[inline(never)]
 pub(crate) fn has_default_hook(world: *const sys::ecs_world_t, id: u64) -> bool {
     let hooks = unsafe { sys::ecs_get_hooks_id(world, id) };
-    let ctor_hooks =
-        unsafe { (*hooks).ctor }.expect("ctor hook is always implemented, either in Rust of C");
-
-    !core::ptr::fn_addr_eq(
-        ctor_hooks,
-        sys::flecs_default_ctor
-            as unsafe extern "C-unwind" fn(
-                *mut core::ffi::c_void,
-                i32,
-                *const sys::ecs_type_info_t,
-            ),
-    )
+    // plain old data types without Default or Clone don't register any hooks, so there's no ctor at all
+    let Some(ctor_hooks) = (unsafe { (*hooks).ctor }) else {
+        return false;
+    };
+
+    type Ctor =
+        unsafe extern "C-unwind" fn(*mut core::ffi::c_void, i32, *const sys::ecs_type_info_t);
+    // types that need drop but don't implement Default have a ctor that zeroes the value
+    !core::ptr::fn_addr_eq(ctor_hooks, sys::flecs_default_ctor as Ctor)
+        && !core::ptr::fn_addr_eq(ctor_hooks, lifecycle_traits::zeroed_ctor as Ctor)
 }
 
 /// Separate the types of an `Archetype` into a `Vec<String>`.
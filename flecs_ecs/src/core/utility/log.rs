@@ -53,3 +53,20 @@ pub fn enable_timedelta_logging(enabled: bool) {
         sys::ecs_log_enable_timedelta(enabled);
     }
 }
+
+/// Log an error with the flecs log, where it can be captured like the errors of flecs itself.
+#[track_caller]
+pub(crate) fn log_error(message: &str) {
+    let location = core::panic::Location::caller();
+    let file = compact_str::format_compact!("{}\0", location.file());
+    let message = compact_str::format_compact!("{}\0", message);
+    unsafe {
+        sys::ecs_log_(
+            -3,
+            file.as_ptr() as *const _,
+            location.line() as i32,
+            c"%s".as_ptr(),
+            message.as_ptr() as *const core::ffi::c_char,
+        );
+    }
+}
@@ -5,6 +5,7 @@ mod errors;
 mod functions;
 pub(crate) mod id_map;
 mod log;
+mod small_cstring;
 pub mod traits;
 pub mod types;
 
@@ -12,6 +13,7 @@ pub use errors::*;
 pub use functions::*;
 pub(crate) use id_map::*;
 pub use log::*;
+pub(crate) use small_cstring::SmallCString;
 
 #[doc(hidden)]
 pub use traits::*;
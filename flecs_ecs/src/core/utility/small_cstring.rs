@@ -0,0 +1,99 @@
+//! Nul terminated strings for the names and paths that are passed to flecs.
+
+use core::ffi::c_char;
+
+extern crate alloc;
+use alloc::vec::Vec;
+
+/// Strings up to this length, excluding the nul terminator, are stored inline.
+const INLINE_CAPACITY: usize = 127;
+
+/// A nul terminated copy of a string, stored inline when it is short enough.
+///
+/// Names, paths and lookups are converted for every call, which would otherwise allocate in
+/// inner loops. Strings that don't fit inline are stored on the heap.
+///
+/// Like the C API, the string ends at the first nul byte.
+pub(crate) enum SmallCString {
+    Inline {
+        buf: [u8; INLINE_CAPACITY + 1],
+        len: u8,
+    },
+    Heap(Vec<u8>),
+}
+
+impl SmallCString {
+    #[inline]
+    pub(crate) fn new(s: &str) -> Self {
+        let bytes = s.as_bytes();
+        if bytes.len() <= INLINE_CAPACITY {
+            let mut buf = [0; INLINE_CAPACITY + 1];
+            buf[..bytes.len()].copy_from_slice(bytes);
+            SmallCString::Inline {
+                buf,
+                len: bytes.len() as u8,
+            }
+        } else {
+            let mut buf = Vec::with_capacity(bytes.len() + 1);
+            buf.extend_from_slice(bytes);
+            buf.push(0);
+            SmallCString::Heap(buf)
+        }
+    }
+
+    /// Pointer to the nul terminated string, valid as long as `self` is alive.
+    #[inline]
+    pub(crate) fn as_ptr(&self) -> *const c_char {
+        match self {
+            SmallCString::Inline { buf, .. } => buf.as_ptr() as *const c_char,
+            SmallCString::Heap(buf) => buf.as_ptr() as *const c_char,
+        }
+    }
+
+    /// Whether the string is empty, not counting the nul terminator.
+    #[inline]
+    pub(crate) fn is_empty(&self) -> bool {
+        match self {
+            SmallCString::Inline { len, .. } => *len == 0,
+            SmallCString::Heap(buf) => buf.len() == 1,
+        }
+    }
+
+    #[cfg(test)]
+    fn is_inline(&self) -> bool {
+        matches!(self, SmallCString::Inline { .. })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ffi::CStr;
+
+    fn to_str(s: &SmallCString) -> &str {
+        unsafe { CStr::from_ptr(s.as_ptr()) }.to_str().unwrap()
+    }
+
+    #[test]
+    fn small_cstring_inline_and_heap() {
+        let empty = SmallCString::new("");
+        assert!(empty.is_empty());
+        assert!(empty.is_inline());
+        assert_eq!(to_str(&empty), "");
+
+        let short = SmallCString::new("parent::child");
+        assert!(!short.is_empty());
+        assert!(short.is_inline());
+        assert_eq!(to_str(&short), "parent::child");
+
+        let max = "a".repeat(INLINE_CAPACITY);
+        assert!(SmallCString::new(&max).is_inline());
+        assert_eq!(to_str(&SmallCString::new(&max)), max);
+
+        let long = "b".repeat(INLINE_CAPACITY + 1);
+        let heap = SmallCString::new(&long);
+        assert!(!heap.is_inline());
+        assert!(!heap.is_empty());
+        assert_eq!(to_str(&heap), long);
+    }
+}
@@ -0,0 +1,55 @@
+use core::convert::Infallible;
+use core::ops::ControlFlow;
+
+/// The return type of callbacks that are invoked for each matched entity, such as the ones passed
+/// to [`QueryAPI::each()`] and [`SystemAPI::each()`].
+///
+/// Callbacks either return `()`, or [`ControlFlow`] to stop iterating early. When a callback
+/// returns [`ControlFlow::Break`], it is not invoked for the remaining entities, and queries
+/// return the value it was called with.
+pub trait CallbackResult: Sized {
+    /// The value of the callback when it stops iterating.
+    type Break;
+
+    /// If the callback can stop iterating.
+    #[doc(hidden)]
+    const CAN_BREAK: bool;
+
+    /// Get if the callback wants to continue iterating.
+    #[doc(hidden)]
+    fn branch(self) -> ControlFlow<Self::Break>;
+
+    /// The result of the iteration, after the callback stopped or was invoked for all entities.
+    #[doc(hidden)]
+    fn from_flow(flow: ControlFlow<Self::Break>) -> Self;
+}
+
+impl CallbackResult for () {
+    type Break = Infallible;
+
+    const CAN_BREAK: bool = false;
+
+    #[inline(always)]
+    fn branch(self) -> ControlFlow<Infallible> {
+        ControlFlow::Continue(())
+    }
+
+    #[inline(always)]
+    fn from_flow(_: ControlFlow<Infallible>) -> Self {}
+}
+
+impl<B> CallbackResult for ControlFlow<B> {
+    type Break = B;
+
+    const CAN_BREAK: bool = true;
+
+    #[inline(always)]
+    fn branch(self) -> ControlFlow<B> {
+        self
+    }
+
+    #[inline(always)]
+    fn from_flow(flow: ControlFlow<B>) -> Self {
+        flow
+    }
+}
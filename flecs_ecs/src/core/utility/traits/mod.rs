@@ -1,3 +1,4 @@
+mod callback_result;
 mod id_operations;
 mod inout_oper;
 mod into_component_id;
@@ -7,6 +8,7 @@ mod query_api;
 mod system_api;
 mod world_provider;
 
+pub use callback_result::*;
 pub use id_operations::*;
 pub use inout_oper::*;
 pub use into_component_id::*;
@@ -27,6 +29,7 @@ pub mod private {
     use crate::core::*;
     use crate::sys;
     use core::ffi::c_void;
+    use core::ops::ControlFlow;
 
     #[cfg(feature = "std")]
     extern crate std;
@@ -76,10 +79,35 @@ pub mod private {
         /// # See also
         ///
         /// * C++ API: `iter_invoker::invoke_callback`
-        unsafe extern "C-unwind" fn execute_each<const CALLED_FROM_RUN: bool, Func>(
+        unsafe extern "C-unwind" fn execute_each<const CALLED_FROM_RUN: bool, Func, R>(
             iter: *mut sys::ecs_iter_t,
         ) where
-            Func: FnMut(T::TupleType<'_>),
+            Func: FnMut(T::TupleType<'_>) -> R,
+            R: CallbackResult,
+        {
+            unsafe {
+                let _ = Self::invoke_each::<CALLED_FROM_RUN, Func, R>(&mut *iter);
+            }
+        }
+
+        /// Run callback of the each functionality, for callbacks that can stop iterating.
+        unsafe extern "C-unwind" fn execute_each_until_break<Func, R>(iter: *mut sys::ecs_iter_t)
+        where
+            Func: FnMut(T::TupleType<'_>) -> R,
+            R: CallbackResult,
+        {
+            unsafe {
+                Self::run_until_break(iter, |iter| Self::invoke_each::<false, Func, R>(iter));
+            }
+        }
+
+        /// Invoke the each callback for the entities of the current table.
+        unsafe fn invoke_each<const CALLED_FROM_RUN: bool, Func, R>(
+            iter: &mut sys::ecs_iter_t,
+        ) -> ControlFlow<R::Break>
+        where
+            Func: FnMut(T::TupleType<'_>) -> R,
+            R: CallbackResult,
         {
             unsafe {
                 const {
@@ -89,8 +117,7 @@ pub mod private {
                     );
                 }
                 #[cfg(feature = "flecs_safety_readwrite_locks")]
-                let world = WorldRef::from_ptr((*iter).world);
-                let iter = &mut *iter;
+                let world = WorldRef::from_ptr(iter.world);
                 #[cfg(feature = "flecs_safety_readwrite_locks")]
                 let components_access = world.components_access_map();
 
@@ -121,9 +148,13 @@ pub mod private {
                     sys::ecs_table_lock(iter.world, iter.table);
                 }
 
+                let mut flow = ControlFlow::Continue(());
                 for i in 0..iter_count {
                     let tuple = components_data.get_tuple(&*iter, i);
-                    each(tuple);
+                    flow = each(tuple).branch();
+                    if flow.is_break() {
+                        break;
+                    }
                 }
 
                 if !CALLED_FROM_RUN {
@@ -139,6 +170,8 @@ pub mod private {
                         &world,
                     );
                 }
+
+                flow
             }
         }
 
@@ -152,10 +185,38 @@ pub mod private {
         ///
         /// * C++ API: `iter_invoker::invoke_callback`
         #[doc(alias = "iter_invoker::invoke_callback")]
-        unsafe extern "C-unwind" fn execute_each_entity<const CALLED_FROM_RUN: bool, Func>(
+        unsafe extern "C-unwind" fn execute_each_entity<const CALLED_FROM_RUN: bool, Func, R>(
+            iter: *mut sys::ecs_iter_t,
+        ) where
+            Func: FnMut(EntityView, T::TupleType<'_>) -> R,
+            R: CallbackResult,
+        {
+            unsafe {
+                let _ = Self::invoke_each_entity::<CALLED_FROM_RUN, Func, R>(&mut *iter);
+            }
+        }
+
+        /// Run callback of the `each_entity` functionality, for callbacks that can stop iterating.
+        unsafe extern "C-unwind" fn execute_each_entity_until_break<Func, R>(
             iter: *mut sys::ecs_iter_t,
         ) where
-            Func: FnMut(EntityView, T::TupleType<'_>),
+            Func: FnMut(EntityView, T::TupleType<'_>) -> R,
+            R: CallbackResult,
+        {
+            unsafe {
+                Self::run_until_break(iter, |iter| {
+                    Self::invoke_each_entity::<false, Func, R>(iter)
+                });
+            }
+        }
+
+        /// Invoke the `each_entity` callback for the entities of the current table.
+        unsafe fn invoke_each_entity<const CALLED_FROM_RUN: bool, Func, R>(
+            iter: &mut sys::ecs_iter_t,
+        ) -> ControlFlow<R::Break>
+        where
+            Func: FnMut(EntityView, T::TupleType<'_>) -> R,
+            R: CallbackResult,
         {
             unsafe {
                 const {
@@ -165,8 +226,7 @@ pub mod private {
                     );
                 }
                 #[cfg(feature = "flecs_safety_readwrite_locks")]
-                let world = WorldRef::from_ptr((*iter).world);
-                let iter = &mut *iter;
+                let world = WorldRef::from_ptr(iter.world);
                 #[cfg(feature = "flecs_safety_readwrite_locks")]
                 let components_access = world.components_access_map();
 
@@ -206,12 +266,16 @@ pub mod private {
                     sys::ecs_table_lock(iter.world, iter.table);
                 }
 
+                let mut flow = ControlFlow::Continue(());
                 for i in 0..iter_count {
                     let world = WorldRef::from_ptr(iter.world);
                     let entity = EntityView::new_from(world, *iter.entities.add(i));
                     let tuple = components_data.get_tuple(&*iter, i);
 
-                    each_entity(entity, tuple);
+                    flow = each_entity(entity, tuple).branch();
+                    if flow.is_break() {
+                        break;
+                    }
                 }
 
                 if !CALLED_FROM_RUN {
@@ -227,6 +291,8 @@ pub mod private {
                         &world,
                     );
                 }
+
+                flow
             }
         }
 
@@ -240,9 +306,33 @@ pub mod private {
         ///
         /// * C++ API: `iter_invoker::invoke_callback`
         #[doc(alias = "iter_invoker::invoke_callback")]
-        unsafe extern "C-unwind" fn execute_each_iter<Func>(iter: *mut sys::ecs_iter_t)
+        unsafe extern "C-unwind" fn execute_each_iter<Func, R>(iter: *mut sys::ecs_iter_t)
+        where
+            Func: FnMut(TableIter<false, P>, usize, T::TupleType<'_>) -> R,
+            R: CallbackResult,
+        {
+            unsafe {
+                let _ = Self::invoke_each_iter::<Func, R>(&mut *iter);
+            }
+        }
+
+        /// Run callback of the `each_iter` functionality, for callbacks that can stop iterating.
+        unsafe extern "C-unwind" fn execute_each_iter_until_break<Func, R>(
+            iter: *mut sys::ecs_iter_t,
+        ) where
+            Func: FnMut(TableIter<false, P>, usize, T::TupleType<'_>) -> R,
+            R: CallbackResult,
+        {
+            unsafe {
+                Self::run_until_break(iter, |iter| Self::invoke_each_iter::<Func, R>(iter));
+            }
+        }
+
+        /// Invoke the `each_iter` callback for the entities of the current table.
+        unsafe fn invoke_each_iter<Func, R>(iter: &mut sys::ecs_iter_t) -> ControlFlow<R::Break>
         where
-            Func: FnMut(TableIter<false, P>, usize, T::TupleType<'_>),
+            Func: FnMut(TableIter<false, P>, usize, T::TupleType<'_>) -> R,
+            R: CallbackResult,
         {
             unsafe {
                 const {
@@ -252,8 +342,7 @@ pub mod private {
                     );
                 }
                 #[cfg(feature = "flecs_safety_readwrite_locks")]
-                let world = WorldRef::from_ptr((*iter).world);
-                let iter = &mut *iter;
+                let world = WorldRef::from_ptr(iter.world);
                 #[cfg(feature = "flecs_safety_readwrite_locks")]
                 let components_access = world.components_access_map();
 
@@ -281,11 +370,15 @@ pub mod private {
 
                 sys::ecs_table_lock(iter.world, iter.table);
 
+                let mut flow = ControlFlow::Continue(());
                 for i in 0..iter_count {
                     let tuple = components_data.get_tuple(&*iter, i);
                     let iter_t = TableIter::new(iter);
 
-                    each_iter(iter_t, i, tuple);
+                    flow = each_iter(iter_t, i, tuple).branch();
+                    if flow.is_break() {
+                        break;
+                    }
                 }
                 sys::ecs_table_unlock(iter.world, iter.table);
 
@@ -298,6 +391,27 @@ pub mod private {
                         &world,
                     );
                 }
+
+                flow
+            }
+        }
+
+        /// Iterate the tables of a run callback, until `invoke` stops iterating.
+        ///
+        /// The remaining tables are still iterated, without invoking the callback, so that the
+        /// iterator is finished the same way for systems and observers.
+        unsafe fn run_until_break<B>(
+            iter: *mut sys::ecs_iter_t,
+            mut invoke: impl FnMut(&mut sys::ecs_iter_t) -> ControlFlow<B>,
+        ) {
+            unsafe {
+                let iter = &mut *iter;
+                while sys::ecs_iter_next(iter) {
+                    if invoke(iter).is_break() {
+                        while sys::ecs_iter_next(iter) {}
+                        return;
+                    }
+                }
             }
         }
 
@@ -1,4 +1,5 @@
 use core::ffi::c_char;
+use core::ops::ControlFlow;
 
 use flecs_ecs::core::*;
 use flecs_ecs::sys;
@@ -7,7 +8,7 @@ use flecs_ecs::sys;
 extern crate std;
 
 extern crate alloc;
-use alloc::string::String;
+use alloc::{string::String, vec::Vec};
 
 #[cfg(feature = "flecs_json")]
 use alloc::string::ToString;
@@ -19,6 +20,19 @@ pub enum FirstOnlyError {
     MoreThanOneEntity,
 }
 
+impl core::fmt::Display for FirstOnlyError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+        match self {
+            FirstOnlyError::NoEntities => write!(f, "query has no matching entities"),
+            FirstOnlyError::MoreThanOneEntity => {
+                write!(f, "query has more than one matching entity")
+            }
+        }
+    }
+}
+
+impl core::error::Error for FirstOnlyError {}
+
 pub trait IterOperations {
     #[doc(hidden)]
     fn retrieve_iter(&self) -> sys::ecs_iter_t;
@@ -49,12 +63,42 @@ where
     /// The following function signatures is valid:
     ///  - func(comp1 : &mut T1, comp2 : &mut T2, ...)
     ///
+    /// The function can return [`ControlFlow`] to stop iterating early. Iteration stops as soon
+    /// as it returns [`ControlFlow::Break`], which is then returned from `each`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// use core::ops::ControlFlow;
+    ///
+    /// #[derive(Component)]
+    /// struct Health(i32);
+    ///
+    /// let world = World::new();
+    /// world.entity().set(Health(10));
+    /// world.entity().set(Health(-5));
+    /// world.entity().set(Health(20));
+    ///
+    /// let query = world.new_query::<&Health>();
+    ///
+    /// let invalid = query.each(|health| {
+    ///     if health.0 < 0 {
+    ///         return ControlFlow::Break(health.0);
+    ///     }
+    ///     ControlFlow::Continue(())
+    /// });
+    ///
+    /// assert_eq!(invalid, ControlFlow::Break(-5));
+    /// ```
+    ///
     /// # See also
     ///
     /// * [`World::each()`]
+    /// * [`QueryAPI::iter_while()`]
     /// * C++ API: `iterable::each`
     #[doc(alias = "iterable::each")]
-    fn each(&self, mut func: impl FnMut(T::TupleType<'_>)) {
+    fn each<R: CallbackResult>(&self, mut func: impl FnMut(T::TupleType<'_>) -> R) -> R {
         const {
             assert!(
                 !T::CONTAINS_ANY_TAG_TERM,
@@ -66,6 +110,9 @@ where
             #[cfg(feature = "flecs_safety_readwrite_locks")]
             let world = self.world();
             let world_ptr = self.world_ptr_mut();
+            #[cfg(feature = "flecs_profiler")]
+            let mut profile =
+                crate::addons::profiler::ProfileScope::query(world_ptr, self.query_ptr());
             let mut iter = self.retrieve_iter();
             #[cfg(feature = "flecs_safety_readwrite_locks")]
             let components_access = world.components_access_map();
@@ -73,6 +120,8 @@ where
             iter.flags |= sys::EcsIterCppEach;
 
             while self.iter_next(&mut iter) {
+                #[cfg(feature = "flecs_profiler")]
+                profile.table(iter.count);
                 let mut components_data = T::create_ptrs(&iter);
 
                 let iter_count = {
@@ -95,9 +144,13 @@ where
 
                 sys::ecs_table_lock(world_ptr, iter.table);
 
+                let mut flow = ControlFlow::Continue(());
                 for i in 0..iter_count {
                     let tuple = components_data.get_tuple(&iter, i);
-                    func(tuple);
+                    flow = func(tuple).branch();
+                    if flow.is_break() {
+                        break;
+                    }
                 }
 
                 sys::ecs_table_unlock(world_ptr, iter.table);
@@ -111,7 +164,15 @@ where
                         &world,
                     );
                 }
+
+                if flow.is_break() {
+                    // the iterator is not iterated until completion, so it has to be cleaned up
+                    sys::ecs_iter_fini(&mut iter);
+                    return R::from_flow(flow);
+                }
             }
+
+            R::from_flow(ControlFlow::Continue(()))
         }
     }
 
@@ -120,12 +181,17 @@ where
     /// The following function signatures is valid:
     ///  - func(e : Entity , comp1 : &mut T1, comp2 : &mut T2, ...)
     ///
+    /// Like [`QueryAPI::each()`], the function can return [`ControlFlow`] to stop iterating early.
+    ///
     /// # See also
     ///
     /// * [`World::each_entity()`]
     /// * C++ API: `iterable::each`
     #[doc(alias = "iterable::each")]
-    fn each_entity(&self, mut func: impl FnMut(EntityView, T::TupleType<'_>)) {
+    fn each_entity<R: CallbackResult>(
+        &self,
+        mut func: impl FnMut(EntityView, T::TupleType<'_>) -> R,
+    ) -> R {
         const {
             assert!(
                 !T::CONTAINS_ANY_TAG_TERM,
@@ -137,6 +203,9 @@ where
             #[cfg(feature = "flecs_safety_readwrite_locks")]
             let world = self.world();
             let world_ptr = self.world_ptr_mut();
+            #[cfg(feature = "flecs_profiler")]
+            let mut profile =
+                crate::addons::profiler::ProfileScope::query(world_ptr, self.query_ptr());
             let mut iter = self.retrieve_iter();
             #[cfg(feature = "flecs_safety_readwrite_locks")]
             let components_access = world.components_access_map();
@@ -144,6 +213,8 @@ where
             iter.flags |= sys::EcsIterCppEach;
 
             while self.iter_next(&mut iter) {
+                #[cfg(feature = "flecs_profiler")]
+                profile.table(iter.count);
                 ecs_assert!(
                     !iter.entities.is_null(),
                     FlecsErrorCode::InvalidParameter,
@@ -172,6 +243,7 @@ where
 
                 sys::ecs_table_lock(world_ptr, iter.table);
 
+                let mut flow = ControlFlow::Continue(());
                 // TODO random thought, I think I can determine the elements is a ref or not before the for loop and then pass two arrays with the indices of the ref and non ref elements
                 // I will come back to this in the future, my thoughts are somewhere else right now. If my assumption is correct, this will get rid of the branch in the for loop
                 // and potentially allow for more conditions for vectorization to happen. This could potentially offer a (small) performance boost since the branch predictor avoids probably
@@ -181,7 +253,10 @@ where
                     let world = self.world();
                     let tuple = components_data.get_tuple(&iter, i);
 
-                    func(EntityView::new_from(world, *iter.entities.add(i)), tuple);
+                    flow = func(EntityView::new_from(world, *iter.entities.add(i)), tuple).branch();
+                    if flow.is_break() {
+                        break;
+                    }
                 }
 
                 sys::ecs_table_unlock(world_ptr, iter.table);
@@ -195,7 +270,15 @@ where
                         &world,
                     );
                 }
+
+                if flow.is_break() {
+                    // the iterator is not iterated until completion, so it has to be cleaned up
+                    sys::ecs_iter_fini(&mut iter);
+                    return R::from_flow(flow);
+                }
             }
+
+            R::from_flow(ControlFlow::Continue(()))
         }
     }
 
@@ -204,6 +287,8 @@ where
     /// The `usize` argument contains the index of the entity being iterated,
     /// which can be used to obtain entity-specific data from the `TableIter` object.
     ///
+    /// Like [`QueryAPI::each()`], the function can return [`ControlFlow`] to stop iterating early.
+    ///
     /// # Example
     /// ```
     /// use flecs_ecs::prelude::*;
@@ -238,7 +323,10 @@ where
     /// // Output:
     /// //  "adam": Position { x: 10, y: 20 } - "(flecs_ecs.main.Likes,eva)"
     /// ```
-    fn each_iter(&self, mut func: impl FnMut(TableIter<false, P>, usize, T::TupleType<'_>))
+    fn each_iter<R: CallbackResult>(
+        &self,
+        mut func: impl FnMut(TableIter<false, P>, usize, T::TupleType<'_>) -> R,
+    ) -> R
     where
         P: ComponentId,
     {
@@ -253,6 +341,9 @@ where
             #[cfg(feature = "flecs_safety_readwrite_locks")]
             let world = self.world();
             let world_ptr = self.world_ptr_mut();
+            #[cfg(feature = "flecs_profiler")]
+            let mut profile =
+                crate::addons::profiler::ProfileScope::query(world_ptr, self.query_ptr());
             let mut iter = self.retrieve_iter();
             #[cfg(feature = "flecs_safety_readwrite_locks")]
             let components_access = world.components_access_map();
@@ -260,6 +351,8 @@ where
             iter.flags |= sys::EcsIterCppEach;
 
             while self.iter_next(&mut iter) {
+                #[cfg(feature = "flecs_profiler")]
+                profile.table(iter.count);
                 let mut components_data = T::create_ptrs(&iter);
                 let iter_count = {
                     if iter.count == 0 && iter.table.is_null() {
@@ -281,11 +374,15 @@ where
 
                 sys::ecs_table_lock(world_ptr, iter.table);
 
+                let mut flow = ControlFlow::Continue(());
                 for i in 0..iter_count {
                     let tuple = components_data.get_tuple(&iter, i);
                     let iter_t = TableIter::new(&mut iter);
 
-                    func(iter_t, i, tuple);
+                    flow = func(iter_t, i, tuple).branch();
+                    if flow.is_break() {
+                        break;
+                    }
                 }
 
                 sys::ecs_table_unlock(world_ptr, iter.table);
@@ -299,7 +396,129 @@ where
                         &world,
                     );
                 }
+
+                if flow.is_break() {
+                    // the iterator is not iterated until completion, so it has to be cleaned up
+                    sys::ecs_iter_fini(&mut iter);
+                    return R::from_flow(flow);
+                }
             }
+
+            R::from_flow(ControlFlow::Continue(()))
+        }
+    }
+
+    /// Each iterator that can stop early.
+    ///
+    /// Like [`QueryAPI::each()`] with a function that returns [`ControlFlow`], for which the
+    /// type of the break value doesn't have to be inferred from the return type.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// use core::ops::ControlFlow;
+    ///
+    /// #[derive(Component)]
+    /// struct Health(i32);
+    ///
+    /// let world = World::new();
+    /// world.entity().set(Health(10));
+    /// world.entity().set(Health(-5));
+    /// world.entity().set(Health(20));
+    ///
+    /// let query = world.new_query::<&Health>();
+    ///
+    /// let invalid = query.each_while(|health| {
+    ///     if health.0 < 0 {
+    ///         return ControlFlow::Break(health.0);
+    ///     }
+    ///     ControlFlow::Continue(())
+    /// });
+    ///
+    /// assert_eq!(invalid, ControlFlow::Break(-5));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::each()`]
+    /// * [`QueryAPI::iter_while()`]
+    fn each_while<B>(
+        &self,
+        func: impl FnMut(T::TupleType<'_>) -> ControlFlow<B>,
+    ) -> ControlFlow<B> {
+        self.each(func)
+    }
+
+    /// Table iterator that can stop early.
+    ///
+    /// `func` is invoked for each matched table, with an iterator over the entities in the table.
+    /// Iteration stops as soon as `func` returns [`ControlFlow::Break`], which is then returned.
+    /// The iterator is cleaned up when it stops early.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// use core::ops::ControlFlow;
+    ///
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Frozen;
+    ///
+    /// let world = World::new();
+    /// world.entity().set(Position { x: 1.0, y: 2.0 });
+    /// let frozen = world
+    ///     .entity()
+    ///     .set(Position { x: 3.0, y: 4.0 })
+    ///     .add::<Frozen>();
+    ///
+    /// let query = world.new_query::<&Position>();
+    ///
+    /// // find the first table with frozen entities
+    /// let flow = query.iter_while(|it| {
+    ///     if it.table().unwrap().has_type::<Frozen>() {
+    ///         return ControlFlow::Break(it.entity(0).id());
+    ///     }
+    ///     ControlFlow::Continue(())
+    /// });
+    ///
+    /// assert_eq!(flow, ControlFlow::Break(frozen.id()));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::run()`]
+    /// * [`QueryAPI::each_while()`]
+    fn iter_while<B>(
+        &self,
+        mut func: impl FnMut(TableIter<false, P>) -> ControlFlow<B>,
+    ) -> ControlFlow<B>
+    where
+        P: ComponentId,
+    {
+        unsafe {
+            let world_ptr = self.world_ptr_mut();
+            let mut iter = self.retrieve_iter();
+
+            while self.iter_next(&mut iter) {
+                sys::ecs_table_lock(world_ptr, iter.table);
+                let flow = func(TableIter::new(&mut iter));
+                sys::ecs_table_unlock(world_ptr, iter.table);
+
+                if flow.is_break() {
+                    // the iterator is not iterated until completion, so it has to be cleaned up
+                    sys::ecs_iter_fini(&mut iter);
+                    return flow;
+                }
+            }
+
+            ControlFlow::Continue(())
         }
     }
 
@@ -363,6 +582,12 @@ where
                         &world,
                     );
                 }
+
+                if entity.is_some() {
+                    // the iterator is not iterated until completion, so it has to be cleaned up
+                    sys::ecs_iter_fini(&mut iter);
+                    break;
+                }
             }
 
             entity
@@ -434,6 +659,12 @@ where
                         &world,
                     );
                 }
+
+                if entity_result.is_some() {
+                    // the iterator is not iterated until completion, so it has to be cleaned up
+                    sys::ecs_iter_fini(&mut iter);
+                    break;
+                }
             }
 
             entity_result
@@ -514,6 +745,12 @@ where
                         &world,
                     );
                 }
+
+                if entity_result.is_some() {
+                    // the iterator is not iterated until completion, so it has to be cleaned up
+                    sys::ecs_iter_fini(&mut iter);
+                    break;
+                }
             }
 
             entity_result
@@ -530,6 +767,9 @@ where
     /// are iterated as it provides multiple entities in the same callback
     /// and allows to determine what should happen before and past iteration.
     ///
+    /// The callback may stop calling `next()` before it returns false, for example by breaking out
+    /// of the loop, in which case the iterator is cleaned up after the callback returns.
+    ///
     /// # Example
     ///
     /// ```
@@ -598,26 +838,32 @@ where
     where
         P: ComponentId,
     {
+        #[cfg(feature = "flecs_profiler")]
+        let _profile = unsafe {
+            crate::addons::profiler::ProfileScope::query(self.world_ptr(), self.query_ptr())
+        };
         let mut iter = self.retrieve_iter();
         let mut iter_t = unsafe { TableIter::new(&mut iter) };
         iter_t.iter_mut().flags &= !sys::EcsIterIsValid;
         func(iter_t);
 
-        //TODO flecs will integrate this before V4 release.
-        // // if the while loop with `iter_next()` exited early, the table should be unlocked
-        // // and iter should be cleaned up
-        // if (iter.flags & sys::EcsIterIsValid) != 0 && !iter.table.is_null() {
-        //     unsafe {
-        //         sys::ecs_table_unlock(iter.world, iter.table);
-        //         sys::ecs_iter_fini(&mut iter)
-        //     };
-        // }
-
-        // ecs_assert!(
-        //     iter.flags & sys::EcsIterIsValid != 0,
-        //     FlecsErrorCode::InvalidOperation,
-        //     "when using `run()`, you must call `while it.next()` in the callback"
-        // );
+        // when the callback stopped calling `next()` before the iterator was depleted, the
+        // table has to be unlocked and the iterator cleaned up. Depleted iterators clean up
+        // themselves, which clears the valid flag of the query iterator, or the one that is
+        // chained when the results are paged.
+        let chained = if iter.chain_it.is_null() {
+            &iter
+        } else {
+            unsafe { &*iter.chain_it }
+        };
+        if chained.flags & sys::EcsIterIsValid != 0 {
+            unsafe {
+                if !iter.table.is_null() {
+                    sys::ecs_table_unlock(iter.world, iter.table);
+                }
+                sys::ecs_iter_fini(&mut iter);
+            }
+        }
     }
 
     /// Run iterator with each forwarding.
@@ -930,7 +1176,7 @@ where
     }
 
     fn find_var(&self, name: &str) -> Option<i32> {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
 
         let var_index =
             unsafe { sys::ecs_query_find_var(self.query_ptr(), name.as_ptr() as *const _) };
@@ -1146,6 +1392,94 @@ where
         }
     }
 
+    /// Get the fields of the query for a single entity, if it matches the query.
+    ///
+    /// This evaluates the query for the entity only, instead of iterating all results until the
+    /// entity is found. The fields are resolved the same way as when iterating the query, so
+    /// components that the entity inherits or gets from a parent are provided as well.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the entity matches the query and `func` was invoked, `false` if not.
+    ///
+    /// # Example
+    ///
+    /// ```rust
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component, Debug)]
+    /// struct Health(u32);
+    ///
+    /// #[derive(Component, Debug)]
+    /// struct Armor(u32);
+    ///
+    /// let world = World::new();
+    /// world.component::<Armor>().add_trait::<(flecs::OnInstantiate, flecs::Inherit)>();
+    ///
+    /// let query = world.new_query::<(&Health, &Armor)>();
+    ///
+    /// let knight = world.prefab().set(Armor(10));
+    /// let player = world.entity().is_a_id(knight).set(Health(100));
+    /// let tree = world.entity().set(Health(50));
+    ///
+    /// // the armor is inherited from the prefab
+    /// assert!(query.get_for(player, |(health, armor)| {
+    ///     assert_eq!(health.0, 100);
+    ///     assert_eq!(armor.0, 10);
+    /// }));
+    ///
+    /// assert!(!query.get_for(tree, |_| unreachable!()));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::try_first()`]
+    /// * C API: `ecs_query_has`
+    #[doc(alias = "ecs_query_has")]
+    fn get_for(&self, entity: impl Into<Entity>, func: impl FnOnce(T::TupleType<'_>)) -> bool {
+        let entity = *entity.into();
+        if !unsafe { sys::ecs_is_alive(self.world_ptr(), entity) } {
+            return false;
+        }
+
+        let mut it = unsafe { core::mem::zeroed::<sys::ecs_iter_t>() };
+        let query = self.query_ptr() as *mut sys::ecs_query_t;
+        if !unsafe { sys::ecs_query_has(query, entity, &mut it) } {
+            return false;
+        }
+
+        // a cached query that is evaluated for one entity doesn't report the fields that are
+        // matched through traversal, which the component pointers rely on
+        for field in 0..it.field_count {
+            if !unsafe { sys::ecs_field_is_self(&it, field) } {
+                it.up_fields |= 1 << field;
+            }
+        }
+
+        #[cfg(feature = "flecs_safety_readwrite_locks")]
+        let world = self.world();
+        #[cfg(feature = "flecs_safety_readwrite_locks")]
+        let components_access = world.components_access_map();
+        #[cfg(feature = "flecs_safety_readwrite_locks")]
+        {
+            do_read_write_locks::<INCREMENT>(&it, components_access, T::COUNT as usize, &world);
+        }
+
+        let world_ptr = self.world_ptr_mut();
+        let mut components_data = T::create_ptrs(&it);
+        let tuple = components_data.get_tuple(&it, 0);
+        unsafe { sys::ecs_table_lock(world_ptr, it.table) };
+        func(tuple);
+        unsafe { sys::ecs_table_unlock(world_ptr, it.table) };
+
+        #[cfg(feature = "flecs_safety_readwrite_locks")]
+        {
+            do_read_write_locks::<DECREMENT>(&it, components_access, T::COUNT as usize, &world);
+        }
+        unsafe { sys::ecs_iter_fini(&mut it) };
+        true
+    }
+
     /// iterates over the first entity in the iterator and returns a user-defined result.
     ///
     /// # Returns
@@ -1195,6 +1529,8 @@ where
     /// * `Ok(result)` if there is exactly one entity.
     /// * `Err(FirstOnlyError)` if there are no entities or more than one entity.
     ///
+    /// All matches are counted first, so `func` is only called when there is exactly one.
+    ///
     /// ```rust
     /// use flecs_ecs::prelude::*;
     ///
@@ -1228,57 +1564,55 @@ where
     ///
     /// # See also
     ///
-    /// * [`Query::try_first_entity`]
-    /// * [`Query::first_entity`]
-    /// * [`Query::try_first`]
-    /// * [`Query::first`]
+    /// * [`QueryAPI::try_first_entity`]
+    /// * [`QueryAPI::first_entity`]
+    /// * [`QueryAPI::try_first`]
+    /// * [`QueryAPI::first`]
     fn try_first_only<R>(
         &self,
         func: impl FnOnce(T::TupleType<'_>) -> R,
     ) -> Result<R, FirstOnlyError> {
+        // the matches can be spread over multiple tables, so all of them are counted before
+        // `func` gets to see the data of the match
         let mut it = self.retrieve_iter();
-
-        // Proceed only if we can iterate
-        if self.iter_next(&mut it) {
-            #[cfg(feature = "flecs_safety_readwrite_locks")]
-            let world = self.world();
-            #[cfg(feature = "flecs_safety_readwrite_locks")]
-            let components_access = world.components_access_map();
-            if it.count == 1 {
-                #[cfg(feature = "flecs_safety_readwrite_locks")]
-                {
-                    do_read_write_locks::<INCREMENT>(
-                        &it,
-                        components_access,
-                        T::COUNT as usize,
-                        &world,
-                    );
+        let mut matched = None;
+        let mut index = 0;
+        while self.iter_next(&mut it) {
+            if it.count != 0 {
+                if matched.is_some() || it.count > 1 {
+                    unsafe { sys::ecs_iter_fini(&mut it) };
+                    return Err(FirstOnlyError::MoreThanOneEntity);
                 }
-                let mut components_data = T::create_ptrs(&it);
-                let tuple = components_data.get_tuple(&it, 0);
+                matched = Some(index);
+            }
+            index += 1;
+        }
+        let matched = matched.ok_or(FirstOnlyError::NoEntities)?;
 
-                // Clean up iterator resources safely
-                let result = func(tuple);
-                #[cfg(feature = "flecs_safety_readwrite_locks")]
-                {
-                    do_read_write_locks::<DECREMENT>(
-                        &it,
-                        components_access,
-                        T::COUNT as usize,
-                        &world,
-                    );
-                }
-                unsafe { sys::ecs_iter_fini(&mut it) };
-                Ok(result)
-            } else {
-                // More than one entity
-                unsafe { sys::ecs_iter_fini(&mut it) };
-                Err(FirstOnlyError::MoreThanOneEntity)
+        let mut it = self.retrieve_iter();
+        for _ in 0..=matched {
+            if !self.iter_next(&mut it) {
+                return Err(FirstOnlyError::NoEntities);
             }
-        } else {
-            // No entities in the iterator
-            Err(FirstOnlyError::NoEntities)
         }
+
+        #[cfg(feature = "flecs_safety_readwrite_locks")]
+        let world = self.world();
+        #[cfg(feature = "flecs_safety_readwrite_locks")]
+        let components_access = world.components_access_map();
+        #[cfg(feature = "flecs_safety_readwrite_locks")]
+        {
+            do_read_write_locks::<INCREMENT>(&it, components_access, T::COUNT as usize, &world);
+        }
+        let mut components_data = T::create_ptrs(&it);
+        let tuple = components_data.get_tuple(&it, 0);
+        let result = func(tuple);
+        #[cfg(feature = "flecs_safety_readwrite_locks")]
+        {
+            do_read_write_locks::<DECREMENT>(&it, components_access, T::COUNT as usize, &world);
+        }
+        unsafe { sys::ecs_iter_fini(&mut it) };
+        Ok(result)
     }
 
     /// iterates over the first entity in the iterator and returns a user-defined result.
@@ -1330,8 +1664,61 @@ where
         }
     }
 
+    /// Return the only matching entity.
+    ///
+    /// This is useful for entities that are expected to be unique, like the player or the camera.
+    ///
+    /// # Returns
+    ///
+    /// * `Ok(entity)` if there is exactly one matching entity.
+    /// * `Err(FirstOnlyError)` if there are no matching entities or more than one.
+    ///
+    /// ```rust
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Player;
+    ///
+    /// let world = World::new();
+    /// let query = world.query::<()>().with::<Player>().build();
+    ///
+    /// assert_eq!(query.single(), Err(FirstOnlyError::NoEntities));
+    ///
+    /// let player = world.entity().add::<Player>();
+    /// assert_eq!(query.single(), Ok(player));
+    ///
+    /// world.entity().add::<Player>();
+    /// assert_eq!(query.single(), Err(FirstOnlyError::MoreThanOneEntity));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::try_first_entity`]
+    /// * [`QueryAPI::try_first_only`]
+    fn single(&self) -> Result<EntityView<'a>, FirstOnlyError> {
+        let mut it = self.retrieve_iter();
+        let mut result = None;
+
+        while self.iter_next(&mut it) {
+            if it.count == 0 {
+                continue;
+            }
+
+            if result.is_some() || it.count > 1 {
+                unsafe { sys::ecs_iter_fini(&mut it) };
+                return Err(FirstOnlyError::MoreThanOneEntity);
+            }
+
+            result = Some(EntityView::new_from(self.world(), unsafe {
+                *it.entities.add(0)
+            }));
+        }
+
+        result.ok_or(FirstOnlyError::NoEntities)
+    }
+
     /// Returns true if iterator yields at least once result.
-    fn is_true(&mut self) -> bool {
+    fn is_true(&self) -> bool {
         let mut it = self.retrieve_iter();
 
         let result = self.iter_next(&mut it);
@@ -1360,6 +1747,446 @@ where
         result
     }
 
+    /// Fold all matching entities into a single value, in a single pass over the results.
+    ///
+    /// # Arguments
+    ///
+    /// * `init`: the initial value of the accumulator
+    /// * `func`: the function that combines the accumulator with the components of an entity
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Health {
+    ///     value: i32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.entity().set(Health { value: 10 });
+    /// world.entity().set(Health { value: 30 });
+    ///
+    /// let query = world.new_query::<&Health>();
+    /// let max = query.fold(i32::MIN, |max, health| max.max(health.value));
+    /// assert_eq!(max, 30);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::sum_by()`]
+    /// * [`QueryAPI::filter_map()`]
+    fn fold<R>(&self, init: R, mut func: impl FnMut(R, T::TupleType<'_>) -> R) -> R {
+        let mut acc = Some(init);
+        self.each(|components| {
+            // the accumulator is only taken for the duration of the call
+            let value = acc.take().unwrap();
+            acc = Some(func(value, components));
+        });
+        acc.unwrap()
+    }
+
+    /// Sum a value computed from the components of each matching entity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Mass {
+    ///     value: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.entity().set(Mass { value: 1.5 });
+    /// world.entity().set(Mass { value: 2.5 });
+    ///
+    /// let total: f32 = world.new_query::<&Mass>().sum_by(|mass| mass.value);
+    /// assert_eq!(total, 4.0);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::fold()`]
+    fn sum_by<S>(&self, mut func: impl FnMut(T::TupleType<'_>) -> S) -> S
+    where
+        S: Default + core::ops::AddAssign,
+    {
+        let mut sum = S::default();
+        self.each(|components| sum += func(components));
+        sum
+    }
+
+    /// Lazily compute a value from the components of each matching entity.
+    ///
+    /// The returned iterator runs `func` for one entity at a time as it is advanced, without
+    /// collecting the results. The table of the current entity stays locked until the iterator
+    /// moves on to the next table or is dropped, so don't add or remove components of entities in
+    /// that table while iterating, or defer the world first.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Health {
+    ///     value: i32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.entity().set(Health { value: 10 });
+    /// world.entity().set(Health { value: 30 });
+    ///
+    /// let query = world.new_query::<&Health>();
+    /// let max = query.map(|health| health.value).max();
+    /// assert_eq!(max, Some(30));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::filter_map()`]
+    /// * [`QueryAPI::filter()`]
+    fn map<'q, R>(
+        &'q self,
+        mut func: impl FnMut(T::TupleType<'_>) -> R + 'q,
+    ) -> impl Iterator<Item = R> + 'q
+    where
+        'a: 'q,
+        T: 'q,
+    {
+        QueryFilterMap::new(self, move |_, components| Some(func(components)))
+    }
+
+    /// Lazily compute a value from the components of each matching entity, skipping entities for
+    /// which `func` returns `None`.
+    ///
+    /// Like [`QueryAPI::map()`], the results are not collected and the table of the current
+    /// entity stays locked while iterating.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Health {
+    ///     value: i32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.entity_named("a").set(Health { value: 0 });
+    /// world.entity_named("b").set(Health { value: 30 });
+    ///
+    /// let query = world.new_query::<&Health>();
+    /// let mut alive = query.filter_map(|health| (health.value > 0).then_some(health.value));
+    /// assert_eq!(alive.next(), Some(30));
+    /// assert_eq!(alive.next(), None);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::map()`]
+    /// * [`QueryAPI::fold()`]
+    fn filter_map<'q, R>(
+        &'q self,
+        mut func: impl FnMut(T::TupleType<'_>) -> Option<R> + 'q,
+    ) -> impl Iterator<Item = R> + 'q
+    where
+        'a: 'q,
+        T: 'q,
+    {
+        QueryFilterMap::new(self, move |_, components| func(components))
+    }
+
+    /// Lazily iterate the matching entities for which `func` returns true.
+    ///
+    /// Like [`QueryAPI::map()`], the entities are not collected and the table of the current
+    /// entity stays locked while iterating.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Health {
+    ///     value: i32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.entity_named("a").set(Health { value: 0 });
+    /// let b = world.entity_named("b").set(Health { value: 30 });
+    ///
+    /// let query = world.new_query::<&Health>();
+    /// assert_eq!(query.filter(|health| health.value > 0).count(), 1);
+    /// assert_eq!(query.filter(|health| health.value > 0).next(), Some(b));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::filter_map()`]
+    fn filter<'q>(
+        &'q self,
+        mut func: impl FnMut(T::TupleType<'_>) -> bool + 'q,
+    ) -> impl Iterator<Item = EntityView<'q>> + 'q
+    where
+        'a: 'q,
+        T: 'q,
+    {
+        QueryFilterMap::new(self, move |entity, components| {
+            func(components).then_some(entity)
+        })
+    }
+
+    /// Copy the components of all matching entities into column-major buffers.
+    ///
+    /// Returns the matched entities together with one `Vec` per requested component, where the
+    /// value at each index belongs to the entity at the same index. Columns are copied table by
+    /// table, which makes this useful for handing data off to GPU uploads or external libraries
+    /// that expect contiguous arrays.
+    ///
+    /// Each requested component must be a field of the query and implement [`Copy`]. Components
+    /// matched on another entity, such as a parent or prefab, are repeated for every entity.
+    ///
+    /// # Panics
+    ///
+    /// If a requested component is not a field of the query, or is an optional field that is not
+    /// set for all results.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component, Clone, Copy)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// #[derive(Component, Clone, Copy)]
+    /// struct Velocity {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// let a = world
+    ///     .entity()
+    ///     .set(Position { x: 1.0, y: 2.0 })
+    ///     .set(Velocity { x: 3.0, y: 4.0 });
+    ///
+    /// let query = world.new_query::<(&Position, &Velocity)>();
+    /// let (entities, (positions, velocities)) =
+    ///     query.extract::<(Vec<Position>, Vec<Velocity>)>();
+    ///
+    /// assert_eq!(entities, [a.id()]);
+    /// assert_eq!(positions[0].y, 2.0);
+    /// assert_eq!(velocities[0].x, 3.0);
+    /// ```
+    fn extract<C: ExtractColumns>(&self) -> (Vec<Entity>, C) {
+        let world = self.world();
+        let mut entities = Vec::new();
+        let mut columns = C::default();
+
+        let mut it = self.retrieve_iter();
+        while self.iter_next(&mut it) {
+            if !it.entities.is_null() {
+                let ids = unsafe { core::slice::from_raw_parts(it.entities, it.count as usize) };
+                entities.extend(ids.iter().map(|&id| Entity::new(id)));
+            }
+            if let Err(message) = columns.extend_from(world, &it) {
+                unsafe { sys::ecs_iter_fini(&mut it) };
+                panic!("{message}");
+            }
+        }
+
+        (entities, columns)
+    }
+
+    /// Iterate the column of component `C` of each matched table as a slice.
+    ///
+    /// `func` is invoked once per table with the entities of the table and their values of `C`,
+    /// where the value at each index belongs to the entity at the same index. Unlike
+    /// [`QueryAPI::each()`] there is no per entity overhead, which makes this a faster
+    /// alternative for numeric crunching.
+    ///
+    /// The values are only read, so `C` can be a read-only field of the query, such as `&C`. To
+    /// write them, see [`QueryAPI::column_chunks_mut()`].
+    ///
+    /// The table is locked while `func` runs. Operations that move entities out of or into a
+    /// locked table are only caught by flecs asserts, which are compiled out of release builds
+    /// unless the `flecs_force_enable_ecs_asserts` feature is enabled. Such operations may
+    /// reallocate the column, so inside `func` structural changes to the entities of the table
+    /// have to be deferred, e.g. with [`World::defer_begin()`]. The slices can't outlive the
+    /// callback for the same reason.
+    ///
+    /// # Panics
+    ///
+    /// If `C` is not a field of the query or is matched on another entity, such as a parent or
+    /// prefab. Tables for which an optional `C` is not set are skipped.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Mass(f32);
+    ///
+    /// let world = World::new();
+    /// world.entity().set(Mass(1.0));
+    /// world.entity().set(Mass(2.0));
+    ///
+    /// let query = world.new_query::<&Mass>();
+    /// let mut total = 0.0;
+    /// query.column_chunks::<Mass>(|entities, masses| {
+    ///     assert_eq!(entities.len(), masses.len());
+    ///     total += masses.iter().map(|mass| mass.0).sum::<f32>();
+    /// });
+    /// assert_eq!(total, 3.0);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::column_chunks_mut()`]
+    /// * [`QueryAPI::extract()`]
+    fn column_chunks<C>(&self, mut func: impl FnMut(&[Entity], &[C]))
+    where
+        C: ComponentId + DataComponent,
+    {
+        self.column_chunks_impl::<C, false>(|entities, ptr| {
+            // SAFETY: the column has a value for each entity, and isn't written while the table
+            // is locked
+            func(entities, unsafe {
+                core::slice::from_raw_parts(ptr, entities.len())
+            });
+        });
+    }
+
+    /// Iterate the column of component `C` of each matched table as a mutable slice.
+    ///
+    /// The mutable slice doesn't alias other references, as the query iterator hands out each
+    /// table once. Getting `C` from the entities of the table inside the callback, e.g. with
+    /// [`EntityViewGet::get()`], creates a second reference to the same value and must be
+    /// avoided. Structural changes to the entities of the table have to be deferred, as with
+    /// [`QueryAPI::column_chunks()`].
+    ///
+    /// # Panics
+    ///
+    /// If `C` is not a field of the query, is matched on another entity, or is a read-only field
+    /// of the query, such as `&C`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Position(f32);
+    ///
+    /// #[derive(Component)]
+    /// struct Velocity(f32);
+    ///
+    /// let world = World::new();
+    /// let e = world.entity().set(Position(0.0)).set(Velocity(2.0));
+    ///
+    /// let query = world.new_query::<(&mut Position, &Velocity)>();
+    /// query.column_chunks_mut::<Position>(|_, positions| {
+    ///     for position in positions {
+    ///         position.0 += 1.0;
+    ///     }
+    /// });
+    /// e.get::<&Position>(|p| assert_eq!(p.0, 1.0));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::column_chunks()`]
+    fn column_chunks_mut<C>(&self, mut func: impl FnMut(&[Entity], &mut [C]))
+    where
+        C: ComponentId + DataComponent,
+    {
+        self.column_chunks_impl::<C, true>(|entities, ptr| {
+            // SAFETY: the column has a value for each entity, is a writable field of the query
+            // and the iterator hands out each table once
+            func(entities, unsafe {
+                core::slice::from_raw_parts_mut(ptr, entities.len())
+            });
+        });
+    }
+
+    /// Invoke `func` with the entities and a pointer to the column of `C` of each matched
+    /// table, while the table is locked. When `WRITE` is set, `C` has to be writable.
+    #[doc(hidden)]
+    fn column_chunks_impl<C, const WRITE: bool>(&self, mut func: impl FnMut(&[Entity], *mut C))
+    where
+        C: ComponentId + DataComponent,
+    {
+        let world = self.world();
+        let world_ptr = self.world_ptr_mut();
+        let id = C::id(world);
+        #[cfg(feature = "flecs_safety_readwrite_locks")]
+        let components_access = world.components_access_map();
+
+        let mut iter = self.retrieve_iter();
+        while self.iter_next(&mut iter) {
+            let count = iter.count as usize;
+            let column = field_of::<C>(&iter, id).and_then(|field| {
+                if count == 0 || !unsafe { sys::ecs_field_is_set(&iter, field) } {
+                    return Ok(None);
+                }
+
+                if !unsafe { sys::ecs_field_is_self(&iter, field) } {
+                    return Err(format!(
+                        "component `{}` is not matched on the entities of the query",
+                        core::any::type_name::<C>()
+                    ));
+                }
+
+                let ptr = unsafe { sys::ecs_field_w_size(&iter, core::mem::size_of::<C>(), field) }
+                    as *mut C;
+                if ptr.is_null() {
+                    return Err(format!(
+                        "component `{}` is not stored in a table column",
+                        core::any::type_name::<C>()
+                    ));
+                }
+
+                if WRITE {
+                    let query = unsafe { &*self.query_ptr() };
+                    let term = query.terms[..query.term_count as usize]
+                        .iter()
+                        .find(|term| term.field_index == field);
+                    if term.is_some_and(|term| term.inout == InOutKind::In as i16) {
+                        return Err(format!(
+                            "component `{}` is read-only in the query",
+                            core::any::type_name::<C>()
+                        ));
+                    }
+                }
+                Ok(Some(ptr))
+            });
+
+            let ptr = match column {
+                Ok(Some(ptr)) => ptr,
+                Ok(None) => continue,
+                Err(message) => {
+                    unsafe { sys::ecs_iter_fini(&mut iter) };
+                    panic!("{message}");
+                }
+            };
+
+            #[cfg(feature = "flecs_safety_readwrite_locks")]
+            do_read_write_locks::<INCREMENT>(&iter, components_access, T::COUNT as usize, &world);
+
+            unsafe {
+                sys::ecs_table_lock(world_ptr, iter.table);
+                let entities = core::slice::from_raw_parts(iter.entities as *const Entity, count);
+                func(entities, ptr);
+                sys::ecs_table_unlock(world_ptr, iter.table);
+            }
+
+            #[cfg(feature = "flecs_safety_readwrite_locks")]
+            do_read_write_locks::<DECREMENT>(&iter, components_access, T::COUNT as usize, &world);
+        }
+    }
+
     /// Limit results to tables with specified group id (grouped queries only)
     ///
     /// # Arguments
@@ -23,9 +23,50 @@ where
     #[doc(alias = "system_builder_i::ctx")]
     fn set_context(&mut self, context: *mut c_void) -> &mut Self;
 
-    fn each<Func>(&mut self, func: Func) -> <Self as builder::Builder<'a>>::BuiltType
+    /// Make the callback methods, like [`SystemAPI::each()`], return a `Result` with an error
+    /// instead of panicking when the query is invalid.
+    ///
+    /// This is useful for systems and observers of which the query comes from user input, such
+    /// as expressions passed to [`expr`](QueryBuilderImpl::expr) by scripts or tooling.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// let world = World::new();
+    ///
+    /// let result = world
+    ///     .system::<()>()
+    ///     .expr("DoesNotExist")
+    ///     .fallible()
+    ///     .run(|mut it| while it.next() {});
+    /// assert!(result.is_err());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`TryBuilder::try_build()`]
+    fn fallible(&mut self) -> Fallible<'_, Self>
     where
-        Func: FnMut(T::TupleType<'_>) + 'static,
+        Self: TryBuilder<'a> + Sized,
+    {
+        Fallible { builder: self }
+    }
+
+    /// Each iterator.
+    /// The "each" iterator accepts a function that is invoked for each matching entity.
+    ///
+    /// The function can return [`ControlFlow`](core::ops::ControlFlow) to stop iterating early.
+    /// When it returns [`ControlFlow::Break`](core::ops::ControlFlow::Break), it's not invoked for
+    /// the remaining entities until the system or observer runs again.
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::each()`]
+    fn each<Func, R>(&mut self, func: Func) -> <Self as builder::Builder<'a>>::BuiltType
+    where
+        Func: FnMut(T::TupleType<'_>) -> R + 'static,
+        R: CallbackResult,
     {
         const {
             assert!(
@@ -39,16 +80,33 @@ where
 
         self.set_callback_binding_context(each_static_ref as *mut _ as *mut c_void);
         self.set_callback_binding_context_free(Some(Self::free_callback::<Func>));
-        self.set_desc_callback(Some(
-            Self::execute_each::<false, Func> as unsafe extern "C-unwind" fn(_),
-        ));
+        if R::CAN_BREAK {
+            self.set_desc_run(Some(
+                Self::execute_each_until_break::<Func, R> as unsafe extern "C-unwind" fn(_),
+            ));
+        } else {
+            self.set_desc_callback(Some(
+                Self::execute_each::<false, Func, R> as unsafe extern "C-unwind" fn(_),
+            ));
+        }
 
         self.build()
     }
 
-    fn each_entity<Func>(&mut self, func: Func) -> <Self as builder::Builder<'a>>::BuiltType
+    /// Each iterator.
+    /// The "each" iterator accepts a function that is invoked for each matching entity, with the
+    /// entity as first argument.
+    ///
+    /// Like [`SystemAPI::each()`], the function can return [`ControlFlow`](core::ops::ControlFlow)
+    /// to stop iterating early.
+    ///
+    /// # See also
+    ///
+    /// * [`QueryAPI::each_entity()`]
+    fn each_entity<Func, R>(&mut self, func: Func) -> <Self as builder::Builder<'a>>::BuiltType
     where
-        Func: FnMut(EntityView, T::TupleType<'_>) + 'static,
+        Func: FnMut(EntityView, T::TupleType<'_>) -> R + 'static,
+        R: CallbackResult,
     {
         const {
             assert!(
@@ -62,9 +120,15 @@ where
 
         self.set_callback_binding_context(each_entity_static_ref as *mut _ as *mut c_void);
         self.set_callback_binding_context_free(Some(Self::free_callback::<Func>));
-        self.set_desc_callback(Some(
-            Self::execute_each_entity::<false, Func> as unsafe extern "C-unwind" fn(_),
-        ));
+        if R::CAN_BREAK {
+            self.set_desc_run(Some(
+                Self::execute_each_entity_until_break::<Func, R> as unsafe extern "C-unwind" fn(_),
+            ));
+        } else {
+            self.set_desc_callback(Some(
+                Self::execute_each_entity::<false, Func, R> as unsafe extern "C-unwind" fn(_),
+            ));
+        }
 
         self.build()
     }
@@ -74,6 +138,9 @@ where
     /// The `usize` argument contains the index of the entity being iterated,
     /// which can be used to obtain entity-specific data from the `TableIter` object.
     ///
+    /// Like [`SystemAPI::each()`], the function can return [`ControlFlow`](core::ops::ControlFlow)
+    /// to stop iterating early.
+    ///
     /// # Example
     /// ```
     /// use flecs_ecs::prelude::*;
@@ -108,9 +175,10 @@ where
     /// // Output:
     /// //  "adam": Position2 { x: 10, y: 20 } - "(flecs_ecs.main.Likes,eva)"
     /// ```
-    fn each_iter<Func>(&mut self, func: Func) -> <Self as builder::Builder<'a>>::BuiltType
+    fn each_iter<Func, R>(&mut self, func: Func) -> <Self as builder::Builder<'a>>::BuiltType
     where
-        Func: FnMut(TableIter<false, P>, usize, T::TupleType<'_>) + 'static,
+        Func: FnMut(TableIter<false, P>, usize, T::TupleType<'_>) -> R + 'static,
+        R: CallbackResult,
     {
         const {
             assert!(
@@ -125,9 +193,15 @@ where
         self.set_callback_binding_context(each_iter_static_ref as *mut _ as *mut c_void);
         self.set_callback_binding_context_free(Some(Self::free_callback::<Func>));
 
-        self.set_desc_callback(Some(
-            Self::execute_each_iter::<Func> as unsafe extern "C-unwind" fn(_),
-        ));
+        if R::CAN_BREAK {
+            self.set_desc_run(Some(
+                Self::execute_each_iter_until_break::<Func, R> as unsafe extern "C-unwind" fn(_),
+            ));
+        } else {
+            self.set_desc_callback(Some(
+                Self::execute_each_iter::<Func, R> as unsafe extern "C-unwind" fn(_),
+            ));
+        }
 
         self.build()
     }
@@ -335,7 +409,7 @@ where
         self.set_callback_binding_context_free(Some(Self::free_callback::<FuncEach>));
 
         self.set_desc_callback(Some(
-            Self::execute_each::<true, FuncEach> as unsafe extern "C-unwind" fn(_),
+            Self::execute_each::<true, FuncEach, ()> as unsafe extern "C-unwind" fn(_),
         ));
 
         self.build()
@@ -450,13 +524,92 @@ where
         self.set_callback_binding_context_free(Some(Self::free_callback::<FuncEachEntity>));
 
         self.set_desc_callback(Some(
-            Self::execute_each_entity::<true, FuncEachEntity> as unsafe extern "C-unwind" fn(_),
+            Self::execute_each_entity::<true, FuncEachEntity, ()>
+                as unsafe extern "C-unwind" fn(_),
         ));
 
         self.build()
     }
 }
 
+/// Builds with [`TryBuilder::try_build()`] instead of [`Builder::build()`], returned by
+/// [`SystemAPI::fallible()`].
+pub struct Fallible<'b, B> {
+    builder: &'b mut B,
+}
+
+impl<'a, B: TryBuilder<'a>> Builder<'a> for Fallible<'_, B> {
+    type BuiltType = Result<B::BuiltType, QueryError>;
+
+    fn build(&mut self) -> Self::BuiltType {
+        self.builder.try_build()
+    }
+}
+
+impl<'a, P, T, B> private::internal_SystemAPI<'a, P, T> for Fallible<'_, B>
+where
+    T: QueryTuple,
+    P: ComponentId,
+    B: private::internal_SystemAPI<'a, P, T>,
+{
+    fn set_callback_binding_context(&mut self, binding_ctx: *mut c_void) -> &mut Self {
+        self.builder.set_callback_binding_context(binding_ctx);
+        self
+    }
+
+    fn set_callback_binding_context_free(
+        &mut self,
+        binding_ctx_free: flecs_ecs_sys::ecs_ctx_free_t,
+    ) -> &mut Self {
+        self.builder
+            .set_callback_binding_context_free(binding_ctx_free);
+        self
+    }
+
+    fn set_run_binding_context(&mut self, binding_ctx: *mut c_void) -> &mut Self {
+        self.builder.set_run_binding_context(binding_ctx);
+        self
+    }
+
+    fn set_run_binding_context_free(
+        &mut self,
+        run_ctx_free: flecs_ecs_sys::ecs_ctx_free_t,
+    ) -> &mut Self {
+        self.builder.set_run_binding_context_free(run_ctx_free);
+        self
+    }
+
+    fn desc_binding_context(&self) -> *mut c_void {
+        self.builder.desc_binding_context()
+    }
+
+    fn set_desc_callback(
+        &mut self,
+        callback: Option<unsafe extern "C-unwind" fn(*mut flecs_ecs_sys::ecs_iter_t)>,
+    ) {
+        self.builder.set_desc_callback(callback);
+    }
+
+    fn set_desc_run(
+        &mut self,
+        callback: Option<unsafe extern "C-unwind" fn(*mut flecs_ecs_sys::ecs_iter_t)>,
+    ) {
+        self.builder.set_desc_run(callback);
+    }
+}
+
+impl<'a, P, T, B> SystemAPI<'a, P, T> for Fallible<'_, B>
+where
+    T: QueryTuple,
+    P: ComponentId,
+    B: SystemAPI<'a, P, T> + TryBuilder<'a>,
+{
+    fn set_context(&mut self, context: *mut c_void) -> &mut Self {
+        self.builder.set_context(context);
+        self
+    }
+}
+
 macro_rules! implement_reactor_api {
     ($param:ty, $type:ty) => {
         impl<'a, T> internal_SystemAPI<'a, $param, T> for $type
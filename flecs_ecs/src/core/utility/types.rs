@@ -1,6 +1,8 @@
 #![doc(hidden)]
 use core::ffi::c_void;
 
+use crate::core::utility::is_thread_panicking;
+
 pub type FTime = f32;
 
 pub(crate) type EcsCtxFreeT = extern "C-unwind" fn(*mut c_void);
@@ -61,7 +63,7 @@ pub(crate) struct ObserverEntityBindingCtx {
 
 impl Drop for ObserverEntityBindingCtx {
     fn drop(&mut self) {
-        if std::thread::panicking() {
+        if is_thread_panicking() {
             return;
         }
 
@@ -95,7 +95,7 @@ impl Default for World {
 
 impl Drop for World {
     fn drop(&mut self) {
-        if std::thread::panicking() {
+        if is_thread_panicking() {
             return;
         }
 
@@ -120,6 +120,11 @@ impl Drop for World {
                         Please ensure that all `Query` objects are out of scope before the world is destroyed.");
                 }
 
+                #[cfg(feature = "std")]
+                if ctx.panic_on_assert {
+                    ecs_os_api::set_panic_on_assert(false);
+                }
+
                 let ctx = unsafe { Box::from_raw(ctx as *mut WorldCtx) };
                 drop(ctx);
             }
@@ -227,10 +232,41 @@ impl World {
     /// let world_info = world.info();
     ///
     /// assert!(world_info.delta_time > 0.0);
-    /// //assert!(world_info.world_time_total_raw > 0.0); //BUG TODO
+    /// assert!(world_info.world_time_total_raw > 0.0);
     /// //assert!(world_info.systems_ran_frame == 0);
     /// ```
     ///
+    /// The `cmd` field counts the deferred commands processed by merges. Subtracting two
+    /// snapshots shows how many structural changes a frame or a deferred block produced:
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// # #[derive(Component)]
+    /// # struct Position {
+    /// #     x: f32,
+    /// #     y: f32,
+    /// # }
+    /// # #[derive(Component)]
+    /// # struct Enemy;
+    /// let world = World::new();
+    /// world.component::<Position>();
+    /// world.component::<Enemy>();
+    /// let e = world.entity();
+    ///
+    /// let before = world.info().cmd;
+    ///
+    /// world.defer(|_| {
+    ///     e.set(Position { x: 1.0, y: 2.0 });
+    ///     e.add::<Enemy>();
+    ///     e.remove::<Enemy>();
+    /// });
+    ///
+    /// let merged = world.info().cmd - before;
+    /// // the commands for `e` were combined into a single table move
+    /// assert_eq!(merged.batched_entity_count, 1);
+    /// assert_eq!(merged.batched_command_count, 3);
+    /// ```
+    ///
     /// # See also
     ///
     /// * C++ API: `world::get_info`
@@ -389,6 +425,7 @@ impl World {
         unsafe {
             sys::ecs_frame_end(self.raw_world.as_ptr());
         }
+        self.flush_deferred_runs();
     }
 
     /// Begin readonly mode.
@@ -629,7 +666,9 @@ impl World {
     /// * C++ API: `world::defer_end`
     #[doc(alias = "world::defer_end")]
     pub fn defer_end(&self) -> bool {
-        unsafe { sys::ecs_defer_end(self.raw_world.as_ptr()) }
+        let result = unsafe { sys::ecs_defer_end(self.raw_world.as_ptr()) };
+        self.flush_deferred_runs();
+        result
     }
 
     /// Test whether deferring is enabled.
@@ -671,17 +710,39 @@ impl World {
 
     /// Defers all operations executed in the passed-in closure.
     ///
+    /// Operations are queued while `func` runs and flushed once it returns, after which the value
+    /// returned by `func` is returned. Commands for the same entity are batched, so an entity
+    /// that gets several components moves to its new table once.
+    ///
+    /// Calls can be nested, in which case the commands are only flushed when the outermost call
+    /// returns. The same holds when the world is already deferred, such as inside a system or
+    /// between [`World::defer_begin()`] and [`World::defer_end()`].
+    ///
     /// # Arguments
     ///
-    /// * `func` - The closure to execute.
+    /// * `func` - The closure to execute, which receives the world.
     ///
     /// # Examples
+    ///
     /// ```
-    /// # use flecs_ecs::core::World;
-    /// # let world = World::new();
-    /// let return_something_if_wanted = world.defer(|| {
-    ///     // deferred operations here
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Health(u32);
+    ///
+    /// let world = World::new();
+    ///
+    /// let spawned = world.defer(|world| {
+    ///     let entities: Vec<_> = (0..3).map(|i| world.entity().set(Health(i)).id()).collect();
+    ///
+    ///     // commands are not flushed yet, also not when deferring again
+    ///     world.defer(|world| world.entity().set(Health(3)));
+    ///     assert!(!world.entity_from_id(entities[0]).has::<Health>());
+    ///
+    ///     entities
     /// });
+    ///
+    /// assert!(world.entity_from_id(spawned[0]).has::<Health>());
+    /// assert_eq!(world.count::<Health>(), 4);
     /// ```
     ///
     /// # See also
@@ -693,14 +754,15 @@ impl World {
     /// * [`World::is_deferred()`]
     /// * C++ API: `world::defer`
     #[doc(alias = "world::defer")]
-    pub fn defer<T>(&self, func: impl FnOnce() -> T) -> T {
+    pub fn defer<'w, T>(&'w self, func: impl FnOnce(&'w World) -> T) -> T {
         unsafe {
             sys::ecs_defer_begin(self.raw_world.as_ptr());
         }
-        let result = func();
+        let result = func(self);
         unsafe {
             sys::ecs_defer_end(self.raw_world.as_ptr());
         }
+        self.flush_deferred_runs();
         result
     }
 
@@ -1261,10 +1323,19 @@ impl World {
     ///
     /// This function limits the range of issued entity IDs between `min` and `max`.
     ///
+    /// Giving worlds disjoint ranges lets them create entities without agreeing on ids first,
+    /// for example when a server and its clients each create entities that are replicated to
+    /// the others. Entities from other ranges are created with [`World::make_alive()`].
+    ///
+    /// Set the range before entities are created in it. New ids continue after the highest id
+    /// that was made alive, so entities from other ranges should have lower ids than this range,
+    /// for example by giving the server the lowest range. Ids of deleted entities are recycled
+    /// regardless of the range, which [`World::enable_range_check()`] can catch.
+    ///
     /// # Arguments
     ///
-    /// * `min` - Minimum entity ID issued.
-    /// * `max` - Maximum entity ID issued.
+    /// * `min` - Minimum entity ID issued. Passing 0 continues after the highest issued id.
+    /// * `max` - Maximum entity ID issued, or 0 for no maximum.
     ///
     /// # Example
     ///
@@ -1286,6 +1357,7 @@ impl World {
     ///
     /// # See also
     ///
+    /// * [`World::entity_range()`]
     /// * [`World::enable_range_check()`]
     /// * [`World::preallocate_entity_count()`]
     /// * C++ API: `world::set_entity_range`
@@ -1294,16 +1366,49 @@ impl World {
         unsafe { sys::ecs_set_entity_range(self.raw_world.as_ptr(), *min.into(), *max.into()) };
     }
 
+    /// Get the entity range set with [`World::set_entity_range()`].
+    ///
+    /// # Returns
+    ///
+    /// The minimum and maximum entity id that are issued, which are 0 when no range is set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    /// assert_eq!(world.entity_range(), (Entity::new(0), Entity::new(0)));
+    ///
+    /// world.set_entity_range(5000, 10000);
+    /// assert_eq!(world.entity_range(), (Entity::new(5000), Entity::new(10000)));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::set_entity_range()`]
+    pub fn entity_range(&self) -> (Entity, Entity) {
+        let info = self.info();
+        (Entity::new(info.min_id), Entity::new(info.max_id))
+    }
+
     /// Enforce that operations cannot modify entities outside of the specified range.
     ///
     /// This function ensures that only entities within the specified range can
     /// be modified. Use this function if specific parts of the code are only allowed
     /// to modify a certain set of entities, as could be the case for networked applications.
     ///
+    /// Components are not created in the entity range, so they should be registered before the
+    /// range check is enabled, for example with [`World::register_components()`].
+    ///
     /// # Arguments
     ///
     /// * `enabled` - True if the range check should be enabled, false otherwise.
     ///
+    /// # Returns
+    ///
+    /// Whether the range check was enabled before, so it can be restored.
+    ///
     /// # Example
     ///
     /// ```should_panic
@@ -1326,8 +1431,144 @@ impl World {
     /// * [`World::set_entity_range()`]
     /// * C++ API: `world::enable_range_check`
     #[doc(alias = "world::enable_range_check")]
-    pub fn enable_range_check(&self, enabled: bool) {
-        unsafe { sys::ecs_enable_range_check(self.raw_world.as_ptr(), enabled) };
+    pub fn enable_range_check(&self, enabled: bool) -> bool {
+        unsafe { sys::ecs_enable_range_check(self.raw_world.as_ptr(), enabled) }
+    }
+
+    /// Make query and system iteration order deterministic across runs.
+    ///
+    /// Entity ids are allocated in the same order when the same operations are done in the same
+    /// order, but the order in which tables are created and matched can differ, for example when
+    /// components are registered in a different order. When enabled, queries and systems created
+    /// afterwards that don't specify their own `order_by` iterate entities sorted by id, which is
+    /// required for lockstep networking and replays.
+    ///
+    /// Sorting forces a query to be cached and adds a cost to iterating a query after its tables
+    /// changed. Multi threaded systems are not sorted, and sorted queries can't be iterated from a
+    /// multi threaded system.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - True if iteration should be deterministic, false otherwise.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Tag;
+    ///
+    /// let world = World::new();
+    /// world.set_deterministic(true);
+    ///
+    /// let e1 = world.entity().set(Position { x: 1.0, y: 2.0 }).add::<Tag>();
+    /// let e2 = world.entity().set(Position { x: 3.0, y: 4.0 });
+    ///
+    /// let query = world.new_query::<&Position>();
+    /// let mut order = Vec::new();
+    /// query.each_entity(|e, _| order.push(e.id()));
+    ///
+    /// // iterated by id, not by table
+    /// assert_eq!(order, [e1.id(), e2.id()]);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::is_deterministic()`]
+    /// * [`QueryBuilderImpl::order_by()`]
+    pub fn set_deterministic(&self, enabled: bool) {
+        self.world_ctx_mut().is_deterministic = enabled;
+    }
+
+    /// Test if deterministic iteration is enabled.
+    ///
+    /// # See also
+    ///
+    /// * [`World::set_deterministic()`]
+    pub fn is_deterministic(&self) -> bool {
+        self.world_ctx().is_deterministic
+    }
+
+    /// Turn the asserts of flecs into Rust panics, instead of aborting the process.
+    ///
+    /// Flecs checks for misuse of the API, such as using an entity that is not alive or adding
+    /// components while the world is readonly, and aborts when a check fails. With this enabled the
+    /// check panics with the message logged by flecs, which shows a Rust backtrace and can be
+    /// expected with `#[should_panic]` in tests.
+    ///
+    /// Flecs asserts are only checked in debug builds of flecs. The world is in an undefined state
+    /// after an assert panicked, it should only be dropped.
+    ///
+    /// The abort function of flecs is shared by all worlds, so this is process-global: asserts
+    /// panic in every world and on every thread while at least one world has this enabled. Use
+    /// [`World::catch_assert()`] to only turn the asserts of a single call into an error.
+    ///
+    /// # Example
+    ///
+    /// ```should_panic
+    /// # use flecs_ecs::prelude::*;
+    /// let world = World::new();
+    /// world.set_panic_on_assert(true);
+    ///
+    /// #[derive(Component)]
+    /// struct Tag;
+    ///
+    /// let e = world.entity();
+    /// e.destruct();
+    ///
+    /// // panics with the assert of flecs, instead of aborting
+    /// e.add::<Tag>();
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::catch_assert()`]
+    /// * [`World::is_panic_on_assert()`]
+    #[cfg(feature = "std")]
+    pub fn set_panic_on_assert(&self, enabled: bool) {
+        let ctx = self.world_ctx_mut();
+        if ctx.panic_on_assert != enabled {
+            ctx.panic_on_assert = enabled;
+            ecs_os_api::set_panic_on_assert(enabled);
+        }
+    }
+
+    /// Test if flecs asserts panic instead of aborting for this world on the current thread.
+    ///
+    /// This is the case when it was enabled with [`World::set_panic_on_assert()`], or for every
+    /// world on the thread of a [`TestWorld`](crate::testing::TestWorld).
+    ///
+    /// # See also
+    ///
+    /// * [`World::set_panic_on_assert()`]
+    #[cfg(feature = "std")]
+    pub fn is_panic_on_assert(&self) -> bool {
+        self.world_ctx().panic_on_assert || ecs_os_api::is_thread_panic_on_assert()
+    }
+
+    /// Run `func`, returning an error instead of aborting when a flecs assert fails in it.
+    ///
+    /// Asserts panic while `func` runs, as with [`World::set_panic_on_assert()`], but only on the
+    /// current thread, so asserts on other threads still abort. The panic is caught and returned
+    /// as [`FlecsError::Assert`], other panics are resumed. The world is in an undefined state
+    /// after an assert failed, it should only be dropped.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FlecsError::Assert`] with the message logged by flecs when an assert failed.
+    ///
+    /// # See also
+    ///
+    /// * [`World::set_panic_on_assert()`]
+    #[cfg(feature = "std")]
+    pub fn catch_assert<R>(&self, func: impl FnOnce() -> R) -> Result<R, FlecsError> {
+        ecs_os_api::catch_assert(func).map_err(FlecsError::Assert)
     }
 
     /// Get the current scope. Get the scope set by `set_scope`.
@@ -1587,7 +1828,7 @@ impl World {
 
     /// Helper function for [`World::try_lookup()`] and [`World::try_lookup_recursive()`].
     fn try_lookup_impl(&self, name: &str, recursively: bool) -> Option<EntityView> {
-        let name = compact_str::format_compact!("{}\0", name);
+        let name = SmallCString::new(name);
 
         let entity_id = unsafe {
             sys::ecs_lookup_path_w_sep(
@@ -1654,6 +1895,47 @@ impl World {
         self.try_lookup_impl(name, false)
     }
 
+    /// Lookup entity by name, only the current scope is searched.
+    ///
+    /// Like [`World::try_lookup()`], but returns [`FlecsError::Unresolved`] with the name when no
+    /// entity is found.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// let world = World::new();
+    /// let e = world.entity_named("foo");
+    ///
+    /// assert_eq!(world.lookup_or_err("foo"), Ok(e));
+    /// assert_eq!(
+    ///     world.lookup_or_err("bar"),
+    ///     Err(FlecsError::Unresolved("bar".into()))
+    /// );
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::lookup_recursive_or_err()`]
+    pub fn lookup_or_err(&self, name: &str) -> Result<EntityView<'_>, FlecsError> {
+        self.try_lookup(name)
+            .ok_or_else(|| FlecsError::Unresolved(name.into()))
+    }
+
+    /// Lookup entity by name, recursively traversing up the scopes until found.
+    ///
+    /// Like [`World::try_lookup_recursive()`], but returns [`FlecsError::Unresolved`] with the
+    /// name when no entity is found.
+    ///
+    /// # See also
+    ///
+    /// * [`World::lookup_or_err()`]
+    pub fn lookup_recursive_or_err(&self, name: &str) -> Result<EntityView<'_>, FlecsError> {
+        self.try_lookup_recursive(name)
+            .ok_or_else(|| FlecsError::Unresolved(name.into()))
+    }
+
     /// Sets a singleton component of type `T` on the world.
     ///
     /// # Arguments
@@ -1771,6 +2053,45 @@ impl World {
         self.modified_id(T::id(self));
     }
 
+    /// Mutate singleton component and signal that it was modified.
+    ///
+    /// This runs the callback with mutable access to the singleton, then emits the `OnSet` event
+    /// for it like [`World::modified()`].
+    ///
+    /// # Panics
+    ///
+    /// - If the world does not have the singleton component.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Score(u32);
+    ///
+    /// let world = World::new();
+    /// world.set(Score(0));
+    ///
+    /// world.observer::<flecs::OnSet, &Score>().each(|score| {
+    ///     println!("score: {}", score.0);
+    /// });
+    ///
+    /// world.modify(|score: &mut Score| score.0 += 10);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::get()`]
+    /// * [`World::modified()`]
+    pub fn modify<T, R>(&self, callback: impl FnOnce(&mut T) -> R) -> R
+    where
+        T: ComponentId + DataComponent + ComponentType<Struct>,
+    {
+        let result = self.get::<&mut T>(callback);
+        self.modified::<T>();
+        result
+    }
+
     /// set the version of the provided entity.
     pub fn set_version(&self, entity: impl Into<Entity>) {
         unsafe { sys::ecs_set_version(self.raw_world.as_ptr(), *entity.into()) };
@@ -1912,6 +2233,42 @@ pub trait WorldGet<Return> {
     ) -> Return
     where
         T::OnlyType: ComponentOrPairId;
+
+    /// Like [`WorldGet::try_get()`], but returns [`FlecsError::MissingComponent`] with the
+    /// requested type when the singleton isn't set.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// pub struct Position {
+    ///     pub x: f32,
+    ///     pub y: f32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// pub struct Velocity {
+    ///     pub x: f32,
+    ///     pub y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.set(Position { x: 10.0, y: 20.0 });
+    ///
+    /// assert_eq!(world.get_or_err::<&Position>(|pos| pos.x), Ok(10.0));
+    /// assert!(matches!(
+    ///     world.get_or_err::<&Velocity>(|vel| vel.x),
+    ///     Err(FlecsError::MissingComponent(_))
+    /// ));
+    /// ```
+    fn get_or_err<T: GetTupleTypeOperation>(
+        &self,
+        callback: impl for<'e> FnOnce(T::ActualType<'e>) -> Return,
+    ) -> Result<Return, FlecsError>
+    where
+        T::OnlyType: ComponentOrPairId;
 }
 
 impl<Return> WorldGet<Return> for World {
@@ -1942,6 +2299,17 @@ impl<Return> WorldGet<Return> for World {
         );
         entity.get::<T>(callback)
     }
+
+    fn get_or_err<T: GetTupleTypeOperation>(
+        &self,
+        callback: impl for<'e> FnOnce(T::ActualType<'e>) -> Return,
+    ) -> Result<Return, FlecsError>
+    where
+        T::OnlyType: ComponentOrPairId,
+    {
+        self.try_get::<T>(callback)
+            .ok_or(FlecsError::MissingComponent(core::any::type_name::<T>()))
+    }
 }
 
 impl World {
@@ -2442,6 +2810,117 @@ impl World {
         EntityView::new(self).each_child(callback);
     }
 
+    /// Iterate all alive entities in the world, excluding builtin entities.
+    ///
+    /// Builtin entities are the `flecs` module and everything in its scope. Components registered
+    /// by the application or by addons outside of that scope are visited. Unlike queries this
+    /// also visits entities without components. Entities created by `func` are not visited,
+    /// entities deleted by `func` are skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function invoked for each entity.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// let world = World::new();
+    /// world.entity_named("a");
+    /// world.entity_named("b").child_of_id(world.entity_named("parent"));
+    ///
+    /// let mut names = Vec::new();
+    /// world.each_alive_entity(|e| names.push(e.name()));
+    /// assert!(names.iter().any(|name| name == "b"));
+    /// // builtin entities are not visited
+    /// assert!(!names.iter().any(|name| name == "flecs"));
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::each_table()`]
+    /// * [`World::entity_count()`]
+    pub fn each_alive_entity(&self, mut func: impl FnMut(EntityView)) {
+        let world = self.raw_world.as_ptr();
+        let entities = unsafe { sys::ecs_get_entities(world) };
+        let ids = if entities.alive_count > 0 {
+            // copy the ids so `func` can create and delete entities
+            unsafe { core::slice::from_raw_parts(entities.ids, entities.alive_count as usize) }
+                .to_vec()
+        } else {
+            Vec::new()
+        };
+
+        for id in ids {
+            if unsafe { sys::ecs_is_alive(world, id) } && !self.is_builtin(id) {
+                func(EntityView::new_from(self, id));
+            }
+        }
+    }
+
+    /// Iterate all non-empty tables in the world that store entities other than builtin entities.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The function invoked for each table.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.entity().set(Position { x: 1.0, y: 2.0 });
+    /// world.entity().set(Position { x: 3.0, y: 4.0 });
+    ///
+    /// let mut count = 0;
+    /// world.each_table(|table| {
+    ///     if table.archetype().contains::<Position>() {
+    ///         count += table.count();
+    ///     }
+    /// });
+    /// assert_eq!(count, 2);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::each_alive_entity()`]
+    /// * [`World::table_count()`]
+    pub fn each_table(&self, mut func: impl FnMut(Table)) {
+        let mut tables = Vec::new();
+        let mut seen = hashbrown::HashSet::new();
+        self.each_alive_entity(|e| {
+            let table = unsafe { sys::ecs_get_table(self.raw_world.as_ptr(), *e.id()) };
+            if let Some(table) = NonNull::new(table) {
+                if seen.insert(table) {
+                    tables.push(table);
+                }
+            }
+        });
+
+        for table in tables {
+            func(Table::new(self, table));
+        }
+    }
+
+    /// Test if the entity is the `flecs` module or is in its scope.
+    pub(crate) fn is_builtin(&self, entity: sys::ecs_entity_t) -> bool {
+        let world = self.raw_world.as_ptr();
+        let mut current = entity;
+        while current != 0 {
+            if current == flecs::Flecs::ID {
+                return true;
+            }
+            current = unsafe { sys::ecs_get_parent(world, current) };
+        }
+        false
+    }
+
     /// create alias for component
     ///
     /// # Type Parameters
@@ -2462,7 +2941,7 @@ impl World {
     #[doc(alias = "world::use")]
     #[inline(always)]
     pub fn set_alias_component<T: ComponentId>(&self, alias: &str) -> EntityView {
-        let alias = compact_str::format_compact!("{}\0", alias);
+        let alias = SmallCString::new(alias);
 
         let id = T::id(self);
         if alias.is_empty() {
@@ -2496,8 +2975,8 @@ impl World {
     #[doc(alias = "world::use")]
     #[inline(always)]
     pub fn set_alias_entity_by_name(&self, name: &str, alias: &str) -> EntityView {
-        let name = compact_str::format_compact!("{}\0", name);
-        let alias = compact_str::format_compact!("{}\0", alias);
+        let name = SmallCString::new(name);
+        let alias = SmallCString::new(alias);
 
         let id = unsafe {
             sys::ecs_lookup_path_w_sep(
@@ -2527,7 +3006,7 @@ impl World {
     #[doc(alias = "world::use")]
     #[inline(always)]
     pub fn set_alias_entity(&self, entity: impl Into<Entity>, alias: &str) {
-        let alias = compact_str::format_compact!("{}\0", alias);
+        let alias = SmallCString::new(alias);
 
         let entity = *entity.into();
         if alias.is_empty() {
@@ -2585,12 +3064,9 @@ impl World {
     ///
     /// # Type Parameters
     ///
+    /// * `First` - The first element of the pair.
     /// * `Second` - The second element of the pair.
     ///
-    /// # Arguments
-    ///
-    /// * `first` - The ID of the first element of the pair.
-    ///
     /// # Returns
     ///
     /// The number of entities with the provided pair.
@@ -2599,11 +3075,71 @@ impl World {
     ///
     /// * C++ API: `world::count`
     #[doc(alias = "world::count")]
-    pub fn count_second<Second: ComponentId>(&self, first: impl Into<Entity>) -> i32 {
-        self.count_id((first.into(), Second::id(self)))
+    pub fn count_pair<First: ComponentId, Second: ComponentId>(&self) -> i32 {
+        self.count_id((First::id(self), Second::id(self)))
     }
 
-    /// Count entities with the provided pair.
+    /// Get the number of alive entities in the world.
+    ///
+    /// This includes builtin entities such as components, modules and systems.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// let world = World::new();
+    /// let before = world.entity_count();
+    ///
+    /// let e = world.entity();
+    /// world.entity();
+    /// assert_eq!(world.entity_count(), before + 2);
+    ///
+    /// e.destruct();
+    /// assert_eq!(world.entity_count(), before + 1);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::table_count()`]
+    /// * C API: `ecs_get_entities`
+    #[doc(alias = "ecs_get_entities")]
+    pub fn entity_count(&self) -> i32 {
+        unsafe { sys::ecs_get_entities(self.raw_world.as_ptr()).alive_count }
+    }
+
+    /// Get the number of tables in the world.
+    ///
+    /// # See also
+    ///
+    /// * [`World::entity_count()`]
+    /// * [`World::info()`]
+    pub fn table_count(&self) -> i32 {
+        self.info().table_count
+    }
+
+    /// Count entities with the provided pair.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `Second` - The second element of the pair.
+    ///
+    /// # Arguments
+    ///
+    /// * `first` - The ID of the first element of the pair.
+    ///
+    /// # Returns
+    ///
+    /// The number of entities with the provided pair.
+    ///
+    /// # See also
+    ///
+    /// * C++ API: `world::count`
+    #[doc(alias = "world::count")]
+    pub fn count_second<Second: ComponentId>(&self, first: impl Into<Entity>) -> i32 {
+        self.count_id((first.into(), Second::id(self)))
+    }
+
+    /// Count entities with the provided pair.
     ///
     /// # Type Parameters
     ///
@@ -2792,6 +3328,7 @@ impl World {
     ///
     /// # See also
     ///
+    /// * [`World::with_guard_id()`]
     /// * C++ API: `world::with`
     #[doc(alias = "world::with")]
     pub fn with_id(&self, id: impl IntoId, mut func: impl FnMut()) {
@@ -2907,6 +3444,153 @@ impl World {
         );
     }
 
+    /// Get the id that is added to new entities, set by [`World::set_with()`] or
+    /// [`World::with()`].
+    ///
+    /// # See also
+    ///
+    /// * [`World::set_with_id()`]
+    /// * C++ API: `world::get_with`
+    #[doc(alias = "world::get_with")]
+    pub fn get_with(&self) -> Option<IdView<'_>> {
+        let id = unsafe { sys::ecs_get_with(self.raw_world.as_ptr()) };
+
+        if id == 0 {
+            None
+        } else {
+            Some(IdView::new_from_id(self, id))
+        }
+    }
+
+    /// Add an id to all entities that are created from now on, until it's reset.
+    ///
+    /// Pass `0` to stop adding an id. It is considered good practice to restore the previous
+    /// id, [`World::with_guard_id()`] does this automatically.
+    ///
+    /// # Arguments
+    ///
+    /// * `id`: The id to create entities with.
+    ///
+    /// # Returns
+    ///
+    /// The previously set id, `0` if there was none.
+    ///
+    /// # See also
+    ///
+    /// * [`World::get_with()`]
+    /// * [`World::set_with()`]
+    /// * [`World::with_id()`]
+    /// * C++ API: `world::set_with`
+    #[doc(alias = "world::set_with")]
+    pub fn set_with_id(&self, id: impl IntoId) -> Id {
+        Id(unsafe { sys::ecs_set_with(self.raw_world.as_ptr(), *id.into()) })
+    }
+
+    /// Add a component, tag or pair to all entities that are created from now on, until it's
+    /// reset.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T`: The component, tag or pair to create entities with.
+    ///
+    /// # Returns
+    ///
+    /// The previously set id, `0` if there was none.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct Loaded;
+    ///
+    /// let world = World::new();
+    ///
+    /// let previous = world.set_with::<Loaded>();
+    /// let e = world.entity();
+    /// world.set_with_id(previous);
+    ///
+    /// assert!(e.has::<Loaded>());
+    /// assert!(!world.entity().has::<Loaded>());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::get_with()`]
+    /// * [`World::set_with_id()`]
+    /// * [`World::with()`]
+    /// * C++ API: `world::set_with`
+    #[doc(alias = "world::set_with")]
+    pub fn set_with<T: ComponentOrPairId>(&self) -> Id {
+        self.set_with_id(T::get_id(self))
+    }
+
+    /// Add an id to all entities that are created while the returned guard is alive.
+    ///
+    /// The previously set id is restored when the guard is dropped.
+    ///
+    /// # Arguments
+    ///
+    /// * `id`: The id to create entities with.
+    ///
+    /// # See also
+    ///
+    /// * [`World::with_guard()`]
+    /// * [`World::with_id()`]
+    pub fn with_guard_id(&self, id: impl IntoId) -> WithGuard<'_> {
+        WithGuard {
+            world: self.world(),
+            previous: self.set_with_id(id),
+        }
+    }
+
+    /// Add a component, tag or pair to all entities that are created while the returned guard
+    /// is alive.
+    ///
+    /// The previously set id is restored when the guard is dropped. This makes it possible to
+    /// tag everything that is created while loading a scene, and to unload it again by deleting
+    /// the entities with the tag.
+    ///
+    /// # Type Parameters
+    ///
+    /// * `T`: The component, tag or pair to create entities with.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// use flecs_ecs::prelude::*;
+    ///
+    /// #[derive(Component)]
+    /// struct SceneRoot;
+    ///
+    /// let world = World::new();
+    ///
+    /// let level = world.entity_named("level");
+    ///
+    /// {
+    ///     let _guard = world.with_guard_id((world.component_id::<SceneRoot>(), level));
+    ///     world.entity_named("player");
+    ///     world.entity_named("enemy");
+    /// }
+    ///
+    /// let outside = world.entity_named("camera");
+    /// assert_eq!(world.count_id((world.component_id::<SceneRoot>(), level)), 2);
+    ///
+    /// // unload the scene
+    /// world.delete_entities_with_id((world.component_id::<SceneRoot>(), level));
+    /// assert!(world.try_lookup("player").is_none());
+    /// assert!(outside.is_alive());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::with_guard_id()`]
+    /// * [`World::with()`]
+    pub fn with_guard<T: ComponentOrPairId>(&self) -> WithGuard<'_> {
+        self.with_guard_id(T::get_id(self))
+    }
+
     /// Delete all entities with the given id
     ///
     /// # Arguments
@@ -2925,6 +3609,23 @@ impl World {
 
     /// Delete all entities with the given component
     ///
+    /// Pairs are passed as a tuple, which also accepts wildcards:
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Likes;
+    ///
+    /// #[derive(Component)]
+    /// struct Apples;
+    ///
+    /// let world = World::new();
+    /// let e = world.entity().add::<(Likes, Apples)>();
+    ///
+    /// world.delete_entities_with::<(Likes, flecs::Wildcard)>();
+    /// assert!(!e.is_alive());
+    /// ```
+    ///
     /// # Type Parameters
     ///
     /// * `T`: The component type to delete.
@@ -3034,6 +3735,24 @@ impl World {
 
     /// Remove all instances of the given component from entities
     ///
+    /// Unlike [`World::delete_entities_with`], the entities stay alive.
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Likes;
+    ///
+    /// #[derive(Component)]
+    /// struct Apples;
+    ///
+    /// let world = World::new();
+    /// let e = world.entity().add::<(Likes, Apples)>();
+    ///
+    /// world.remove_all::<(Likes, Apples)>();
+    /// assert!(e.is_alive());
+    /// assert!(!e.has::<(Likes, Apples)>());
+    /// ```
+    ///
     /// # Type Parameters
     ///
     /// * `T`: The component type to remove.
@@ -3250,6 +3969,133 @@ impl World {
             sys::ecs_run_post_frame(self.raw_world.as_ptr(), action, ctx);
         }
     }
+
+    /// Run closure after completing the current frame.
+    ///
+    /// This can only be called while a frame is in progress, for example from a system run by
+    /// [`World::progress()`], or between [`World::frame_begin()`] and [`World::frame_end()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// let world = World::new();
+    ///
+    /// world.system::<()>().run(|it| {
+    ///     it.world().post_frame(|world| {
+    ///         world.entity_named("spawned_after_frame");
+    ///     });
+    /// });
+    ///
+    /// world.progress();
+    /// assert!(world.try_lookup("spawned_after_frame").is_some());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::run_post_frame()`]
+    pub fn post_frame<F>(&self, action: F)
+    where
+        F: FnOnce(WorldRef) + 'static,
+    {
+        unsafe extern "C-unwind" fn run_action<F>(world: *mut sys::ecs_world_t, ctx: *mut c_void)
+        where
+            F: FnOnce(WorldRef) + 'static,
+        {
+            let action = unsafe { Box::from_raw(ctx as *mut F) };
+            action(unsafe { WorldRef::from_ptr(world) });
+        }
+
+        let ctx = Box::into_raw(Box::new(action)) as *mut c_void;
+        self.run_post_frame(Some(run_action::<F>), ctx);
+    }
+
+    /// Run closure with full access to the world once it is safe to do so.
+    ///
+    /// Systems and observers run while the world is deferred or readonly, which restricts what
+    /// they can do. Rare operations that need the world itself, like registering a component or
+    /// creating a query from inside a system, can be passed to this function instead.
+    ///
+    /// When `func` runs depends on the state of the world:
+    ///
+    /// * while a frame is in progress, `func` runs at the end of the frame after commands are
+    ///   merged, like [`World::post_frame()`].
+    /// * while the world is deferred outside of a frame, `func` runs when deferring ends through
+    ///   [`World::defer()`] or [`World::defer_end()`], or else after the next frame.
+    /// * otherwise `func` runs immediately.
+    ///
+    /// # Arguments
+    ///
+    /// * `func` - The closure to run, which receives the world.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Spawner {
+    ///     rate: u32,
+    /// }
+    ///
+    /// #[derive(Component)]
+    /// struct Enemy(u32);
+    ///
+    /// let world = World::new();
+    /// world.entity().set(Spawner { rate: 1 });
+    ///
+    /// world.system::<&Spawner>().each_iter(|it, _, _| {
+    ///     it.world().run_deferred(|world| {
+    ///         // creating a query isn't possible while the system runs
+    ///         let enemies = world.new_query::<&Enemy>();
+    ///         world.entity().set(Enemy(enemies.count() as u32));
+    ///     });
+    /// });
+    ///
+    /// world.progress();
+    /// world.progress();
+    /// assert_eq!(world.count::<Enemy>(), 2);
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::post_frame()`]
+    /// * [`World::defer()`]
+    pub fn run_deferred<F>(&self, func: F)
+    where
+        F: FnOnce(&World) + 'static,
+    {
+        let flags = unsafe {
+            sys::ecs_world_get_flags(sys::ecs_get_world(self.world_ptr() as *const c_void))
+        };
+
+        if flags & sys::EcsWorldFrameInProgress != 0 {
+            self.post_frame(move |world| {
+                func(&world);
+                world.flush_deferred_runs();
+            });
+        } else if self.is_deferred() || self.is_readonly() {
+            self.world_ctx_mut().deferred_runs.push(Box::new(func));
+        } else {
+            func(self);
+        }
+    }
+
+    /// Run the closures queued by [`World::run_deferred()`] once the world is no longer deferred.
+    pub(crate) fn flush_deferred_runs(&self) {
+        if self.is_deferred() || self.is_readonly() {
+            return;
+        }
+
+        loop {
+            let runs = core::mem::take(&mut self.world_ctx_mut().deferred_runs);
+            if runs.is_empty() {
+                break;
+            }
+            for run in runs {
+                run(self);
+            }
+        }
+    }
 }
 
 /// `EntityView` mixin implementation
@@ -3337,6 +4183,7 @@ impl World {
     /// * [`World::entity_named_cstr()`]
     /// * C++ API: `world::entity`
     #[doc(alias = "world::entity")]
+    #[cfg_attr(feature = "flecs_entity_audit", track_caller)]
     pub fn entity_named(&self, name: &str) -> EntityView {
         EntityView::new_named(self, name)
     }
@@ -3366,6 +4213,7 @@ impl World {
     /// * [`World::entity_named()`]
     /// * C++ API: `world::entity`
     #[doc(alias = "world::entity")]
+    #[cfg_attr(feature = "flecs_entity_audit", track_caller)]
     pub fn entity_named_cstr(&self, name: &CStr) -> EntityView {
         EntityView::new_named_cstr(self, name)
     }
@@ -3378,6 +4226,7 @@ impl World {
     /// * [`World::entity_named_cstr()`]
     /// * C++ API: `world::entity`
     #[doc(alias = "world::entity")]
+    #[cfg_attr(feature = "flecs_entity_audit", track_caller)]
     pub fn entity(&self) -> EntityView {
         EntityView::new(self)
     }
@@ -3431,6 +4280,7 @@ impl World {
     /// * [`World::prefab_type_named()`]
     /// * C++ API: `world::prefab`
     #[doc(alias = "world::prefab")]
+    #[cfg_attr(feature = "flecs_entity_audit", track_caller)]
     pub fn prefab(&self) -> EntityView {
         let result = EntityView::new(self);
         result.add_id(flecs::Prefab::ID);
@@ -3454,6 +4304,7 @@ impl World {
     /// * [`World::prefab_type_named()`]
     /// * C++ API: `world::prefab`
     #[doc(alias = "world::prefab")]
+    #[cfg_attr(feature = "flecs_entity_audit", track_caller)]
     pub fn prefab_named<'a>(&'a self, name: &str) -> EntityView<'a> {
         let result = EntityView::new_named(self, name);
         result.add_id(ECS_PREFAB);
@@ -3661,13 +4512,17 @@ impl World {
     ///
     /// * C++ API: `world::component`
     #[doc(alias = "world::component")]
-    #[inline(never)]
+    #[inline]
     pub fn component<T: ComponentId>(&self) -> Component<T::UnderlyingType> {
         Component::<T::UnderlyingType>::new(self)
     }
 
     /// Find or register component.
     ///
+    /// Components are registered with their full type name as symbol. If another crate or
+    /// dynamic library already registered `T` with this world, the existing component is
+    /// returned and `name` is ignored.
+    ///
     /// # Type Parameters
     ///
     /// * `T` - The component type.
@@ -3747,6 +4602,79 @@ impl World {
         UntypedComponent::new_from(self, id)
     }
 
+    /// Lookup a component by name.
+    ///
+    /// This makes it possible to find components that are only known at run time,
+    /// for example by tooling or scripting code.
+    ///
+    /// # Panics
+    ///
+    /// Ensure that the component exists before using it.
+    /// Use the [`World::try_lookup_component()`] variant otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the component to lookup.
+    ///
+    /// # See also
+    ///
+    /// * [`World::lookup()`]
+    /// * [`World::try_lookup_component()`]
+    pub fn lookup_component(&self, name: &str) -> UntypedComponent<'_> {
+        self.try_lookup_component(name).unwrap_or_else(|| {
+            panic!(
+                "Component {} not found, when unsure, use try_lookup_component",
+                name
+            )
+        })
+    }
+
+    /// Lookup a component by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the component to lookup.
+    ///
+    /// # Returns
+    ///
+    /// The component if found, otherwise `None`. Entities that exist but are not
+    /// a component also return `None`. Components are looked up by their path, which
+    /// for Rust types includes the module path unless registered with
+    /// [`World::component_named()`].
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// #[meta]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.component_named::<Position>("Position").meta();
+    ///
+    /// let position = world.try_lookup_component("Position").unwrap();
+    /// let e = world.entity().set_json_id(position, "{\"x\": 10, \"y\": 20}", None);
+    ///
+    /// e.get::<&Position>(|p| {
+    ///     assert_eq!(p.x, 10.0);
+    ///     assert_eq!(p.y, 20.0);
+    /// });
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::lookup_component()`]
+    /// * [`World::try_lookup()`]
+    pub fn try_lookup_component(&self, name: &str) -> Option<UntypedComponent<'_>> {
+        self.try_lookup(name)
+            .filter(|e| e.has::<flecs::Component>())
+            .map(|e| UntypedComponent::new_from(self, e))
+    }
+
     /// Convert enum constant to entity
     ///
     /// # Type Parameters
@@ -4021,6 +4949,7 @@ impl World {
     /// * [`World::new_query()`]
     /// * [`World::new_query_named()`]
     /// * [`World::query()`]
+    /// * [`World::query_by_name()`]
     /// * C++ API: `world::query_builder`
     #[doc(alias = "world::query_builder")]
     pub fn query_named<'a, Components>(&'a self, name: &str) -> QueryBuilder<'a, Components>
@@ -4066,6 +4995,78 @@ impl World {
             .expect("entity / query is not alive or valid")
     }
 
+    /// Get a named query by name.
+    ///
+    /// This makes it possible to find queries created with [`World::query_named()`] or
+    /// [`World::new_query_named()`] in code that only knows the name, for example tooling or
+    /// scripting code. The query stays alive when the [`Query`] it was created with is dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there is no query with the name.
+    /// Use the [`World::try_query_by_name()`] variant otherwise.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the query.
+    ///
+    /// # See also
+    ///
+    /// * [`World::query_named()`]
+    /// * [`World::try_query_by_name()`]
+    pub fn query_by_name(&self, name: &str) -> Query<()> {
+        self.try_query_by_name(name).unwrap_or_else(|| {
+            panic!(
+                "Query {} not found, when unsure, use try_query_by_name",
+                name
+            )
+        })
+    }
+
+    /// Get a named query by name.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the query.
+    ///
+    /// # Returns
+    ///
+    /// The query if found, otherwise `None`. Entities that exist but are not a query
+    /// also return `None`.
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use flecs_ecs::prelude::*;
+    /// #[derive(Component)]
+    /// struct Position {
+    ///     x: f32,
+    ///     y: f32,
+    /// }
+    ///
+    /// let world = World::new();
+    /// world.entity().set(Position { x: 10.0, y: 20.0 });
+    ///
+    /// world
+    ///     .query_named::<&Position>("movement::positions")
+    ///     .set_cached()
+    ///     .build();
+    ///
+    /// let query = world.try_query_by_name("movement::positions").unwrap();
+    /// assert_eq!(query.count(), 1);
+    ///
+    /// assert!(world.try_query_by_name("movement::velocities").is_none());
+    /// ```
+    ///
+    /// # See also
+    ///
+    /// * [`World::query_by_name()`]
+    /// * [`World::try_query_from()`]
+    pub fn try_query_by_name(&self, name: &str) -> Option<Query<()>> {
+        self.try_lookup(name)
+            .and_then(|query_entity| self.try_query_from(query_entity))
+    }
+
     /// Create and iterate an uncached query.
     ///
     /// This function creates a query and immediately iterates it.
@@ -4375,7 +5376,9 @@ impl World {
     #[doc(alias = "world::progress")]
     #[inline(always)]
     pub fn progress_time(&self, delta_time: f32) -> bool {
-        unsafe { sys::ecs_progress(self.raw_world.as_ptr(), delta_time) }
+        let result = unsafe { sys::ecs_progress(self.raw_world.as_ptr(), delta_time) };
+        self.flush_deferred_runs();
+        result
     }
 
     /// Run pipeline.
@@ -4596,6 +5599,41 @@ impl World {
         }
     }
 
+    /// Enable or disable measuring the total time spent in a frame.
+    ///
+    /// The result is stored in the `frame_time_total` field of [`World::info()`].
+    /// Measuring frame time is enabled automatically when a target FPS is set.
+    ///
+    /// # See also
+    ///
+    /// * [`World::measure_system_time()`]
+    /// * C++ API: `world::measure_frame_time`
+    #[doc(alias = "world::measure_frame_time")]
+    #[inline(always)]
+    pub fn measure_frame_time(&self, enable: bool) {
+        unsafe {
+            sys::ecs_measure_frame_time(self.raw_world.as_ptr(), enable);
+        }
+    }
+
+    /// Enable or disable measuring the time spent in systems.
+    ///
+    /// When enabled, the time spent in each system is accumulated and can be read with
+    /// `System::time_spent()`. The total for the world is stored in
+    /// the `system_time_total` field of [`World::info()`].
+    ///
+    /// # See also
+    ///
+    /// * [`World::measure_frame_time()`]
+    /// * C++ API: `world::measure_system_time`
+    #[doc(alias = "world::measure_system_time")]
+    #[inline(always)]
+    pub fn measure_system_time(&self, enable: bool) {
+        unsafe {
+            sys::ecs_measure_system_time(self.raw_world.as_ptr(), enable);
+        }
+    }
+
     /// Reset world clock. Reset the clock that keeps track of the total time passed in the simulation.
     ///
     /// # See also
@@ -4709,3 +5747,21 @@ impl World {
         unsafe { sys::ecs_delete_empty_tables(self.raw_world.as_ptr(), &desc) }
     }
 }
+
+/// Restores the id that is added to new entities when dropped, created by
+/// [`World::with_guard()`].
+#[must_use = "the id is restored right away when the guard isn't bound to a variable"]
+pub struct WithGuard<'a> {
+    world: WorldRef<'a>,
+    previous: Id,
+}
+
+impl Drop for WithGuard<'_> {
+    fn drop(&mut self) {
+        if is_thread_panicking() {
+            return;
+        }
+
+        self.world.set_with_id(self.previous);
+    }
+}
@@ -1,4 +1,5 @@
 use super::{FlecsArray, FlecsIdMap, World};
+use crate::core::utility::is_thread_panicking;
 use crate::sys;
 
 #[cfg(feature = "flecs_safety_readwrite_locks")]
@@ -8,16 +9,47 @@ use super::ReadWriteComponentsMap;
 extern crate std;
 
 extern crate alloc;
+use alloc::boxed::Box;
 use alloc::vec;
 
+/// A closure passed to `World::run_deferred`.
+pub(crate) type DeferredRun = Box<dyn FnOnce(&World)>;
+
 pub(crate) struct WorldCtx {
     query_ref_count: i32,
     pub(crate) components: FlecsIdMap,
     pub(crate) components_array: FlecsArray,
     is_panicking: bool,
+    pub(crate) is_deterministic: bool,
+    #[cfg(feature = "std")]
+    pub(crate) panic_on_assert: bool,
+    pub(crate) name_index: Option<super::NameIndex>,
+    // Closures passed to `World::run_deferred` while deferred outside of a frame
+    pub(crate) deferred_runs: vec::Vec<DeferredRun>,
+    #[cfg(feature = "flecs_spatial")]
+    pub(crate) spatial: Option<crate::addons::spatial::SpatialGrid>,
+    #[cfg(feature = "flecs_entity_audit")]
+    pub(crate) entity_audit: super::EntityAudit,
     #[cfg(feature = "flecs_safety_readwrite_locks")]
     // Track entity reads and writes for thread safety
     pub(crate) component_access: ReadWriteComponentsMap,
+    #[cfg(feature = "flecs_json")]
+    pub(crate) migrations: alloc::vec::Vec<crate::addons::json::ComponentMigration>,
+    #[cfg(feature = "flecs_json")]
+    pub(crate) guids: Option<crate::addons::json::GuidIndex>,
+    #[cfg(feature = "flecs_json")]
+    pub(crate) replication: Option<crate::addons::json::Replication>,
+    #[cfg(all(feature = "flecs_script", feature = "flecs_system"))]
+    pub(crate) script_callbacks: crate::addons::script::CallbackRegistry,
+    #[cfg(all(feature = "std", feature = "flecs_script", feature = "flecs_json"))]
+    pub(crate) prefab_files: crate::addons::prefab_registry::PrefabFiles,
+    #[cfg(feature = "std")]
+    pub(crate) command_sender: Option<super::CommandSender>,
+    #[cfg(feature = "flecs_async")]
+    pub(crate) task_spawner: Option<crate::addons::async_task::TaskSpawner>,
+    pub(crate) detached_callbacks: Option<super::DetachedCallbacks>,
+    #[cfg(feature = "flecs_profiler")]
+    pub(crate) profiler: crate::addons::profiler::Profiler,
 }
 
 impl WorldCtx {
@@ -27,8 +59,34 @@ impl WorldCtx {
             components: Default::default(),
             components_array: vec![0; 500],
             is_panicking: false,
+            is_deterministic: false,
+            #[cfg(feature = "std")]
+            panic_on_assert: false,
+            name_index: None,
+            deferred_runs: vec::Vec::new(),
+            #[cfg(feature = "flecs_spatial")]
+            spatial: None,
+            #[cfg(feature = "flecs_entity_audit")]
+            entity_audit: Default::default(),
             #[cfg(feature = "flecs_safety_readwrite_locks")]
             component_access: ReadWriteComponentsMap::new(),
+            #[cfg(feature = "flecs_json")]
+            migrations: alloc::vec::Vec::new(),
+            #[cfg(feature = "flecs_json")]
+            guids: None,
+            #[cfg(feature = "flecs_json")]
+            replication: None,
+            #[cfg(all(feature = "flecs_script", feature = "flecs_system"))]
+            script_callbacks: Default::default(),
+            #[cfg(all(feature = "std", feature = "flecs_script", feature = "flecs_json"))]
+            prefab_files: Default::default(),
+            #[cfg(feature = "std")]
+            command_sender: None,
+            #[cfg(feature = "flecs_async")]
+            task_spawner: None,
+            detached_callbacks: None,
+            #[cfg(feature = "flecs_profiler")]
+            profiler: Default::default(),
         }
     }
 
@@ -69,7 +127,7 @@ impl WorldCtx {
     }
 
     pub(crate) fn is_panicking(&self) -> bool {
-        self.is_panicking || std::thread::panicking()
+        self.is_panicking || is_thread_panicking()
     }
 }
 
@@ -4,8 +4,7 @@
 //!
 //! [Flecs]: https://www.flecs.dev/
 
-//this is commented since `no_std` is not ready yet
-//#![cfg_attr(not(feature = "std"), no_std)] // Enable `no_std` if `std` feature is disabled
+#![cfg_attr(not(feature = "std"), no_std)] // Enable `no_std` if `std` feature is disabled
 #![allow(dead_code)]
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 
@@ -17,9 +16,6 @@ compile_error!(
     "Features 'flecs_force_build_release_c' and 'flecs_force_build_debug_c' cannot be enabled at the same time."
 );
 
-#[cfg(not(feature = "std"))]
-const _: () = panic!("no_std is not ready yet");
-
 #[cfg(feature = "std")]
 extern crate std;
 
@@ -33,6 +29,9 @@ pub mod core;
 
 pub mod addons;
 
+#[cfg(feature = "std")]
+pub mod testing;
+
 /// this is to allow using the proc macro's inside lib itself that implements its own traits.
 extern crate self as flecs_ecs;
 
@@ -0,0 +1,243 @@
+//! Utilities for writing tests against a [`World`].
+//!
+//! * [`TestWorld`] is a world that is set up for tests.
+//! * [`assert_matches!`](crate::assert_matches) asserts what a query matches.
+//!
+//! # Example
+//!
+//! ```
+//! use flecs_ecs::assert_matches;
+//! use flecs_ecs::prelude::*;
+//! use flecs_ecs::testing::TestWorld;
+//!
+//! #[derive(Component)]
+//! struct Position {
+//!     x: f32,
+//!     y: f32,
+//! }
+//!
+//! #[derive(Component)]
+//! struct Velocity {
+//!     x: f32,
+//!     y: f32,
+//! }
+//!
+//! let world = TestWorld::new();
+//!
+//! let e = world
+//!     .entity()
+//!     .set(Position { x: 0.0, y: 0.0 })
+//!     .set(Velocity { x: 1.0, y: 0.0 });
+//! world.entity().set(Position { x: 5.0, y: 5.0 });
+//!
+//! world
+//!     .system::<(&mut Position, &Velocity)>()
+//!     .each(|(p, v)| p.x += v.x);
+//!
+//! world.step_n(3);
+//!
+//! assert_matches!(world, &Position, count == 2);
+//! assert_matches!(world, (&Position, &Velocity), count == 1);
+//! assert_matches!(world, (&Position, &Velocity), contains e);
+//! e.get::<&Position>(|p| assert_eq!(p.x, 3.0));
+//! ```
+
+use core::cell::Cell;
+use core::marker::PhantomData;
+use core::ops::Deref;
+
+use crate::core::*;
+
+extern crate alloc;
+use alloc::{string::String, vec::Vec};
+
+/// A [`World`] set up for tests, which derefs to [`World`].
+///
+/// Compared to [`World::new()`], a test world:
+///
+/// * panics when a flecs assert fails on the thread of the test world, like
+///   [`World::catch_assert()`]. Unlike [`World::set_panic_on_assert()`], this doesn't affect
+///   worlds on other threads, such as tests that run in parallel.
+/// * iterates entities in the order of their ids, see [`World::set_deterministic()`]. Ids are
+///   handed out in the same order in every test world, so tests see the same ids on every run.
+/// * collects the warnings and errors logged by flecs on the current thread, see
+///   [`TestWorld::errors()`] and [`TestWorld::warnings()`].
+/// * steps frames with a fixed delta time, see [`TestWorld::step()`].
+///
+/// Because logs are collected per thread, a test world can't be sent to another thread.
+pub struct TestWorld {
+    world: World,
+    delta_time: Cell<f32>,
+    prev_logs: Option<Vec<(i32, String)>>,
+    _not_send: PhantomData<*const ()>,
+}
+
+impl TestWorld {
+    /// The delta time of a step, unless changed with [`TestWorld::set_delta_time()`].
+    pub const DEFAULT_DELTA_TIME: f32 = 1.0 / 60.0;
+
+    /// Create a new test world.
+    pub fn new() -> Self {
+        let world = World::new();
+        ecs_os_api::set_thread_panic_on_assert(true);
+        world.set_deterministic(true);
+
+        Self {
+            world,
+            delta_time: Cell::new(Self::DEFAULT_DELTA_TIME),
+            prev_logs: ecs_os_api::capture_logs(),
+            _not_send: PhantomData,
+        }
+    }
+
+    /// The world under test.
+    pub fn world(&self) -> &World {
+        &self.world
+    }
+
+    /// Set the delta time that is passed to systems by [`TestWorld::step()`].
+    pub fn set_delta_time(&self, delta_time: f32) -> &Self {
+        self.delta_time.set(delta_time);
+        self
+    }
+
+    /// Run the systems once with a fixed delta time.
+    ///
+    /// # See also
+    ///
+    /// * [`World::progress_time()`]
+    #[cfg(feature = "flecs_pipeline")]
+    pub fn step(&self) -> &Self {
+        self.world.progress_time(self.delta_time.get());
+        self
+    }
+
+    /// Run the systems `frames` times with a fixed delta time.
+    #[cfg(feature = "flecs_pipeline")]
+    pub fn step_n(&self, frames: usize) -> &Self {
+        for _ in 0..frames {
+            self.step();
+        }
+        self
+    }
+
+    /// The errors logged by flecs on this thread since the world was created.
+    pub fn errors(&self) -> Vec<String> {
+        ecs_os_api::captured_logs(-3, false)
+    }
+
+    /// The warnings logged by flecs on this thread since the world was created.
+    pub fn warnings(&self) -> Vec<String> {
+        ecs_os_api::captured_logs(-2, false)
+    }
+
+    /// Remove and return the errors logged by flecs, so that following assertions only see new
+    /// errors.
+    pub fn take_errors(&self) -> Vec<String> {
+        ecs_os_api::captured_logs(-3, true)
+    }
+
+    /// Remove and return the warnings logged by flecs.
+    pub fn take_warnings(&self) -> Vec<String> {
+        ecs_os_api::captured_logs(-2, true)
+    }
+
+    /// Assert that flecs logged no errors.
+    ///
+    /// # Panics
+    ///
+    /// Panics with the logged errors if there are any.
+    #[track_caller]
+    pub fn assert_no_errors(&self) {
+        let errors = self.errors();
+        assert!(errors.is_empty(), "flecs logged errors: {errors:#?}");
+    }
+}
+
+impl Default for TestWorld {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for TestWorld {
+    type Target = World;
+
+    fn deref(&self) -> &World {
+        &self.world
+    }
+}
+
+impl Drop for TestWorld {
+    fn drop(&mut self) {
+        ecs_os_api::restore_logs(self.prev_logs.take());
+        ecs_os_api::set_thread_panic_on_assert(false);
+    }
+}
+
+/// Assert what a query matches, creating the query from its components.
+///
+/// # Forms
+///
+/// * `assert_matches!(world, Components, count == 3)` asserts the number of matched entities,
+///   any comparison operator can be used.
+/// * `assert_matches!(world, Components, contains entity)` asserts that an entity is matched.
+/// * `assert_matches!(world, Components, !contains entity)` asserts that an entity is not matched.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::assert_matches;
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Health(u32);
+///
+/// #[derive(Component)]
+/// struct Poison(u32);
+///
+/// let world = World::new();
+/// let healthy = world.entity().set(Health(10));
+/// let poisoned = world.entity().set(Health(5)).set(Poison(1));
+///
+/// assert_matches!(world, &Health, count == 2);
+/// assert_matches!(world, (&Health, &Poison), count < 2);
+/// assert_matches!(world, (&Health, &Poison), contains poisoned);
+/// assert_matches!(world, (&Health, &Poison), !contains healthy);
+/// ```
+#[macro_export]
+macro_rules! assert_matches {
+    ($world:expr, $components:ty, count $op:tt $expected:expr $(,)?) => {{
+        let query = ($world).new_query::<$components>();
+        let count = $crate::core::QueryAPI::count(&query);
+        let expected = $expected;
+        assert!(
+            count $op expected,
+            "expected the number of entities matching `{}` to be {} {}, found {}",
+            ::core::stringify!($components),
+            ::core::stringify!($op),
+            expected,
+            count
+        );
+    }};
+    ($world:expr, $components:ty, contains $entity:expr $(,)?) => {{
+        let query = ($world).new_query::<$components>();
+        let entity: $crate::core::Entity = ($entity).into();
+        assert!(
+            $crate::core::QueryAPI::get_for(&query, entity, |_| {}),
+            "expected entity {} to match `{}`",
+            entity,
+            ::core::stringify!($components)
+        );
+    }};
+    ($world:expr, $components:ty, !contains $entity:expr $(,)?) => {{
+        let query = ($world).new_query::<$components>();
+        let entity: $crate::core::Entity = ($entity).into();
+        assert!(
+            !$crate::core::QueryAPI::get_for(&query, entity, |_| {}),
+            "expected entity {} not to match `{}`",
+            entity,
+            ::core::stringify!($components)
+        );
+    }};
+}
@@ -1,5 +1,5 @@
 #![allow(dead_code)]
-use flecs_ecs::core::{ComponentInfo, EntityViewGet, World};
+use flecs_ecs::core::*;
 use flecs_ecs_derive::Component;
 
 // normal structs
@@ -70,3 +70,137 @@ fn copy_hook_not_implemented_for_drop_types() {
 
     let _entity_cloned = e_orig.duplicate(true); // PANICS
 }
+
+// plain old data, no drop glue
+#[derive(Component, Debug, PartialEq)]
+struct NoCloneNoDefaultPod {
+    x: i32,
+    y: i32,
+}
+
+#[test]
+fn set_no_clone_no_default() {
+    let world = World::new();
+    let e = world.entity().set(NoCloneNoDefaultPod { x: 1, y: 2 });
+
+    e.get::<&NoCloneNoDefaultPod>(|p| {
+        assert_eq!(p, &NoCloneNoDefaultPod { x: 1, y: 2 });
+    });
+
+    world.defer_begin();
+    e.set(NoCloneNoDefaultPod { x: 3, y: 4 });
+    world.defer_end();
+
+    e.get::<&NoCloneNoDefaultPod>(|p| {
+        assert_eq!(p, &NoCloneNoDefaultPod { x: 3, y: 4 });
+    });
+}
+
+#[test]
+fn copy_hook_falls_back_to_memcpy_for_pod_types() {
+    let world = World::new();
+    let e_orig = world.entity().set(NoCloneNoDefaultPod { x: 10, y: 20 });
+
+    let entity_cloned = e_orig.duplicate(true);
+
+    entity_cloned.get::<&NoCloneNoDefaultPod>(|p| {
+        assert_eq!(p, &NoCloneNoDefaultPod { x: 10, y: 20 });
+    });
+}
+
+#[test]
+fn prefab_instantiate_no_clone_no_default() {
+    let world = World::new();
+    world
+        .component::<NoCloneNoDefaultPod>()
+        .add_trait::<(flecs::OnInstantiate, flecs::Override)>();
+
+    let prefab = world.prefab().set(NoCloneNoDefaultPod { x: 5, y: 6 });
+    let inst = world.entity().is_a_id(prefab);
+
+    assert!(inst.owns::<NoCloneNoDefaultPod>());
+    inst.get::<&NoCloneNoDefaultPod>(|p| {
+        assert_eq!(p, &NoCloneNoDefaultPod { x: 5, y: 6 });
+    });
+}
+
+#[test]
+#[should_panic]
+fn add_no_clone_no_default_panics() {
+    let world = World::new();
+    world.entity().add::<NoCloneNoDefaultPod>();
+}
+
+#[derive(Component, Clone)]
+#[flecs(clone)]
+struct GenericCloneDrop<T: Send + Sync> {
+    data: Vec<T>,
+}
+
+#[test]
+fn clone_attribute_registers_copy_hook_for_generic_types() {
+    let world = World::new();
+    world
+        .component::<GenericCloneDrop<String>>()
+        .add_trait::<(flecs::OnInstantiate, flecs::Override)>();
+
+    let prefab = world.prefab().set(GenericCloneDrop {
+        data: vec!["data".to_string()],
+    });
+    let inst = world.entity().is_a_id(prefab);
+    let dup = inst.duplicate(true);
+
+    inst.get::<&mut GenericCloneDrop<String>>(|g| g.data.push("inst".to_string()));
+
+    prefab.get::<&GenericCloneDrop<String>>(|g| assert_eq!(g.data, ["data"]));
+    inst.get::<&GenericCloneDrop<String>>(|g| assert_eq!(g.data, ["data", "inst"]));
+    dup.get::<&GenericCloneDrop<String>>(|g| assert_eq!(g.data, ["data"]));
+}
+
+#[derive(Component, Clone, Copy, Default)]
+#[flecs(pod)]
+struct PodDefault {
+    value: u32,
+}
+
+#[test]
+fn pod_attribute_registers_no_hooks() {
+    let world = World::new();
+    let hooks = world.component::<PodDefault>().get_hooks();
+    assert!(hooks.ctor.is_none());
+    assert!(hooks.copy.is_none());
+    assert!(hooks.move_.is_none());
+    assert!(hooks.dtor.is_none());
+
+    world
+        .component::<PodDefault>()
+        .add_trait::<(flecs::OnInstantiate, flecs::Override)>();
+
+    let prefab = world.prefab().set(PodDefault { value: 5 });
+    let inst = world.entity().is_a_id(prefab);
+    let dup = inst.set(PodDefault { value: 6 }).duplicate(true);
+
+    prefab.get::<&PodDefault>(|p| assert_eq!(p.value, 5));
+    dup.get::<&PodDefault>(|p| assert_eq!(p.value, 6));
+}
+
+#[test]
+fn on_copy_hook_deep_copies_prefab() {
+    let world = World::new();
+    world
+        .component::<DefaultNoCloneDrop>()
+        .on_copy(|d| DefaultNoCloneDrop {
+            _data: format!("{} copy", d._data),
+        })
+        .add_trait::<(flecs::OnInstantiate, flecs::Override)>();
+
+    let prefab = world.prefab().set(DefaultNoCloneDrop {
+        _data: "data".to_string(),
+    });
+    let inst = world.entity().is_a_id(prefab);
+    let dup = inst.duplicate(true);
+
+    prefab.get::<&DefaultNoCloneDrop>(|d| assert_eq!(d._data, "data"));
+    inst.get::<&DefaultNoCloneDrop>(|d| assert_eq!(d._data, "data copy"));
+    dup.get::<&DefaultNoCloneDrop>(|d| assert_eq!(d._data, "data copy copy"));
+}
@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 use crate::common_test::*;
+use core::sync::atomic::{AtomicIsize, Ordering};
 
 #[test]
 fn component_lifecycle_count_in_add_hook() {
@@ -52,3 +53,180 @@ fn component_lifecycle_count_in_remove_hook() {
 
     assert_eq!(world.cloned::<&Count>().0, 0);
 }
+
+/// Counts the values that are alive, so that tests can check that every value that is created
+/// is dropped exactly once.
+struct Live(&'static AtomicIsize);
+
+impl Live {
+    fn new(count: &'static AtomicIsize) -> Self {
+        count.fetch_add(1, Ordering::SeqCst);
+        Self(count)
+    }
+}
+
+impl Clone for Live {
+    fn clone(&self) -> Self {
+        Self::new(self.0)
+    }
+}
+
+impl Drop for Live {
+    fn drop(&mut self) {
+        self.0.fetch_sub(1, Ordering::SeqCst);
+    }
+}
+
+#[derive(Component, Clone)]
+struct Tracked {
+    name: String,
+    items: Vec<u32>,
+    boxed: Box<u32>,
+    live: Live,
+}
+
+impl Tracked {
+    fn new(live: &'static AtomicIsize, value: u32) -> Self {
+        Self {
+            name: format!("tracked {value}"),
+            items: vec![value; 4],
+            boxed: Box::new(value),
+            live: Live::new(live),
+        }
+    }
+}
+
+fn live_count(live: &AtomicIsize) -> isize {
+    live.load(Ordering::SeqCst)
+}
+
+#[test]
+fn component_lifecycle_drop_on_delete() {
+    static LIVE: AtomicIsize = AtomicIsize::new(0);
+    let world = World::new();
+
+    let entities: Vec<_> = (0..5)
+        .map(|i| world.entity().set(Tracked::new(&LIVE, i)).id())
+        .collect();
+    assert_eq!(live_count(&LIVE), 5);
+
+    // delete rows in the middle, at the start and at the end of the table
+    world.entity_from_id(entities[2]).destruct();
+    assert_eq!(live_count(&LIVE), 4);
+    world.entity_from_id(entities[0]).destruct();
+    assert_eq!(live_count(&LIVE), 3);
+    world.entity_from_id(entities[4]).destruct();
+    assert_eq!(live_count(&LIVE), 2);
+
+    world
+        .entity_from_id(entities[1])
+        .get::<&Tracked>(|t| assert_eq!((t.name.as_str(), *t.boxed), ("tracked 1", 1)));
+    world
+        .entity_from_id(entities[3])
+        .get::<&Tracked>(|t| assert_eq!(t.items, [3; 4]));
+
+    drop(world);
+    assert_eq!(live_count(&LIVE), 0);
+}
+
+#[test]
+fn component_lifecycle_drop_on_remove() {
+    static LIVE: AtomicIsize = AtomicIsize::new(0);
+    let world = World::new();
+
+    let entities: Vec<_> = (0..5)
+        .map(|i| {
+            world
+                .entity()
+                .set(Tracked::new(&LIVE, i))
+                .set(Position { x: i as i32, y: 0 })
+                .id()
+        })
+        .collect();
+
+    // removing a component from a row that isn't the last moves the last row into it
+    world.entity_from_id(entities[1]).remove::<Tracked>();
+    assert_eq!(live_count(&LIVE), 4);
+
+    // moving to another table doesn't drop or duplicate the value
+    let moved = world.entity_from_id(entities[2]).add::<TagA>();
+    assert_eq!(live_count(&LIVE), 4);
+    moved.get::<&Tracked>(|t| assert_eq!(t.name, "tracked 2"));
+    moved.remove::<Position>();
+    assert_eq!(live_count(&LIVE), 4);
+
+    // overwriting drops the previous value
+    let set = world
+        .entity_from_id(entities[3])
+        .set(Tracked::new(&LIVE, 30));
+    assert_eq!(live_count(&LIVE), 4);
+    set.get::<&Tracked>(|t| assert_eq!(*t.boxed, 30));
+
+    world.entity_from_id(entities[0]).clear();
+    assert_eq!(live_count(&LIVE), 3);
+
+    world.defer(|world| {
+        world.entity_from_id(entities[4]).remove::<Tracked>();
+        world
+            .entity_from_id(entities[3])
+            .set(Tracked::new(&LIVE, 31));
+    });
+    assert_eq!(live_count(&LIVE), 2);
+
+    drop(world);
+    assert_eq!(live_count(&LIVE), 0);
+}
+
+#[test]
+fn component_lifecycle_drop_sparse() {
+    static LIVE: AtomicIsize = AtomicIsize::new(0);
+    let world = World::new();
+    world.component::<Tracked>().add_trait::<flecs::Sparse>();
+
+    let entities: Vec<_> = (0..4)
+        .map(|i| world.entity().set(Tracked::new(&LIVE, i)).id())
+        .collect();
+
+    world.entity_from_id(entities[0]).remove::<Tracked>();
+    world.entity_from_id(entities[1]).destruct();
+    world
+        .entity_from_id(entities[2])
+        .set(Tracked::new(&LIVE, 20));
+    assert_eq!(live_count(&LIVE), 2);
+
+    world
+        .entity_from_id(entities[2])
+        .get::<&Tracked>(|t| assert_eq!(t.name, "tracked 20"));
+
+    drop(world);
+    assert_eq!(live_count(&LIVE), 0);
+}
+
+#[test]
+fn component_lifecycle_drop_copies() {
+    static LIVE: AtomicIsize = AtomicIsize::new(0);
+    let world = World::new();
+    world
+        .component::<Tracked>()
+        .add_trait::<(flecs::OnInstantiate, flecs::Override)>();
+
+    let prefab = world.prefab().set(Tracked::new(&LIVE, 1));
+    let instances: Vec<_> = (0..3).map(|_| world.entity().is_a_id(prefab)).collect();
+    assert_eq!(live_count(&LIVE), 4);
+
+    instances[1].set(Tracked::new(&LIVE, 2));
+    assert_eq!(live_count(&LIVE), 4);
+
+    // duplicates copy the value of the entity, not the value of its prefab
+    let copy = instances[1].duplicate(true);
+    assert_eq!(live_count(&LIVE), 5);
+    copy.get::<&Tracked>(|t| assert_eq!(t.items, [2; 4]));
+    instances[2].get::<&Tracked>(|t| assert_eq!(t.items, [1; 4]));
+
+    instances[0].destruct();
+    prefab.destruct();
+    assert_eq!(live_count(&LIVE), 3);
+
+    drop(world);
+    assert_eq!(live_count(&LIVE), 0);
+}
@@ -1,5 +1,6 @@
 #![allow(dead_code)]
 use crate::common_test::*;
+use core::alloc::Layout;
 
 #[test]
 fn temp_test_hook() {
@@ -215,3 +216,454 @@ fn on_component_registration_named() {
         assert_eq!(count.0, 2);
     });
 }
+
+#[test]
+fn component_lookup_by_name() {
+    let world = World::new();
+
+    let position = world.component_named::<Position>("Position");
+    world.entity_named("NotAComponent");
+
+    assert_eq!(world.lookup_component("Position").id(), position.id());
+    assert!(world.try_lookup_component("NotAComponent").is_none());
+    assert!(world.try_lookup_component("DoesNotExist").is_none());
+
+    let e = world.entity().add_id(world.lookup_component("Position"));
+    assert!(e.has::<Position>());
+
+    let e = world.entity().add_by_name("Position").unwrap();
+    assert!(e.has::<Position>());
+    assert_eq!(
+        e.add_by_name("NotAComponent"),
+        Err(FlecsError::Unresolved("NotAComponent".into()))
+    );
+}
+
+#[test]
+fn component_sparse_on_registration() {
+    #[derive(Component)]
+    #[on_registration]
+    struct Label {
+        text: String,
+    }
+
+    impl OnComponentRegistration for Label {
+        fn on_component_registration(world: WorldRef, component_id: Entity) {
+            world
+                .component_untyped_from_id(component_id)
+                .add_trait::<flecs::Sparse>();
+        }
+    }
+
+    let world = World::new();
+
+    let e1 = world.entity().set(Label {
+        text: "foo".to_string(),
+    });
+    let e2 = world.entity().set(Position { x: 10, y: 20 }).set(Label {
+        text: "bar".to_string(),
+    });
+
+    assert!(world.component::<Label>().has::<flecs::Sparse>());
+
+    // moving the entity to another table keeps the value
+    e1.add::<TagA>();
+    e1.get::<&Label>(|l| assert_eq!(l.text, "foo"));
+
+    e2.set(Label {
+        text: "baz".to_string(),
+    });
+    e2.get::<&mut Label>(|l| l.text.push('!'));
+
+    let mut texts = Vec::new();
+    world
+        .query::<&Label>()
+        .build()
+        .each(|l| texts.push(l.text.clone()));
+    texts.sort();
+    assert_eq!(texts, ["baz!", "foo"]);
+
+    e1.remove::<Label>();
+    assert!(!e1.has::<Label>());
+    assert_eq!(world.query::<&Label>().build().count(), 1);
+}
+
+#[test]
+fn component_registered_by_symbol_elsewhere() {
+    use flecs_ecs::sys;
+
+    #[derive(Component)]
+    struct Health {
+        value: i32,
+    }
+
+    #[derive(Component)]
+    struct Stamina {
+        value: i32,
+    }
+
+    let world = World::new();
+
+    // Register the components the way another crate or dynamic library would,
+    // which has its own component index but uses the same type names as symbol.
+    let register_elsewhere = |name: &str, symbol: &str| {
+        let name = std::ffi::CString::new(name).unwrap();
+        let symbol = std::ffi::CString::new(symbol).unwrap();
+        unsafe {
+            let entity_desc = sys::ecs_entity_desc_t {
+                name: name.as_ptr(),
+                symbol: symbol.as_ptr(),
+                sep: c"::".as_ptr(),
+                use_low_id: true,
+                ..Default::default()
+            };
+            let entity = sys::ecs_entity_init(world.ptr_mut(), &entity_desc);
+            let component_desc = sys::ecs_component_desc_t {
+                _canary: 0,
+                entity,
+                type_: sys::ecs_type_info_t {
+                    size: core::mem::size_of::<i32>() as i32,
+                    alignment: core::mem::align_of::<i32>() as i32,
+                    hooks: Default::default(),
+                    component: 0,
+                    name: core::ptr::null(),
+                },
+            };
+            Entity::new(sys::ecs_component_init(world.ptr_mut(), &component_desc))
+        }
+    };
+
+    let health = register_elsewhere("plugin::Health", core::any::type_name::<Health>());
+    let stamina = register_elsewhere("plugin::Stamina", core::any::type_name::<Stamina>());
+
+    assert_eq!(world.component::<Health>().id(), health);
+    assert_eq!(
+        world.component_named::<Stamina>("game::Stamina").id(),
+        stamina
+    );
+    assert_eq!(
+        world.component::<Stamina>().path().unwrap(),
+        "::plugin::Stamina"
+    );
+
+    let e = world
+        .entity()
+        .set(Health { value: 10 })
+        .set(Stamina { value: 5 });
+    assert!(e.has_id(health));
+    assert!(e.has_id(stamina));
+}
+
+#[test]
+fn component_zero_sized_is_tag() {
+    #[derive(Component)]
+    struct Marker;
+
+    #[derive(Component)]
+    struct Empty {}
+
+    let world = World::new();
+
+    assert!(world.component::<Marker>().is_tag());
+    assert!(world.component::<Empty>().is_tag());
+    assert_eq!(world.component::<Marker>().size(), 0);
+
+    assert!(!world.component::<Position>().is_tag());
+    assert_eq!(
+        world.component::<Position>().size(),
+        core::mem::size_of::<Position>()
+    );
+
+    // tags don't add a column to the table
+    let e = world
+        .entity()
+        .add::<Marker>()
+        .add::<Empty>()
+        .set(Position { x: 1, y: 2 });
+    let table = e.table().unwrap();
+    assert!(table.find_column_index::<Marker>().is_none());
+    assert!(table.find_column_index::<Empty>().is_none());
+    assert!(table.find_column_index::<Position>().is_some());
+}
+
+#[test]
+fn component_register_components() {
+    let world = World::new();
+    let position = world.component::<Position>().id();
+
+    world.register_components::<(Position, Velocity, Mass, TagA)>();
+
+    assert!(Velocity::is_registered_with_world(&world));
+    assert!(Mass::is_registered_with_world(&world));
+    assert!(TagA::is_registered_with_world(&world));
+    assert_eq!(world.component::<Position>().id(), position);
+
+    let velocity = *world.component::<Velocity>().id();
+    assert_eq!(*world.component::<Mass>().id(), velocity + 1);
+    assert_eq!(*world.component::<TagA>().id(), velocity + 2);
+}
+
+#[test]
+fn component_register_for_each_type() {
+    #[derive(Component)]
+    struct Changed(u32);
+
+    fn count_sets<T: ComponentId>(world: &World) {
+        world.observer::<flecs::OnSet, &T>().each_entity(|e, _| {
+            if e.has::<Changed>() {
+                e.get::<&mut Changed>(|c| c.0 += 1);
+            } else {
+                e.set(Changed(1));
+            }
+        });
+    }
+
+    let world = World::new();
+    flecs_ecs::register_for_each_type!(world, count_sets::<T>, [Position, Velocity]);
+    flecs_ecs::register_for_each_type!(&world, count_sets, [Mass,]);
+
+    let e = world
+        .entity()
+        .set(Position { x: 1, y: 2 })
+        .set(Velocity { x: 1, y: 2 })
+        .set(Mass { value: 1 });
+    e.get::<&Changed>(|c| assert_eq!(c.0, 3));
+}
+
+#[test]
+fn component_on_reflected_component() {
+    #[derive(Component, Default)]
+    struct Seen(Vec<Entity>);
+
+    let world = World::new();
+    world.set(Seen::default());
+    world.component::<Position>().member::<i32>("x");
+
+    let observer = world.on_reflected_component(|component| {
+        let id = component.id();
+        component.world().get::<&mut Seen>(|seen| seen.0.push(id));
+    });
+
+    let health = world
+        .component_untyped_named("Health")
+        .member::<f32>("value")
+        .id();
+    world.component::<Velocity>();
+
+    world.get::<&Seen>(|seen| {
+        assert!(seen.0.contains(&health));
+        assert!(!seen.0.contains(&world.component::<Position>().id()));
+        assert!(!seen.0.contains(&world.component::<Velocity>().id()));
+    });
+
+    observer.destruct();
+    let armor = world
+        .component_untyped_named("Armor")
+        .member::<f32>("value")
+        .id();
+    world.get::<&Seen>(|seen| assert!(!seen.0.contains(&armor)));
+}
+
+#[test]
+fn component_id_per_world() {
+    let world1 = World::new();
+    let world2 = World::new();
+
+    // register in a different order, so the ids differ between the worlds
+    let pos1 = world1.component::<Position>().id();
+    let vel2 = world2.component::<Velocity>().id();
+    let vel1 = world1.component::<Velocity>().id();
+    let pos2 = world2.component::<Position>().id();
+
+    assert_ne!(pos1, pos2);
+    assert_eq!(Position::id(&world1), *pos1);
+    assert_eq!(Position::id(&world2), *pos2);
+    assert_eq!(Velocity::id(&world1), *vel1);
+    assert_eq!(Velocity::id(&world2), *vel2);
+
+    // generic components are looked up by type id instead
+    let int = world1.component::<Template<i32>>().id();
+    let uint = world1.component::<Template<u32>>().id();
+    assert_ne!(int, uint);
+    assert_eq!(Template::<i32>::id(&world1), *int);
+    assert!(!Template::<i32>::is_registered_with_world(&world2));
+}
+
+#[test]
+fn component_over_aligned() {
+    #[derive(Component, Default, Clone, Copy)]
+    #[repr(align(64))]
+    struct Lanes([f32; 4]);
+
+    #[derive(Component, Default, Clone, Copy)]
+    #[repr(align(128))]
+    struct Padded(u8);
+
+    let world = World::new();
+    world.component::<Padded>().add_trait::<flecs::Sparse>();
+
+    assert_eq!(world.component::<Lanes>().layout(), Layout::new::<Lanes>());
+    assert_eq!(world.component::<Padded>().layout().align(), 128);
+    assert_eq!(world.component::<TagA>().layout(), Layout::new::<()>());
+
+    for i in 0..100 {
+        let e = world.entity().set(Lanes([i as f32; 4])).set(Padded(1));
+        if i % 3 == 0 {
+            e.add::<TagA>();
+        }
+    }
+    world.defer(|world| {
+        for i in 0..100 {
+            world.entity().set(Lanes([i as f32; 4])).set(Padded(2));
+        }
+    });
+
+    let mut count = 0;
+    world.each::<(&Lanes, &Padded)>(|(lanes, padded)| {
+        assert_eq!(lanes as *const Lanes as usize % 64, 0);
+        assert_eq!(padded as *const Padded as usize % 128, 0);
+        count += 1;
+    });
+    assert_eq!(count, 200);
+}
+
+#[test]
+#[should_panic(expected = "components can be aligned to at most")]
+fn component_over_aligned_unsupported() {
+    #[derive(Component)]
+    #[repr(align(256))]
+    struct TooAligned(u8);
+
+    let world = World::new();
+    world.component::<TooAligned>();
+}
+
+#[test]
+fn component_external() {
+    #[derive(Debug, PartialEq)]
+    struct Body {
+        velocity: i32,
+    }
+
+    std::thread_local! {
+        static BODIES: core::cell::UnsafeCell<Vec<Body>> = const { core::cell::UnsafeCell::new(Vec::new()) };
+    }
+
+    fn bodies() -> *mut Vec<Body> {
+        BODIES.with(core::cell::UnsafeCell::get)
+    }
+
+    fn body(index: usize) -> *mut Body {
+        unsafe { (*bodies()).as_mut_ptr().add(index) }
+    }
+
+    let world = World::new();
+
+    let mut entities = Vec::new();
+    for i in 0..2 {
+        unsafe { (*bodies()).push(Body { velocity: i }) };
+        entities.push(
+            world
+                .entity()
+                .set(unsafe { External::new(i as usize, body) }),
+        );
+    }
+    entities[0].add::<TagA>();
+
+    // reallocate the external storage, the keys still resolve
+    unsafe { (*bodies()).reserve(1000) };
+
+    world
+        .new_query::<&mut External<Body>>()
+        .each(|body| body.velocity += 10);
+
+    entities[1].get::<&External<Body>>(|body| {
+        assert_eq!(body.key(), 1);
+        assert_eq!(**body, Body { velocity: 11 });
+    });
+
+    entities[0].remove::<External<Body>>();
+    assert_eq!(
+        unsafe { &*bodies() },
+        &[Body { velocity: 10 }, Body { velocity: 11 }]
+    );
+}
+
+#[test]
+fn component_external_accessor() {
+    std::thread_local! {
+        static HEALTH: core::cell::RefCell<Vec<(u8, u8)>> = const { core::cell::RefCell::new(Vec::new()) };
+    }
+
+    // the external storage packs the health as (current, max), flecs sees it as a percentage
+    fn get(index: usize) -> f32 {
+        HEALTH.with_borrow(|health| {
+            let (current, max) = health[index];
+            current as f32 / max as f32
+        })
+    }
+
+    fn set(index: usize, percentage: f32) {
+        HEALTH.with_borrow_mut(|health| {
+            let (current, max) = &mut health[index];
+            *current = (percentage * *max as f32) as u8;
+        });
+    }
+
+    HEALTH.with_borrow_mut(|health| health.extend([(50, 100), (10, 10)]));
+
+    let world = World::new();
+    let a = world.entity().set(ExternalAccessor::new(0, get, set));
+    let b = world.entity().set(ExternalAccessor::new(1, get, set));
+
+    world
+        .new_query::<&mut ExternalAccessor<f32>>()
+        .each(|health| health.update(|h| *h /= 2.0));
+
+    a.get::<&ExternalAccessor<f32>>(|health| {
+        assert_eq!(health.key(), 0);
+        assert_eq!((health.get() * 100.0) as i32, 25);
+    });
+    b.get::<&mut ExternalAccessor<f32>>(|health| health.set(1.0));
+    HEALTH.with_borrow(|health| assert_eq!(*health, [(25, 100), (10, 10)]));
+
+    // changes made by the external storage are seen on the next access
+    HEALTH.with_borrow_mut(|health| health[0].0 = 100);
+    a.get::<&ExternalAccessor<f32>>(|health| assert_eq!((health.get() * 100.0) as i32, 100));
+}
+
+#[test]
+fn component_add_with() {
+    let world = World::new();
+
+    world.component::<Position>().add_with::<Velocity>();
+    world.component::<TagA>().add_with::<TagB>();
+    world.component::<Likes>().add_with::<Eats>();
+
+    let e = world.entity().set(Position { x: 1, y: 2 }).add::<TagA>();
+    assert!(e.has::<Velocity>());
+    assert!(e.has::<TagB>());
+    e.get::<&Position>(|pos| assert_eq!(pos.x, 1));
+
+    // relationships add the pair with the same target
+    let e = world.entity().add::<(Likes, Apples)>();
+    assert!(e.has::<(Eats, Apples)>());
+
+    let mass = world.component::<Mass>();
+    world.component::<TagC>().add_with_id(mass);
+    assert!(world.entity().add::<TagC>().has::<Mass>());
+}
+
+#[test]
+fn component_sparse() {
+    let world = World::new();
+    world.component::<Position>().sparse();
+    assert!(world.component::<Position>().has::<flecs::Sparse>());
+
+    let e = world.entity().set(Position { x: 1, y: 2 });
+    let before = e.get::<&Position>(|p| p as *const Position);
+    e.add::<TagA>();
+    let after = e.get::<&Position>(|p| p as *const Position);
+    assert_eq!(before, after);
+    e.get::<&Position>(|p| assert_eq!((p.x, p.y), (1, 2)));
+}
@@ -210,6 +210,20 @@ fn bulk_entity_builder_add_and_set() {
     }
 }
 
+#[test]
+#[should_panic]
+fn bulk_entity_builder_add_id_missing_default_drop() {
+    let world = World::new();
+
+    #[derive(Component)]
+    struct NonDefaultDrop {
+        value: String,
+    }
+
+    let id = world.component_id::<NonDefaultDrop>();
+    world.entity_bulk(10).add_id(id).build();
+}
+
 #[test]
 #[should_panic]
 fn bulk_entity_builder_build_to_table_missing_default() {
@@ -1,6 +1,7 @@
 #![allow(dead_code)]
 
 use crate::common_test::*;
+use flecs_ecs::addons::state_machine::StateMachine;
 
 #[test]
 fn count_target_ids() {
@@ -29,6 +30,120 @@ fn count_target_ids() {
     assert_eq!(e2.target_id_count(r).unwrap(), 1);
 }
 
+#[test]
+fn entity_target_for_rel() {
+    let world = World::new();
+    world
+        .component::<Position>()
+        .add_trait::<(flecs::OnInstantiate, flecs::Inherit)>();
+
+    let base = world.prefab().set(Position { x: 1, y: 2 });
+    let inst = world.entity().is_a_id(base);
+    let parent = world.entity().set(Mass { value: 10 });
+    let child = world
+        .entity()
+        .child_of_id(parent)
+        .set(Velocity { x: 3, y: 4 });
+
+    assert_eq!(inst.target_for_rel::<flecs::IsA, Position>().unwrap(), base);
+    assert_eq!(
+        child.target_for_rel::<flecs::ChildOf, Mass>().unwrap(),
+        parent
+    );
+    // owned components resolve to the entity itself
+    assert_eq!(
+        child.target_for_rel::<flecs::ChildOf, Velocity>().unwrap(),
+        child
+    );
+    assert!(inst.target_for_rel::<flecs::IsA, Velocity>().is_none());
+}
+
+#[test]
+fn entity_on_delete_policy() {
+    let world = World::new();
+
+    let tag_remove = world.entity().on_delete(flecs::Remove);
+    let tag_delete = world.entity().on_delete(flecs::Delete);
+    world.component::<TagA>().on_delete(flecs::Delete);
+
+    assert!(tag_delete.has_id((flecs::OnDelete::ID, flecs::Delete::ID)));
+
+    let e_1 = world.entity().add_id(tag_remove);
+    let e_2 = world.entity().add_id(tag_delete);
+    let e_3 = world.entity().add::<TagA>();
+
+    tag_remove.destruct();
+    tag_delete.destruct();
+    world.component::<TagA>().destruct();
+
+    assert!(e_1.is_alive());
+    assert!(!e_2.is_alive());
+    assert!(!e_3.is_alive());
+}
+
+#[test]
+fn entity_on_delete_target_policy() {
+    let world = World::new();
+
+    world.component::<Rel>().on_delete_target(flecs::Delete);
+    let rel_remove = world.entity().on_delete_target(flecs::Remove);
+
+    assert!(
+        world
+            .component::<Rel>()
+            .has_id((flecs::OnDeleteTarget::ID, flecs::Delete::ID))
+    );
+
+    let parent = world.entity();
+    let e_1 = world.entity().add_first::<Rel>(parent);
+    let e_2 = world.entity().add_id((rel_remove, parent));
+
+    parent.destruct();
+
+    assert!(!e_1.is_alive());
+    assert!(e_2.is_alive());
+    assert!(!e_2.has_id((rel_remove, flecs::Wildcard::ID)));
+}
+
+#[test]
+fn entity_index_generation() {
+    let world = World::new();
+
+    let e = world.entity();
+    assert_eq!(e.generation(), 0);
+    assert_eq!(e.index() as u64, *e.id());
+
+    let old = e.id();
+    e.destruct();
+
+    let recycled = world.entity();
+    assert_eq!(recycled.index(), old.index());
+    assert_eq!(recycled.generation(), old.generation() + 1);
+    assert_ne!(recycled.id(), old);
+
+    assert!(!world.is_alive(old));
+    assert!(world.is_alive(recycled));
+    assert!(world.exists(old));
+    assert_eq!(world.get_alive(old.index() as u64), recycled);
+}
+
+#[test]
+fn entity_id_display() {
+    let world = World::new();
+
+    let e = world.entity();
+    let index = e.id().index();
+    assert_eq!(EntityId::from(e).to_string(), format!("0:{index}"));
+    e.destruct();
+
+    let recycled = EntityId::from(world.entity().id());
+    assert_eq!(recycled, EntityId::new(index, 1));
+    assert_eq!(recycled.to_string(), format!("1:{index}"));
+    assert_eq!(format!("{recycled:?}"), format!("EntityId(1:{index})"));
+    assert!(world.is_alive(recycled));
+    assert_eq!(Entity::from(recycled), recycled.entity());
+}
+
 #[test]
 fn entity_id_reuse() {
     let world = World::new();
@@ -46,3 +161,691 @@ fn entity_id_reuse() {
     );
     assert_eq!(b.archetype().to_string(), first_archetype);
 }
+
+#[test]
+fn entity_emplace_no_default_no_clone() {
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static DROPS: AtomicUsize = AtomicUsize::new(0);
+
+    #[derive(Component)]
+    struct Big {
+        data: Vec<u32>,
+    }
+
+    impl Drop for Big {
+        fn drop(&mut self) {
+            DROPS.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let world = World::new();
+
+    let e = world.entity().emplace(Big { data: vec![1, 2] });
+    e.get::<&Big>(|big| assert_eq!(big.data, [1, 2]));
+    assert_eq!(DROPS.load(Ordering::Relaxed), 0);
+
+    // replacing an existing value drops the old one
+    e.emplace(Big { data: vec![3] });
+    e.get::<&Big>(|big| assert_eq!(big.data, [3]));
+    assert_eq!(DROPS.load(Ordering::Relaxed), 1);
+
+    world.defer_begin();
+    let e2 = world.entity().emplace(Big {
+        data: vec![4, 5, 6],
+    });
+    e.emplace(Big { data: vec![7] });
+    world.defer_end();
+
+    e2.get::<&Big>(|big| assert_eq!(big.data, [4, 5, 6]));
+    e.get::<&Big>(|big| assert_eq!(big.data, [7]));
+    assert_eq!(DROPS.load(Ordering::Relaxed), 2);
+
+    e2.destruct();
+    assert_eq!(DROPS.load(Ordering::Relaxed), 3);
+    e.destruct();
+    assert_eq!(DROPS.load(Ordering::Relaxed), 4);
+}
+
+#[test]
+fn entity_archetype_inspection() {
+    let world = World::new();
+
+    let apples = world.entity();
+    let e = world
+        .entity()
+        .set(Position { x: 10, y: 20 })
+        .add::<TagA>()
+        .add_first::<Eats>(apples);
+
+    let archetype = e.archetype();
+    assert_eq!(archetype.count(), 3);
+    assert_eq!(archetype.iter_ids().count(), 3);
+    assert_eq!(archetype[0], archetype.get(0).unwrap().id());
+
+    assert!(archetype.contains::<Position>());
+    assert!(archetype.contains::<TagA>());
+    assert!(!archetype.contains::<Velocity>());
+    assert!(archetype.contains::<(Eats, flecs::Wildcard)>());
+    assert!(archetype.contains_pair(world.component_id::<Eats>(), apples));
+    assert!(!archetype.contains_pair(world.component_id::<Eats>(), e));
+    assert!(archetype.contains_id(world.component_id::<Position>()));
+
+    let table_archetype = e.table().unwrap().archetype();
+    assert_eq!(table_archetype.as_slice(), archetype.as_slice());
+}
+
+#[test]
+fn entity_get_ref_after_table_move() {
+    let world = World::new();
+
+    let e = world.entity().set(Position { x: 10, y: 20 });
+    let mut pos = e.get_ref::<Position>();
+    assert_eq!(pos.entity(), e);
+    assert_eq!(pos.component(), world.component_id::<Position>());
+
+    e.set(Velocity { x: 1, y: 2 });
+    e.add::<TagA>();
+    pos.get(|p| {
+        assert_eq!(p.x, 10);
+        p.x = 30;
+    });
+    e.get::<&Position>(|p| assert_eq!(p.x, 30));
+
+    e.remove::<TagA>();
+    assert!(pos.has());
+    assert_eq!(pos.try_get(|p| p.y), Some(20));
+
+    e.remove::<Position>();
+    assert!(!pos.has());
+    assert_eq!(pos.try_get(|p| p.y), None);
+}
+
+#[test]
+fn entity_id_flags_and_pairs() {
+    let world = World::new();
+    world
+        .component::<Position>()
+        .add_trait::<flecs::CanToggle>();
+
+    let likes = world.entity();
+    let bob = world.entity();
+    let position = world.component_id::<Position>();
+
+    let pair = Id::pair(likes, bob);
+    assert!(pair.is_pair());
+    assert_eq!(pair.first(), likes);
+    assert_eq!(pair.second(), bob);
+    assert_eq!(pair.flags(), flecs::id_flags::Pair::ID);
+    assert_eq!(pair, world.id_from_id((likes, bob)));
+    assert!(!pair.is_wildcard());
+    assert!(Id::pair(likes, flecs::Wildcard::ID).is_wildcard());
+
+    let override_id = Id::with_override(position);
+    assert!(override_id.is_auto_override());
+    assert!(!override_id.is_pair());
+    assert!(override_id.has_flags(flecs::id_flags::AutoOverride::ID));
+    assert_eq!(override_id.remove_flags(), position);
+
+    let toggle_id = Id::toggle(position);
+    assert!(toggle_id.is_toggle());
+    assert!(!toggle_id.is_auto_override());
+    assert_eq!(toggle_id.remove_flags(), position);
+    assert_eq!(Id::from(position).flags(), 0);
+
+    let base = world.prefab().set(Position { x: 1, y: 2 });
+    base.add_id(override_id);
+    let inst = world.entity().is_a_id(base);
+    assert!(inst.owns::<Position>());
+
+    let e = world
+        .entity()
+        .set(Position { x: 1, y: 2 })
+        .add_id(toggle_id);
+    assert!(e.has_id(toggle_id));
+    e.disable::<Position>();
+    assert!(!e.is_enabled::<Position>());
+}
+
+#[test]
+fn entity_builtin_constants_as_ids() {
+    let world = World::new();
+
+    let likes = world.entity();
+    let apples = world.entity();
+    let bob = world.entity().add_id((likes, apples)).add_id(flecs::Prefab);
+
+    assert!(bob.has_id(flecs::Prefab));
+    assert!(bob.has_id((likes, flecs::Wildcard)));
+    assert!(bob.has_id((likes, flecs::Any)));
+    assert!(Id::pair(likes, flecs::Wildcard).is_wildcard());
+    assert_eq!(Id::from(flecs::Wildcard), flecs::Wildcard::ID);
+
+    let override_id = world
+        .id_from_id(likes)
+        .add_flags(flecs::id_flags::AutoOverride);
+    assert_eq!(override_id, Id::with_override(likes));
+    assert!(Id::with_override(likes).has_flags(flecs::id_flags::AutoOverride));
+
+    let query = world
+        .query::<()>()
+        .with_id((likes, flecs::Wildcard))
+        .with_id(flecs::Prefab)
+        .with::<flecs::PredEq>()
+        .set_second_id(bob)
+        .build();
+    assert_eq!(query.count(), 1);
+
+    let query = world
+        .query::<()>()
+        .with_id((likes, flecs::Any))
+        .with_id(flecs::Prefab)
+        .with::<flecs::PredMatch>()
+        .set_second_name("bo")
+        .second()
+        .flags(flecs::IsName)
+        .build();
+    assert_eq!(query.count(), 0);
+    bob.set_name("bob");
+    assert_eq!(query.count(), 1);
+}
+
+#[test]
+fn entity_debug_reflected_values() {
+    #[derive(Component)]
+    #[meta]
+    struct Health {
+        value: i32,
+    }
+
+    let world = World::new();
+    world.component::<Health>().meta();
+
+    let parent = world.entity_named("parent");
+    let e = world
+        .entity_named("player")
+        .child_of_id(parent)
+        .set(Health { value: 42 })
+        .set(Position { x: 1, y: 2 })
+        .add::<TagA>();
+
+    let debug = format!("{e:?}");
+    assert!(debug.contains("\"::parent::player\""));
+    assert!(debug.contains("Health"));
+    assert!(debug.contains("= {\"value\":42}"));
+    // components without reflection data are listed without value
+    assert!(debug.contains("Position"));
+    assert!(!debug.contains("\"x\""));
+    assert!(debug.contains("TagA"));
+}
+
+struct Moving;
+
+flecs_ecs::entity_bundle!(Moving: Position, Velocity, TagA);
+
+fn step(moving: TypedEntity<Moving>) {
+    let velocity = moving.get::<Velocity>(|v| (v.x, v.y));
+    moving.get_mut::<Position>(|p| {
+        p.x += velocity.0;
+        p.y += velocity.1;
+    });
+}
+
+#[test]
+fn entity_typed_handle() {
+    let world = World::new();
+
+    let e = world
+        .entity()
+        .set(Position { x: 1, y: 2 })
+        .set(Velocity { x: 3, y: 4 });
+    assert!(e.try_typed::<Moving>().is_none());
+
+    e.add::<TagA>();
+    let moving = e.typed::<Moving>();
+    assert_eq!(moving.id(), e.id());
+
+    step(moving);
+    step(moving);
+    e.get::<&Position>(|p| assert_eq!((p.x, p.y), (7, 10)));
+
+    e.destruct();
+    assert!(!moving.is_alive());
+    assert!(e.try_typed::<Moving>().is_none());
+
+    // a recycled id with the same components is a different generation
+    let recycled = world
+        .entity()
+        .set(Position { x: 0, y: 0 })
+        .set(Velocity { x: 0, y: 0 })
+        .add::<TagA>();
+    assert_eq!(recycled.index(), e.index());
+    assert!(recycled.try_typed::<Moving>().is_some());
+    assert!(!moving.is_alive());
+}
+
+#[test]
+#[should_panic]
+fn entity_typed_handle_missing_component() {
+    let world = World::new();
+    world
+        .entity()
+        .set(Position { x: 1, y: 2 })
+        .typed::<Moving>();
+}
+
+#[derive(Component, Default)]
+struct Inventory(Vec<u32>);
+
+#[derive(Component)]
+struct Name(String);
+
+#[derive(Bundle)]
+struct MovingBundle {
+    position: Position,
+    velocity: Velocity,
+    inventory: Inventory,
+    tag: TagA,
+}
+
+#[derive(Bundle)]
+struct NamedBundle(Position, Name);
+
+#[test]
+fn entity_spawn_bundle() {
+    let world = World::new();
+
+    // the entity is already in its final table when the first component is added
+    let observer = world
+        .observer::<flecs::OnAdd, ()>()
+        .with::<Position>()
+        .each_entity(|e, _| {
+            assert!(e.has::<Velocity>());
+            assert!(e.has::<Inventory>());
+            assert!(e.has::<TagA>());
+        });
+
+    let e = world.spawn(MovingBundle {
+        position: Position { x: 1, y: 2 },
+        velocity: Velocity { x: 3, y: 4 },
+        inventory: Inventory(vec![1, 2, 3]),
+        tag: TagA {},
+    });
+
+    e.get::<(&Position, &Velocity, &Inventory)>(|(p, v, inventory)| {
+        assert_eq!((p.x, p.y), (1, 2));
+        assert_eq!((v.x, v.y), (3, 4));
+        assert_eq!(inventory.0, [1, 2, 3]);
+    });
+    assert!(e.has::<TagA>());
+
+    observer.entity().destruct();
+
+    let moving = e.typed::<MovingBundle>();
+    assert_eq!(moving.get::<Inventory>(|inventory| inventory.0.len()), 3);
+
+    // inserting into an entity with some of the components replaces them
+    let e2 = world.entity().set(Inventory(vec![4]));
+    e2.insert(MovingBundle {
+        position: Position { x: 5, y: 6 },
+        velocity: Velocity { x: 7, y: 8 },
+        inventory: Inventory(vec![9]),
+        tag: TagA {},
+    });
+    e2.get::<(&Position, &Inventory)>(|(p, inventory)| {
+        assert_eq!((p.x, p.y), (5, 6));
+        assert_eq!(inventory.0, [9]);
+    });
+
+    // components without Default are added separately
+    let e3 = world.spawn(NamedBundle(Position { x: 1, y: 1 }, Name("e3".to_string())));
+    e3.get::<(&Position, &Name)>(|(p, name)| {
+        assert_eq!((p.x, p.y), (1, 1));
+        assert_eq!(name.0, "e3");
+    });
+}
+
+#[test]
+fn entity_modify_batched() {
+    let world = World::new();
+
+    let e = world.entity().add::<TagA>().set(Velocity { x: 1, y: 1 });
+
+    // the entity is in its final table when the OnAdd observer runs
+    let observer = world
+        .observer::<flecs::OnAdd, ()>()
+        .with::<TagB>()
+        .each_entity(|e, _| {
+            assert!(!e.has::<TagA>());
+            assert!(!e.has::<Velocity>());
+            assert!(e.has::<Position>());
+        });
+
+    e.modify(|ops| {
+        ops.remove::<TagA>()
+            .add::<TagB>()
+            .remove::<Velocity>()
+            .set(Position { x: 2, y: 3 });
+    });
+    observer.entity().destruct();
+
+    assert!(!e.has::<TagA>());
+    assert!(!e.has::<Velocity>());
+    assert!(e.has::<TagB>());
+    e.get::<&Position>(|p| assert_eq!((p.x, p.y), (2, 3)));
+
+    // adding and removing the same id in one batch leaves the entity unchanged
+    e.modify(|ops| {
+        ops.add::<TagA>().remove::<TagA>();
+    });
+    assert!(!e.has::<TagA>());
+}
+
+#[test]
+#[cfg(feature = "flecs_entity_audit")]
+fn entity_audit_created_deleted() {
+    let world = World::new();
+
+    let (e, created_line) = (world.entity(), line!());
+    let (named, named_line) = (world.entity_named("named"), line!());
+    let (prefab, prefab_line) = (world.prefab_named("prefab"), line!());
+
+    assert_eq!(world.who_created(e).unwrap().line(), created_line);
+    assert_eq!(world.who_created(e).unwrap().file(), file!());
+    assert_eq!(world.who_created(named).unwrap().line(), named_line);
+    assert_eq!(world.who_created(prefab).unwrap().line(), prefab_line);
+    assert!(world.who_deleted(e).is_none());
+
+    let old = e.id();
+    let deleted_line = line!() + 1;
+    e.destruct();
+    assert_eq!(world.who_deleted(old).unwrap().line(), deleted_line);
+    assert_eq!(world.who_created(old).unwrap().line(), created_line);
+
+    // the recycled id has a new generation with its own history
+    let (recycled, recycled_line) = (world.entity(), line!());
+    assert_eq!(recycled.index(), old.index());
+    assert_eq!(world.who_created(recycled).unwrap().line(), recycled_line);
+    assert!(world.who_deleted(recycled).is_none());
+    assert_eq!(world.who_deleted(old).unwrap().line(), deleted_line);
+}
+
+#[test]
+fn entity_with_record() {
+    let world = World::new();
+
+    let e = world.entity().set(Position { x: 1, y: 2 });
+
+    let x = e.with_record(|record| {
+        assert_eq!(record.entity(), e);
+        assert!(record.has::<Position>());
+        assert!(!record.has::<Velocity>());
+        assert!(record.try_get::<&Velocity, _>(|_| ()).is_none());
+
+        // assigned in place
+        record.set(Position { x: 3, y: 4 });
+        // moves the entity to a new table
+        record.set(Velocity { x: 5, y: 6 });
+        record.add::<TagA>();
+        assert!(record.has::<Velocity>());
+        assert!(record.table().unwrap().has_type::<TagA>());
+
+        record.get::<(&Position, &Velocity), _>(|(p, v)| p.x + v.x)
+    });
+
+    assert_eq!(x, 8);
+    assert!(e.has::<TagA>());
+    e.get::<&Position>(|p| assert_eq!(p.y, 4));
+}
+
+#[test]
+fn entity_with_children() {
+    let world = World::new();
+
+    let mut unnamed = world.entity_null();
+    let mut other = world.entity_null();
+    let parent = world.entity_named("parent").with_children(|parent| {
+        parent.entity_named("a").set(Position { x: 1, y: 2 });
+        parent.entity_named("b").with_children(|b| {
+            b.entity_named("c").add::<TagA>();
+            assert_eq!(b.parent().name(), "b");
+        });
+        unnamed = parent.entity();
+
+        // the scope of the world is unchanged
+        other = world.entity();
+    });
+
+    let a = world.lookup("parent::a");
+    a.get::<&Position>(|p| assert_eq!((p.x, p.y), (1, 2)));
+    assert!(world.lookup("parent::b::c").has::<TagA>());
+    assert_eq!(unnamed.parent().unwrap(), parent);
+    assert!(other.parent().is_none());
+
+    // named children that exist are returned
+    parent.with_children(|parent| {
+        assert_eq!(parent.entity_named("a"), a);
+        assert_eq!(parent.entity_named("b::c"), world.lookup("parent::b::c"));
+    });
+
+    let mut count = 0;
+    parent.each_child(|_| count += 1);
+    assert_eq!(count, 3);
+
+    // children can be created while deferred
+    world.defer(|world| {
+        world.entity_named("deferred").with_children(|parent| {
+            parent.entity_named("child").set(Position { x: 3, y: 4 });
+        });
+    });
+    world
+        .lookup("deferred::child")
+        .get::<&Position>(|p| assert_eq!((p.x, p.y), (3, 4)));
+}
+
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+#[repr(C)]
+enum DoorState {
+    Closed,
+    Open,
+    Locked,
+}
+
+#[derive(Component)]
+struct HasKey;
+
+#[test]
+fn entity_state_machine_transitions() {
+    let world = World::new();
+    world
+        .state_machine::<DoorState>()
+        .transition(DoorState::Closed, DoorState::Open)
+        .transition(DoorState::Open, DoorState::Closed)
+        .transition(DoorState::Closed, DoorState::Locked)
+        .transition_if(DoorState::Locked, DoorState::Closed, |door| {
+            door.world().has::<HasKey>()
+        });
+
+    let door = world.entity();
+    assert_eq!(door.state::<DoorState>(), None);
+
+    door.transition(DoorState::Closed).unwrap();
+    door.transition(DoorState::Open).unwrap();
+    assert_eq!(door.state::<DoorState>(), Some(DoorState::Open));
+
+    let err = door.transition(DoorState::Locked).unwrap_err();
+    assert!(matches!(err, FlecsError::ConstraintViolated(_)));
+    assert_eq!(
+        err.to_string(),
+        "transition from Open to Locked is not allowed"
+    );
+    assert_eq!(door.state::<DoorState>(), Some(DoorState::Open));
+
+    door.transition(DoorState::Closed).unwrap();
+    door.transition(DoorState::Locked).unwrap();
+    assert!(door.transition(DoorState::Closed).is_err());
+
+    world.add::<HasKey>();
+    door.transition(DoorState::Closed).unwrap();
+    assert!(door.has_enum(DoorState::Closed));
+    assert!(!door.has_enum(DoorState::Locked));
+}
+
+#[test]
+fn entity_state_machine_enter_exit() {
+    let world = World::new();
+    world.set(Count(0));
+    world
+        .state_machine::<DoorState>()
+        .transition(DoorState::Closed, DoorState::Open)
+        .transition(DoorState::Open, DoorState::Closed)
+        .on_enter(DoorState::Open, |e| {
+            e.world().get::<&mut Count>(|count| count.0 += 1);
+        })
+        .on_exit(DoorState::Open, |e| {
+            e.world().get::<&mut Count>(|count| count.0 += 10);
+        });
+
+    let door = world.entity().add_enum(DoorState::Closed);
+    door.transition(DoorState::Open).unwrap();
+    assert_eq!(world.cloned::<&Count>().0, 1);
+
+    door.transition(DoorState::Closed).unwrap();
+    assert_eq!(world.cloned::<&Count>().0, 11);
+}
+
+#[test]
+fn entity_state_machine_guard_reads_state_machine() {
+    let world = World::new();
+    world
+        .state_machine::<DoorState>()
+        .transition_if(DoorState::Closed, DoorState::Open, |door| {
+            door.world()
+                .try_get::<&mut StateMachine<DoorState>>(|_| ())
+                .is_some()
+        });
+
+    let door = world.entity().add_enum(DoorState::Closed);
+    door.transition(DoorState::Open).unwrap();
+    assert_eq!(door.state::<DoorState>(), Some(DoorState::Open));
+}
+
+fn entity_state_machine_id(world: &World, relationship: Entity) {
+    world.set(Count(0));
+    let closed = world.entity_named("closed").id();
+    let open = world.entity_named("open").id();
+    let locked = world.entity_named("locked").id();
+    world
+        .state_machine_id(relationship)
+        .transition(closed, open)
+        .transition(open, closed)
+        .transition(closed, locked)
+        .transition_if(locked, closed, move |door| {
+            door.world().has::<HasKey>() && door.has_id((relationship, locked))
+        })
+        .on_enter(open, |e| {
+            e.world().get::<&mut Count>(|count| count.0 += 1);
+        })
+        .on_exit(open, |e| {
+            e.world().get::<&mut Count>(|count| count.0 += 10);
+        });
+
+    let door = world.entity();
+    assert_eq!(door.state_id(relationship), None);
+
+    door.transition_id(relationship, closed).unwrap();
+    door.transition_id(relationship, open).unwrap();
+    assert_eq!(door.state_id(relationship).unwrap(), open);
+    assert_eq!(world.cloned::<&Count>().0, 1);
+
+    let err = door.transition_id(relationship, locked).unwrap_err();
+    assert!(matches!(err, FlecsError::ConstraintViolated(_)));
+    assert_eq!(
+        err.to_string(),
+        "transition from open to locked is not allowed"
+    );
+    assert_eq!(door.state_id(relationship).unwrap(), open);
+
+    door.transition_id(relationship, closed).unwrap();
+    assert_eq!(world.cloned::<&Count>().0, 11);
+    door.transition_id(relationship, locked).unwrap();
+    assert!(door.transition_id(relationship, closed).is_err());
+
+    world.add::<HasKey>();
+    door.transition_id(relationship, closed).unwrap();
+    assert!(door.has_id((relationship, closed)));
+    assert!(!door.has_id((relationship, locked)));
+}
+
+#[test]
+fn entity_state_machine_id_exclusive() {
+    let world = World::new();
+    let relationship = world.entity_named("DoorState");
+    entity_state_machine_id(&world, relationship.id());
+    assert!(relationship.has::<flecs::Exclusive>());
+}
+
+#[test]
+fn entity_state_machine_id_union() {
+    let world = World::new();
+    let relationship = world.entity_named("DoorState").add_trait::<flecs::Union>();
+    entity_state_machine_id(&world, relationship.id());
+    assert!(!relationship.has::<flecs::Exclusive>());
+}
+
+#[test]
+fn entity_get_or_err() {
+    let world = World::new();
+
+    let parent = world.entity_named("parent");
+    let child = world
+        .entity_named("child")
+        .child_of_id(parent)
+        .set(Position { x: 1, y: 2 });
+
+    assert_eq!(world.lookup_or_err("parent::child"), Ok(child));
+    assert_eq!(parent.lookup_or_err("child"), Ok(child));
+    assert_eq!(
+        world.lookup_or_err("child"),
+        Err(FlecsError::Unresolved("child".into()))
+    );
+    assert_eq!(
+        child.lookup_recursive_or_err("missing"),
+        Err(FlecsError::Unresolved("missing".into()))
+    );
+
+    assert_eq!(child.get_or_err::<&Position>(|p| p.x), Ok(1));
+    assert_eq!(
+        child.get_or_err::<(&Position, &Velocity)>(|(p, _)| p.x),
+        Err(FlecsError::MissingComponent(core::any::type_name::<(
+            &Position,
+            &Velocity
+        )>()))
+    );
+    assert_eq!(
+        child.get_or_err::<(&Position, Option<&Velocity>)>(|(p, _)| p.y),
+        Ok(2)
+    );
+    assert_eq!(child.cloned_or_err::<&Position>().map(|p| p.y), Ok(2));
+    assert!(matches!(
+        child.cloned_or_err::<&Velocity>(),
+        Err(FlecsError::MissingComponent(_))
+    ));
+
+    child.destruct();
+    assert_eq!(
+        child.get_or_err::<&Position>(|p| p.x),
+        Err(FlecsError::NotAlive)
+    );
+    assert!(matches!(
+        child.cloned_or_err::<&Position>(),
+        Err(FlecsError::NotAlive)
+    ));
+
+    world.set(Position { x: 3, y: 4 });
+    assert_eq!(world.get_or_err::<&Position>(|p| p.x), Ok(3));
+    assert!(matches!(
+        world.get_or_err::<&Velocity>(|v| v.x),
+        Err(FlecsError::MissingComponent(_))
+    ));
+}
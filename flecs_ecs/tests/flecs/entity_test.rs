@@ -2331,7 +2331,7 @@ fn entity_defer_new_w_name() {
     let world = World::new();
     let mut e = world.entity_null();
 
-    world.defer(|| {
+    world.defer(|_| {
         e = world.entity_named("Foo");
         assert!(e.is_valid());
     });
@@ -2345,7 +2345,7 @@ fn entity_defer_new_w_nested_name() {
     let world = World::new();
     let mut e = world.entity_null();
 
-    world.defer(|| {
+    world.defer(|_| {
         e = world.entity_named("Foo::Bar");
         assert!(e.is_valid());
     });
@@ -2361,7 +2361,7 @@ fn entity_defer_new_w_scope_name() {
     let parent = world.entity_named("Parent");
     let mut e = world.entity_null();
 
-    world.defer(|| {
+    world.defer(|_| {
         parent.scope(|_w| {
             e = world.entity_named("Foo");
             assert!(e.is_valid());
@@ -2379,7 +2379,7 @@ fn entity_defer_new_w_scope_nested_name() {
     let parent = world.entity_named("Parent");
     let mut e = world.entity_null();
 
-    world.defer(|| {
+    world.defer(|_| {
         parent.scope(|_w| {
             e = world.entity_named("Foo::Bar");
             assert!(e.is_valid());
@@ -2398,7 +2398,7 @@ fn entity_defer_new_w_scope() {
     let parent = world.entity();
     let mut e = world.entity_null();
 
-    world.defer(|| {
+    world.defer(|_| {
         parent.scope(|_w| {
             e = world.entity();
             assert!(e.is_valid());
@@ -2414,7 +2414,7 @@ fn entity_defer_new_w_with() {
     let mut e = world.entity_null();
     let tag = world.entity();
 
-    world.defer(|| {
+    world.defer(|_| {
         tag.with(|| {
             e = world.entity();
             assert!(e.is_valid());
@@ -2432,7 +2432,7 @@ fn entity_defer_new_w_name_scope_with() {
     let mut e = world.entity_null();
     let parent = world.entity_named("Parent");
 
-    world.defer(|| {
+    world.defer(|_| {
         tag.with(|| {
             parent.scope(|_w| {
                 e = world.entity_named("Foo");
@@ -2459,7 +2459,7 @@ fn entity_defer_new_w_nested_name_scope_with() {
     let parent = world.entity_named("Parent");
     let mut e = world.entity_null();
 
-    world.defer(|| {
+    world.defer(|_| {
         tag.with(|| {
             parent.scope(|_w| {
                 e = world.entity_named("Foo::Bar");
@@ -2484,7 +2484,7 @@ fn entity_defer_w_with_implicit_component() {
     let world = World::new();
     let mut e = world.entity_null();
 
-    world.defer(|| {
+    world.defer(|_| {
         world.with::<Tag>(|| {
             e = world.entity();
             assert!(!e.has::<Tag>());
@@ -2501,7 +2501,7 @@ fn entity_defer_suspend_resume() {
     let world = World::new();
     let e = world.entity();
 
-    world.defer(|| {
+    world.defer(|_| {
         e.set(Position { x: 10, y: 20 });
         assert!(!e.has::<Position>());
 
@@ -3924,3 +3924,85 @@ fn entity_iter_empty_type() {
 
     assert_eq!(count, 0);
 }
+
+#[test]
+fn entity_disable_enable_recursive() {
+    let world = World::new();
+
+    let parent = world.entity().set(Position { x: 0, y: 0 });
+    let child = world
+        .entity()
+        .child_of_id(parent)
+        .set(Position { x: 1, y: 0 });
+    let grandchild = world
+        .entity()
+        .child_of_id(child)
+        .set(Position { x: 2, y: 0 });
+    let other = world.entity().set(Position { x: 3, y: 0 });
+
+    let query = world.new_query::<&Position>();
+    let with_disabled = world
+        .query::<&Position>()
+        .query_flags(QueryFlags::MatchDisabled)
+        .build();
+
+    parent.disable_recursive();
+    assert!(!parent.is_enabled_self());
+    assert!(!child.is_enabled_self());
+    assert!(!grandchild.is_enabled_self());
+    assert!(other.is_enabled_self());
+    assert_eq!(query.count(), 1);
+    assert_eq!(with_disabled.count(), 4);
+
+    parent.enable_recursive();
+    assert!(parent.is_enabled_self());
+    assert!(grandchild.is_enabled_self());
+    assert_eq!(query.count(), 4);
+}
+
+#[test]
+fn entity_add_one_of() {
+    let world = World::new();
+
+    let colors = world.entity_named("Colors");
+    let red = world.entity_named("Red").child_of_id(colors);
+    let tall = world.entity_named("Tall");
+    let color = world.entity_named("Color").add_one_of_id(colors);
+
+    let e = world.entity().try_add_id((color, red)).unwrap();
+    assert!(e.has_id((color, red)));
+
+    let err = e.try_add_id((color, tall)).unwrap_err();
+    assert!(matches!(err, FlecsError::ConstraintViolated(_)));
+    assert!(err.to_string().contains("Tall"), "{err}");
+    assert!(!e.has_id((color, tall)));
+
+    // deleted targets are rejected
+    let green = world.entity_named("Green").child_of_id(colors);
+    let green_id = green.id();
+    green.destruct();
+    let err = e.try_add_id((color, green_id)).unwrap_err();
+    assert!(matches!(err, FlecsError::ConstraintViolated(_)));
+    assert!(err.to_string().contains("not alive"), "{err}");
+
+    // ids without a OneOf constraint are added as usual
+    let e = e.try_add::<(Likes, Apples)>().unwrap();
+    assert!(e.has::<(Likes, Apples)>());
+
+    // targets of the relationship itself
+    let size = world.entity_named("Size").add_trait::<flecs::OneOf>();
+    let large = world.entity_named("Large").child_of_id(size);
+    assert!(e.try_add_id((size, large)).is_ok());
+    assert!(e.try_add_id((size, red)).is_err());
+
+    // queries look up targets in the scope, and reject targets outside of it
+    let query = world.query::<()>().expr("(Color, Red)").build();
+    assert_eq!(query.count(), 1);
+    assert!(
+        world
+            .query::<()>()
+            .with_id((color, tall))
+            .try_build()
+            .is_err()
+    );
+}
@@ -492,7 +492,7 @@ fn flecs_query_docs_compile_test() {
 
     let q = world.new_query::<&Position>();
 
-    world.defer(|| {
+    world.defer(|_| {
         q.each_entity(|e, p| {
             e.add::<Velocity>(); // OK
         });
@@ -2237,7 +2237,7 @@ fn flecs_docs_observers_compile_test() {
     let widget = world.entity_named("widget");
 
     // Create an entity observer
-    widget.observe_payload::<&Resize>(|r| {
+    widget.observe_payload::<Resize>(|r| {
         // ...
     });
 
@@ -616,3 +616,301 @@ fn meta_ser_deser_option() {
         assert_eq!(json, "{\"0\":{\"Some\":42}}");
     }
 }
+
+#[test]
+fn meta_json_world_migration() {
+    #[derive(Component)]
+    #[meta]
+    struct HealthV1 {
+        value: i32,
+    }
+
+    #[derive(Component)]
+    #[meta]
+    struct HealthV2 {
+        current: i32,
+        max: i32,
+    }
+
+    #[derive(Component)]
+    #[meta]
+    struct HealthV3 {
+        current: f32,
+        max: f32,
+    }
+
+    // save made by an older version of the game
+    let old_world = World::new();
+    old_world.component::<HealthV1>().meta();
+    old_world.entity_named("player").set(HealthV1 { value: 50 });
+    old_world.entity_named("rock");
+    let json = old_world.to_json_world(None);
+
+    let world = World::new();
+    world.component::<HealthV1>().meta();
+    world.component::<HealthV2>().meta();
+    world.component::<HealthV3>().meta();
+
+    world
+        .register_migration(|h: &HealthV1| HealthV2 {
+            current: h.value,
+            max: 100,
+        })
+        .register_migration(|h: &HealthV2| HealthV3 {
+            current: h.current as f32,
+            max: h.max as f32,
+        });
+
+    world.from_json_world(&json, None);
+
+    let player = world.lookup("player");
+    assert!(!player.has::<HealthV1>());
+    assert!(!player.has::<HealthV2>());
+    player.get::<&HealthV3>(|h| {
+        assert_eq!(h.current, 50.0);
+        assert_eq!(h.max, 100.0);
+    });
+    assert!(!world.lookup("rock").has::<HealthV3>());
+}
+
+#[test]
+fn meta_apply_migrations_from_observer() {
+    #[derive(Component)]
+    struct HealthV1 {
+        value: i32,
+    }
+
+    #[derive(Component)]
+    struct HealthV2 {
+        value: i32,
+    }
+
+    #[derive(Component)]
+    struct HealthV3 {
+        value: i32,
+    }
+
+    let world = World::new();
+    let boss = world.entity_named("boss").set(HealthV1 { value: 100 });
+    let minion = world.entity_named("minion").set(HealthV1 { value: 10 });
+    let (boss_id, minion_id) = (boss.id(), minion.id());
+
+    // the boss takes its minion along when it is upgraded, and the observer registers the next
+    // migration while migrations run
+    world
+        .observer::<flecs::OnSet, &HealthV2>()
+        .each_entity(move |e, _| {
+            if e == boss_id {
+                e.world().entity_from_id(minion_id).destruct();
+                e.world()
+                    .register_migration(|h: &HealthV2| HealthV3 { value: h.value });
+            }
+        });
+    world.register_migration(|h: &HealthV1| HealthV2 { value: h.value });
+
+    world.apply_migrations();
+    assert!(!minion.is_alive());
+    boss.get::<&HealthV2>(|h| assert_eq!(h.value, 100));
+
+    world.apply_migrations();
+    assert!(!boss.has::<HealthV2>());
+    boss.get::<&HealthV3>(|h| assert_eq!(h.value, 100));
+}
+
+#[test]
+fn meta_record_replay() {
+    use flecs_ecs::addons::json::RecordedOp;
+
+    #[derive(Component)]
+    #[meta]
+    struct Health {
+        value: i32,
+    }
+
+    #[derive(Component)]
+    struct Enemy;
+
+    #[derive(Component)]
+    struct Targets;
+
+    let setup = |world: &World| {
+        world.component::<Health>().meta();
+        world.component::<Enemy>();
+        world.component::<Targets>();
+    };
+
+    let world = World::new();
+    setup(&world);
+    let camp = world.entity_named("camp");
+
+    let recorder = world.record();
+    let player = world.entity_named("player").set(Health { value: 10 });
+    let orc = world
+        .entity()
+        .add::<Enemy>()
+        .child_of_id(player)
+        .add_first::<Targets>(player);
+    orc.set(Health { value: 3 });
+    world.entity().add::<Enemy>().destruct();
+    player.set(Health { value: 7 });
+    orc.remove::<Enemy>();
+    // entities that existed before recording keep their name
+    camp.add::<Enemy>();
+    // systems created while recording are not part of the log
+    world.system::<&Health>().each(|_| {});
+    let log = recorder.stop();
+
+    let health = world.component_id::<Health>().into();
+    let enemy = world.component_id::<Enemy>().into();
+    let child_of = Id::pair(flecs::ChildOf::ID, player);
+    let targets = Id::pair(world.component_id::<Targets>(), player);
+    let deleted = log.ops[10].clone();
+    let RecordedOp::Create(deleted_id, None) = deleted else {
+        panic!("expected create")
+    };
+    assert_eq!(
+        log.ops,
+        [
+            RecordedOp::Create(player.id(), None),
+            RecordedOp::SetName(player.id(), "player".to_string()),
+            RecordedOp::Add(player.id(), health),
+            RecordedOp::Set(player.id(), health, "{\"value\":10}".to_string()),
+            RecordedOp::Create(orc.id(), None),
+            RecordedOp::Add(orc.id(), enemy),
+            RecordedOp::Add(orc.id(), child_of),
+            RecordedOp::Add(orc.id(), targets),
+            RecordedOp::Add(orc.id(), health),
+            RecordedOp::Set(orc.id(), health, "{\"value\":3}".to_string()),
+            deleted.clone(),
+            RecordedOp::Add(deleted_id, enemy),
+            RecordedOp::Delete(deleted_id),
+            RecordedOp::Set(player.id(), health, "{\"value\":7}".to_string()),
+            RecordedOp::Remove(orc.id(), enemy),
+            RecordedOp::Create(camp.id(), Some("::camp".to_string())),
+            RecordedOp::Add(camp.id(), enemy),
+        ]
+    );
+
+    // replay into a world with components registered in a different order
+    let replay_world = World::new();
+    replay_world.component::<Targets>();
+    setup(&replay_world);
+    let entities = log.replay(&replay_world).unwrap();
+
+    let replayed_player = replay_world.lookup("player");
+    assert_eq!(entities[&player.id()], replayed_player.id());
+    let replayed_orc = replay_world.entity_from_id(entities[&orc.id()]);
+    assert!(replayed_orc.has_first::<Targets>(replayed_player));
+    assert_eq!(replayed_orc.parent().unwrap(), replayed_player);
+    assert!(!replayed_orc.has::<Enemy>());
+    replayed_player.get::<&Health>(|h| assert_eq!(h.value, 7));
+    replayed_orc.get::<&Health>(|h| assert_eq!(h.value, 3));
+    assert!(replay_world.lookup("camp").has::<Enemy>());
+    assert_eq!(replay_world.count::<Enemy>(), 1);
+    assert_eq!(replay_world.count::<Health>(), 2);
+
+    // components must exist in the world the log is replayed into
+    let missing_world = World::new();
+    missing_world.component::<Health>().meta();
+    let enemy_path = world.component::<Enemy>().path().unwrap();
+    assert_eq!(
+        log.replay(&missing_world),
+        Err(FlecsError::Unresolved(enemy_path))
+    );
+}
+
+#[test]
+fn meta_replicate_changes() {
+    use flecs_ecs::addons::json::{Change, Guid};
+
+    #[derive(Component)]
+    #[meta]
+    struct Health {
+        value: i32,
+    }
+
+    #[derive(Component)]
+    struct Enemy;
+
+    #[derive(Component)]
+    #[meta]
+    struct Local {
+        value: i32,
+    }
+
+    let setup = |world: &World| {
+        world.component::<Health>().meta().replicate();
+        world.component::<Enemy>().replicate();
+        world.component::<Local>().meta();
+    };
+
+    let server = World::new();
+    setup(&server);
+    let client = World::new();
+    setup(&client);
+
+    let orc = server.entity();
+    let orc_guid = orc.guid();
+    orc.add::<Enemy>()
+        .set(Health { value: 10 })
+        .set(Local { value: 1 });
+    orc.set(Health { value: 8 });
+    // entities without a guid are not replicated
+    server.entity().set(Health { value: 5 });
+
+    let health = server.component::<Health>().path().unwrap();
+    let enemy = server.component::<Enemy>().path().unwrap();
+    let changes = server.drain_changes();
+    assert_eq!(
+        changes.changes,
+        [
+            Change::Add {
+                entity: orc_guid,
+                component: enemy.clone(),
+            },
+            Change::Add {
+                entity: orc_guid,
+                component: health.clone(),
+            },
+            Change::Set {
+                entity: orc_guid,
+                component: health.clone(),
+                value: "{\"value\":8}".to_string(),
+            },
+        ]
+    );
+    assert!(server.drain_changes().is_empty());
+
+    client.apply_changes(&changes).unwrap();
+    let client_orc = client.resolve_guid(orc_guid).unwrap();
+    assert!(client_orc.has::<Enemy>());
+    assert!(!client_orc.has::<Local>());
+    client_orc.get::<&Health>(|h| assert_eq!(h.value, 8));
+    // applied changes are not captured again
+    assert!(client.drain_changes().is_empty());
+
+    // changes made on the client flow back the same way
+    client_orc.remove::<Enemy>();
+    server.apply_changes(&client.drain_changes()).unwrap();
+    assert!(!orc.has::<Enemy>());
+
+    orc.destruct();
+    let changes = server.drain_changes();
+    assert_eq!(changes.changes, [Change::Delete(orc_guid)]);
+    client.apply_changes(&changes).unwrap();
+    assert!(client.resolve_guid(orc_guid).is_none());
+    assert_eq!(client.count::<Health>(), 0);
+
+    // components must be registered in the world the changes are applied to
+    let missing = World::new();
+    let changes = flecs_ecs::addons::json::ChangeSet {
+        changes: vec![Change::Add {
+            entity: Guid(1),
+            component: enemy.clone(),
+        }],
+    };
+    assert_eq!(
+        missing.apply_changes(&changes),
+        Err(FlecsError::Unresolved(enemy))
+    );
+}
@@ -80,3 +80,630 @@ fn test_meta_debug_stringify() {
         r#"{"name":"bob", "components":{"flecs.meta_test_rust.test_meta_debug_stringify.Position":"Position { x: 1.0, y: 2.0 }", "flecs.meta_test_rust.test_meta_debug_stringify.Velocity":"Velocity { x: 3, y: 4 }"}}"#
     );
 }
+
+#[test]
+fn meta_world_ser_deser_anonymous_entity_references() {
+    #[derive(Component)]
+    struct Target {
+        entity: Entity,
+    }
+
+    #[derive(Component)]
+    struct Health {
+        value: i32,
+    }
+
+    let world = World::new();
+    world.component::<Target>().member::<Entity>("entity");
+    world.component::<Health>().member::<i32>("value");
+
+    let target = world.entity().set(Health { value: 42 });
+    world.entity_named("source").set(Target {
+        entity: target.id(),
+    });
+
+    let json = world.to_json_world(None);
+
+    let world = World::new();
+    world.component::<Target>().member::<Entity>("entity");
+    world.component::<Health>().member::<i32>("value");
+
+    world.from_json_world(json.as_str(), None);
+
+    // the reference still points at the (anonymous) entity that holds the data
+    let source = world.lookup("source");
+    let loaded_target = source.get::<&Target>(|t| t.entity);
+    assert!(world.is_alive(loaded_target));
+    loaded_target.entity_view(&world).get::<&Health>(|h| {
+        assert_eq!(h.value, 42);
+    });
+}
+
+#[test]
+fn meta_world_ser_deser_guids() {
+    use flecs_ecs::addons::json::Guid;
+
+    #[derive(Component)]
+    struct Target {
+        entity: Entity,
+    }
+
+    #[derive(Component)]
+    struct Health {
+        value: i32,
+    }
+
+    let setup = |world: &World| {
+        world.component::<Target>().member::<Entity>("entity");
+        world.component::<Health>().member::<i32>("value");
+    };
+
+    let world = World::new();
+    setup(&world);
+
+    let enemy = world.entity().set(Health { value: 42 });
+    let guid = enemy.guid();
+    assert_eq!(enemy.guid(), guid);
+    assert_eq!(world.resolve_guid(guid).unwrap(), enemy);
+    let other = world.entity().set(Health { value: 7 });
+    let other_guid = other.guid();
+    assert_ne!(other_guid, guid);
+    world
+        .entity_named("source")
+        .set(Target { entity: enemy.id() });
+
+    let json = world.to_json_world_with_guids(None);
+
+    // the level is loaded before the save game, so the enemy exists with another id
+    let world = World::new();
+    setup(&world);
+    world.entity();
+    world.enable_guids();
+    let existing = world.entity().set(guid).set(Health { value: 100 });
+
+    world.from_json_world_with_guids(&json).unwrap();
+
+    assert_eq!(world.resolve_guid(guid).unwrap(), existing);
+    existing.get::<&Health>(|h| assert_eq!(h.value, 42));
+    let source = world.lookup("source");
+    assert_eq!(source.get::<&Target>(|t| t.entity), existing.id());
+
+    let loaded_other = world.resolve_guid(other_guid).unwrap();
+    loaded_other.get::<&Health>(|h| assert_eq!(h.value, 7));
+    assert_eq!(world.count::<Health>(), 2);
+
+    // a loaded world can be saved and loaded again
+    let json = world.to_json_world_with_guids(None);
+    let reloaded = World::new();
+    setup(&reloaded);
+    reloaded.from_json_world_with_guids(&json).unwrap();
+    assert_eq!(reloaded.count::<Health>(), 2);
+    assert!(reloaded.resolve_guid(other_guid).is_some());
+
+    other.destruct();
+    let world = other.world();
+    assert!(world.resolve_guid(other_guid).is_none());
+
+    // the guid is known before the world merges the deferred set
+    world.defer_begin();
+    let deferred = world.entity();
+    let deferred_guid = deferred.guid();
+    assert_eq!(deferred.guid(), deferred_guid);
+    assert_eq!(world.resolve_guid(deferred_guid).unwrap(), deferred);
+    world.defer_end();
+    assert_eq!(deferred.cloned::<&Guid>(), deferred_guid);
+    assert_eq!(deferred.guid(), deferred_guid);
+
+    assert!(matches!(
+        World::new().from_json_world_with_guids("{}"),
+        Err(FlecsError::ParseError(_))
+    ));
+}
+
+#[test]
+fn meta_entity_diff_apply() {
+    #[derive(Component)]
+    struct Health {
+        value: i32,
+    }
+
+    #[derive(Component)]
+    struct Armor {
+        value: i32,
+    }
+
+    #[derive(Component)]
+    struct Player;
+
+    #[derive(Component)]
+    struct Stunned;
+
+    let world = World::new();
+    world.component::<Health>().member::<i32>("value");
+    world.component::<Armor>().member::<i32>("value");
+
+    let e = world
+        .entity()
+        .set(Health { value: 10 })
+        .add::<Player>()
+        .add::<Stunned>();
+
+    let snapshot = world
+        .entity()
+        .set(Health { value: 50 })
+        .set(Armor { value: 5 })
+        .add::<Player>();
+
+    let diff = e.diff(snapshot);
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(diff.changed[0].0, world.component_id::<Health>());
+    assert_eq!(diff.added.len(), 1);
+    assert_eq!(diff.added[0].0, world.component_id::<Armor>());
+    assert!(diff.added[0].1.is_some());
+    assert_eq!(diff.removed, vec![world.component_id::<Stunned>()]);
+
+    e.apply_diff(&diff);
+
+    assert!(e.has::<Player>());
+    assert!(!e.has::<Stunned>());
+    e.get::<(&Health, &Armor)>(|(h, a)| {
+        assert_eq!(h.value, 50);
+        assert_eq!(a.value, 5);
+    });
+
+    assert!(e.diff(snapshot).is_empty());
+}
+
+#[test]
+fn meta_entity_diff_unreflected() {
+    #[derive(Component, Default)]
+    struct Counter {
+        value: i32,
+    }
+
+    #[derive(Component)]
+    struct Label {
+        text: String,
+    }
+
+    #[derive(Component, Clone)]
+    struct Slot {
+        index: core::num::NonZeroU32,
+    }
+
+    let world = World::new();
+
+    let e = world.entity();
+    let snapshot = world
+        .entity()
+        .set(Counter { value: 5 })
+        .set(Label {
+            text: "enemy".to_string(),
+        })
+        .set(Slot {
+            index: core::num::NonZeroU32::MIN,
+        });
+
+    let diff = e.diff(snapshot);
+    assert_eq!(diff.added.len(), 3);
+    assert!(diff.added.iter().all(|(_, value)| value.is_none()));
+
+    // without reflection data only Default components can be added, with their default value
+    e.apply_diff(&diff);
+    e.get::<&Counter>(|c| assert_eq!(c.value, 0));
+    assert!(!e.has::<Label>());
+    assert!(!e.has::<Slot>());
+    snapshot.get::<&Slot>(|s| assert_eq!(s.index.get(), 1));
+    snapshot.get::<&Label>(|l| assert_eq!(l.text, "enemy"));
+}
+
+#[test]
+fn meta_entity_diff_ignores_identity() {
+    #[derive(Component)]
+    struct Health {
+        value: i32,
+    }
+
+    let world = World::new();
+    world.component::<Health>().member::<i32>("value");
+
+    let base = world.prefab_named("base");
+    let e = world
+        .entity_named("e")
+        .child_of_id(world.entity_named("parent_a"))
+        .set(Health { value: 10 });
+    let snapshot = world
+        .entity_named("snapshot")
+        .child_of_id(world.entity_named("parent_b"))
+        .is_a_id(base)
+        .add::<flecs::Disabled>()
+        .set(Health { value: 50 });
+
+    let diff = e.diff(snapshot);
+    assert!(diff.added.is_empty());
+    assert!(diff.removed.is_empty());
+    assert_eq!(diff.changed.len(), 1);
+    assert_eq!(diff.changed[0].0, world.component_id::<Health>());
+
+    e.apply_diff(&diff);
+
+    assert_eq!(e.path().unwrap(), "::parent_a::e");
+    assert!(!e.has_id((flecs::IsA::ID, base)));
+    assert!(e.is_enabled_self());
+    e.get::<&Health>(|h| assert_eq!(h.value, 50));
+    assert!(e.diff(snapshot).is_empty());
+}
+
+#[test]
+fn meta_save_restore_subtree() {
+    #[derive(Component)]
+    struct Health {
+        value: i32,
+    }
+
+    #[derive(Component)]
+    struct Boss;
+
+    #[derive(Component)]
+    struct Guards;
+
+    #[derive(Component)]
+    struct InLevel;
+
+    let world = World::new();
+    world.component::<Health>().member::<i32>("value");
+
+    let level = world.entity_named("level");
+    let room = world.entity_named("room").add_first::<InLevel>(level);
+    let boss = world
+        .entity_named("boss")
+        .child_of_id(room)
+        .add::<Boss>()
+        .set(Health { value: 100 });
+    let minion = world
+        .entity()
+        .child_of_id(boss)
+        .add_first::<Guards>(boss)
+        .set(Health { value: 10 });
+
+    let checkpoint = room.save_subtree();
+
+    boss.set(Health { value: 20 });
+    minion.destruct();
+    room.destruct();
+    assert!(world.try_lookup("room").is_none());
+
+    let room = world.restore_subtree(&checkpoint, Entity::null()).unwrap();
+    assert_eq!(room.name(), "room");
+    assert!(room.has_first::<InLevel>(level));
+
+    let boss = world.lookup("room::boss");
+    assert!(boss.has::<Boss>());
+    boss.get::<&Health>(|h| assert_eq!(h.value, 100));
+
+    let mut minions = Vec::new();
+    boss.each_child(|child| minions.push(child.id()));
+    assert_eq!(minions.len(), 1);
+    let minion = world.entity_from_id(minions[0]);
+    assert!(minion.has_first::<Guards>(boss));
+    minion.get::<&Health>(|h| assert_eq!(h.value, 10));
+
+    // a checkpoint can be restored more than once, under another parent
+    let copy = world.restore_subtree(&checkpoint, level).unwrap();
+    assert_eq!(copy.path().unwrap(), "::level::room");
+    assert!(world.lookup("level::room::boss").has::<Boss>());
+    assert_eq!(world.new_query::<&Boss>().count(), 2);
+
+    // references outside of the tree must resolve, otherwise nothing is restored
+    copy.destruct();
+    room.destruct();
+    level.destruct();
+    assert_eq!(
+        world.restore_subtree(&checkpoint, Entity::null()),
+        Err(FlecsError::Unresolved("::level".into()))
+    );
+    assert!(world.try_lookup("room").is_none());
+    assert_eq!(world.new_query::<&Boss>().count(), 0);
+}
+
+#[test]
+fn meta_try_from_json_errors() {
+    #[derive(Component)]
+    struct Health {
+        value: i32,
+    }
+
+    #[derive(Component)]
+    struct Player;
+
+    let world = World::new();
+    world.component::<Health>().member::<i32>("value");
+
+    let e = world.entity();
+    e.try_set_json::<Health>(r#"{"value": 10}"#, None).unwrap();
+    e.get::<&Health>(|h| assert_eq!(h.value, 10));
+
+    let Err(FlecsError::ParseError(message)) = e.try_set_json::<Health>(r#"{"health": 20}"#, None)
+    else {
+        panic!("unknown member should fail to deserialize");
+    };
+    assert!(message.contains("health"));
+    e.get::<&Health>(|h| assert_eq!(h.value, 10));
+
+    // a component that wasn't there isn't added when the JSON is invalid
+    let e2 = world.entity();
+    assert!(
+        e2.try_set_json::<Health>(r#"{"health": 20}"#, None)
+            .is_err()
+    );
+    assert!(!e2.has::<Health>());
+
+    assert_eq!(
+        e.try_set_json::<Player>("{}", None).unwrap_err(),
+        FlecsError::NotAComponent
+    );
+
+    let dead = world.entity();
+    dead.destruct();
+    assert_eq!(
+        dead.try_set_json::<Health>(r#"{"value": 10}"#, None)
+            .unwrap_err(),
+        FlecsError::NotAlive
+    );
+
+    assert!(matches!(
+        e.try_from_json(r#"{"components": 5}"#),
+        Err(FlecsError::ParseError(_))
+    ));
+    assert!(matches!(
+        world.try_from_json_world("[1, 2]", None),
+        Err(FlecsError::ParseError(_))
+    ));
+
+    let json = world.to_json_world(None);
+    assert!(world.try_from_json_world(&json, None).is_ok());
+}
+
+#[test]
+fn meta_eval_expr() {
+    use flecs_ecs::addons::script::ScriptVars;
+
+    #[derive(Component, Default, Debug, PartialEq)]
+    #[meta]
+    struct Quest {
+        kills: i32,
+        gold: f32,
+    }
+
+    let world = World::new();
+    world.component_named::<Quest>("Quest").meta();
+
+    let player = world.entity().set(Quest {
+        kills: 12,
+        gold: 2.5,
+    });
+
+    let mut vars = ScriptVars::new(&world);
+    vars.set_entity("player", player).set("required", 10i32);
+
+    let condition = "$player[Quest].kills >= $required";
+    assert_eq!(world.eval_expr::<bool>(condition, Some(&vars)), Ok(true));
+
+    // variables can be updated between evaluations
+    vars.set("required", 20i32);
+    assert_eq!(world.eval_expr::<bool>(condition, Some(&vars)), Ok(false));
+
+    // results are converted to the requested type
+    assert_eq!(
+        world.eval_expr::<f64>("$player[Quest].gold * 2", Some(&vars)),
+        Ok(5.0)
+    );
+    assert_eq!(
+        world.eval_expr::<Quest>("{kills: 1, gold: 2}", None),
+        Ok(Quest {
+            kills: 1,
+            gold: 2.0
+        })
+    );
+
+    // or keep the type of the expression
+    let value = world.eval_expr_value("$required + 1", Some(&vars)).unwrap();
+    assert_eq!(value.to_expr(), "21");
+    assert_eq!(value.get::<f32>(), None);
+
+    let err = world
+        .eval_expr::<i32>("$missing + 1", Some(&vars))
+        .unwrap_err();
+    assert!(err.to_string().contains("missing"), "{err}");
+}
+
+#[test]
+#[allow(clippy::float_cmp)]
+fn meta_member_path() {
+    #[derive(Component, Default, Clone, Debug, PartialEq)]
+    #[meta]
+    struct Vec3 {
+        x: f32,
+        y: f32,
+        z: f32,
+    }
+
+    #[derive(Component)]
+    #[meta]
+    struct Transform {
+        translation: Vec3,
+        scale: f64,
+    }
+
+    #[derive(Component)]
+    struct Velocity;
+
+    let world = World::new();
+    world.component::<Vec3>().meta();
+    world.component_named::<Transform>("Transform").meta();
+
+    use core::sync::atomic::{AtomicUsize, Ordering};
+
+    static SET_COUNT: AtomicUsize = AtomicUsize::new(0);
+    world.observer::<flecs::OnSet, &Transform>().each(|_| {
+        SET_COUNT.fetch_add(1, Ordering::Relaxed);
+    });
+
+    let e = world.entity().set(Transform {
+        translation: Vec3 {
+            x: 1.0,
+            y: 2.0,
+            z: 3.0,
+        },
+        scale: 1.5,
+    });
+    SET_COUNT.store(0, Ordering::Relaxed);
+
+    e.set_member("Transform.translation.y", 5.0f32);
+    assert_eq!(e.get_member::<f32>("Transform.translation.y"), 5.0);
+    e.get::<&Transform>(|t| assert_eq!(t.translation.y, 5.0));
+    assert_eq!(SET_COUNT.load(Ordering::Relaxed), 1);
+
+    // nested structs can be read as a whole
+    assert_eq!(
+        e.get_member::<Vec3>("Transform.translation"),
+        Vec3 {
+            x: 1.0,
+            y: 5.0,
+            z: 3.0
+        }
+    );
+
+    // primitives are converted
+    e.set_member("Transform.scale", 2i32);
+    assert_eq!(e.get_member::<f32>("Transform.scale"), 2.0);
+
+    assert!(matches!(
+        e.try_get_member::<f32>("Transform.translation.w"),
+        Err(FlecsError::ParseError(_))
+    ));
+    assert!(matches!(
+        e.try_get_member::<Vec3>("Transform.scale"),
+        Err(FlecsError::ParseError(_))
+    ));
+    assert_eq!(
+        e.try_get_member::<f32>("Velocity.x"),
+        Err(FlecsError::NotAComponent)
+    );
+    assert_eq!(
+        world
+            .entity()
+            .try_set_member("Transform.scale", 1.0f64)
+            .err(),
+        Some(FlecsError::InvalidParameter)
+    );
+}
+
+#[test]
+fn meta_prefab_registry_reload() {
+    #[derive(Component)]
+    struct Attack {
+        value: i32,
+    }
+
+    #[derive(Component)]
+    struct Defense {
+        value: i32,
+    }
+
+    let world = World::new();
+    world
+        .component_named::<Attack>("Attack")
+        .member::<i32>("value");
+    world
+        .component_named::<Defense>("Defense")
+        .member::<i32>("value");
+
+    let dir = std::env::temp_dir().join(format!("flecs_prefab_registry_{}", std::process::id()));
+    std::fs::create_dir_all(&dir).unwrap();
+    let script = dir.join("ships.flecs");
+    let json = dir.join("turrets.json");
+
+    std::fs::write(&script, "prefab SpaceShip {\n  Attack: {50}\n}\n").unwrap();
+    let registry = world.prefab_registry();
+    let prefabs = registry.load(&script).unwrap();
+    assert_eq!(prefabs.len(), 1);
+    let ship = prefabs[0];
+    assert_eq!(ship.name(), "SpaceShip");
+
+    let inherited = world.entity().is_a_id(ship);
+    let overridden = world.entity().is_a_id(ship).set(Attack { value: 99 });
+
+    std::fs::write(
+        &script,
+        "prefab SpaceShip {\n  Attack: {75}\n  Defense: {10}\n}\n",
+    )
+    .unwrap();
+    assert_eq!(registry.reload_changed().unwrap(), [ship]);
+    inherited.get::<&Attack>(|attack| assert_eq!(attack.value, 75));
+    inherited.get::<&Defense>(|defense| assert_eq!(defense.value, 10));
+    overridden.get::<&Attack>(|attack| assert_eq!(attack.value, 99));
+    assert!(registry.reload_changed().unwrap().is_empty());
+
+    std::fs::write(&script, "prefab SpaceShip {\n  Defense: {10}\n}\n").unwrap();
+    registry.load(&script).unwrap();
+    assert!(!ship.has::<Attack>());
+    assert!(!inherited.has::<Attack>());
+    assert!(overridden.has::<Attack>());
+
+    // a file that fails to load leaves the prefabs as they were
+    std::fs::write(&script, "prefab SpaceShip {\n  Defense: {\n").unwrap();
+    assert!(matches!(
+        registry.load(&script),
+        Err(FlecsError::ParseError(_))
+    ));
+    ship.get::<&Defense>(|defense| assert_eq!(defense.value, 10));
+
+    let turret = world.prefab_named("Turret").set(Attack { value: 5 });
+    std::fs::write(&json, world.to_json_world(None)).unwrap();
+    turret.destruct();
+
+    let prefabs = registry.load(&json).unwrap();
+    assert!(prefabs.iter().any(|prefab| prefab.name() == "Turret"));
+    world
+        .lookup("Turret")
+        .get::<&Attack>(|attack| assert_eq!(attack.value, 5));
+
+    assert!(matches!(
+        registry.load(dir.join("missing.flecs")),
+        Err(FlecsError::Io(_))
+    ));
+    assert_eq!(registry.files().len(), 3);
+
+    std::fs::remove_dir_all(&dir).unwrap();
+}
+
+#[test]
+#[cfg(feature = "flecs_prefab_watch")]
+fn meta_prefab_registry_watch() {
+    #[derive(Component)]
+    struct Attack {
+        value: i32,
+    }
+
+    let world = World::new();
+    world
+        .component_named::<Attack>("Attack")
+        .member::<i32>("value");
+
+    let path =
+        std::env::temp_dir().join(format!("flecs_prefab_watch_{}.flecs", std::process::id()));
+    std::fs::write(&path, "prefab SpaceShip {\n  Attack: {50}\n}\n").unwrap();
+    let ship = world.prefab_registry().load(&path).unwrap()[0];
+    let instance = world.entity().is_a_id(ship);
+
+    world.prefab_registry().watch(1.0);
+    std::fs::write(&path, "prefab SpaceShip {\n  Attack: {75}\n}\n").unwrap();
+
+    world.progress_time(0.5);
+    instance.get::<&Attack>(|attack| assert_eq!(attack.value, 50));
+    world.progress_time(0.6);
+    instance.get::<&Attack>(|attack| assert_eq!(attack.value, 75));
+
+    std::fs::remove_file(&path).unwrap();
+}
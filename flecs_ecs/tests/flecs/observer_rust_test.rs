@@ -53,3 +53,152 @@ fn observer_panic_on_add_4() {
 
     world.entity().set(Position { x: 10, y: 20 });
 }
+
+#[test]
+fn observer_entity_observe_payload() {
+    #[derive(Component)]
+    struct Damage {
+        amount: i32,
+    }
+
+    let world = World::new();
+    world.set(Count(0));
+
+    let player = world.entity().set(Mass { value: 100 });
+    player.observe_payload_entity(|e, damage: &Damage| {
+        e.get::<&mut Mass>(|mass| mass.value -= damage.amount);
+        e.world().get::<&mut Count>(|count| count.0 += 1);
+    });
+
+    let other = world.entity().set(Mass { value: 100 });
+
+    player.emit(&Damage { amount: 10 });
+    other.emit(&Damage { amount: 20 });
+
+    player.get::<&Mass>(|mass| assert_eq!(mass.value, 90));
+    other.get::<&Mass>(|mass| assert_eq!(mass.value, 100));
+    world.get::<&Count>(|count| assert_eq!(count.0, 1));
+}
+
+#[test]
+fn observer_entity_observe_cleanup() {
+    #[derive(Component)]
+    struct Clicked;
+
+    let world = World::new();
+
+    let observer_count = || {
+        world
+            .query::<()>()
+            .with::<flecs::Observer>()
+            .build()
+            .count()
+    };
+    let before = observer_count();
+
+    let widget = world.entity();
+    widget.observe::<Clicked>(|| {});
+    assert_eq!(observer_count(), before + 1);
+
+    widget.destruct();
+    assert_eq!(observer_count(), before);
+}
+
+#[test]
+fn observer_wildcard_pair_second() {
+    #[derive(Component)]
+    struct Buff {
+        stacks: i32,
+    }
+
+    let world = World::new();
+
+    let added = world.entity().set(Count(0)).id();
+    let removed = world.entity().set(Count(0)).id();
+
+    world
+        .observer::<flecs::OnSet, &(Buff, flecs::Wildcard)>()
+        .each_iter(move |it, _, buff| {
+            let target = it.pair_second::<Buff>(0);
+            if target == it.world().component_id::<TagA>() {
+                assert_eq!(buff.stacks, 1);
+            } else {
+                assert_eq!(target, it.world().component_id::<TagB>());
+                assert_eq!(buff.stacks, 2);
+            }
+            it.world()
+                .entity_from_id(added)
+                .get::<&mut Count>(|count| count.0 += 1);
+        });
+
+    world
+        .observer::<flecs::OnRemove, ()>()
+        .with::<(Buff, flecs::Wildcard)>()
+        .run(move |mut it| {
+            while it.next() {
+                // the remaining pair is removed when the world is deleted
+                if it.pair_second::<Buff>(0) == it.world().component_id::<TagB>() {
+                    it.world()
+                        .entity_from_id(removed)
+                        .get::<&mut Count>(|count| count.0 += it.count() as i32);
+                }
+            }
+        });
+
+    let e = world
+        .entity()
+        .set_pair::<Buff, TagA>(Buff { stacks: 1 })
+        .set_pair::<Buff, TagB>(Buff { stacks: 2 });
+    world
+        .entity_from_id(added)
+        .get::<&Count>(|count| assert_eq!(count.0, 2));
+
+    e.remove::<(Buff, TagB)>();
+    world
+        .entity_from_id(removed)
+        .get::<&Count>(|count| assert_eq!(count.0, 1));
+}
+
+#[test]
+#[should_panic]
+fn observer_wildcard_pair_second_wrong_relationship() {
+    let world = World::new();
+
+    world
+        .observer::<flecs::OnAdd, ()>()
+        .with::<(TagA, flecs::Wildcard)>()
+        .each_iter(|it, _, _| {
+            it.pair_second::<TagB>(0);
+        });
+
+    world.entity().add::<(TagA, TagB)>();
+}
+
+#[test]
+fn observer_try_build() {
+    let world = World::new();
+    world.component_named::<Position>("Position");
+
+    let Err(err) = world
+        .observer::<flecs::OnSet, ()>()
+        .expr("Unknown")
+        .fallible()
+        .each_entity(|e, ()| e.destruct())
+    else {
+        panic!("observer with unresolved identifier should fail to build");
+    };
+    assert!(err.message().contains("Unknown"));
+
+    let observer = world
+        .observer::<flecs::OnSet, &Position>()
+        .fallible()
+        .each_entity(|e, p| {
+            e.set(Velocity { x: p.x, y: p.y });
+        })
+        .ok()
+        .unwrap();
+    assert!(observer.is_alive());
+
+    let e = world.entity().set(Position { x: 10, y: 20 });
+    e.get::<&Velocity>(|v| assert_eq!(v.x, 10));
+}
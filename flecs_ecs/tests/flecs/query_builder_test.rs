@@ -4995,23 +4995,23 @@ fn query_builder_is_valid() {
 }
 
 #[test]
-#[ignore = "We don't support unresolved queries. TODO introduce a try_build command which allows fails."]
 fn query_builder_unresolved_by_name() {
-    // let world = create_world();
-
-    // let q = world.query::<()>()
-    //     .flags(EcsQueryAllowUnresolvedByName)
-    //     .expr("$this == Foo")
-    //     .set_cache_kind(QueryCacheKind::Auto)
-    //     .build();
+    let world = World::new();
 
-    // assert!(q);
+    let q = world
+        .query::<()>()
+        .query_flags(QueryFlags::AllowUnresolvedByName)
+        .expr("$this == Foo")
+        .set_cache_kind(QueryCacheKind::Auto)
+        .try_build()
+        .ok()
+        .unwrap();
 
-    // test_false(q.iterable().is_true());
+    assert!(!q.is_true());
 
-    // world.entity_named("Foo");
+    world.entity_named("Foo");
 
-    // test_true(q.iterable().is_true());
+    assert!(q.is_true());
 }
 
 #[test]
@@ -43,6 +43,328 @@ fn query_rust_pass_query_to_system() {
     sys.run();
 }
 
+#[test]
+fn query_rust_term_access_from_type() {
+    let world = World::new();
+
+    let q = world
+        .query::<(&Position, &mut Velocity, Option<&Mass>)>()
+        .with::<Other>()
+        .with::<(Likes, Apples)>()
+        .with::<&Value>()
+        .with::<&mut Count2>()
+        .build();
+
+    assert_eq!(q.term(0).inout(), InOutKind::In);
+    assert_eq!(q.term(1).inout(), InOutKind::InOut);
+    assert_eq!(q.term(2).inout(), InOutKind::In);
+    assert_eq!(q.term(3).inout(), InOutKind::None);
+    assert_eq!(q.term(4).inout(), InOutKind::None);
+    assert_eq!(q.term(5).inout(), InOutKind::In);
+    assert_eq!(q.term(6).inout(), InOutKind::InOut);
+}
+
+#[test]
+fn query_rust_term_without_access_does_not_mark_changed() {
+    let world = World::new();
+
+    world.entity().set(Position { x: 1, y: 2 });
+
+    let q_read = world.query::<&Position>().set_cached().build();
+    let q_filter = world.query::<()>().with::<Position>().build();
+    let q_write = world.query::<&mut Position>().build();
+
+    // first call enables change detection
+    assert!(q_read.is_changed());
+    q_read.each(|_| {});
+    assert!(!q_read.is_changed());
+
+    q_filter.each_entity(|_, _| {});
+    assert!(!q_read.is_changed());
+
+    q_write.each(|_| {});
+    assert!(q_read.is_changed());
+}
+
+#[test]
+fn query_rust_wildcard_pair_matched_second() {
+    let world = World::new();
+
+    let e1 = world
+        .entity()
+        .set_pair::<Position, Apples>(Position { x: 1, y: 2 })
+        .set_pair::<Position, Pears>(Position { x: 3, y: 4 });
+    let e2 = world
+        .entity()
+        .set_pair::<Position, Apples>(Position { x: 5, y: 6 });
+
+    let apples = world.component_id::<Apples>();
+    let pears = world.component_id::<Pears>();
+
+    let query = world.new_query::<&(Position, flecs::Wildcard)>();
+
+    let mut matched = Vec::new();
+    query.each_iter(|it, index, p| {
+        let pair = it.pair(0).unwrap();
+        assert_eq!(pair.first_id(), world.component_id::<Position>());
+        matched.push((it.entity(index).id(), pair.second_id().id(), p.x, p.y));
+    });
+    matched.sort();
+
+    let mut expected = vec![
+        (e1.id(), apples, 1, 2),
+        (e1.id(), pears, 3, 4),
+        (e2.id(), apples, 5, 6),
+    ];
+    expected.sort();
+    assert_eq!(matched, expected);
+
+    let mut count = 0;
+    query.run(|mut it| {
+        while it.next() {
+            let second = *it.pair(0).unwrap().second_id().id();
+            let p = it.field::<Position>(0).unwrap();
+            assert!(it.field::<Velocity>(0).is_none());
+            for i in it.iter() {
+                if second == *pears {
+                    assert_eq!(p[i].x, 3);
+                }
+                count += 1;
+            }
+        }
+    });
+    assert_eq!(count, 3);
+}
+
+#[test]
+fn query_rust_dsl_macro() {
+    let world = World::new();
+
+    let parent = world.entity().add::<TagA>();
+    let other_parent = world.entity();
+
+    let e1 = world
+        .entity()
+        .child_of_id(parent)
+        .set(Position { x: 1, y: 2 });
+    world
+        .entity()
+        .child_of_id(parent)
+        .set(Position { x: 3, y: 4 })
+        .set(Velocity { x: 1, y: 1 });
+    world
+        .entity()
+        .child_of_id(other_parent)
+        .set(Position { x: 5, y: 6 });
+
+    let dsl = query!(
+        world,
+        &Position,
+        !Velocity,
+        (flecs::ChildOf, $"parent"),
+        TagA($"parent")
+    )
+    .build();
+
+    let builder = world
+        .query::<&Position>()
+        .without::<Velocity>()
+        .with::<flecs::ChildOf>()
+        .set_second_name("$parent")
+        .with::<TagA>()
+        .set_src_name("$parent")
+        .build();
+
+    assert_eq!(dsl.to_string(), builder.to_string());
+
+    let mut matched = Vec::new();
+    dsl.each_entity(|e, p| matched.push((e.id(), p.x)));
+    assert_eq!(matched, [(e1.id(), 1)]);
+}
+
+#[test]
+fn query_try_build() {
+    let world = World::new();
+    world.component_named::<Position>("Position");
+    world.component_named::<Velocity>("Velocity");
+    world.entity().set(Position { x: 10, y: 20 });
+
+    let Err(err) = world.query::<()>().expr("Position, (").try_build() else {
+        panic!("query with syntax error should fail to build");
+    };
+    assert!(!err.message().is_empty());
+    assert!(err.to_string().starts_with("invalid query: "));
+
+    let Err(err) = world.query::<()>().expr("Unknown").try_build() else {
+        panic!("query with unresolved identifier should fail to build");
+    };
+    assert!(err.message().contains("Unknown"));
+
+    let query = world
+        .query::<&Position>()
+        .expr("!Velocity")
+        .try_build()
+        .ok()
+        .unwrap();
+    assert_eq!(query.count(), 1);
+}
+
+#[test]
+fn query_iter_to_str() {
+    let world = World::new();
+
+    world.entity_named("e").set(Position { x: 1, y: 2 });
+
+    let mut count = 0;
+    world.query::<&Position>().build().run(|mut it| {
+        while it.next() {
+            let result = it.to_str();
+            assert!(result.contains("Position"));
+            count += 1;
+        }
+    });
+    assert_eq!(count, 1);
+}
+
+#[test]
+fn query_fold_sum_map() {
+    use core::cell::Cell;
+
+    let world = World::new();
+
+    world.entity().set(Position { x: 1, y: 2 });
+    world
+        .entity()
+        .set(Position { x: 3, y: 4 })
+        .set(Velocity { x: 1, y: 1 });
+    world.entity().set(Position { x: 5, y: 6 });
+
+    let query = world.new_query::<&Position>();
+    assert_eq!(query.fold(0, |acc, p| acc + p.x * p.y), 2 + 12 + 30);
+    assert_eq!(query.sum_by(|p| p.x), 9);
+    assert_eq!(query.filter(|p| p.x > 2).count(), 2);
+
+    let mut xs: Vec<_> = query.map(|p| p.x).collect();
+    xs.sort();
+    assert_eq!(xs, [1, 3, 5]);
+
+    let mut large: Vec<_> = query.filter_map(|p| (p.y > 3).then_some(p.y)).collect();
+    large.sort();
+    assert_eq!(large, [4, 6]);
+
+    // the values are computed lazily, one entity at a time
+    let calls = Cell::new(0);
+    let mut lazy = query.map(|p| {
+        calls.set(calls.get() + 1);
+        p.x
+    });
+    assert_eq!(calls.get(), 0);
+    lazy.next();
+    assert_eq!(calls.get(), 1);
+    drop(lazy);
+
+    // the tables are unlocked after an iterator that was not exhausted is dropped
+    let first = query.filter(|p| p.x > 2).next().unwrap();
+    first.add::<TagA>();
+    assert!(first.has::<TagA>());
+
+    let moving_entity = query.filter(|p| p.x == 3).next().unwrap();
+    assert!(moving_entity.has::<Velocity>());
+
+    let moving = world.new_query::<(&Position, &Velocity)>();
+    assert_eq!(moving.sum_by(|(p, v)| p.x + v.x), 4);
+
+    let empty = world.new_query::<&Mass>();
+    assert_eq!(empty.fold(7, |acc, m| acc + m.value), 7);
+    assert_eq!(empty.map(|m| m.value).next(), None);
+}
+
+#[test]
+fn query_single() {
+    let world = World::new();
+
+    let query = world.new_query::<&Position>();
+    assert_eq!(query.single(), Err(FirstOnlyError::NoEntities));
+    assert!(!query.is_true());
+
+    let e = world.entity().set(Position { x: 1, y: 2 });
+    assert_eq!(query.single(), Ok(e));
+    assert_eq!(query.try_first_only(|p| p.x), Ok(1));
+    assert!(query.is_true());
+
+    // the second match is in another table
+    world
+        .entity()
+        .set(Position { x: 3, y: 4 })
+        .set(Velocity { x: 0, y: 0 });
+    assert_eq!(query.single(), Err(FirstOnlyError::MoreThanOneEntity));
+    assert_eq!(
+        query.try_first_only(|p| p.x),
+        Err(FirstOnlyError::MoreThanOneEntity)
+    );
+    assert_eq!(query.try_first_entity(), Some(e));
+
+    // the callback doesn't run when there is more than one match
+    let query_mut = world.new_query::<&mut Position>();
+    assert_eq!(
+        query_mut.try_first_only(|p| p.x = 10),
+        Err(FirstOnlyError::MoreThanOneEntity)
+    );
+    e.get::<&Position>(|p| assert_eq!(p.x, 1));
+
+    // the only match can be in a later table
+    e.destruct();
+    assert_eq!(query.try_first_only(|p| p.x), Ok(3));
+}
+
+#[test]
+fn query_deterministic_order() {
+    let world = World::new();
+    assert!(!world.is_deterministic());
+
+    let e1 = world.entity();
+    let e2 = world.entity();
+    let e3 = world.entity();
+
+    // tables are created in a different order than the entities
+    e3.set(Position { x: 3, y: 0 }).add::<TagA>();
+    e2.set(Position { x: 2, y: 0 });
+    e1.set(Position { x: 1, y: 0 }).set(Velocity { x: 0, y: 0 });
+
+    let by_table = world.new_query::<&Position>();
+    assert_ne!(by_table.map(|p| p.x).collect::<Vec<_>>(), [1, 2, 3]);
+
+    world.set_deterministic(true);
+    assert!(world.is_deterministic());
+
+    let by_id = world.new_query::<&Position>();
+    assert_eq!(by_id.map(|p| p.x).collect::<Vec<_>>(), [1, 2, 3]);
+
+    // entities moving between tables keep their place
+    e1.remove::<Velocity>();
+    e2.add::<TagA>();
+    assert_eq!(by_id.map(|p| p.x).collect::<Vec<_>>(), [1, 2, 3]);
+
+    // an explicit order is kept
+    let descending = world
+        .query::<&Position>()
+        .order_by::<Position>(|_e1, p1: &Position, _e2, p2: &Position| -> i32 {
+            (p1.x < p2.x) as i32 - (p1.x > p2.x) as i32
+        })
+        .build();
+    assert_eq!(descending.map(|p| p.x).collect::<Vec<_>>(), [3, 2, 1]);
+
+    #[derive(Component, Default)]
+    struct Order(Vec<Entity>);
+
+    world.set(Order::default());
+    world.system::<&Position>().each_entity(|e, _| {
+        e.world().get::<&mut Order>(|order| order.0.push(e.id()));
+    });
+    world.progress();
+    world.get::<&Order>(|order| assert_eq!(order.0, [e1.id(), e2.id(), e3.id()]));
+}
+
 #[test]
 fn test_trait_query() {
     pub trait Shapes {
@@ -118,3 +440,623 @@ fn test_trait_query() {
 
     assert_eq!(count, 6);
 }
+
+#[derive(Component, Clone, Copy, PartialEq, Debug)]
+struct Speed(i32);
+
+#[test]
+fn query_extract_columns() {
+    let world = World::new();
+
+    let parent = world.entity().set(Speed(10));
+    let a = world.entity().set(Position { x: 1, y: 2 }).set(Speed(1));
+    let b = world
+        .entity()
+        .set(Position { x: 3, y: 4 })
+        .set(Speed(2))
+        .add::<TagA>();
+    let c = world.entity().set(Position { x: 5, y: 6 }).set(Speed(3));
+
+    let query = world.new_query::<(&Position, &Speed)>();
+    let (entities, (positions, speeds)) = query.extract::<(Vec<Position>, Vec<Speed>)>();
+    assert_eq!(entities, [a.id(), c.id(), b.id()]);
+    assert_eq!(
+        positions.iter().map(|p| (p.x, p.y)).collect::<Vec<_>>(),
+        [(1, 2), (5, 6), (3, 4)]
+    );
+    assert_eq!(speeds, [Speed(1), Speed(3), Speed(2)]);
+
+    // a single column
+    let (_, speeds) = query.extract::<Vec<Speed>>();
+    assert_eq!(speeds.len(), 3);
+
+    // shared components are repeated for each entity
+    let d = world
+        .entity()
+        .set(Position { x: 7, y: 8 })
+        .child_of_id(parent);
+    let e = world
+        .entity()
+        .set(Position { x: 9, y: 0 })
+        .child_of_id(parent);
+    let query = world.query::<(&Position, &Speed)>().term_at(1).up().build();
+    let (entities, (positions, speeds)) = query.extract::<(Vec<Position>, Vec<Speed>)>();
+    assert_eq!(entities, [d.id(), e.id()]);
+    assert_eq!(positions.len(), 2);
+    assert_eq!(speeds, [Speed(10), Speed(10)]);
+}
+
+#[test]
+#[should_panic]
+fn query_extract_not_a_field() {
+    let world = World::new();
+    world.entity().set(Position { x: 1, y: 2 }).set(Speed(1));
+
+    let query = world.new_query::<&Position>();
+    let _ = query.extract::<Vec<Speed>>();
+}
+
+#[test]
+fn query_extract_not_a_field_finishes_iterator() {
+    let world = World::new();
+    let e = world.entity().set(Position { x: 1, y: 2 }).set(Speed(1));
+
+    let query = world.new_query::<&Position>();
+    let result = std::panic::catch_unwind(core::panic::AssertUnwindSafe(|| {
+        query.extract::<Vec<Speed>>()
+    }));
+    assert!(result.is_err());
+
+    // the iterator was finished before the panic, so the query can be iterated again
+    e.add::<TagA>();
+    let (entities, positions) = query.extract::<Vec<Position>>();
+    assert_eq!(entities, [e.id()]);
+    assert_eq!(positions[0].y, 2);
+}
+
+#[test]
+fn query_column_chunks() {
+    let world = World::new();
+
+    let a = world.entity().set(Position { x: 1, y: 2 }).set(Speed(1));
+    let b = world
+        .entity()
+        .set(Position { x: 3, y: 4 })
+        .set(Speed(2))
+        .add::<TagA>();
+    let c = world.entity().set(Position { x: 5, y: 6 }).set(Speed(3));
+
+    let query = world.new_query::<(&mut Position, &Speed)>();
+
+    let mut chunks = Vec::new();
+    query.column_chunks::<Speed>(|entities, speeds| {
+        assert_eq!(entities.len(), speeds.len());
+        chunks.push((entities.to_vec(), speeds.to_vec()));
+    });
+    assert_eq!(
+        chunks,
+        [
+            (vec![a.id(), c.id()], vec![Speed(1), Speed(3)]),
+            (vec![b.id()], vec![Speed(2)])
+        ]
+    );
+
+    query.column_chunks_mut::<Position>(|_, positions| {
+        for position in positions {
+            position.x *= 10;
+        }
+    });
+    a.get::<&Position>(|p| assert_eq!(p.x, 10));
+    b.get::<&Position>(|p| assert_eq!(p.x, 30));
+    c.get::<&Position>(|p| assert_eq!(p.x, 50));
+}
+
+#[test]
+#[should_panic]
+fn query_column_chunks_mut_read_only() {
+    let world = World::new();
+    world.entity().set(Position { x: 1, y: 2 }).set(Speed(1));
+
+    let query = world.new_query::<(&mut Position, &Speed)>();
+    query.column_chunks_mut::<Speed>(|_, _| {});
+}
+
+#[test]
+fn query_iterable_offset_limit() {
+    let world = World::new();
+
+    // entities in two tables, the page spans both
+    let mut entities = Vec::new();
+    for i in 0..4 {
+        entities.push(world.entity().set(Position { x: i, y: 0 }).id());
+    }
+    for i in 4..8 {
+        entities.push(
+            world
+                .entity()
+                .set(Position { x: i, y: 0 })
+                .add::<TagA>()
+                .id(),
+        );
+    }
+
+    let query = world.new_query::<&Position>();
+
+    let mut page = Vec::new();
+    query
+        .iterable()
+        .offset(2)
+        .limit(4)
+        .each_entity(|e, p| page.push((e.id(), p.x)));
+    assert_eq!(
+        page,
+        [
+            (entities[2], 2),
+            (entities[3], 3),
+            (entities[4], 4),
+            (entities[5], 5)
+        ]
+    );
+
+    let mut xs = Vec::new();
+    query.iterable().limit(3).each(|p| xs.push(p.x));
+    assert_eq!(xs, [0, 1, 2]);
+
+    let mut xs = Vec::new();
+    query.iterable().offset(5).each(|p| xs.push(p.x));
+    assert_eq!(xs, [5, 6, 7]);
+
+    let mut xs = Vec::new();
+    query.iterable().offset(6).limit(10).run(|mut it| {
+        while it.next() {
+            let p = it.field::<Position>(0).unwrap();
+            for i in it.iter() {
+                xs.push(p[i].x);
+            }
+        }
+    });
+    assert_eq!(xs, [6, 7]);
+
+    assert_eq!(query.iterable().offset(3).limit(2).count(), 2);
+    assert_eq!(query.iterable().offset(8).count(), 0);
+
+    // iterating a page twice fails like iterating any iterable twice, instead of advancing the
+    // finished iterator
+    let mut iter = query.iterable();
+    iter.offset(2).limit(3);
+    let mut xs = Vec::new();
+    iter.each(|p| xs.push(p.x));
+    assert_eq!(xs, [2, 3, 4]);
+    assert!(matches!(
+        world.catch_assert(|| iter.each(|_| {})),
+        Err(FlecsError::Assert(_))
+    ));
+}
+
+#[test]
+fn query_get_for() {
+    let world = World::new();
+
+    let parent = world.entity().set(Velocity { x: 5, y: 6 });
+    let a = world
+        .entity()
+        .set(Position { x: 1, y: 2 })
+        .child_of_id(parent);
+    let b = world
+        .entity()
+        .set(Position { x: 3, y: 4 })
+        .child_of_id(parent);
+    let no_parent = world.entity().set(Position { x: 7, y: 8 });
+
+    let query = world
+        .query::<(&mut Position, &Velocity)>()
+        .term_at(1)
+        .parent()
+        .set_cached()
+        .build();
+
+    assert!(query.get_for(b, |(p, v)| {
+        assert_eq!(p.x, 3);
+        assert_eq!(v.x, 5);
+        p.x += v.x;
+    }));
+    b.get::<&Position>(|p| assert_eq!(p.x, 8));
+    // other entities in the table are not affected
+    a.get::<&Position>(|p| assert_eq!(p.x, 1));
+
+    assert!(!query.get_for(no_parent, |_| panic!("entity doesn't match")));
+    assert!(!query.get_for(parent, |_| panic!("entity doesn't match")));
+
+    b.destruct();
+    assert!(!query.get_for(b, |_| panic!("entity is not alive")));
+
+    let uncached = world
+        .query::<(&Position, &Velocity)>()
+        .term_at(1)
+        .parent()
+        .build();
+    assert!(uncached.get_for(a, |(p, v)| {
+        assert_eq!(p.y, 2);
+        assert_eq!(v.y, 6);
+    }));
+}
+
+#[test]
+fn query_each_while_break() {
+    use core::ops::ControlFlow;
+
+    let world = World::new();
+    world.entity().set(Position { x: 1, y: 0 });
+    world.entity().set(Position { x: 2, y: 0 });
+    world.entity().set(Position { x: 3, y: 0 }).add::<TagA>();
+    world.entity().set(Position { x: 4, y: 0 }).add::<TagB>();
+
+    let query = world.new_query::<&Position>();
+
+    let mut visited = 0;
+    let flow = query.each_while(|p| {
+        visited += 1;
+        if p.x >= 2 {
+            return ControlFlow::Break(p.x);
+        }
+        ControlFlow::Continue(())
+    });
+    assert_eq!(flow, ControlFlow::Break(2));
+    assert_eq!(visited, 2);
+
+    let flow = query.each_while(|_| ControlFlow::<()>::Continue(()));
+    assert_eq!(flow, ControlFlow::Continue(()));
+
+    let mut tables = 0;
+    let flow = query.iter_while(|it| {
+        tables += 1;
+        if it.table().unwrap().has_type::<TagA>() {
+            return ControlFlow::Break(it.count());
+        }
+        ControlFlow::Continue(())
+    });
+    assert_eq!(flow, ControlFlow::Break(1));
+    assert_eq!(tables, 2);
+
+    // the tables are unlocked, so entities can be moved out of them
+    world.defer(|world| {
+        query.each_entity(|e, _| {
+            world.entity_from_id(e).add::<Velocity>();
+        });
+    });
+    assert_eq!(world.new_query::<&Velocity>().count(), 4);
+}
+
+#[test]
+fn query_find_stops_at_first_match() {
+    let world = World::new();
+    let first = world.entity().set(Position { x: 1, y: 0 });
+    world.entity().set(Position { x: 1, y: 0 }).add::<TagA>();
+
+    let query = world.new_query::<&Position>();
+    assert_eq!(query.find(|p| p.x == 1), Some(first));
+    assert_eq!(query.find_entity(|_, p| p.x == 1), Some(first));
+    assert_eq!(query.find_iter(|_, _, p| p.x == 1), Some(first));
+}
+
+#[test]
+fn query_run_break_early() {
+    let world = World::new();
+    let e = world.entity().set(Position { x: 1, y: 0 });
+    world.entity().set(Position { x: 2, y: 0 }).add::<TagA>();
+
+    let query = world.new_query::<&Position>();
+    let mut tables = 0;
+    query.run(|mut it| {
+        // only the first table is iterated
+        if it.next() {
+            tables += 1;
+        }
+    });
+    assert_eq!(tables, 1);
+
+    // the table of the entity is not left locked
+    e.add::<TagB>();
+    assert!(e.has::<TagB>());
+}
+
+#[test]
+fn query_by_name() {
+    let world = World::new();
+    world.entity().set(Position { x: 1, y: 2 });
+    world
+        .entity()
+        .set(Position { x: 3, y: 4 })
+        .set(Velocity { x: 1, y: 1 });
+
+    world
+        .query_named::<&Position>("positions")
+        .set_cached()
+        .build();
+    world.new_query_named::<(&Position, &Velocity)>("movement::moving");
+
+    let positions = world.query_by_name("positions");
+    assert_eq!(positions.count(), 2);
+    assert_eq!(positions.entity().name(), "positions");
+
+    let moving = world.try_query_by_name("movement::moving").unwrap();
+    assert_eq!(moving.count(), 1);
+
+    assert!(world.try_query_by_name("velocities").is_none());
+    world.entity_named("not_a_query");
+    assert!(world.try_query_by_name("not_a_query").is_none());
+}
+
+#[test]
+fn query_iter_vars() {
+    let world = World::new();
+
+    let apples = world.entity_named("apples");
+    let pears = world.entity_named("pears");
+    let bob = world.entity_named("bob").add_first::<TagA>(apples);
+    world.entity_named("alice").add_first::<TagA>(pears);
+
+    let query = world
+        .query::<()>()
+        .with::<&TagA>()
+        .second()
+        .set_var("food")
+        .build();
+
+    let mut found = Vec::new();
+    query.run(|mut it| {
+        while it.next() {
+            assert!(it.try_get_var_by_name("snack").is_none());
+            let vars = it.vars();
+            assert_eq!(vars.len(), 1);
+            assert_eq!(vars[0].0, "food");
+            found.push((it.entity(0).id(), vars[0].1.id()));
+        }
+    });
+    assert_eq!(found.len(), 2);
+    assert!(found.contains(&(bob.id(), apples.id())));
+
+    let mut json = String::new();
+    query.run(|mut it| json = it.to_json(None).unwrap());
+    assert!(json.contains("\"bob\""));
+    assert!(json.contains("\"alice\""));
+}
+
+#[test]
+fn query_runtime_ids() {
+    let world = World::new();
+
+    let health = world
+        .component_untyped_named("Health")
+        .member::<i32>("value")
+        .id();
+    let likes = world.entity_named("Likes");
+    let apples = world.entity_named("Apples");
+    let hidden = world.entity_named("Hidden");
+
+    let new_entity = || unsafe { world.entity().set_ptr(health, (&0i32 as *const i32).cast()) };
+    let e1 = new_entity().add_id((likes, apples));
+    let e2 = new_entity().add_id((likes, apples));
+    new_entity().add_id((likes, apples)).add_id(hidden);
+    new_entity();
+
+    let query = world
+        .query::<()>()
+        .with_id(health)
+        .with_pair_ids(likes, apples)
+        .without_id(hidden)
+        .build();
+
+    query.each_iter(|it, row, _| {
+        let mut cursor = it.field_cursor(0, row as i32);
+        cursor.push();
+        cursor.member("value");
+        cursor.set_int(*it.entity(row).id() as i64);
+    });
+
+    let mut found = Vec::new();
+    query.run(|mut it| {
+        while it.next() {
+            let field = it.field_untyped(0);
+            assert_eq!(field.len(), it.count());
+            assert_eq!(field.size(), core::mem::size_of::<i32>());
+            assert!(!field.is_shared());
+            for row in 0..field.len() {
+                let value = unsafe { *(field.at(row) as *const i32) };
+                found.push((it.entity(row).id(), value));
+            }
+        }
+    });
+    assert_eq!(
+        found,
+        [(e1.id(), *e1.id() as i32), (e2.id(), *e2.id() as i32)]
+    );
+
+    let without_pair = world
+        .query::<()>()
+        .with_id(health)
+        .without_pair_ids(likes, apples)
+        .build();
+    assert_eq!(without_pair.count(), 1);
+}
+
+#[test]
+#[should_panic]
+fn query_field_cursor_read_only() {
+    let world = World::new();
+    world
+        .component_named::<Position>("Position")
+        .member::<i32>("x")
+        .member::<i32>("y");
+    world.entity().set(Position { x: 1, y: 2 });
+
+    world.new_query::<&Position>().each_iter(|it, row, _| {
+        it.field_cursor(0, row as i32);
+    });
+}
+
+#[test]
+fn query_field_untyped_raw_memory() {
+    let world = World::new();
+    world
+        .component::<Mass>()
+        .add_trait::<(flecs::OnInstantiate, flecs::Inherit)>();
+
+    let prefab = world.prefab().set(Mass { value: 7 });
+    world.entity().set(Position { x: 1, y: 2 }).is_a_id(prefab);
+    world.entity().set(Position { x: 3, y: 4 }).is_a_id(prefab);
+
+    let query = world
+        .query::<(&Position, &Mass)>()
+        .term_at(1)
+        .up_id(flecs::IsA::ID)
+        .build();
+
+    // copy the columns without knowing their types, like a replication layer would
+    let mut positions = Vec::new();
+    let mut masses = Vec::new();
+    query.run(|mut it| {
+        while it.next() {
+            let position = it.field_untyped(0);
+            assert_eq!(position.stride(), core::mem::size_of::<Position>());
+            positions.extend_from_slice(unsafe { position.as_bytes() });
+
+            let mass = it.field_untyped(1);
+            assert!(mass.is_shared());
+            assert_eq!(mass.stride(), 0);
+            for row in 0..it.count() {
+                let value = unsafe { mass.as_ptr().add(row * mass.stride()) };
+                masses.push(unsafe { (*(value as *const Mass)).value });
+            }
+        }
+    });
+    assert_eq!(positions.len(), 2 * core::mem::size_of::<Position>());
+    assert_eq!(masses, [7, 7]);
+
+    // write the copied memory back
+    query.run(|mut it| {
+        while it.next() {
+            let mut position = it.field_untyped(0);
+            let bytes = unsafe { position.as_bytes_mut() };
+            bytes.copy_from_slice(&positions[..bytes.len()]);
+            bytes[..4].copy_from_slice(&10i32.to_ne_bytes());
+        }
+    });
+    query.each(|(pos, _)| assert!(pos.x == 10 || pos.x == 3));
+}
+
+#[test]
+fn query_modify_term() {
+    let world = World::new();
+
+    let parent = world.entity().set(Mass { value: 1 });
+    let child = world.entity().child_of_id(parent).set(Mass { value: 2 });
+    let other = world.entity().set(Mass { value: 3 });
+    world.entity().set(Position { x: 0, y: 0 });
+
+    let mut query = world
+        .query::<(&Position, &Mass)>()
+        .term_at(1)
+        .set_src_id(parent)
+        .build();
+    let mut values = Vec::new();
+    query.each(|(_, mass)| values.push(mass.value));
+
+    query.modify_term(1, |term| {
+        term.set_src_id(other);
+    });
+    assert_eq!(query.term(1).src_id(), other.id());
+    query.each(|(_, mass)| values.push(mass.value));
+    assert_eq!(values, [1, 3]);
+
+    // toggle between matching the entity itself and its parent
+    let mut query = world.query::<&Mass>().build();
+    assert_eq!(query.count(), 3);
+    query.modify_term(0, |term| {
+        term.up();
+    });
+    let mut found = Vec::new();
+    query.each_entity(|e, mass| found.push((e.id(), mass.value)));
+    assert_eq!(found, [(child.id(), 1)]);
+    query.modify_term(0, |term| {
+        term.self_();
+    });
+    assert_eq!(query.count(), 3);
+
+    // the query is unchanged when the term is invalid
+    assert!(
+        query
+            .try_modify_term(0, |term| {
+                term.up_id(world.entity());
+            })
+            .is_err()
+    );
+    assert_eq!(query.count(), 3);
+
+    let clone = query.clone();
+    assert!(
+        query
+            .try_modify_term(0, |term| {
+                term.up();
+            })
+            .is_err()
+    );
+    drop(clone);
+
+    let mut cached = world.query::<&Mass>().set_cached().build();
+    assert!(
+        cached
+            .try_modify_term(0, |term| {
+                term.up();
+            })
+            .is_err()
+    );
+}
+
+#[test]
+fn query_iter_entities() {
+    let world = World::new();
+
+    let mut created = Vec::new();
+    for i in 0..3000 {
+        created.push(world.entity().set(Position { x: i, y: 0 }).id());
+    }
+    world.entity().set(Position { x: 0, y: 0 }).add::<TagA>();
+
+    let query = world.new_query::<&mut Position>();
+
+    let mut seen = Vec::new();
+    query.run(|mut it| {
+        while it.next() {
+            assert!(!it.is_empty());
+            let entities = it.entities();
+            assert_eq!(entities.len(), it.count());
+            for (row, &e) in entities.iter().enumerate() {
+                assert_eq!(it.entity(row).id(), e);
+            }
+            seen.extend_from_slice(entities);
+        }
+    });
+    assert_eq!(seen.len(), 3001);
+    assert_eq!(seen[..3000], created[..]);
+}
+
+#[test]
+#[cfg(feature = "flecs_parallel")]
+fn query_iter_par_for_each() {
+    let world = World::new();
+
+    for i in 0..3000 {
+        world.entity().set(Position { x: i, y: 0 });
+    }
+    world.entity().set(Position { x: 0, y: 0 }).add::<TagA>();
+
+    let query = world.new_query::<&mut Position>();
+    query.run(|mut it| {
+        while it.next() {
+            it.par_for_each::<Position>(0, |e, p| p.y = p.x + *e as i32);
+        }
+    });
+
+    query.each_entity(|e, p| assert_eq!(p.y, p.x + *e.id() as i32));
+}
@@ -1343,3 +1343,75 @@ fn filter_does_not_panic() {
         let _ = entity.cloned::<&Foo>();
     });
 }
+
+#[test]
+#[cfg(feature = "flecs_safety_access_journal")]
+fn access_journal_reports_both_threads() {
+    let world = World::new();
+    let entity = world.entity().set(Foo(0));
+    let id = entity.id();
+
+    world.set_stage_count(2);
+    world.readonly_begin(false);
+    let stage = world.stage(1);
+
+    let message = entity.get::<&Foo>(|_| {
+        std::thread::scope(|scope| {
+            let payload = std::thread::Builder::new()
+                .name("writer".into())
+                .spawn_scoped(scope, move || {
+                    stage.entity_from_id(id).get::<&mut Foo>(|_| {});
+                })
+                .unwrap()
+                .join()
+                .unwrap_err();
+            payload.downcast::<String>().map(|s| *s).unwrap_or_default()
+        })
+    });
+    world.readonly_end();
+
+    assert!(message.contains("write on thread 'writer'"), "{message}");
+    assert!(
+        message.contains("conflicts with read on thread"),
+        "{message}"
+    );
+    assert!(message.contains(file!()), "{message}");
+}
+
+#[test]
+#[cfg(feature = "flecs_safety_access_journal")]
+fn access_journal_keeps_application_borrow_after_system() {
+    let world = World::new();
+    let entity = world.entity().set(Foo(0));
+    let id = entity.id();
+    let system = world.system::<&Foo>().each(|_| {});
+
+    world.set_stage_count(2);
+    world.readonly_begin(false);
+    let stage = world.stage(1);
+
+    let message = entity.get::<&Foo>(|_| {
+        // the borrow of the system isn't journaled, so releasing it must keep the one above
+        system.run_worker(0, 2, 0.0, core::ptr::null_mut());
+
+        std::thread::scope(|scope| {
+            let payload = std::thread::Builder::new()
+                .name("writer".into())
+                .spawn_scoped(scope, move || {
+                    stage.entity_from_id(id).get::<&mut Foo>(|_| {});
+                })
+                .unwrap()
+                .join()
+                .unwrap_err();
+            payload.downcast::<String>().map(|s| *s).unwrap_or_default()
+        })
+    });
+    world.readonly_end();
+
+    assert!(message.contains("write on thread 'writer'"), "{message}");
+    assert!(
+        message.contains("conflicts with read on thread"),
+        "{message}"
+    );
+    assert!(message.contains(file!()), "{message}");
+}
@@ -2435,3 +2435,530 @@ fn system_register_twice_w_each_run() {
         assert_eq!(count.b, 1);
     });
 }
+
+#[test]
+fn system_measure_time_spent() {
+    let world = World::new();
+    world.measure_system_time(true);
+
+    let sys = world
+        .system::<&Position>()
+        .each(|_p| std::thread::sleep(core::time::Duration::from_millis(1)));
+
+    world.entity().set(Position { x: 10, y: 20 });
+    world.entity().set(Position { x: 30, y: 40 });
+
+    assert_eq!(sys.time_spent(), 0.0);
+
+    world.progress();
+
+    assert!(sys.time_spent() > 0.0);
+    assert!(world.info().system_time_total > 0.0);
+    assert_eq!(sys.query().count(), 2);
+}
+
+#[test]
+fn system_fixed_timestep() {
+    use flecs_ecs::addons::pipeline::FixedTimestep;
+
+    #[derive(Component)]
+    struct FixedUpdate;
+
+    let world = World::new();
+    world.set(Count(0));
+
+    let fixed_pipeline = world
+        .pipeline()
+        .with_id(flecs::system::System::ID)
+        .with::<&FixedUpdate>()
+        .build();
+
+    world
+        .system::<&mut Count>()
+        .kind::<FixedUpdate>()
+        .term_at(0)
+        .singleton()
+        .run(|mut it| {
+            while it.next() {
+                assert_eq!(it.delta_time(), 0.25);
+                assert_eq!(it.delta_system_time(), 0.25);
+                it.field_mut::<Count>(0).unwrap()[0].0 += 1;
+            }
+        });
+
+    let mut fixed = FixedTimestep::new(fixed_pipeline, 0.25);
+
+    // the default pipeline doesn't run fixed systems
+    world.progress_time(0.625);
+    world.get::<&Count>(|count| assert_eq!(count.0, 0));
+
+    assert_eq!(fixed.run(&world, world.info().delta_time), 2);
+    assert_eq!(fixed.alpha(), 0.5);
+    world.get::<&Count>(|count| assert_eq!(count.0, 2));
+
+    assert_eq!(fixed.run(&world, 0.0625), 0);
+    assert_eq!(fixed.run(&world, 0.0625), 1);
+    assert_eq!(fixed.alpha(), 0.0);
+
+    // excess time is dropped when the step limit is reached
+    fixed.set_max_steps(2);
+    assert_eq!(fixed.run(&world, 1.125), 2);
+    assert_eq!(fixed.alpha(), 0.5);
+    world.get::<&Count>(|count| assert_eq!(count.0, 5));
+}
+
+#[test]
+fn system_run_pipelines_independently() {
+    #[derive(Component)]
+    struct Simulation;
+
+    #[derive(Component)]
+    struct Render;
+
+    let world = World::new();
+    world.set(Count(0));
+    world.set(Count2 { a: 0, b: 0 });
+
+    let simulation = world
+        .pipeline()
+        .with_id(flecs::system::System::ID)
+        .with::<&Simulation>()
+        .build()
+        .entity();
+
+    let render = world
+        .pipeline()
+        .with_id(flecs::system::System::ID)
+        .with::<&Render>()
+        .build()
+        .entity();
+
+    world.system::<()>().kind::<Simulation>().run(|it| {
+        assert_eq!(it.delta_time(), 0.5);
+        it.world().get::<&mut Count>(|count| count.0 += 1);
+    });
+
+    world.system::<()>().kind::<Render>().run(|it| {
+        let world = it.world();
+        world.get::<&mut Count2>(|count| count.a += 1);
+        world.post_frame(|world| world.get::<&mut Count2>(|count| count.b += 1));
+    });
+
+    world.run_pipeline_id_time(simulation, 0.5);
+    world.run_pipeline_id_time(simulation, 0.5);
+    world.get::<&Count>(|count| assert_eq!(count.0, 2));
+    world.get::<&Count2>(|count| assert_eq!((count.a, count.b), (0, 0)));
+
+    // post frame actions require a frame in progress
+    world.frame_begin(0.0);
+    world.run_pipeline_id(render);
+    world.get::<&Count2>(|count| assert_eq!((count.a, count.b), (1, 0)));
+    world.frame_end();
+    world.get::<&Count>(|count| assert_eq!(count.0, 2));
+    world.get::<&Count2>(|count| assert_eq!((count.a, count.b), (1, 1)));
+
+    // neither pipeline is the default pipeline
+    world.progress();
+    world.get::<&Count>(|count| assert_eq!(count.0, 2));
+    world.get::<&Count2>(|count| assert_eq!((count.a, count.b), (1, 1)));
+}
+
+#[test]
+fn system_pipeline_schedule() {
+    use flecs_ecs::addons::pipeline::{ScheduleStep, SystemAccess};
+
+    let world = World::new();
+
+    assert!(world.pipeline_schedule().is_none());
+    assert!(world.pipeline_to_string().is_empty());
+
+    let spawn = world
+        .system_named::<()>("Spawn")
+        .kind::<flecs::pipeline::PreUpdate>()
+        .write::<Position>()
+        .run(|mut it| while it.next() {});
+
+    let movement = world
+        .system_named::<(&mut Position, &Velocity)>("Move")
+        .each(|(_p, _v)| {});
+
+    let render = world
+        .system_named::<&Position>("Render")
+        .kind::<flecs::pipeline::OnStore>()
+        .each(|_p| {});
+
+    // inactive systems are not part of the schedule
+    world
+        .entity()
+        .set(Position { x: 1, y: 2 })
+        .set(Velocity { x: 1, y: 1 });
+
+    world.progress();
+
+    let schedule = world.pipeline_schedule().unwrap();
+    assert_eq!(schedule.sync_point_count(), 1);
+    let systems: Vec<_> = schedule.systems().map(|s| s.system).collect();
+    assert_eq!(systems, [spawn.id(), movement.id(), render.id()]);
+    assert!(matches!(schedule.steps[1], ScheduleStep::SyncPoint));
+
+    let position = world.component_id::<Position>();
+    let velocity = world.component_id::<Velocity>();
+    let spawn_access = schedule.systems().next().unwrap();
+    assert_eq!(spawn_access.deferred_writes, [position]);
+    assert_eq!(
+        spawn_access.phase,
+        Some(Entity::new(flecs::pipeline::PreUpdate::ID))
+    );
+
+    let move_access = SystemAccess::new(&world, movement);
+    assert_eq!(move_access.reads, [position, velocity]);
+    assert_eq!(move_access.writes, [position]);
+    assert!(move_access.deferred_writes.is_empty());
+
+    let text = world.pipeline_to_string();
+    assert!(text.contains("PreUpdate\n  Spawn"));
+    assert!(text.contains("sync point: Move accesses"));
+    assert!(text.contains("written by Spawn"));
+    assert!(text.contains("OnStore\n  Render reads:"));
+
+    let dot = world.pipeline_to_dot();
+    assert!(dot.starts_with("digraph pipeline {"));
+    assert!(dot.contains("shape=diamond"));
+    assert!(dot.contains("n0 -> n1;"));
+}
+
+#[test]
+fn system_pipeline_conflicts() {
+    use flecs_ecs::addons::pipeline::{ConflictReason, SystemConflict};
+
+    let world = World::new();
+
+    assert!(world.pipeline_conflicts_to_string().is_empty());
+
+    let movement = world
+        .system_named::<(&mut Position, &Velocity)>("Move")
+        .multi_threaded()
+        .each(|(_p, _v)| {});
+
+    let damp = world
+        .system_named::<&mut Velocity>("Damp")
+        .multi_threaded()
+        .each(|_v| {});
+
+    let grow = world
+        .system_named::<&mut Mass>("Grow")
+        .multi_threaded()
+        .each(|_m| {});
+
+    let log = world
+        .system_named::<&Mass>("Log")
+        .immediate(true)
+        .each(|_m| {});
+
+    world
+        .entity()
+        .set(Position { x: 1, y: 2 })
+        .set(Velocity { x: 1, y: 1 })
+        .set(Mass { value: 1 });
+
+    world.progress();
+
+    let velocity = world.component_id::<Velocity>();
+    let schedule = world.pipeline_schedule().unwrap();
+    let conflicts = schedule.conflicts();
+
+    assert_eq!(
+        conflicts[0],
+        SystemConflict {
+            first: movement.id(),
+            second: damp.id(),
+            reason: ConflictReason::ReadWrite(velocity.into()),
+        }
+    );
+    // systems that access different components don't conflict
+    assert!(
+        !conflicts
+            .iter()
+            .any(|c| c.first == movement.id() && c.second == grow.id())
+    );
+    assert!(conflicts.contains(&SystemConflict {
+        first: grow.id(),
+        second: log.id(),
+        reason: ConflictReason::Immediate(log.id()),
+    }));
+
+    let table = world.pipeline_conflicts_to_string();
+    assert!(table.starts_with("first"));
+    assert!(table.contains("Move  | Damp   | read/write"));
+    assert!(table.contains("Log is immediate"));
+    assert!(table.contains("not multi threaded"));
+}
+
+#[test]
+fn system_script_callback() {
+    #[derive(Component)]
+    #[meta]
+    struct Health {
+        value: i32,
+    }
+
+    let world = World::new();
+    world.component_named::<Health>("Health").meta();
+
+    world.register_callback("regenerate", |it, row| {
+        it.entity(row)
+            .get::<&mut Health>(|health| health.value += 1);
+    });
+    assert!(world.callbacks().contains("regenerate"));
+    assert!(!world.callbacks().contains("poison"));
+
+    world.run_code(
+        "callbacks.flecs",
+        r#"
+        using flecs.rust
+
+        regenerate_system {
+          ScriptSystem: {callback: "regenerate", query: "Health"}
+        }
+
+        poison_system {
+          ScriptSystem: {callback: "poison", query: "Health"}
+          (DependsOn, flecs.pipeline.PostUpdate)
+        }
+
+        player {
+          Health: {value: 10}
+        }
+        "#,
+    );
+
+    let regenerate = world.lookup("regenerate_system::system");
+    assert!(regenerate.has::<flecs::system::System>());
+    assert!(regenerate.has_id((flecs::DependsOn::ID, flecs::pipeline::OnUpdate::ID)));
+
+    // systems of which the callback isn't registered yet are created on registration
+    let poison = world.lookup("poison_system");
+    assert!(poison.try_lookup("system").is_none());
+
+    world.progress();
+    let player = world.lookup("player");
+    player.get::<&Health>(|health| assert_eq!(health.value, 11));
+
+    world.register_callback("poison", |it, row| {
+        it.entity(row)
+            .get::<&mut Health>(|health| health.value -= 3);
+    });
+    let poison = poison.lookup("system");
+    assert!(poison.has::<flecs::system::System>());
+    assert!(poison.has_id((flecs::DependsOn::ID, flecs::pipeline::PostUpdate::ID)));
+
+    world.progress();
+    player.get::<&Health>(|health| assert_eq!(health.value, 9));
+}
+
+#[test]
+fn system_script_callback_update() {
+    use flecs_ecs::addons::script::ScriptSystem;
+
+    #[derive(Component)]
+    struct Health {
+        value: i32,
+    }
+
+    #[derive(Component)]
+    struct Poisoned;
+
+    let world = World::new();
+    world.component_named::<Health>("Health");
+    world.component_named::<Poisoned>("Poisoned");
+
+    world.register_callback("regenerate", |it, row| {
+        it.entity(row)
+            .get::<&mut Health>(|health| health.value += 1);
+    });
+
+    let player = world.entity().set(Health { value: 10 });
+    let regenerate = world.entity_named("regenerate_system").set(ScriptSystem {
+        callback: "regenerate".into(),
+        query: "Health".into(),
+    });
+    world.progress();
+    player.get::<&Health>(|health| assert_eq!(health.value, 11));
+    let system = regenerate.lookup("system");
+
+    // setting the component again replaces the system
+    regenerate.set(ScriptSystem {
+        callback: "regenerate".into(),
+        query: "Health, Poisoned".into(),
+    });
+    world.progress();
+    player.get::<&Health>(|health| assert_eq!(health.value, 11));
+    assert!(!system.is_alive());
+    assert!(regenerate.lookup("system").has::<flecs::system::System>());
+
+    // an invalid query logs an error instead of panicking
+    regenerate.set(ScriptSystem {
+        callback: "regenerate".into(),
+        query: "Unknown".into(),
+    });
+    assert!(regenerate.try_lookup("system").is_none());
+    world.progress();
+    player.get::<&Health>(|health| assert_eq!(health.value, 11));
+}
+
+#[test]
+fn system_each_break() {
+    use core::ops::ControlFlow;
+
+    let world = World::new();
+    world.set(Mass { value: 0 });
+
+    for x in 0..3 {
+        world.entity().set(Position { x, y: 0 });
+    }
+    world.entity().set(Position { x: 3, y: 0 }).add::<TagA>();
+
+    // moves two entities per run
+    let system = world.system::<&mut Position>().each_iter(|it, _, p| {
+        p.y += 1;
+        let moved = it.world().get::<&mut Mass>(|moved| {
+            moved.value += 1;
+            moved.value
+        });
+        if moved % 2 == 0 {
+            return ControlFlow::Break(());
+        }
+        ControlFlow::Continue(())
+    });
+
+    system.run();
+    assert_eq!(world.get::<&Mass>(|moved| moved.value), 2);
+
+    system.run();
+    assert_eq!(world.get::<&Mass>(|moved| moved.value), 4);
+
+    let mut total = 0;
+    world.each::<&Position>(|p| total += p.y);
+    assert_eq!(total, 4);
+
+    world.set(Mass { value: 0 });
+    world
+        .observer::<flecs::OnSet, &Position>()
+        .each_entity(|e, p| {
+            e.world().get::<&mut Mass>(|observed| observed.value += 1);
+            if p.x > 0 {
+                return ControlFlow::Break(());
+            }
+            ControlFlow::Continue(())
+        });
+
+    world.entity().set(Position { x: 0, y: 0 });
+    world.entity().set(Position { x: 1, y: 0 });
+    assert_eq!(world.get::<&Mass>(|observed| observed.value), 2);
+}
+
+#[test]
+fn system_run_deferred() {
+    #[derive(Component)]
+    struct Registered(i32);
+
+    let world = World::new();
+    world.set(Mass { value: 0 });
+    world.entity().set(Position { x: 1, y: 2 });
+
+    world.system::<&Position>().each_iter(|it, _, _| {
+        it.world().run_deferred(|world| {
+            // registering a component and creating a query need the world
+            let query = world.new_query::<&Registered>();
+            world.entity().set(Registered(query.count()));
+            world.get::<&mut Mass>(|runs| runs.value += 1);
+        });
+    });
+
+    world.progress();
+    world.progress();
+    assert_eq!(world.get::<&Mass>(|runs| runs.value), 2);
+    assert_eq!(world.count::<Registered>(), 2);
+
+    // runs immediately when the world isn't deferred
+    world.run_deferred(|world| world.get::<&mut Mass>(|runs| runs.value += 1));
+    assert_eq!(world.get::<&Mass>(|runs| runs.value), 3);
+
+    // runs once deferring ends
+    world.defer(|world| {
+        world.run_deferred(|world| world.get::<&mut Mass>(|runs| runs.value += 1));
+        world.defer(|world| {
+            world.run_deferred(|world| world.get::<&mut Mass>(|runs| runs.value += 1));
+        });
+        assert_eq!(world.get::<&Mass>(|runs| runs.value), 3);
+    });
+    assert_eq!(world.get::<&Mass>(|runs| runs.value), 5);
+}
+
+#[test]
+fn system_time_module() {
+    use flecs_ecs::addons::time::{RealTime, Time, TimeModule};
+
+    let world = World::new();
+    world.import::<TimeModule>();
+    world.get::<&Time>(|time| assert_eq!(*time, Time::default()));
+
+    // systems in later phases see the time of the current frame
+    world
+        .system::<&Time>()
+        .term_at(0)
+        .singleton()
+        .each_iter(|it, _, time| {
+            assert_eq!(time.delta, it.delta_time());
+            assert_eq!(
+                time.frame_count,
+                it.world().info().frame_count_total as u64 + 1
+            );
+        });
+
+    world.set_time_scale(2.0);
+    world.progress_time(0.5);
+    world.progress_time(0.25);
+
+    world.get::<&Time>(|time| {
+        assert_eq!(time.delta, 0.5);
+        assert_eq!(time.elapsed, 1.5);
+        assert_eq!(time.frame_count, 2);
+    });
+    world.get::<&RealTime>(|time| {
+        assert_eq!(time.delta, 0.25);
+        assert_eq!(time.elapsed, 0.75);
+        assert_eq!(time.frame_count, 2);
+    });
+}
+
+#[test]
+fn system_try_build() {
+    let world = World::new();
+    world.component_named::<Position>("Position");
+    let e = world.entity().set(Position { x: 10, y: 20 });
+
+    let Err(err) = world
+        .system::<()>()
+        .expr("Position, (")
+        .fallible()
+        .each_entity(|e, ()| e.destruct())
+    else {
+        panic!("system with syntax error should fail to build");
+    };
+    assert!(!err.message().is_empty());
+
+    let Err(err) = world.system::<()>().expr("Unknown").try_build() else {
+        panic!("system with unresolved identifier should fail to build");
+    };
+    assert!(err.message().contains("Unknown"));
+
+    let system = world
+        .system::<&mut Position>()
+        .fallible()
+        .each(|p| p.x += 1)
+        .ok()
+        .unwrap();
+    system.run();
+    e.get::<&Position>(|p| assert_eq!(p.x, 11));
+}
@@ -2,6 +2,8 @@
 use flecs_ecs::prelude::*;
 use flecs_ecs::sys;
 
+use crate::common_test::*;
+
 #[test]
 fn world_no_panic_clone_test() {
     let world = World::default();
@@ -65,3 +67,1410 @@ fn world_finis_reentrancy() {
 
     // world will be destroyed here, and hook above will be called.
 }
+
+#[test]
+fn world_delete_with_id() {
+    let world = World::new();
+
+    let tag = world.entity();
+    let e_1 = world.entity().add_id(tag);
+    let e_2 = world.entity().add_id(tag);
+    let e_3 = world.entity().add_id(tag);
+
+    world.delete_entities_with_id(tag);
+
+    assert!(!e_1.is_alive());
+    assert!(!e_2.is_alive());
+    assert!(!e_3.is_alive());
+}
+
+#[test]
+fn world_delete_with_type() {
+    let world = World::new();
+
+    let e_1 = world.entity().add::<Position>();
+    let e_2 = world.entity().add::<Position>();
+    let e_3 = world.entity().add::<Position>();
+
+    world.delete_entities_with::<Position>();
+
+    assert!(!e_1.is_alive());
+    assert!(!e_2.is_alive());
+    assert!(!e_3.is_alive());
+}
+
+#[test]
+fn world_delete_with_pair() {
+    let world = World::new();
+
+    let rel = world.entity();
+    let obj = world.entity();
+    let e_1 = world.entity().add_id((rel, obj));
+    let e_2 = world.entity().add_id((rel, obj));
+    let e_3 = world.entity().add_id((rel, obj));
+
+    world.delete_entities_with_id((rel, obj));
+
+    assert!(!e_1.is_alive());
+    assert!(!e_2.is_alive());
+    assert!(!e_3.is_alive());
+}
+
+#[test]
+fn world_delete_with_pair_type() {
+    let world = World::new();
+
+    let e_1 = world.entity().add::<(Rel, Obj)>();
+    let e_2 = world.entity().add::<(Rel, Obj)>();
+    let e_3 = world.entity().add::<(Rel, Obj)>();
+    let e_4 = world.entity().add::<(Rel, Obj2)>();
+
+    world.delete_entities_with::<(Rel, Obj)>();
+
+    assert!(!e_1.is_alive());
+    assert!(!e_2.is_alive());
+    assert!(!e_3.is_alive());
+    assert!(e_4.is_alive());
+}
+
+#[test]
+fn world_delete_with_pair_wildcard() {
+    let world = World::new();
+
+    let e_1 = world.entity().add::<(Rel, Obj)>();
+    let e_2 = world.entity().add::<(Rel, Obj2)>();
+    let e_3 = world.entity().add::<Position>();
+
+    world.delete_entities_with::<(Rel, flecs::Wildcard)>();
+
+    assert!(!e_1.is_alive());
+    assert!(!e_2.is_alive());
+    assert!(e_3.is_alive());
+}
+
+#[test]
+fn world_remove_all_id() {
+    let world = World::new();
+
+    let tag_a = world.entity();
+    let tag_b = world.entity();
+    let e_1 = world.entity().add_id(tag_a);
+    let e_2 = world.entity().add_id(tag_a);
+    let e_3 = world.entity().add_id(tag_a).add_id(tag_b);
+
+    world.remove_all_id(tag_a);
+
+    assert!(e_1.is_alive());
+    assert!(e_2.is_alive());
+    assert!(e_3.is_alive());
+
+    assert!(!e_1.has_id(tag_a));
+    assert!(!e_2.has_id(tag_a));
+    assert!(!e_3.has_id(tag_a));
+
+    assert!(e_3.has_id(tag_b));
+}
+
+#[test]
+fn world_remove_all_type() {
+    let world = World::new();
+
+    let e_1 = world.entity().add::<Position>();
+    let e_2 = world.entity().add::<Position>();
+    let e_3 = world.entity().add::<Position>().add::<Velocity>();
+
+    world.remove_all::<Position>();
+
+    assert!(e_1.is_alive());
+    assert!(e_2.is_alive());
+    assert!(e_3.is_alive());
+
+    assert!(!e_1.has::<Position>());
+    assert!(!e_2.has::<Position>());
+    assert!(!e_3.has::<Position>());
+
+    assert!(e_3.has::<Velocity>());
+}
+
+#[test]
+fn world_remove_all_pair() {
+    let world = World::new();
+
+    let rel = world.entity();
+    let obj_a = world.entity();
+    let obj_b = world.entity();
+    let e_1 = world.entity().add_id((rel, obj_a));
+    let e_2 = world.entity().add_id((rel, obj_a));
+    let e_3 = world.entity().add_id((rel, obj_a)).add_id((rel, obj_b));
+
+    world.remove_all_id((rel, obj_a));
+
+    assert!(e_1.is_alive());
+    assert!(e_2.is_alive());
+    assert!(e_3.is_alive());
+
+    assert!(!e_1.has_id((rel, obj_a)));
+    assert!(!e_2.has_id((rel, obj_a)));
+    assert!(!e_3.has_id((rel, obj_a)));
+
+    assert!(e_3.has_id((rel, obj_b)));
+}
+
+#[test]
+fn world_remove_all_pair_type() {
+    let world = World::new();
+
+    let e_1 = world.entity().add::<(Rel, Obj)>();
+    let e_2 = world.entity().add::<(Rel, Obj)>();
+    let e_3 = world.entity().add::<(Rel, Obj)>().add::<(Rel, Obj2)>();
+
+    world.remove_all::<(Rel, Obj)>();
+
+    assert!(e_1.is_alive());
+    assert!(e_2.is_alive());
+    assert!(e_3.is_alive());
+
+    assert!(!e_1.has::<(Rel, Obj)>());
+    assert!(!e_2.has::<(Rel, Obj)>());
+    assert!(!e_3.has::<(Rel, Obj)>());
+
+    assert!(e_3.has::<(Rel, Obj2)>());
+}
+
+#[test]
+fn world_delete_with_children() {
+    let world = World::new();
+
+    let parent = world.entity();
+    let child_1 = world.entity().child_of_id(parent);
+    let child_2 = world.entity().child_of_id(parent);
+    let other = world.entity();
+
+    world.delete_entities_with_id((flecs::ChildOf::ID, parent));
+
+    assert!(parent.is_alive());
+    assert!(!child_1.is_alive());
+    assert!(!child_2.is_alive());
+    assert!(other.is_alive());
+}
+
+#[test]
+fn world_info_command_counts() {
+    let world = World::new();
+    world.component::<Position>();
+
+    let e1 = world.entity();
+    let e2 = world.entity();
+    let e3 = world.entity();
+
+    let before = world.info().cmd;
+
+    world.defer(|_| {
+        e1.set(Position { x: 10, y: 20 });
+        e2.set(Position { x: 30, y: 40 });
+        e3.destruct();
+    });
+
+    let merged = world.info().cmd - before;
+    assert_eq!(merged.delete_count, 1);
+    assert_eq!(merged.set_count, 2);
+    assert_eq!(merged.batched_entity_count, 0);
+
+    world.progress();
+    assert!(world.info().world_time_total_raw > 0.0);
+    assert_eq!(world.info().frame_count_total, 1);
+}
+
+#[test]
+fn world_import_module_once() {
+    use core::sync::atomic::{AtomicU32, Ordering};
+
+    static DEFINED: AtomicU32 = AtomicU32::new(0);
+
+    #[derive(Component)]
+    struct Physics;
+
+    #[derive(Component)]
+    struct Gravity {
+        value: f32,
+    }
+
+    impl Module for Physics {
+        fn module(world: &World) {
+            world.module::<Physics>("game::physics");
+            world.component::<Gravity>();
+            DEFINED.fetch_add(1, Ordering::Relaxed);
+        }
+    }
+
+    let world = World::new();
+
+    let module = world.import::<Physics>();
+    assert!(module.has::<flecs::Module>());
+    assert_eq!(module.path().unwrap(), "::game::physics");
+    assert!(
+        world
+            .component::<Gravity>()
+            .path()
+            .unwrap()
+            .starts_with("::game::physics::")
+    );
+
+    // importing again returns the same module without running the definition
+    assert_eq!(world.import::<Physics>(), module);
+    assert_eq!(DEFINED.load(Ordering::Relaxed), 1);
+
+    // module scope is reset after importing
+    assert_eq!(world.get_scope(), None);
+    assert!(world.entity().parent().is_none());
+}
+
+#[test]
+fn world_modify_singleton() {
+    use crate::common_test::Count;
+
+    let world = World::new();
+    world.set(Position { x: 10, y: 20 });
+    world.set(Count(0));
+
+    world
+        .observer::<flecs::OnSet, &Position>()
+        .singleton()
+        .each_iter(|it, _, pos| {
+            assert_eq!(pos.x, 30);
+            it.world().get::<&mut Count>(|count| count.0 += 1);
+        });
+
+    let x = world.modify(|pos: &mut Position| {
+        pos.x = 30;
+        pos.x
+    });
+    assert_eq!(x, 30);
+    world.get::<&Count>(|count| assert_eq!(count.0, 1));
+    world.get::<&Position>(|pos| assert_eq!((pos.x, pos.y), (30, 20)));
+}
+
+#[test]
+fn world_entity_and_table_count() {
+    let world = World::new();
+    world.component::<Position>();
+    world.component::<Eats>();
+    world.component::<Likes>();
+    world.component::<Apples>();
+
+    let entities = world.entity_count();
+    let tables = world.table_count();
+
+    let apples = world.entity();
+    let e1 = world.entity().set(Position { x: 1, y: 2 });
+    let e2 = world
+        .entity()
+        .set(Position { x: 3, y: 4 })
+        .add_first::<Eats>(apples);
+    world.entity().add::<(Likes, Apples)>();
+
+    assert_eq!(world.entity_count(), entities + 4);
+    assert!(world.table_count() > tables);
+
+    assert_eq!(world.count::<Position>(), 2);
+    assert_eq!(world.count_pair::<Likes, Apples>(), 1);
+    assert_eq!(world.count_first::<Eats>(apples), 1);
+
+    e1.destruct();
+    e2.destruct();
+    assert_eq!(world.entity_count(), entities + 2);
+    assert_eq!(world.count::<Position>(), 0);
+
+    #[cfg(feature = "flecs_stats")]
+    {
+        let counts = world.allocation_counts();
+        assert!(counts.alloc_count >= counts.free_count);
+        assert_eq!(
+            counts.outstanding_alloc_count,
+            counts.alloc_count - counts.free_count
+        );
+    }
+}
+
+#[test]
+fn world_each_alive_entity_and_table() {
+    let world = World::new();
+
+    let empty = world.entity();
+    let parent = world.entity_named("parent");
+    let e1 = world
+        .entity()
+        .set(Position { x: 1, y: 2 })
+        .child_of_id(parent);
+    let e2 = world
+        .entity()
+        .set(Position { x: 3, y: 4 })
+        .child_of_id(parent);
+    let deleted = world.entity().add::<TagA>();
+    deleted.destruct();
+
+    let mut visited = Vec::new();
+    world.each_alive_entity(|e| {
+        assert!(!e.has_id((flecs::ChildOf::ID, flecs::Flecs::ID)));
+        visited.push(e.id());
+    });
+    assert!(visited.contains(&empty.id()));
+    assert!(visited.contains(&parent.id()));
+    assert!(visited.contains(&e1.id()));
+    assert!(visited.contains(&e2.id()));
+    assert!(!visited.contains(&deleted.id()));
+    assert!(!visited.contains(&Entity::new(flecs::Flecs::ID)));
+    assert!(!visited.contains(&Entity::new(flecs::ChildOf::ID)));
+
+    // entities can be deleted while iterating
+    world.each_alive_entity(|e| {
+        if e.has::<Position>() {
+            e.destruct();
+        }
+    });
+    assert!(!e1.is_alive());
+    assert!(!e2.is_alive());
+
+    let e3 = world.entity().set(Position { x: 5, y: 6 }).add::<TagA>();
+    let mut tables = 0;
+    let mut rows = 0;
+    world.each_table(|table| {
+        if table.archetype().contains::<Position>() {
+            tables += 1;
+            rows += table.count();
+            assert_eq!(table.archetype().as_slice(), e3.archetype().as_slice());
+        }
+    });
+    assert_eq!(tables, 1);
+    assert_eq!(rows, 1);
+}
+
+#[test]
+fn world_defer_nested_return_value() {
+    let world = World::new();
+
+    let (a, b) = world.defer(|world| {
+        assert!(world.is_deferred());
+        let a = world.entity().set(Position { x: 1, y: 2 });
+
+        let b = world.defer(|world| world.entity().set(Position { x: 3, y: 4 }));
+        // the inner defer doesn't flush the commands of the outer one
+        assert!(world.is_deferred());
+        assert!(!a.has::<Position>());
+        assert!(!b.has::<Position>());
+
+        (a.id(), b)
+    });
+
+    assert!(!world.is_deferred());
+    assert!(world.entity_from_id(a).has::<Position>());
+    b.get::<&Position>(|p| assert_eq!(p.x, 3));
+}
+
+#[test]
+fn world_command_sender_from_thread() {
+    let world = World::new();
+    world.component::<Position>();
+    let mass = world.component::<Mass>().id();
+    let old = world.entity_named("old");
+
+    let sender = world.command_sender();
+    let old_id = old.id();
+    let (a, b) = std::thread::spawn(move || {
+        let a = sender.create_named("a");
+        sender.set(a, Position { x: 1, y: 2 });
+        unsafe {
+            sender.set_bytes(a, mass, 10i32.to_ne_bytes());
+            // skipped, because the size doesn't match
+            sender.set_bytes(a, mass, 20i64.to_ne_bytes());
+        }
+
+        let b = sender.create();
+        sender.set(b, Position { x: 3, y: 4 });
+        sender.delete(b);
+        sender.delete(old_id);
+        (a, b)
+    })
+    .join()
+    .unwrap();
+
+    assert_eq!(world.command_sender().len(), 8);
+    assert!(world.sent_entity(a).is_none());
+
+    world.progress();
+
+    assert!(world.command_sender().is_empty());
+    let a = world.sent_entity(a).unwrap();
+    assert_eq!(a.name(), "a");
+    a.get::<(&Position, &Mass)>(|(p, m)| {
+        assert_eq!(p.y, 2);
+        assert_eq!(m.value, 10);
+    });
+    assert!(world.sent_entity(b).is_none());
+    assert!(!old.is_alive());
+
+    // entities deleted without the sender are no longer resolved
+    let sender = world.command_sender();
+    let c = sender.create();
+    world.apply_sent_commands();
+    world.sent_entity(c).unwrap().destruct();
+    sender.set(c, Position { x: 5, y: 6 });
+    world.apply_sent_commands();
+    assert!(world.sent_entity(c).is_none());
+}
+
+#[test]
+fn world_traversal() {
+    #[derive(Component)]
+    struct Feeds;
+
+    let world = World::new();
+
+    let root = world.entity().set(Mass { value: 100 });
+    let a = world.entity().add_first::<Feeds>(root);
+    let b = world.entity().add_first::<Feeds>(root);
+    let a1 = world.entity().add_first::<Feeds>(a);
+    let b1 = world.entity().add_first::<Feeds>(b);
+    let a2 = world.entity().add_first::<Feeds>(a1);
+
+    let grid = world.traversal::<Feeds>();
+    assert_eq!(grid.relationship(), world.component::<Feeds>());
+    assert_eq!(grid.parent(a2).unwrap(), a1);
+    assert!(grid.parent(root).is_none());
+    assert_eq!(grid.depth(a2), 3);
+
+    let mut ancestors = Vec::new();
+    grid.ancestors(a2, |e| ancestors.push(e.id()));
+    assert_eq!(ancestors, [a1.id(), a.id(), root.id()]);
+
+    let mut depth_first = Vec::new();
+    grid.depth_first(root, |e, depth| depth_first.push((e.id(), depth)));
+    assert_eq!(depth_first.len(), 5);
+    let pos = |id: Entity| depth_first.iter().position(|&(e, _)| e == id).unwrap();
+    assert_eq!(pos(a1.id()), pos(a.id()) + 1);
+    assert_eq!(pos(a2.id()), pos(a1.id()) + 1);
+    assert_eq!(pos(b1.id()), pos(b.id()) + 1);
+    assert!(depth_first.contains(&(a2.id(), 3)));
+
+    let mut breadth_first = Vec::new();
+    grid.breadth_first(root, |e, depth| breadth_first.push((e.id(), depth)));
+    let depths: Vec<usize> = breadth_first.iter().map(|&(_, depth)| depth).collect();
+    assert_eq!(depths, [1, 1, 2, 2, 3]);
+
+    // cycles are visited once
+    root.add_first::<Feeds>(a2);
+    let mut count = 0;
+    grid.depth_first(root, |_, _| count += 1);
+    assert_eq!(count, 5);
+    root.remove_first::<Feeds>(a2);
+
+    // queries can match components on the targets of a traversable relationship
+    #[derive(Component)]
+    struct Supplies;
+
+    let supply = world.traversal::<Supplies>();
+    supply.make_traversable();
+
+    let root = world.entity().set(Mass { value: 100 });
+    let a = world.entity().add_first::<Supplies>(root);
+    let a1 = world.entity().add_first::<Supplies>(a);
+    world.entity().add_first::<Supplies>(a1);
+    world.entity().add_first::<Supplies>(root);
+
+    let query = world
+        .query::<&Mass>()
+        .term_at(0)
+        .cascade_type::<Supplies>()
+        .set_cached()
+        .build();
+    let mut matched = Vec::new();
+    query.each_entity(|e, mass| {
+        assert_eq!(mass.value, 100);
+        matched.push(supply.depth(e));
+    });
+    assert_eq!(matched, [1, 1, 2, 3]);
+}
+
+#[test]
+fn world_with_guard() {
+    let world = World::new();
+
+    assert!(world.get_with().is_none());
+
+    {
+        let _guard = world.with_guard::<TagA>();
+        assert_eq!(world.get_with().unwrap(), world.component_id::<TagA>());
+
+        let a = world.entity();
+        assert!(a.has::<TagA>());
+
+        {
+            let _guard = world.with_guard::<TagB>();
+            let b = world.entity();
+            assert!(b.has::<TagB>());
+            assert!(!b.has::<TagA>());
+        }
+
+        assert!(world.entity().has::<TagA>());
+    }
+
+    assert!(world.get_with().is_none());
+    assert!(!world.entity().has::<TagA>());
+
+    let previous = world.set_with::<TagB>();
+    assert!(world.entity().has::<TagB>());
+    world.set_with_id(previous);
+    assert!(!world.entity().has::<TagB>());
+
+    assert_eq!(world.count::<TagA>(), 2);
+    world.delete_entities_with::<TagA>();
+    assert_eq!(world.count::<TagA>(), 0);
+}
+
+#[test]
+#[cfg(feature = "flecs_scenes")]
+fn world_scenes() {
+    let world = World::new();
+
+    let level = world.entity_named("level");
+    let other = world.entity_named("other");
+
+    let player = {
+        let _scene = world.scene_guard(level);
+        let player = world.entity_named("player").add::<TagA>();
+        world.entity_named("sword").child_of_id(player);
+        world.prefab_named("enemy_prefab");
+        player
+    };
+
+    world.load_scene(other, || {
+        world.entity_named("tree");
+    });
+
+    let camera = world.entity_named("camera");
+    assert!(world.get_with().is_none());
+
+    assert_eq!(world.scene_count(level), 3);
+    assert_eq!(world.scene_count(other), 1);
+
+    let mut entities = Vec::new();
+    world.each_scene_entity(level, |e| entities.push(e.name()));
+    entities.sort();
+    assert_eq!(entities, ["enemy_prefab", "player", "sword"]);
+
+    world.unload_scene(level);
+
+    assert!(!player.is_alive());
+    assert!(world.try_lookup("player::sword").is_none());
+    assert!(world.try_lookup("enemy_prefab").is_none());
+    assert!(world.try_lookup("tree").is_some());
+    assert!(camera.is_alive());
+    assert!(level.is_alive());
+    assert_eq!(world.scene_count(level), 0);
+}
+
+#[test]
+#[cfg(feature = "flecs_scenes")]
+fn world_scene_json() {
+    let world = World::new();
+    world
+        .component::<Position>()
+        .member::<i32>("x")
+        .member::<i32>("y");
+
+    let level = world.entity_named("level");
+    world.load_scene(level, || {
+        let player = world
+            .entity_named("player")
+            .set(Position { x: 1, y: 2 })
+            .add::<TagA>();
+        world.entity_named("sword").child_of_id(player);
+    });
+    world.entity_named("camera").set(Position { x: 3, y: 4 });
+
+    let json = world.scene_to_json(level);
+
+    let world = World::new();
+    world
+        .component::<Position>()
+        .member::<i32>("x")
+        .member::<i32>("y");
+    world.component::<TagA>();
+    world.scene_from_json(&json).unwrap();
+
+    let level = world.lookup("level");
+    assert_eq!(world.scene_count(level), 2);
+    assert!(world.try_lookup("camera").is_none());
+
+    let player = world.lookup("player");
+    assert!(player.has::<TagA>());
+    player.get::<&Position>(|p| assert_eq!((p.x, p.y), (1, 2)));
+    assert_eq!(world.lookup("player::sword").parent().unwrap(), player);
+
+    world.unload_scene(level);
+    assert!(world.try_lookup("player").is_none());
+}
+
+#[test]
+fn world_rename_event() {
+    let world = World::new();
+
+    let e = world.entity_named("goblin");
+
+    world.set(Mass { value: 0 });
+    e.observe_payload_entity::<Renamed>(|e, renamed| {
+        e.world().get::<&mut Mass>(|count| {
+            let expected = if count.value == 0 { "goblin" } else { "orc" };
+            assert_eq!(renamed.previous, expected);
+            count.value += 1;
+        });
+    });
+
+    world.rename(e, "orc");
+    e.set_name("orc");
+    e.set_name("troll");
+
+    assert_eq!(e.name(), "troll");
+    world.get::<&Mass>(|count| assert_eq!(count.value, 2));
+}
+
+#[test]
+fn world_find_by_name() {
+    let world = World::new();
+
+    let goblin = world.entity_named("Enemy_Goblin");
+    world.entity_named("enemy_orc");
+    world.prefab_named("Enemy_Prefab");
+    world.entity_named("player");
+
+    let names = |found: Vec<EntityView>| {
+        let mut names: Vec<String> = found.iter().map(|e| e.name()).collect();
+        names.sort();
+        names
+    };
+
+    assert_eq!(
+        names(world.find_by_name_prefix("Enemy_")),
+        ["Enemy_Goblin", "Enemy_Prefab"]
+    );
+    assert_eq!(
+        names(world.find_by_name_ignore_case("ENEMY_ORC")),
+        ["enemy_orc"]
+    );
+
+    world.enable_name_index();
+    world.enable_name_index();
+
+    assert_eq!(
+        names(world.find_by_name_prefix("Enemy_")),
+        ["Enemy_Goblin", "Enemy_Prefab"]
+    );
+    assert_eq!(
+        names(world.find_by_name_ignore_case("ENEMY_ORC")),
+        ["enemy_orc"]
+    );
+
+    world.rename(goblin, "Friend_Goblin");
+    world.entity_named("Enemy_Troll");
+    world.lookup("enemy_orc").destruct();
+
+    assert_eq!(
+        names(world.find_by_name_prefix("Enemy_")),
+        ["Enemy_Prefab", "Enemy_Troll"]
+    );
+    assert!(world.find_by_name_ignore_case("enemy_orc").is_empty());
+    assert_eq!(
+        names(world.find_by_name_ignore_case("friend_goblin")),
+        ["Friend_Goblin"]
+    );
+}
+
+#[test]
+fn world_entity_symbol() {
+    let world = World::new();
+
+    let e = world.entity();
+    assert_eq!(e.symbol(), "");
+    assert_eq!(e.get_symbol(), None);
+
+    let c = world.component::<Position>();
+    assert!(c.get_symbol().is_some());
+}
+
+#[cfg(feature = "flecs_spatial")]
+impl flecs_ecs::addons::spatial::SpatialPosition for Position {
+    fn position(&self) -> [f32; 3] {
+        [self.x as f32, self.y as f32, 0.0]
+    }
+}
+
+#[test]
+#[cfg(feature = "flecs_spatial")]
+fn world_spatial_query_aabb() {
+    let world = World::new();
+
+    let existing = world.entity().set(Position { x: 1, y: 1 });
+    world.enable_spatial::<Position>(4.0);
+
+    let near = world.entity().set(Position { x: 3, y: -2 }).add::<TagA>();
+    let moved = world
+        .entity()
+        .set(Position { x: 100, y: 100 })
+        .add::<TagA>();
+    let far = world.entity().set(Position { x: -50, y: 20 });
+
+    assert_eq!(world.spatial().count(), 4);
+
+    let found = |min: [f32; 3], max: [f32; 3]| {
+        let mut found = Vec::new();
+        world
+            .spatial()
+            .query_aabb(min, max)
+            .each(|e| found.push(e.id()));
+        found.sort();
+        found
+    };
+
+    let mut expected = vec![existing.id(), near.id()];
+    expected.sort();
+    assert_eq!(found([-5.0, -5.0, 0.0], [5.0, 5.0, 0.0]), expected);
+    assert_eq!(
+        found([-1000.0, -1000.0, -1.0], [1000.0, 1000.0, 1.0]).len(),
+        4
+    );
+    assert_eq!(found([-50.0, 20.0, 0.0], [-50.0, 20.0, 0.0]), [far.id()]);
+
+    moved.set(Position { x: 2, y: 2 });
+    far.remove::<Position>();
+    near.destruct();
+
+    let mut expected = vec![existing.id(), moved.id()];
+    expected.sort();
+    assert_eq!(found([-5.0, -5.0, 0.0], [5.0, 5.0, 0.0]), expected);
+    assert!(found([-60.0, 10.0, 0.0], [-40.0, 30.0, 0.0]).is_empty());
+    assert_eq!(world.spatial().count(), 2);
+
+    let query = world.new_query::<&Position>();
+    let tagged = world.query::<()>().with::<TagA>().build();
+    let area = world.spatial().query_aabb([0.0, 0.0, 0.0], [5.0, 5.0, 0.0]);
+
+    let mut count = 0;
+    area.each_matching(&query, |_, pos| {
+        assert!(pos.x <= 5);
+        count += 1;
+    });
+    assert_eq!(count, 2);
+
+    let mut count = 0;
+    area.each_matching(&tagged, |e, _| {
+        assert_eq!(e, moved);
+        count += 1;
+    });
+    assert_eq!(count, 1);
+}
+
+#[test]
+#[cfg(feature = "flecs_tween")]
+fn world_tween_member() {
+    extern crate alloc;
+    use alloc::rc::Rc;
+    use core::cell::Cell;
+    use flecs_ecs::addons::timer::TimerAPI;
+    use flecs_ecs::addons::tween::*;
+
+    #[derive(Component, Debug, PartialEq)]
+    #[meta]
+    struct Health {
+        value: i32,
+        max: f32,
+    }
+
+    let world = World::new();
+    world.component_named::<Health>("Health").meta();
+
+    let e = world.entity().set(Health {
+        value: 0,
+        max: 10.0,
+    });
+    let set_count = Rc::new(Cell::new(0));
+    let count = set_count.clone();
+    world
+        .observer::<flecs::OnSet, &TweenState>()
+        .each(move |_| count.set(count.get() + 1));
+
+    let tween_entity = world
+        .tween(e, "Health.value")
+        .to(100.0)
+        .duration(4.0)
+        .entity();
+    assert_eq!(tween_entity.parent(), Some(e));
+    // the state is set once, when the tween is configured
+    assert_eq!(set_count.get(), 1);
+
+    world.progress_time(1.0);
+    e.get::<&Health>(|h| assert_eq!(h.value, 25));
+    tween_entity.get::<&TweenState>(|state| {
+        assert_eq!(state.path(), "Health.value");
+        assert!((state.progress() - 0.25).abs() < f32::EPSILON);
+    });
+
+    world.progress_time(3.0);
+    e.get::<&Health>(|h| assert_eq!(h.value, 100));
+    assert!(!tween_entity.is_alive());
+
+    // tweens can be paced by a timer
+    let timer = world.timer().set_interval(2.0);
+    world
+        .tween(e, "Health.max")
+        .from(0.0)
+        .to(8.0)
+        .duration(4.0)
+        .ease(Ease::In)
+        .tick_source(timer);
+
+    // the timer hasn't ticked yet
+    world.progress_time(1.0);
+    e.get::<&Health>(|h| {
+        assert_eq!(
+            *h,
+            Health {
+                value: 100,
+                max: 10.0
+            }
+        );
+    });
+    world.progress_time(1.0);
+    e.get::<&Health>(|h| {
+        assert_eq!(
+            *h,
+            Health {
+                value: 100,
+                max: 1.0
+            }
+        );
+    });
+
+    // tweens are deleted with the entity they animate
+    let tween = world.tween(e, "Health.value").to(0.0).entity();
+    e.destruct();
+    assert!(!tween.is_alive());
+}
+
+#[test]
+fn world_catch_assert() {
+    let world = World::new();
+    let e = world.entity();
+    e.destruct();
+
+    assert_eq!(world.catch_assert(|| 1), Ok(1));
+
+    let result = world.catch_assert(|| {
+        e.add::<TagA>();
+    });
+    let Err(FlecsError::Assert(message)) = result else {
+        panic!("expected an assert, got {result:?}");
+    };
+    assert!(message.contains("INVALID_PARAMETER"), "{message}");
+    assert!(!world.is_panic_on_assert());
+}
+
+#[test]
+fn world_entity_range_partitioning() {
+    let server = World::new();
+    let client = World::new();
+    server.set_entity_range(1000, 2000);
+    client.set_entity_range(5000, 6000);
+    assert_eq!(
+        client.entity_range(),
+        (Entity::new(5000), Entity::new(6000))
+    );
+
+    let replicated: Vec<Entity> = (0..3)
+        .map(|i| server.entity().set(Position { x: i, y: 0 }).id())
+        .collect();
+    assert!(replicated.iter().all(|e| (1000..2000).contains(&**e)));
+
+    // the client replicates the entities of the server with their ids
+    for (i, &e) in replicated.iter().enumerate() {
+        client.make_alive(e).set(Position { x: i as i32, y: 0 });
+    }
+
+    let local = client.entity().set(Position { x: 10, y: 0 });
+    assert!((5000..6000).contains(&*local.id()));
+    assert_eq!(client.entity().id(), *local.id() + 1);
+    assert_eq!(client.new_query::<&Position>().count(), 4);
+
+    // with the range check, the client can only create entities in its own range, so components
+    // are registered first
+    client.component::<TagA>();
+    assert!(!client.enable_range_check(true));
+    client.entity().add::<TagA>();
+    assert!(client.enable_range_check(false));
+    client.make_alive(1500).add::<TagA>();
+    assert_eq!(client.new_query::<&TagA>().count(), 2);
+}
+
+#[test]
+#[cfg(feature = "flecs_rust_rest")]
+fn world_rest_server() {
+    use std::io::{Read, Write};
+
+    let world = World::new();
+    world
+        .component_named::<Position>("Position")
+        .member::<i32>("x")
+        .member::<i32>("y");
+    world
+        .component_named::<Velocity>("Velocity")
+        .add_trait::<flecs::CanToggle>();
+    world.component_named::<TagA>("TagA");
+    let e = world
+        .entity_named("parent::child")
+        .set(Position { x: 1, y: 2 })
+        .set(Velocity { x: 0, y: 0 })
+        .add::<TagA>();
+    let removed = world.entity_named("removed");
+    let addr = world.serve_rest("127.0.0.1:0").unwrap();
+
+    let client = std::thread::spawn(move || {
+        let request_with_headers = |method: &str, target: &str, headers: &str| {
+            let mut stream = std::net::TcpStream::connect(addr).unwrap();
+            write!(
+                stream,
+                "{method} {target} HTTP/1.1\r\nHost: localhost\r\n{headers}\r\n"
+            )
+            .unwrap();
+            let mut response = String::new();
+            stream.read_to_string(&mut response).unwrap();
+            response
+        };
+        let request = |method: &str, target: &str| request_with_headers(method, target, "");
+
+        [
+            request("GET", "/entity/parent/child"),
+            request("GET", "/component/parent/child?component=Position"),
+            request(
+                "PUT",
+                "/component/parent/child?component=Position&value=%7B%22x%22%3A5%7D",
+            ),
+            request("GET", "/query?expr=Position&entity_ids=true"),
+            request("PUT", "/entity/spawned"),
+            request("GET", "/entity/missing"),
+            request("GET", "/query?expr=Position("),
+            request(
+                "PUT",
+                "/toggle/parent/child?enable=false&component=Velocity",
+            ),
+            request(
+                "PUT",
+                "/toggle/parent/child?enable=false&component=Position",
+            ),
+            request("PUT", "/toggle/parent/child?enable=false"),
+            request("DELETE", "/component/parent/child?component=TagA"),
+            request("DELETE", "/entity/removed"),
+            request_with_headers("GET", "/entity/parent/child", &"X: y\r\n".repeat(200)),
+        ]
+    });
+
+    while !client.is_finished() {
+        world.progress();
+    }
+    let [
+        entity,
+        component,
+        set,
+        query,
+        spawned,
+        missing,
+        invalid,
+        toggle_component,
+        toggle_untoggleable,
+        toggle_entity,
+        remove,
+        delete,
+        too_many_headers,
+    ] = client.join().unwrap();
+
+    assert!(entity.starts_with("HTTP/1.1 200"), "{entity}");
+    assert!(
+        entity.contains(r#""components":{"Position":{"x":1, "y":2}"#),
+        "{entity}"
+    );
+    assert!(component.ends_with(r#"{"x":1, "y":2}"#), "{component}");
+    assert!(set.starts_with("HTTP/1.1 200"), "{set}");
+    assert!(query.contains(&format!(r#""id":{}"#, e.id())), "{query}");
+    assert!(spawned.starts_with("HTTP/1.1 200"), "{spawned}");
+    assert!(missing.starts_with("HTTP/1.1 404"), "{missing}");
+    assert!(invalid.starts_with("HTTP/1.1 400"), "{invalid}");
+
+    e.get::<&Position>(|p| assert_eq!((p.x, p.y), (5, 2)));
+    let spawned_id = world.lookup("spawned").id();
+    assert!(
+        spawned.ends_with(&format!(r#"{{"id":"{spawned_id}"}}"#)),
+        "{spawned}"
+    );
+
+    assert!(
+        toggle_component.starts_with("HTTP/1.1 200"),
+        "{toggle_component}"
+    );
+    assert!(
+        toggle_untoggleable.starts_with("HTTP/1.1 400"),
+        "{toggle_untoggleable}"
+    );
+    assert!(toggle_entity.starts_with("HTTP/1.1 200"), "{toggle_entity}");
+    assert!(!e.is_enabled::<Velocity>());
+    assert!(e.is_enabled::<Position>());
+    assert!(e.has::<flecs::Disabled>());
+
+    assert!(remove.starts_with("HTTP/1.1 200"), "{remove}");
+    assert!(!e.has::<TagA>());
+    assert!(delete.starts_with("HTTP/1.1 200"), "{delete}");
+    assert!(!removed.is_alive());
+    assert!(
+        too_many_headers.starts_with("HTTP/1.1 400"),
+        "{too_many_headers}"
+    );
+}
+
+#[test]
+#[should_panic(expected = "INVALID_PARAMETER")]
+fn world_panic_on_assert() {
+    let world = World::new();
+    world.set_panic_on_assert(true);
+    assert!(world.is_panic_on_assert());
+
+    let e = world.entity();
+    e.destruct();
+    e.add::<TagA>();
+}
+
+#[test]
+fn world_test_world() {
+    use flecs_ecs::assert_matches;
+    use flecs_ecs::testing::TestWorld;
+
+    let world = TestWorld::new();
+    assert!(world.is_panic_on_assert());
+    assert!(world.is_deterministic());
+    // asserts only panic on the thread of the test world
+    let other_thread = std::thread::spawn(|| World::new().is_panic_on_assert());
+    assert!(!other_thread.join().unwrap());
+
+    let e = world
+        .entity()
+        .set(Position { x: 0, y: 0 })
+        .set(Velocity { x: 1, y: 2 });
+    let still = world.entity().set(Position { x: 10, y: 10 });
+
+    world.system::<(&mut Position, &Velocity)>().each(|(p, v)| {
+        p.x += v.x;
+        p.y += v.y;
+    });
+
+    world.set(Mass { value: 0 });
+    world.system::<()>().run(|mut it| {
+        while it.next() {}
+        it.world().get::<&mut Mass>(|m| {
+            assert!((it.delta_time() - 0.5).abs() < f32::EPSILON);
+            m.value += 1;
+        });
+    });
+
+    world.set_delta_time(0.5);
+    world.step().step_n(2);
+
+    e.get::<&Position>(|p| assert_eq!((p.x, p.y), (3, 6)));
+    world.get::<&Mass>(|m| assert_eq!(m.value, 3));
+
+    assert_matches!(world, &Position, count == 2);
+    assert_matches!(world, (&Position, &Velocity), count < 2);
+    assert_matches!(world, (&Position, &Velocity), contains e);
+    assert_matches!(world, (&Position, &Velocity), !contains still);
+
+    world.assert_no_errors();
+    unsafe {
+        sys::ecs_log_(-2, c"test".as_ptr(), 1, c"careful".as_ptr());
+        sys::ecs_log_(-3, c"test".as_ptr(), 2, c"broken".as_ptr());
+    }
+    assert_eq!(world.warnings(), ["careful"]);
+    assert_eq!(world.take_errors(), ["broken"]);
+    assert!(world.errors().is_empty());
+    assert_eq!(world.take_warnings(), ["careful"]);
+    assert!(world.warnings().is_empty());
+}
+
+#[test]
+#[should_panic(expected = "to match")]
+fn world_assert_matches_fails() {
+    let world = World::new();
+    let e = world.entity().set(Position { x: 0, y: 0 });
+    flecs_ecs::assert_matches!(world, (&Position, &Velocity), contains e);
+}
+
+#[test]
+fn world_rebind_callbacks() {
+    #[derive(Component, Default)]
+    struct Name {
+        value: String,
+    }
+
+    #[derive(Component, Clone)]
+    struct Added(i32);
+
+    fn register(world: &World, step: i32) {
+        world.component::<Name>().on_add(move |e, _| {
+            e.world().get::<&mut Added>(|count| count.0 += step);
+        });
+        world
+            .system_named::<&mut Position>("Move")
+            .each(move |p| p.x += step);
+        world
+            .observer_named::<flecs::OnSet, &mut Position>("Clamp")
+            .each(move |p| p.y = step);
+    }
+
+    let world = World::new();
+    world.set(Added(0));
+    register(&world, 1);
+    let anonymous = world.system::<&Position>().each(|_| {});
+
+    let e = world.entity().set(Position { x: 0, y: 0 }).set(Name {
+        value: "goblin".to_string(),
+    });
+    let name = world.component_id::<Name>();
+    let move_system = world.lookup("Move");
+    world.progress();
+    e.get::<&Position>(|p| assert_eq!((p.x, p.y), (1, 1)));
+
+    world.detach_callbacks();
+    let hooks = unsafe { *sys::ecs_get_hooks_id(world.ptr_mut(), *name) };
+    assert!(hooks.ctor.is_none() && hooks.dtor.is_none() && hooks.move_ctor.is_none());
+    assert!(hooks.on_add.is_none() && hooks.lifecycle_ctx.is_null());
+
+    world.rebind_callbacks(|world| register(world, 10));
+    let hooks = unsafe { *sys::ecs_get_hooks_id(world.ptr_mut(), *name) };
+    assert!(hooks.ctor.is_some() && hooks.dtor.is_some() && hooks.move_ctor.is_some());
+    assert!(hooks.on_add.is_some() && !hooks.lifecycle_ctx.is_null());
+
+    assert_eq!(world.component_id::<Name>(), name);
+    assert_eq!(world.lookup("Move"), move_system);
+    assert!(!anonymous.is_alive());
+
+    world.progress();
+    e.get::<&Position>(|p| assert_eq!(p.x, 11));
+    e.set(Position { x: 0, y: 0 });
+    e.get::<&Position>(|p| assert_eq!(p.y, 10));
+
+    world.entity().add::<Name>();
+    assert_eq!(world.cloned::<&Added>().0, 11);
+    e.get::<&Name>(|n| assert_eq!(n.value, "goblin"));
+}
+
+#[test]
+#[cfg(feature = "flecs_async")]
+fn world_spawn_task() {
+    use flecs_ecs::addons::async_task::TaskHandle;
+
+    let world = World::new();
+    let e = world.entity().spawn_task(async { Position { x: 1, y: 2 } });
+    assert!(e.has::<TaskHandle<Position>>());
+
+    for _ in 0..1000 {
+        world.progress();
+        if !e.has::<TaskHandle<Position>>() {
+            break;
+        }
+        std::thread::sleep(core::time::Duration::from_millis(1));
+    }
+    e.get::<&Position>(|p| assert_eq!((p.x, p.y), (1, 2)));
+}
+
+#[test]
+#[cfg(feature = "flecs_async")]
+fn world_spawn_task_cancel() {
+    extern crate alloc;
+    use alloc::rc::Rc;
+    use core::cell::RefCell;
+    use core::task::{Context, Waker};
+    use flecs_ecs::addons::async_task::{TaskFuture, TaskHandle};
+
+    let world = World::new();
+    let tasks = Rc::new(RefCell::new(Vec::<TaskFuture>::new()));
+    let spawned = tasks.clone();
+    world.set_task_spawner(move |task| spawned.borrow_mut().push(task));
+
+    let e = world
+        .entity()
+        .spawn_task_with(async { 10 }, |e, value: i32| {
+            e.set(Position { x: value, y: 0 });
+        });
+    let cancelled = world
+        .entity()
+        .spawn_task_with(async { 20 }, |e, value: i32| {
+            e.set(Position { x: value, y: 0 });
+        });
+    cancelled.remove::<TaskHandle<i32>>();
+
+    let mut cx = Context::from_waker(Waker::noop());
+    for task in tasks.borrow_mut().iter_mut() {
+        assert!(task.as_mut().poll(&mut cx).is_ready());
+    }
+    assert!(e.get::<&TaskHandle<i32>>(TaskHandle::is_finished));
+
+    world.progress();
+    assert!(!e.has::<TaskHandle<i32>>());
+    e.get::<&Position>(|p| assert_eq!(p.x, 10));
+    assert!(!cancelled.has::<Position>());
+}
+
+#[test]
+#[cfg(feature = "flecs_events")]
+fn world_events_readers() {
+    use flecs_ecs::addons::events::*;
+
+    let world = World::new();
+    world.send_event(1);
+
+    let mut first = EventReader::<i32>::default();
+    let mut second = EventReader::<i32>::default();
+    world.get::<&Events<i32>>(|events| {
+        assert_eq!(first.read(events).copied().collect::<Vec<_>>(), [1]);
+        assert!(first.is_empty(events));
+        assert_eq!(second.len(events), 1);
+    });
+
+    world.progress();
+    world.send_event(2);
+    world.get::<&Events<i32>>(|events| {
+        assert_eq!(first.read(events).copied().collect::<Vec<_>>(), [2]);
+        assert_eq!(events.len(), 2);
+    });
+
+    // the first event is dropped after two updates
+    world.progress();
+    world.get::<&Events<i32>>(|events| {
+        assert_eq!(events.iter().copied().collect::<Vec<_>>(), [2]);
+        assert_eq!(second.read(events).copied().collect::<Vec<_>>(), [2]);
+    });
+
+    world.send_event(3);
+    world.get::<&mut Events<i32>>(|events| {
+        assert_eq!(events.drain().collect::<Vec<_>>(), [2, 3]);
+        assert!(first.is_empty(events));
+    });
+}
+
+#[test]
+#[cfg(feature = "flecs_events")]
+fn world_events_send_deferred() {
+    use flecs_ecs::addons::events::*;
+
+    let world = World::new();
+    world.defer_begin();
+    world.send_event(1);
+    world.send_event(2);
+    assert!(world.has::<Events<i32>>());
+    world.defer_end();
+
+    world.get::<&Events<i32>>(|events| {
+        assert_eq!(events.iter().copied().collect::<Vec<_>>(), [1, 2]);
+    });
+}
+
+#[test]
+#[cfg(feature = "flecs_events")]
+fn world_events_system_reader() {
+    use flecs_ecs::addons::events::*;
+
+    let world = World::new();
+    world.add_events::<i32>();
+
+    let mut reader = EventReader::<i32>::default();
+    world
+        .system::<&Events<i32>>()
+        .term_at(0)
+        .singleton()
+        .each_iter(move |it, _, events| {
+            let sum: i32 = reader.read(events).sum();
+            it.world().get::<&mut Position>(|p| p.x += sum);
+        });
+    world
+        .system::<&mut Events<i32>>()
+        .term_at(0)
+        .singleton()
+        .each(|events| events.send(1));
+    world.set(Position { x: 0, y: 0 });
+
+    // events sent after the reader ran are read in the next frame
+    world.progress();
+    world.progress();
+    world.progress();
+    world.get::<&Position>(|p| assert_eq!(p.x, 2));
+}
+
+#[test]
+#[cfg(feature = "flecs_profiler")]
+fn world_profiler_systems_and_queries() {
+    extern crate alloc;
+    use alloc::sync::Arc;
+    use flecs_ecs::addons::profiler::ProfileKind;
+    use std::sync::Mutex;
+
+    let world = World::new();
+    world.entity().set(Position { x: 0, y: 0 });
+    world.entity().set(Position { x: 0, y: 0 }).add::<Tag>();
+    world.entity().set(Position { x: 0, y: 0 }).add::<Tag>();
+
+    let query = world.new_query::<&Position>();
+    let move_system = world
+        .system_named::<&mut Position>("Move")
+        .each(|p| p.x += 1);
+    let run_system = world
+        .system_named::<()>("Count")
+        .run(|mut it| while it.next() {});
+
+    let spans = Arc::new(Mutex::new(Vec::new()));
+    let (enter, exit) = (spans.clone(), spans.clone());
+    world.profiler().set_span_hooks(
+        move |name| enter.lock().unwrap().push(format!("+{name}")),
+        move |name| exit.lock().unwrap().push(format!("-{name}")),
+    );
+
+    world.progress();
+    world.progress();
+    query.each(|_| {});
+
+    let entry = world.profiler().system(move_system).unwrap();
+    assert_eq!(entry.kind, ProfileKind::System);
+    assert_eq!(&*entry.name, "::Move");
+    assert_eq!(entry.invocations, 2);
+    assert_eq!(entry.tables, 4);
+    assert_eq!(entry.entities, 6);
+    assert_eq!(entry.ns_per_invocation(), entry.total.as_nanos() as u64 / 2);
+
+    // the run callback iterates the tables itself, so only its time is recorded
+    let entry = world.profiler().system(run_system).unwrap();
+    assert_eq!(entry.invocations, 2);
+    assert_eq!(entry.entities, 0);
+
+    let entry = world.profiler().query(&query).unwrap();
+    assert_eq!(entry.kind, ProfileKind::Query);
+    assert_eq!(entry.invocations, 1);
+    assert_eq!(entry.tables, 2);
+    assert_eq!(entry.entities, 3);
+
+    let report = world.profiler().report();
+    assert!(report.contains("::Move"));
+    assert!(report.contains("::Count"));
+    assert_eq!(report.lines().count(), 4);
+
+    let spans = spans.lock().unwrap();
+    assert_eq!(spans.len(), 10);
+    assert_eq!(spans[0], "+::Move");
+    assert_eq!(spans[1], "-::Move");
+    assert_eq!(spans[9], format!("-{}", query.to_string()));
+
+    world.profiler().reset();
+    assert!(world.profiler().entries().is_empty());
+}
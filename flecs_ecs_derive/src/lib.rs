@@ -36,6 +36,15 @@ use syn::{
 ///   ctor and copy hooks (Default & Clone) which are used for either `EntityView::add` or `EntityView::duplicate` and some other operations.
 ///   In that case, the user has to manually register the hooks for each variant of T of the generic component
 ///   by using `T::register_ctor_hook` and `T::register_clone_hook`.
+/// - `#[flecs(clone)]` registers the copy hooks with `Clone::clone` for generic types as well, so
+///   that prefab instantiation and duplicating entities deep-copy the component. This requires
+///   the type to implement `Clone`.
+///
+/// # Plain old data
+/// - `#[flecs(pod)]` registers no hooks for the type, which requires it to implement `Copy`.
+///   Values are copied and moved with `memcpy`, which keeps the tables that store the component
+///   on the fast path of flecs. The `Default` implementation isn't used as constructor, so values
+///   have to be set instead of added.
 ///
 /// # Enums:
 ///
@@ -65,7 +74,7 @@ use syn::{
 ///     Jumping,
 /// }
 /// ```
-#[proc_macro_derive(Component, attributes(meta, skip, on_registration))]
+#[proc_macro_derive(Component, attributes(meta, skip, on_registration, flecs))]
 pub fn component_derive(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
     let mut input = parse_macro_input!(input as DeriveInput);
 
@@ -74,6 +83,13 @@ pub fn component_derive(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
         .attrs
         .iter()
         .any(|attr| attr.path().is_ident("on_registration"));
+    let FlecsAttributes {
+        clone: has_clone_attribute,
+        pod: has_pod_attribute,
+    } = match parse_flecs_attributes(&input, has_repr_c) {
+        Ok(attributes) => attributes,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
     let mut generated_impls: Vec<TokenStream> = Vec::new();
 
@@ -90,6 +106,8 @@ pub fn component_derive(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
                 has_fields,
                 &is_tag,
                 has_on_registration,
+                has_clone_attribute,
+                has_pod_attribute,
             ));
         }
         Data::Enum(_) => {
@@ -100,6 +118,8 @@ pub fn component_derive(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
                     true,
                     &is_tag,
                     has_on_registration,
+                    has_clone_attribute,
+                    has_pod_attribute,
                 ));
             } else {
                 generated_impls.push(impl_cached_component_data_enum(
@@ -124,6 +144,75 @@ pub fn component_derive(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
     output.into()
 }
 
+/// `Bundle` macro for defining groups of components that are spawned together.
+///
+/// Each field of the struct must be a component. The macro implements `Bundle`, which adds all
+/// fields to an entity with a single archetype move through `World::spawn` or
+/// `EntityView::insert`. It also implements `EntityBundle` and `BundleContains` for each of the
+/// field types, which allows using the bundle as the marker of a `TypedEntity`.
+///
+/// ## Example:
+///
+/// ```ignore
+/// #[derive(Bundle)]
+/// struct PlayerBundle {
+///     position: Position,
+///     velocity: Velocity,
+///     health: Health,
+/// }
+///
+/// let player = world.spawn(PlayerBundle { position, velocity, health });
+/// ```
+#[proc_macro_derive(Bundle)]
+pub fn bundle_derive(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let struct_name = &input.ident;
+    let (impl_generics, type_generics, where_clause) = input.generics.split_for_impl();
+
+    let Data::Struct(data_struct) = &input.data else {
+        return quote_spanned! { struct_name.span() =>
+            compile_error!("Bundle can only be derived for structs");
+        }
+        .into();
+    };
+
+    let (members, types): (Vec<TokenStream>, Vec<&Type>) = data_struct
+        .fields
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let member = match &field.ident {
+                Some(ident) => quote! { #ident },
+                None => {
+                    let index = syn::Index::from(index);
+                    quote! { #index }
+                }
+            };
+            (member, &field.ty)
+        })
+        .unzip();
+
+    let output = quote! {
+        impl #impl_generics flecs_ecs::core::Bundle for #struct_name #type_generics #where_clause {
+            fn insert_into(self, entity: flecs_ecs::core::EntityView) {
+                #( flecs_ecs::core::bundle_insert(entity, self.#members); )*
+            }
+        }
+
+        impl #impl_generics flecs_ecs::core::EntityBundle for #struct_name #type_generics #where_clause {
+            fn is_matched_by(entity: flecs_ecs::core::EntityView) -> bool {
+                true #( && entity.has::<#types>() )*
+            }
+        }
+
+        #(
+            impl #impl_generics flecs_ecs::core::BundleContains<#types> for #struct_name #type_generics #where_clause {}
+        )*
+    };
+
+    output.into()
+}
+
 fn impl_meta(input: &DeriveInput, has_repr_c: bool, struct_name: Ident) -> TokenStream {
     let has_meta_attribute = input.attrs.iter().any(|attr| attr.path().is_ident("meta"));
 
@@ -269,6 +358,8 @@ fn impl_cached_component_data_struct(
     has_fields: bool,
     is_tag: &TokenStream,
     has_on_registration: bool,
+    has_clone_attribute: bool,
+    has_pod_attribute: bool,
 ) -> proc_macro2::TokenStream {
     let is_generic = !ast.generics.params.is_empty();
 
@@ -368,7 +459,10 @@ fn impl_cached_component_data_struct(
         contains_where_bound = !where_clause.predicates.is_empty();
     }
 
-    let hook_impl = if !is_generic {
+    let hook_impl = if has_pod_attribute {
+        // plain old data is constructed and copied by flecs, without hooks
+        quote! {}
+    } else if !is_generic {
         quote! {
 
             fn __register_default_hooks(type_hooks: &mut flecs_ecs::sys::ecs_type_hooks_t) {
@@ -388,10 +482,12 @@ fn impl_cached_component_data_struct(
             fn __register_clone_hooks(type_hooks: &mut flecs_ecs::sys::ecs_type_hooks_t) {
                 use flecs_ecs::core::component_registration::registration_traits::ComponentInfo;
                 const IMPLS_CLONE: bool = #name::IMPLS_CLONE;
+                const NEEDS_DROP: bool = <#name as ComponentInfo>::NEEDS_DROP;
 
                 if IMPLS_CLONE {
                     flecs_ecs::core::lifecycle_traits::register_copy_lifecycle_action:: <<flecs_ecs::core::component_registration::registration_types::ConditionalTypeSelector<IMPLS_CLONE,#name>as flecs_ecs::core::component_registration::registration_traits::FlecsCloneType> ::Type, >(type_hooks);
-                } else {
+                } else if NEEDS_DROP {
+                    // a bitwise copy would double free, plain old data falls back to flecs' memcpy
                     flecs_ecs::core::lifecycle_traits::register_copy_panic_lifecycle_action::<#name>(
                         type_hooks,
                     );
@@ -418,10 +514,12 @@ fn impl_cached_component_data_struct(
             fn __register_clone_hooks(type_hooks: &mut flecs_ecs::sys::ecs_type_hooks_t) {
                 use flecs_ecs::core::component_registration::registration_traits::ComponentInfo;
                 const IMPLS_CLONE: bool = #name::<'_>::IMPLS_CLONE;
+                const NEEDS_DROP: bool = <#name::<'_> as ComponentInfo>::NEEDS_DROP;
 
                 if IMPLS_CLONE {
                     flecs_ecs::core::lifecycle_traits::register_copy_lifecycle_action:: <<flecs_ecs::core::component_registration::registration_types::ConditionalTypeSelector<IMPLS_CLONE,#name #type_generics>as flecs_ecs::core::component_registration::registration_traits::FlecsCloneType> ::Type, >(type_hooks);
-                } else {
+                } else if NEEDS_DROP {
+                    // a bitwise copy would double free, plain old data falls back to flecs' memcpy
                     flecs_ecs::core::lifecycle_traits::register_copy_panic_lifecycle_action::<#name>(
                         type_hooks,
                     );
@@ -478,12 +576,24 @@ fn impl_cached_component_data_struct(
     //             }
     //         }
     //     }
+    } else if has_clone_attribute {
+        quote! {
+            fn __register_clone_hooks(type_hooks: &mut flecs_ecs::sys::ecs_type_hooks_t) {
+                flecs_ecs::core::lifecycle_traits::register_copy_lifecycle_action::<#name #type_generics>(
+                    type_hooks,
+                );
+            }
+        }
     } else {
         quote! {
             fn __register_clone_hooks(type_hooks: &mut flecs_ecs::sys::ecs_type_hooks_t) {
+                use flecs_ecs::core::component_registration::registration_traits::ComponentInfo;
+
+                if <Self as ComponentInfo>::NEEDS_DROP {
                     flecs_ecs::core::lifecycle_traits::register_copy_panic_lifecycle_action::<#name #type_generics>(
                         type_hooks,
                     );
+                }
             }
         }
     };
@@ -511,7 +621,29 @@ fn impl_cached_component_data_struct(
             const IS_GENERIC: bool = true;
         }
     };
-    let clone_default = if !is_generic
+    let clone_default = if has_pod_attribute {
+        quote! {
+            const IMPLS_CLONE: bool = true;
+            const IMPLS_DEFAULT: bool = false;
+        }
+    } else if contains_any_generic_type && has_clone_attribute {
+        let impls_default = if contains_all_default_bound {
+            quote! {
+                const IMPLS_DEFAULT: bool = {
+                    use flecs_ecs::core::utility::traits::DoesNotImpl;
+                    flecs_ecs::core::utility::types::ImplementsDefault::<#name #type_generics>::IMPLS
+                };
+            }
+        } else {
+            quote! {
+                const IMPLS_DEFAULT: bool = false;
+            }
+        };
+        quote! {
+            const IMPLS_CLONE: bool = true;
+            #impls_default
+        }
+    } else if !is_generic
         || (contains_lifetime_bound && !contains_any_generic_type)
         || (contains_any_generic_type && contains_all_default_bound && contains_all_clone_bound)
     {
@@ -563,12 +695,20 @@ fn impl_cached_component_data_struct(
             }
         }
     };
+    // `#[flecs(clone)]` registers the copy hooks with `Clone::clone`, for generic types as well
+    let clone_bound = if has_clone_attribute {
+        quote! { , Self: Clone }
+    } else if has_pod_attribute {
+        quote! { , Self: Copy }
+    } else {
+        quote! {}
+    };
     let where_clause_quote = if contains_where_bound {
-        quote! { #where_clause Self: 'static }
+        quote! { #where_clause Self: 'static #clone_bound }
     } else {
         quote! {
             where
-            Self: 'static
+            Self: 'static #clone_bound
         }
     };
 
@@ -729,7 +869,7 @@ fn impl_cached_component_data_enum(
 
     let cached_enum_data_impl = quote! {
         const SIZE_ENUM_FIELDS: u32 = #size_variants;
-        type VariantIterator = std::vec::IntoIter<#name #impl_generics>;
+        type VariantIterator = core::array::IntoIter<#name #type_generics, { #size_variants as usize }>;
 
         fn name_cstr(&self) -> &core::ffi::CStr {
             match self {
@@ -750,7 +890,7 @@ fn impl_cached_component_data_enum(
         }
 
         fn iter() -> Self::VariantIterator {
-            vec![#(#variant_constructors),*].into_iter()
+            [#(#variant_constructors),*].into_iter()
         }
     };
 
@@ -789,10 +929,12 @@ fn impl_cached_component_data_enum(
             fn __register_clone_hooks(type_hooks: &mut flecs_ecs::sys::ecs_type_hooks_t) {
                 use flecs_ecs::core::component_registration::registration_traits::ComponentInfo;
                 const IMPLS_CLONE: bool = #name::IMPLS_CLONE;
+                const NEEDS_DROP: bool = <#name as ComponentInfo>::NEEDS_DROP;
 
                 if IMPLS_CLONE {
                     flecs_ecs::core::lifecycle_traits::register_copy_lifecycle_action:: <<flecs_ecs::core::component_registration::registration_types::ConditionalTypeSelector<IMPLS_CLONE,#name>as flecs_ecs::core::component_registration::registration_traits::FlecsCloneType> ::Type, >(type_hooks);
-                } else {
+                } else if NEEDS_DROP {
+                    // a bitwise copy would double free, plain old data falls back to flecs' memcpy
                     flecs_ecs::core::lifecycle_traits::register_copy_panic_lifecycle_action::<#name>(
                         type_hooks,
                     );
@@ -881,6 +1023,43 @@ fn impl_cached_component_data_enum(
     }
 }
 
+#[derive(Default)]
+struct FlecsAttributes {
+    clone: bool,
+    pod: bool,
+}
+
+fn parse_flecs_attributes(input: &syn::DeriveInput, has_repr_c: bool) -> Result<FlecsAttributes> {
+    let mut attributes = FlecsAttributes::default();
+    for attr in &input.attrs {
+        if attr.path().is_ident("flecs") {
+            attr.parse_nested_meta(|meta| {
+                if meta.path.is_ident("clone") {
+                    attributes.clone = true;
+                    Ok(())
+                } else if meta.path.is_ident("pod") {
+                    if has_repr_c && matches!(input.data, Data::Enum(_)) {
+                        return Err(meta.error("`pod` is not supported for C enums"));
+                    }
+                    attributes.pod = true;
+                    Ok(())
+                } else {
+                    Err(meta.error("unsupported flecs attribute, expected `clone` or `pod`"))
+                }
+            })?;
+        }
+    }
+
+    if attributes.clone && attributes.pod {
+        return Err(syn::Error::new_spanned(
+            &input.ident,
+            "`clone` and `pod` can't be combined, `pod` copies values with `memcpy`",
+        ));
+    }
+
+    Ok(attributes)
+}
+
 fn check_repr_c(input: &syn::DeriveInput) -> bool {
     for attr in &input.attrs {
         if attr.path().is_ident("repr") {
@@ -1754,6 +1933,44 @@ pub fn query(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
 ///
 /// See [`query`] for examples & DSL divergences from the flecs spec.
 ///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Position {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[derive(Component)]
+/// struct Velocity {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[derive(Component)]
+/// struct Frozen;
+///
+/// let world = World::new();
+///
+/// let e = world
+///     .entity()
+///     .set(Position { x: 0.0, y: 0.0 })
+///     .set(Velocity { x: 1.0, y: 2.0 });
+///
+/// system!("Move", world, &mut Position, &Velocity, !Frozen).each(|(p, v)| {
+///     p.x += v.x;
+///     p.y += v.y;
+/// });
+///
+/// world.progress();
+///
+/// e.get::<&Position>(|p| assert_eq!((p.x, p.y), (1.0, 2.0)));
+/// assert!(world.try_lookup("Move").is_some());
+/// ```
+///
 /// [`query`]: macro@query
 #[proc_macro]
 pub fn system(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
@@ -1828,6 +2045,36 @@ impl Parse for Observer {
 ///
 /// See [`query`] for examples & DSL divergences from the flecs spec.
 ///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+///
+/// #[derive(Component)]
+/// struct Position {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// #[derive(Component)]
+/// struct Npc;
+///
+/// #[derive(Component)]
+/// struct Count(i32);
+///
+/// let world = World::new();
+/// world.set(Count(0));
+///
+/// observer!("OnSetNpcPosition", world, flecs::OnSet, &Position, Npc).each_entity(|e, _p| {
+///     e.world().get::<&mut Count>(|c| c.0 += 1);
+/// });
+///
+/// world.entity().add::<Npc>().set(Position { x: 1.0, y: 2.0 });
+/// world.entity().set(Position { x: 1.0, y: 2.0 });
+///
+/// world.get::<&Count>(|c| assert_eq!(c.0, 1));
+/// ```
+///
 /// [`query`]: macro@query
 #[proc_macro]
 pub fn observer(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
@@ -1999,10 +2246,9 @@ pub fn ecs_rust_trait(input: ProcMacroTokenStream) -> ProcMacroTokenStream {
 #[proc_macro]
 #[cfg(not(feature = "flecs_query_rust_traits"))]
 pub fn ecs_rust_trait(_: ProcMacroTokenStream) -> ProcMacroTokenStream {
-    const {
-        panic!(
+    ProcMacroTokenStream::from(quote! {
+        compile_error!(
             "The `flecs_query_rust_traits` feature must be enabled to use this procedural macro."
-        )
-    };
-    ProcMacroTokenStream::new()
+        );
+    })
 }
@@ -52,7 +52,7 @@ fn generate_bindings() {
         .blocklist_item("ECS_OVERRIDE")
         .blocklist_item("ECS_TOGGLE")
         .blocklist_item("ECS_AND")
-        // We'll use `libc::FILE` instead.
+        // We'll use `libc::FILE` instead, see lib.rs.
         .blocklist_type("FILE")
         // These have doc comments that trigger doc tests.
         .blocklist_type("ecs_alert_desc_t")
@@ -70,7 +70,6 @@ fn generate_bindings() {
         .raw_line("#![allow(clippy::all)]")
         .raw_line("#![allow(warnings)]")
         .raw_line("use super::*;")
-        .raw_line("use libc::FILE;")
         .clang_arg("-DFLECS_CUSTOM_BUILD")
         .clang_arg("-DFLECS_CPP");
 
@@ -212,6 +211,10 @@ fn main() {
     println!("cargo:rerun-if-changed=src/flecs_rust.c");
     println!("cargo:rerun-if-changed=build.rs");
 
+    // wasm32-unknown-unknown has no OS to implement the OS API with, flecs_ecs provides one
+    let is_wasm_unknown = std::env::var("CARGO_CFG_TARGET_ARCH").as_deref() == Ok("wasm32")
+        && std::env::var("CARGO_CFG_TARGET_OS").as_deref() == Ok("unknown");
+
     #[cfg(not(feature = "disable_build_c"))]
     {
         let mut build = cc::Build::new();
@@ -275,7 +278,9 @@ fn main() {
         build.define("FLECS_APP", None);
 
         #[cfg(feature = "flecs_os_api_impl")]
-        build.define("FLECS_OS_API_IMPL", None);
+        if !is_wasm_unknown {
+            build.define("FLECS_OS_API_IMPL", None);
+        }
 
         #[cfg(feature = "flecs_http")]
         build.define("FLECS_HTTP", None);
@@ -307,6 +312,18 @@ fn main() {
             build.define("FLECS_KEEP_ASSERTS", None);
         }
 
+        // the target has no libc, flecs is built against the libc of the WASI SDK instead, for
+        // which WASI_SYSROOT is set to the `share/wasi-sysroot` directory of the SDK
+        println!("cargo:rerun-if-env-changed=WASI_SYSROOT");
+        if is_wasm_unknown {
+            if let Ok(sysroot) = std::env::var("WASI_SYSROOT") {
+                build.flag(format!("--sysroot={sysroot}"));
+                println!("cargo:rustc-link-search=native={sysroot}/lib/wasm32-wasi");
+                println!("cargo:rustc-link-search=native={sysroot}/lib/wasm32-wasip1");
+                println!("cargo:rustc-link-lib=static=c");
+            }
+        }
+
         let term_count_max = if cfg!(feature = "flecs_term_count_64") {
             64
         } else {
@@ -3,7 +3,6 @@
 #![allow(clippy::all)]
 #![allow(warnings)]
 use super::*;
-use libc::FILE;
 pub const FLECS_TERM_COUNT_MAX: u32 = 32;
 
 pub const FLECS_VERSION_MAJOR: u32 = 4;
@@ -169,6 +168,7 @@ pub const EcsIterNextYield: u32 = 0;
 pub const EcsIterYield: i32 = -1;
 pub const EcsIterNext: u32 = 1;
 pub const FLECS_SPARSE_PAGE_SIZE: u32 = 64;
+pub const ECS_MAX_ALIGN: u32 = 128;
 pub const ECS_STACK_PAGE_SIZE: u32 = 4096;
 pub const ECS_STRBUF_SMALL_STRING_SIZE: u32 = 512;
 pub const ECS_STRBUF_MAX_LIST_DEPTH: u32 = 32;
@@ -346,6 +346,23 @@ impl Default for crate::ecs_iter_to_json_desc_t {
     }
 }
 
+impl Default for crate::ecs_expr_eval_desc_t {
+    fn default() -> Self {
+        Self {
+            name: core::ptr::null(),
+            expr: core::ptr::null(),
+            vars: core::ptr::null(),
+            type_: Default::default(),
+            lookup_action: Default::default(),
+            lookup_ctx: core::ptr::null_mut(),
+            disable_folding: Default::default(),
+            disable_dynamic_variable_binding: Default::default(),
+            allow_unresolved_identifiers: Default::default(),
+            runtime: core::ptr::null_mut(),
+        }
+    }
+}
+
 unsafe impl Sync for crate::EcsIdentifier {}
 unsafe impl Send for crate::EcsIdentifier {}
 unsafe impl Send for crate::EcsPoly {}
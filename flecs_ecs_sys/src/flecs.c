@@ -29598,6 +29598,18 @@ int64_t ecs_block_allocator_free_count = 0;
 
 #ifndef FLECS_USE_OS_ALLOC
 
+/* Alignment of the chunks of a block allocator. Memory returned by malloc is
+ * aligned to 16 bytes, which is enough for most types. Chunks of which the size
+ * is a multiple of a larger power of two can hold arrays of over-aligned types,
+ * and are aligned to that power of two up to ECS_MAX_ALIGN. */
+static
+ecs_size_t flecs_balloc_chunk_align(
+    ecs_size_t chunk_size)
+{
+    ecs_size_t align = chunk_size & -chunk_size;
+    return ECS_MIN(align, ECS_MAX_ALIGN);
+}
+
 static
 ecs_block_allocator_chunk_header_t* flecs_balloc_block(
     ecs_block_allocator_t *allocator)
@@ -29606,11 +29618,19 @@ ecs_block_allocator_chunk_header_t* flecs_balloc_block(
         return NULL;
     }
 
+    ecs_size_t align = flecs_balloc_chunk_align(allocator->chunk_size);
+    ecs_size_t padding = align > 16 ? align : 0;
+
     ecs_block_allocator_block_t *block = 
         ecs_os_malloc(ECS_SIZEOF(ecs_block_allocator_block_t) +
-            allocator->block_size);
+            allocator->block_size + padding);
     ecs_block_allocator_chunk_header_t *first_chunk = ECS_OFFSET(block, 
         ECS_SIZEOF(ecs_block_allocator_block_t));
+    if (padding) {
+        uintptr_t addr = (uintptr_t)first_chunk;
+        first_chunk = (ecs_block_allocator_chunk_header_t*)
+            ((addr + (uintptr_t)align - 1) & ~((uintptr_t)align - 1));
+    }
 
     block->memory = first_chunk;
     if (!allocator->block_tail) {
@@ -31767,9 +31787,13 @@ int64_t ecs_stack_allocator_free_count = 0;
 
 static
 ecs_stack_page_t* flecs_stack_page_new(uint32_t page_id) {
+    /* Align page data to ECS_MAX_ALIGN, so that values with a larger alignment
+     * than malloc guarantees are aligned when their offset in the page is. */
     ecs_stack_page_t *result = ecs_os_malloc(
-        FLECS_STACK_PAGE_OFFSET + ECS_STACK_PAGE_SIZE);
-    result->data = ECS_OFFSET(result, FLECS_STACK_PAGE_OFFSET);
+        FLECS_STACK_PAGE_OFFSET + ECS_STACK_PAGE_SIZE + ECS_MAX_ALIGN);
+    uintptr_t data = (uintptr_t)ECS_OFFSET(result, FLECS_STACK_PAGE_OFFSET);
+    result->data = (void*)((data + ECS_MAX_ALIGN - 1) &
+        ~((uintptr_t)ECS_MAX_ALIGN - 1));
     result->next = NULL;
     result->id = page_id + 1;
     result->sp = 0;
@@ -36714,7 +36738,10 @@ void flecs_id_record_init_sparse(
             ecs_assert(idr->type_info != NULL, ECS_INVALID_OPERATION, 
                 "only components can be marked as sparse");
             idr->sparse = flecs_walloc_t(world, ecs_sparse_t);
-            flecs_sparse_init(idr->sparse, NULL, NULL, idr->type_info->size);
+            /* Use the world allocator, so that pages are aligned like the
+             * columns of tables. */
+            flecs_sparse_init(idr->sparse, &world->allocator, NULL, 
+                idr->type_info->size);
         } else
         if (idr->flags & EcsIdIsUnion) {
             idr->sparse = flecs_walloc_t(world, ecs_switch_t);
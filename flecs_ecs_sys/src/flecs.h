@@ -1524,6 +1524,10 @@ void* ecs_sparse_get(
 #ifndef FLECS_BLOCK_ALLOCATOR_H
 #define FLECS_BLOCK_ALLOCATOR_H
 
+/** Largest alignment of values stored in block and stack allocators. Chunks of
+ * which the size is a multiple of a power of two up to this value are aligned
+ * to that power of two, so that arrays of over-aligned types are aligned. */
+#define ECS_MAX_ALIGN (128)
 
 typedef struct ecs_map_t ecs_map_t;
 
@@ -4,11 +4,19 @@
 //!
 //! [`flecs`]: https://www.flecs.dev/
 
+#![cfg_attr(not(test), no_std)]
+
 #[cfg(all(feature = "force_build_release", feature = "force_build_debug"))]
 compile_error!(
     "Features 'force_build_release' and 'force_build_debug' cannot be enabled at the same time."
 );
 
+// libc has no `FILE` for wasm32-unknown-unknown, flecs only stores a pointer to it
+#[cfg(not(all(target_arch = "wasm32", target_os = "unknown")))]
+use libc::FILE;
+#[cfg(all(target_arch = "wasm32", target_os = "unknown"))]
+pub enum FILE {}
+
 mod bindings;
 mod extensions;
 mod mbindings;
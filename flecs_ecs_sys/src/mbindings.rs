@@ -170,16 +170,18 @@ pub struct WorldInfo {
     pub emit_time_total: f32,
     /// Total time spent in merges.
     pub merge_time_total: f32,
-    /// Time elapsed in simulation.
-    pub world_time_total: f32,
-    /// Time elapsed in simulation (no scaling).
-    pub world_time_total_raw: f32,
     /// Time spent on query rematching.
     pub rematch_time_total: f32,
+    /// Time elapsed in simulation.
+    pub world_time_total: f64,
+    /// Time elapsed in simulation (no scaling).
+    pub world_time_total_raw: f64,
     /// Total number of frames.
     pub frame_count_total: i64,
     /// Total number of merges.
     pub merge_count_total: i64,
+    /// Total number of monitor evaluations.
+    pub eval_comp_monitors_total: i64,
     /// Total number of rematches.
     pub rematch_count_total: i64,
     /// Total number of times a new id was created.
@@ -204,8 +206,6 @@ pub struct WorldInfo {
     pub pair_id_count: i32,
     /// Number of tables.
     pub table_count: i32,
-    /// Number of tables without entities.
-    pub empty_table_count: i32,
     pub cmd: WorldInfoCmd,
     /// Value set by `ecs_set_name_prefix()`. Used
     /// to remove library prefixes of symbol names (such as `Ecs`, `ecs_`) when
@@ -214,8 +214,11 @@ pub struct WorldInfo {
 }
 
 /// Command counts.
+///
+/// Counts are totals since the world was created. Subtract two snapshots to get the number
+/// of commands processed in between, for example by a single merge.
 #[repr(C)]
-#[derive(Debug, Copy, Clone)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
 pub struct WorldInfoCmd {
     /// Add commands processed.
     pub add_count: i64,
@@ -243,6 +246,32 @@ pub struct WorldInfoCmd {
     pub batched_command_count: i64,
 }
 
+impl core::ops::Sub for WorldInfoCmd {
+    type Output = WorldInfoCmd;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        WorldInfoCmd {
+            add_count: self.add_count - rhs.add_count,
+            remove_count: self.remove_count - rhs.remove_count,
+            delete_count: self.delete_count - rhs.delete_count,
+            clear_count: self.clear_count - rhs.clear_count,
+            set_count: self.set_count - rhs.set_count,
+            ensure_count: self.ensure_count - rhs.ensure_count,
+            modified_count: self.modified_count - rhs.modified_count,
+            discard_count: self.discard_count - rhs.discard_count,
+            event_count: self.event_count - rhs.event_count,
+            other_count: self.other_count - rhs.other_count,
+            batched_entity_count: self.batched_entity_count - rhs.batched_entity_count,
+            batched_command_count: self.batched_command_count - rhs.batched_command_count,
+        }
+    }
+}
+
+const _: () = assert!(
+    core::mem::size_of::<WorldInfo>() == core::mem::size_of::<ecs_world_info_t>()
+        && core::mem::offset_of!(WorldInfo, cmd) == core::mem::offset_of!(ecs_world_info_t, cmd)
+);
+
 unsafe extern "C-unwind" {
     #[doc = "Get world info.\n\n @param world The world.\n @return Pointer to the world info. Valid for as long as the world exists."]
     pub fn ecs_get_world_info(world: *const ecs_world_t) -> *const WorldInfo;
@@ -0,0 +1,414 @@
+//! Widget that lists the entities of a world and edits their components.
+
+use std::collections::HashMap;
+
+use egui::collapsing_header::CollapsingState;
+use flecs_ecs::addons::meta::Cursor;
+use flecs_ecs::prelude::*;
+use flecs_ecs::sys;
+
+/// Inspector for the entities of a [`World`] and their components.
+///
+/// The inspector shows the hierarchy of the world on the left, and the components of the selected
+/// entity on the right. Component values are read and written through their reflection data, so
+/// only components registered with `.meta()` can be edited. Primitive and struct members get a
+/// widget of their own, other types such as enums, vectors and opaque types are edited as JSON.
+///
+/// The inspector only keeps the selection and the state of the widgets, so one inspector can be
+/// used for several worlds.
+///
+/// # Example
+///
+/// ```
+/// use flecs_ecs::prelude::*;
+/// use flecs_egui::WorldInspector;
+///
+/// #[derive(Component)]
+/// #[meta]
+/// struct Position {
+///     x: f32,
+///     y: f32,
+/// }
+///
+/// let world = World::new();
+/// world.component::<Position>().meta();
+///
+/// let player = world.entity_named("player").set(Position { x: 1.0, y: 2.0 });
+///
+/// let mut inspector = WorldInspector::new();
+/// inspector.select(player);
+///
+/// let ctx = egui::Context::default();
+/// let _ = ctx.run(egui::RawInput::default(), |ctx| {
+///     inspector.window(ctx, &world);
+/// });
+/// ```
+#[derive(Default)]
+pub struct WorldInspector {
+    selected: Option<Entity>,
+    filter: String,
+    show_builtin: bool,
+    // text of the JSON fields that are being edited, keyed by the id of the widget
+    json_edits: HashMap<egui::Id, String>,
+}
+
+impl WorldInspector {
+    /// Create an inspector without a selected entity.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The entity of which the components are shown.
+    pub fn selected(&self) -> Option<Entity> {
+        self.selected
+    }
+
+    /// Show the components of `entity`.
+    pub fn select(&mut self, entity: impl Into<Entity>) {
+        self.selected = Some(entity.into());
+    }
+
+    /// Clear the selected entity.
+    pub fn deselect(&mut self) {
+        self.selected = None;
+    }
+
+    /// Show entities in the scope of the `flecs` module, such as the builtin components.
+    ///
+    /// These are hidden by default.
+    pub fn show_builtin(&mut self, show: bool) -> &mut Self {
+        self.show_builtin = show;
+        self
+    }
+
+    /// Show the inspector in a window.
+    pub fn window(&mut self, ctx: &egui::Context, world: &World) {
+        egui::Window::new("World Inspector")
+            .default_size([600.0, 400.0])
+            .show(ctx, |ui| self.show(ui, world));
+    }
+
+    /// Show the inspector in `ui`, with the hierarchy and the selected entity side by side.
+    pub fn show(&mut self, ui: &mut egui::Ui, world: &World) {
+        if let Some(selected) = self.selected {
+            if !world.is_alive(selected) {
+                self.selected = None;
+            }
+        }
+
+        ui.columns(2, |columns| {
+            egui::ScrollArea::vertical()
+                .id_salt("flecs_egui_hierarchy")
+                .show(&mut columns[0], |ui| self.hierarchy_ui(ui, world));
+            egui::ScrollArea::vertical()
+                .id_salt("flecs_egui_entity")
+                .show(&mut columns[1], |ui| match self.selected {
+                    Some(selected) => self.entity_ui(ui, world.entity_from_id(selected)),
+                    None => {
+                        ui.weak("No entity selected");
+                    }
+                });
+        });
+    }
+
+    /// Show the entities of the world as a tree, or as a list of matches when filtering by name.
+    pub fn hierarchy_ui(&mut self, ui: &mut egui::Ui, world: &World) {
+        ui.horizontal(|ui| {
+            ui.add(egui::TextEdit::singleline(&mut self.filter).hint_text("Filter by name"));
+            ui.checkbox(&mut self.show_builtin, "Builtin");
+        });
+        ui.separator();
+
+        if self.filter.is_empty() {
+            let mut roots = Vec::new();
+            world.each_child(|entity| {
+                if self.show_builtin || entity.id() != flecs::Flecs::ID {
+                    roots.push(entity.id());
+                }
+            });
+            for entity in roots {
+                self.tree_ui(ui, world.entity_from_id(entity));
+            }
+        } else {
+            let filter = self.filter.to_lowercase();
+            let mut matches = Vec::new();
+            world.each_alive_entity(|entity| {
+                if entity.name().to_lowercase().contains(&filter) {
+                    matches.push(entity.id());
+                }
+            });
+            for entity in matches {
+                let entity = world.entity_from_id(entity);
+                let path = entity.path().unwrap_or_else(|| label(entity));
+                self.entity_label_ui(ui, entity, path);
+            }
+        }
+    }
+
+    /// Show the components of an entity, editing the ones that have reflection data.
+    pub fn entity_ui(&mut self, ui: &mut egui::Ui, entity: EntityView) {
+        ui.heading(label(entity));
+        if let Some(path) = entity.path() {
+            ui.weak(path);
+        }
+        ui.weak(format!("id: {}", entity.id()));
+        ui.separator();
+
+        let world = entity.world();
+        let mut ids = Vec::new();
+        entity.each_component(|id| ids.push(id.id()));
+        for id in ids {
+            self.component_ui(ui, entity, world.id_from_id(id));
+        }
+    }
+
+    fn tree_ui(&mut self, ui: &mut egui::Ui, entity: EntityView) {
+        if !entity.has_children() {
+            self.entity_label_ui(ui, entity, label(entity));
+            return;
+        }
+
+        let id = ui.make_persistent_id(("flecs_egui_tree", *entity.id()));
+        CollapsingState::load_with_default_open(ui.ctx(), id, false)
+            .show_header(ui, |ui| self.entity_label_ui(ui, entity, label(entity)))
+            .body(|ui| {
+                let world = entity.world();
+                let mut children = Vec::new();
+                entity.each_child(|child| children.push(child.id()));
+                for child in children {
+                    self.tree_ui(ui, world.entity_from_id(child));
+                }
+            });
+    }
+
+    fn entity_label_ui(&mut self, ui: &mut egui::Ui, entity: EntityView, text: String) {
+        let selected = self.selected == Some(entity.id());
+        if ui.selectable_label(selected, text).clicked() {
+            self.selected = Some(entity.id());
+        }
+    }
+
+    fn component_ui(&mut self, ui: &mut egui::Ui, entity: EntityView, id: IdView) {
+        let name = id.to_str().to_string();
+        let type_id = id.type_id();
+
+        // tags and components without reflection data only show their name
+        if type_id.id() == 0 || !type_id.has::<flecs::meta::Type>() {
+            ui.label(name);
+            return;
+        }
+
+        egui::CollapsingHeader::new(name)
+            .id_salt(("flecs_egui_component", *entity.id(), *id.id()))
+            .default_open(true)
+            .show(ui, |ui| {
+                let world = entity.world();
+                let ptr = entity.get_untyped_mut(id.id());
+                let mut cursor = world.cursor_id(type_id, ptr);
+                if self.value_ui(ui, &world, &mut cursor, type_id, ui.id()) {
+                    entity.modified_id(id.id());
+                }
+            });
+    }
+
+    /// Show the value the cursor points to, returns if it was changed.
+    fn value_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        world: &World,
+        cursor: &mut Cursor,
+        ty: EntityView,
+        salt: egui::Id,
+    ) -> bool {
+        match type_kind(ty) {
+            Some(sys::ecs_type_kind_t_EcsPrimitiveType) => primitive_ui(ui, cursor, ty),
+            Some(sys::ecs_type_kind_t_EcsStructType) => self.struct_ui(ui, world, cursor, ty, salt),
+            _ => self.json_ui(ui, world, cursor, ty, salt),
+        }
+    }
+
+    fn struct_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        world: &World,
+        cursor: &mut Cursor,
+        ty: EntityView,
+        salt: egui::Id,
+    ) -> bool {
+        let mut members = Vec::new();
+        ty.each_child(|member| {
+            if let Some(count) = member.try_get::<&flecs::meta::Member>(|member| member.count) {
+                members.push((member.name(), count));
+            }
+        });
+
+        let mut changed = false;
+        cursor.push();
+        for (name, count) in members {
+            cursor.member(&name);
+            let member_ty = world.entity_from_id(cursor.get_type().id());
+            let salt = salt.with(&name);
+
+            if count > 1 {
+                // inline arrays are shown but not edited
+                ui.horizontal(|ui| {
+                    ui.label(&name);
+                    ui.weak(format!("[{}; {count}]", label(member_ty)));
+                });
+            } else if type_kind(member_ty) == Some(sys::ecs_type_kind_t_EcsStructType) {
+                egui::CollapsingHeader::new(&name)
+                    .id_salt(salt)
+                    .default_open(true)
+                    .show(ui, |ui| {
+                        changed |= self.value_ui(ui, world, cursor, member_ty, salt);
+                    });
+            } else {
+                ui.horizontal(|ui| {
+                    ui.label(&name);
+                    changed |= self.value_ui(ui, world, cursor, member_ty, salt);
+                });
+            }
+        }
+        cursor.pop();
+
+        changed
+    }
+
+    /// Edit values that don't have a widget as JSON, which is applied when the field loses focus.
+    fn json_ui(
+        &mut self,
+        ui: &mut egui::Ui,
+        world: &World,
+        cursor: &mut Cursor,
+        ty: EntityView,
+        salt: egui::Id,
+    ) -> bool {
+        let ptr = cursor.get_ptr();
+        let id = salt.with("json");
+        let current = world.to_json_id(ty, ptr);
+        let mut text = self
+            .json_edits
+            .remove(&id)
+            .unwrap_or_else(|| current.clone());
+
+        let response = ui.add(egui::TextEdit::singleline(&mut text).id(id));
+        if response.has_focus() {
+            self.json_edits.insert(id, text);
+            false
+        } else if response.lost_focus() && text != current {
+            world.from_json_id(ty, ptr, &text, None);
+            true
+        } else {
+            false
+        }
+    }
+}
+
+fn primitive_ui(ui: &mut egui::Ui, cursor: &mut Cursor, ty: EntityView) -> bool {
+    let kind = ty
+        .try_get::<&flecs::meta::Primitive>(|primitive| primitive.kind)
+        .unwrap_or_default();
+
+    match kind {
+        sys::ecs_primitive_kind_t_EcsBool => {
+            let mut value = cursor.get_bool();
+            let changed = ui.checkbox(&mut value, "").changed();
+            if changed {
+                cursor.set_bool(value);
+            }
+            changed
+        }
+        sys::ecs_primitive_kind_t_EcsChar
+        | sys::ecs_primitive_kind_t_EcsI8
+        | sys::ecs_primitive_kind_t_EcsI16
+        | sys::ecs_primitive_kind_t_EcsI32
+        | sys::ecs_primitive_kind_t_EcsI64
+        | sys::ecs_primitive_kind_t_EcsIPtr => {
+            let range = match kind {
+                sys::ecs_primitive_kind_t_EcsChar | sys::ecs_primitive_kind_t_EcsI8 => {
+                    i8::MIN as i64..=i8::MAX as i64
+                }
+                sys::ecs_primitive_kind_t_EcsI16 => i16::MIN as i64..=i16::MAX as i64,
+                sys::ecs_primitive_kind_t_EcsI32 => i32::MIN as i64..=i32::MAX as i64,
+                _ => i64::MIN..=i64::MAX,
+            };
+            let mut value = cursor.get_int();
+            let changed = ui
+                .add(egui::DragValue::new(&mut value).range(range))
+                .changed();
+            if changed {
+                cursor.set_int(value);
+            }
+            changed
+        }
+        sys::ecs_primitive_kind_t_EcsByte
+        | sys::ecs_primitive_kind_t_EcsU8
+        | sys::ecs_primitive_kind_t_EcsU16
+        | sys::ecs_primitive_kind_t_EcsU32
+        | sys::ecs_primitive_kind_t_EcsU64
+        | sys::ecs_primitive_kind_t_EcsUPtr => {
+            let range = match kind {
+                sys::ecs_primitive_kind_t_EcsByte | sys::ecs_primitive_kind_t_EcsU8 => {
+                    0..=u8::MAX as u64
+                }
+                sys::ecs_primitive_kind_t_EcsU16 => 0..=u16::MAX as u64,
+                sys::ecs_primitive_kind_t_EcsU32 => 0..=u32::MAX as u64,
+                _ => 0..=u64::MAX,
+            };
+            let mut value = cursor.get_uint();
+            let changed = ui
+                .add(egui::DragValue::new(&mut value).range(range))
+                .changed();
+            if changed {
+                cursor.set_uint(value);
+            }
+            changed
+        }
+        sys::ecs_primitive_kind_t_EcsF32 | sys::ecs_primitive_kind_t_EcsF64 => {
+            let mut value = cursor.get_float();
+            let changed = ui
+                .add(egui::DragValue::new(&mut value).speed(0.1))
+                .changed();
+            if changed {
+                cursor.set_float(value);
+            }
+            changed
+        }
+        sys::ecs_primitive_kind_t_EcsString => {
+            let ptr = cursor.get_string();
+            let mut value = if ptr.is_null() {
+                String::new()
+            } else {
+                unsafe { core::ffi::CStr::from_ptr(ptr) }
+                    .to_string_lossy()
+                    .into_owned()
+            };
+            let changed = ui.text_edit_singleline(&mut value).changed();
+            if changed {
+                cursor.set_string(&value);
+            }
+            changed
+        }
+        _ => {
+            // entities and ids are shown by name, as they can't be picked from a text field
+            let entity = cursor.get_entity();
+            if entity.id() == 0 {
+                ui.weak("none");
+            } else {
+                ui.label(entity.path().unwrap_or_else(|| label(entity)));
+            }
+            false
+        }
+    }
+}
+
+fn type_kind(ty: EntityView) -> Option<sys::ecs_type_kind_t> {
+    ty.try_get::<&flecs::meta::Type>(|ty| ty.kind)
+}
+
+/// The name of the entity, or its id if it has no name.
+fn label(entity: EntityView) -> String {
+    entity
+        .get_name()
+        .unwrap_or_else(|| format!("#{}", entity.id()))
+}
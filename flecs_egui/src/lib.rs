@@ -0,0 +1,21 @@
+//! [egui] widgets to inspect and edit [flecs] worlds from inside an application.
+//!
+//! [`WorldInspector`] lists the entities of a world as a hierarchy and shows the components of
+//! the selected entity. Components with reflection data, registered with `.meta()`, can be edited
+//! in place. It is a native alternative to the [flecs explorer], which runs in a browser and
+//! connects through the REST api.
+//!
+//! The widgets are behind the `egui` feature, which is enabled by default.
+//!
+//! [egui]: https://github.com/emilk/egui
+//! [flecs]: https://www.flecs.dev/
+//! [flecs explorer]: https://www.flecs.dev/explorer/
+
+#[cfg(feature = "egui")]
+pub use egui;
+
+#[cfg(feature = "egui")]
+mod inspector;
+
+#[cfg(feature = "egui")]
+pub use inspector::*;
@@ -0,0 +1,173 @@
+#![allow(clippy::float_cmp)]
+
+extern crate alloc;
+
+use alloc::rc::Rc;
+use core::cell::Cell;
+
+use flecs_ecs::prelude::*;
+use flecs_egui::WorldInspector;
+
+#[derive(Component, Default)]
+#[meta]
+struct Position {
+    x: f32,
+    y: f32,
+}
+
+#[derive(Component, Default)]
+#[meta]
+struct Transform {
+    position: Position,
+    scale: f64,
+    visible: bool,
+}
+
+#[derive(Component, Default)]
+#[meta]
+struct Label {
+    text: String,
+}
+
+#[derive(Component, Default)]
+#[meta]
+struct Visible {
+    visible: bool,
+}
+
+#[derive(Component)]
+struct Player;
+
+fn run(inspector: &mut WorldInspector, world: &World) {
+    let ctx = egui::Context::default();
+    // the first frame only lays out the widgets
+    for _ in 0..2 {
+        let _ = ctx.run(egui::RawInput::default(), |ctx| {
+            egui::CentralPanel::default().show(ctx, |ui| inspector.show(ui, world));
+        });
+    }
+}
+
+fn run_entity_ui(
+    inspector: &mut WorldInspector,
+    ctx: &egui::Context,
+    entity: EntityView,
+    events: Vec<egui::Event>,
+) {
+    let input = egui::RawInput {
+        events,
+        ..Default::default()
+    };
+    let _ = ctx.run(input, |ctx| {
+        egui::CentralPanel::default().show(ctx, |ui| inspector.entity_ui(ui, entity));
+    });
+}
+
+fn world() -> World {
+    let world = World::new();
+    world.component::<Position>().meta();
+    world.component::<Transform>().meta();
+    world.component::<Label>().meta();
+    world.component::<Visible>().meta();
+    world
+}
+
+#[test]
+fn inspector_show_entity() {
+    let world = world();
+
+    let parent = world
+        .entity_named("parent")
+        .set(Transform {
+            position: Position { x: 1.0, y: 2.0 },
+            scale: 1.0,
+            visible: true,
+        })
+        .add::<Player>();
+    let child = world
+        .entity_named("child")
+        .child_of_id(parent)
+        .set(Label {
+            text: "hello".to_string(),
+        })
+        .set(Position { x: 3.0, y: 4.0 });
+
+    let mut inspector = WorldInspector::new();
+    assert_eq!(inspector.selected(), None);
+    run(&mut inspector, &world);
+
+    inspector.select(child);
+    run(&mut inspector, &world);
+    assert_eq!(inspector.selected(), Some(child.id()));
+
+    // showing the components doesn't modify them
+    child.get::<(&Label, &Position)>(|(label, position)| {
+        assert_eq!(label.text, "hello");
+        assert_eq!(position.x, 3.0);
+    });
+
+    inspector.select(parent);
+    inspector.show_builtin(true);
+    run(&mut inspector, &world);
+    assert_eq!(inspector.selected(), Some(parent.id()));
+}
+
+#[test]
+fn inspector_deselect_deleted() {
+    let world = world();
+
+    let entity = world.entity().set(Position { x: 1.0, y: 2.0 });
+
+    let mut inspector = WorldInspector::new();
+    inspector.select(entity);
+    entity.destruct();
+
+    run(&mut inspector, &world);
+    assert_eq!(inspector.selected(), None);
+}
+
+#[test]
+fn inspector_edit_value() {
+    let world = world();
+
+    let entity = world.entity().set(Visible { visible: false });
+    let set_count = Rc::new(Cell::new(0));
+    let count = set_count.clone();
+    world
+        .observer::<flecs::OnSet, &Visible>()
+        .each(move |_| count.set(count.get() + 1));
+
+    let mut inspector = WorldInspector::new();
+    let ctx = egui::Context::default();
+    run_entity_ui(&mut inspector, &ctx, entity, Vec::new());
+
+    // the checkbox of the member is the last widget that can be clicked
+    let pos = ctx
+        .viewport(|viewport| {
+            viewport
+                .prev_pass
+                .widgets
+                .get_layer(egui::LayerId::background())
+                .filter(|widget| widget.sense.senses_click())
+                .last()
+                .map(|widget| widget.rect.center())
+        })
+        .unwrap();
+
+    let button = |pressed| egui::Event::PointerButton {
+        pos,
+        button: egui::PointerButton::Primary,
+        pressed,
+        modifiers: egui::Modifiers::default(),
+    };
+    run_entity_ui(
+        &mut inspector,
+        &ctx,
+        entity,
+        vec![egui::Event::PointerMoved(pos), button(true)],
+    );
+    run_entity_ui(&mut inspector, &ctx, entity, vec![button(false)]);
+
+    entity.get::<&Visible>(|visible| assert!(visible.visible));
+    assert_eq!(set_count.get(), 1);
+}